@@ -21,9 +21,11 @@ pub fn parse_vote(data: &[u8]) -> Result<VoteAccountType, ParseAccountError> {
     let votes = vote_state
         .votes
         .iter()
-        .map(|lockout| UiLockout {
+        .enumerate()
+        .map(|(i, lockout)| UiLockout {
             slot: lockout.slot,
             confirmation_count: lockout.confirmation_count,
+            latency: vote_state.vote_latencies.get(i).copied(),
         })
         .collect();
     let authorized_voters = vote_state
@@ -87,6 +89,10 @@ pub struct UiVoteState {
 struct UiLockout {
     slot: Slot,
     confirmation_count: u32,
+    /// Number of slots the vote took to land, only recorded once vote latencies are used to award
+    /// credits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latency: Option<u8>,
 }
 
 impl From<&Lockout> for UiLockout {
@@ -94,6 +100,7 @@ impl From<&Lockout> for UiLockout {
         Self {
             slot: lockout.slot,
             confirmation_count: lockout.confirmation_count,
+            latency: None,
         }
     }
 }
@@ -144,4 +151,35 @@ mod test {
         let bad_data = vec![0; 4];
         assert!(parse_vote(&bad_data).is_err());
     }
+
+    #[test]
+    fn test_parse_vote_with_vote_latencies() {
+        let mut vote_state = VoteState::default();
+        vote_state.process_next_vote_slot(1, 0, 3, true);
+        vote_state.process_next_vote_slot(2, 0, 3, true);
+        let mut vote_account_data: Vec<u8> = vec![0; VoteState::size_of_with_vote_latencies()];
+        let versioned = VoteStateVersions::new_current(vote_state);
+        assert!(matches!(
+            versioned,
+            VoteStateVersions::CurrentWithVoteLatencies(_, _)
+        ));
+        VoteState::serialize(&versioned, &mut vote_account_data).unwrap();
+
+        let VoteAccountType::Vote(ui_vote_state) = parse_vote(&vote_account_data).unwrap();
+        assert_eq!(
+            ui_vote_state.votes,
+            vec![
+                UiLockout {
+                    slot: 1,
+                    confirmation_count: 2,
+                    latency: Some(2),
+                },
+                UiLockout {
+                    slot: 2,
+                    confirmation_count: 1,
+                    latency: Some(1),
+                },
+            ]
+        );
+    }
 }
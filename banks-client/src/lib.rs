@@ -7,12 +7,16 @@
 
 pub use {
     crate::error::BanksClientError,
-    solana_banks_interface::{BanksClient as TarpcClient, TransactionStatus},
+    solana_banks_interface::{
+        AccountDiff, BanksClient as TarpcClient, BanksTransactionResultWithReport,
+        BanksTransactionResultWithSimulation, InvocationReport, TransactionExecutionReport,
+        TransactionSimulationDetails, TransactionStatus,
+    },
 };
 use {
     borsh::BorshDeserialize,
     futures::{future::join_all, Future, FutureExt, TryFutureExt},
-    solana_banks_interface::{BanksRequest, BanksResponse, BanksTransactionResultWithSimulation},
+    solana_banks_interface::{BanksRequest, BanksResponse},
     solana_program::{
         clock::Slot, fee_calculator::FeeCalculator, hash::Hash, program_pack::Pack, pubkey::Pubkey,
         rent::Rent, sysvar::Sysvar,
@@ -22,7 +26,7 @@ use {
         commitment_config::CommitmentLevel,
         message::Message,
         signature::Signature,
-        transaction::{self, Transaction},
+        transaction::{self, VersionedTransaction},
     },
     tarpc::{
         client::{self, NewClient, RequestDispatch},
@@ -59,10 +63,10 @@ impl BanksClient {
     pub fn send_transaction_with_context(
         &mut self,
         ctx: Context,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         self.inner
-            .send_transaction_with_context(ctx, transaction)
+            .send_transaction_with_context(ctx, transaction.into())
             .map_err(Into::into)
     }
 
@@ -114,30 +118,57 @@ impl BanksClient {
     pub fn process_transaction_with_commitment_and_context(
         &mut self,
         ctx: Context,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
         commitment: CommitmentLevel,
     ) -> impl Future<Output = Result<Option<transaction::Result<()>>, BanksClientError>> + '_ {
         self.inner
-            .process_transaction_with_commitment_and_context(ctx, transaction, commitment)
+            .process_transaction_with_commitment_and_context(ctx, transaction.into(), commitment)
             .map_err(Into::into)
     }
 
     pub fn process_transaction_with_preflight_and_commitment_and_context(
         &mut self,
         ctx: Context,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
         commitment: CommitmentLevel,
     ) -> impl Future<Output = Result<BanksTransactionResultWithSimulation, BanksClientError>> + '_
     {
         self.inner
             .process_transaction_with_preflight_and_commitment_and_context(
                 ctx,
-                transaction,
+                transaction.into(),
                 commitment,
             )
             .map_err(Into::into)
     }
 
+    pub fn process_transaction_with_report_and_commitment_and_context(
+        &mut self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithReport, BanksClientError>> + '_ {
+        self.inner
+            .process_transaction_with_report_and_commitment_and_context(
+                ctx,
+                transaction.into(),
+                commitment,
+            )
+            .map_err(Into::into)
+    }
+
+    pub fn simulate_transaction_with_commitment_and_context(
+        &mut self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithSimulation, BanksClientError>> + '_
+    {
+        self.inner
+            .simulate_transaction_with_commitment_and_context(ctx, transaction.into(), commitment)
+            .map_err(Into::into)
+    }
+
     pub fn get_account_with_commitment_and_context(
         &mut self,
         ctx: Context,
@@ -154,7 +185,7 @@ impl BanksClient {
     /// blockhash expires.
     pub fn send_transaction(
         &mut self,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         self.send_transaction_with_context(context::current(), transaction)
     }
@@ -205,7 +236,7 @@ impl BanksClient {
     /// reached the given level of commitment.
     pub fn process_transaction_with_commitment(
         &mut self,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
         commitment: CommitmentLevel,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         let mut ctx = context::current();
@@ -223,7 +254,7 @@ impl BanksClient {
     /// after the transaction has been rejected or reached the given level of commitment.
     pub fn process_transaction_with_preflight_and_commitment(
         &mut self,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
         commitment: CommitmentLevel,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         let mut ctx = context::current();
@@ -260,7 +291,7 @@ impl BanksClient {
     /// after the transaction has been finalized or rejected.
     pub fn process_transaction_with_preflight(
         &mut self,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         self.process_transaction_with_preflight_and_commitment(
             transaction,
@@ -271,14 +302,41 @@ impl BanksClient {
     /// Send a transaction and return until the transaction has been finalized or rejected.
     pub fn process_transaction(
         &mut self,
-        transaction: Transaction,
+        transaction: impl Into<VersionedTransaction>,
     ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
         self.process_transaction_with_commitment(transaction, CommitmentLevel::default())
     }
 
-    pub async fn process_transactions_with_commitment(
+    /// Send a transaction and return after the transaction has been rejected or reached the given
+    /// level of commitment, along with a report of its execution: the compute units consumed by
+    /// each program invocation, the changes made to each account, and the accounts data growth.
+    /// The report is not available if the transaction fails sanitization.
+    pub fn process_transaction_with_report_and_commitment(
         &mut self,
-        transactions: Vec<Transaction>,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithReport, BanksClientError>> + '_ {
+        let mut ctx = context::current();
+        ctx.deadline += Duration::from_secs(50);
+        self.process_transaction_with_report_and_commitment_and_context(
+            ctx,
+            transaction,
+            commitment,
+        )
+    }
+
+    /// Send a transaction and return after the transaction has been finalized or rejected, along
+    /// with a report of its execution.
+    pub fn process_transaction_with_report(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithReport, BanksClientError>> + '_ {
+        self.process_transaction_with_report_and_commitment(transaction, CommitmentLevel::default())
+    }
+
+    pub async fn process_transactions_with_commitment<T: Into<VersionedTransaction>>(
+        &mut self,
+        transactions: Vec<T>,
         commitment: CommitmentLevel,
     ) -> Result<(), BanksClientError> {
         let mut clients: Vec<_> = transactions.iter().map(|_| self.clone()).collect();
@@ -293,13 +351,38 @@ impl BanksClient {
     }
 
     /// Send transactions and return until the transaction has been finalized or rejected.
-    pub fn process_transactions(
-        &mut self,
-        transactions: Vec<Transaction>,
-    ) -> impl Future<Output = Result<(), BanksClientError>> + '_ {
+    pub fn process_transactions<'a, T: Into<VersionedTransaction> + 'a>(
+        &'a mut self,
+        transactions: Vec<T>,
+    ) -> impl Future<Output = Result<(), BanksClientError>> + 'a {
         self.process_transactions_with_commitment(transactions, CommitmentLevel::default())
     }
 
+    /// Simulate a transaction at the given commitment level, returning its result along with
+    /// the logs, compute units, return data, inner instructions and post-simulation accounts.
+    /// The transaction is not committed.
+    pub fn simulate_transaction_with_commitment(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithSimulation, BanksClientError>> + '_
+    {
+        self.simulate_transaction_with_commitment_and_context(
+            context::current(),
+            transaction,
+            commitment,
+        )
+    }
+
+    /// Simulate a transaction at the time of the most recent root slot.
+    pub fn simulate_transaction(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithSimulation, BanksClientError>> + '_
+    {
+        self.simulate_transaction_with_commitment(transaction, CommitmentLevel::default())
+    }
+
     /// Return the most recent rooted slot. All transactions at or below this slot
     /// are said to be finalized. The cluster will not fork to a higher slot.
     pub fn get_root_slot(&mut self) -> impl Future<Output = Result<Slot, BanksClientError>> + '_ {
@@ -477,7 +560,13 @@ mod tests {
             bank::Bank, bank_forks::BankForks, commitment::BlockCommitmentCache,
             genesis_utils::create_genesis_config,
         },
-        solana_sdk::{message::Message, signature::Signer, system_instruction},
+        solana_sdk::{
+            message::{v0, Message, VersionedMessage},
+            native_token::sol_to_lamports,
+            signature::{Keypair, Signer},
+            system_instruction, system_program,
+            transaction::Transaction,
+        },
         std::sync::{Arc, RwLock},
         tarpc::transport,
         tokio::{runtime::Runtime, time::sleep},
@@ -569,4 +658,135 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_banks_server_simulate_versioned_transaction() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        // Versioned messages are sanitized with the SlotHashes sysvar, which the genesis bank
+        // doesn't have yet
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis.genesis_config));
+        let bank = Bank::new_from_parent(&bank0, &Pubkey::default(), 1);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = Arc::new(RwLock::new(BankForks::new(bank)));
+
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let instruction = system_instruction::transfer(&mint_pubkey, &bob_pubkey, 1);
+
+        Runtime::new()?.block_on(async {
+            let client_transport =
+                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
+                    .await;
+            let mut banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let message =
+                v0::Message::try_compile(&mint_pubkey, &[instruction], &[], recent_blockhash)
+                    .unwrap();
+            let transaction = VersionedTransaction::try_new(
+                VersionedMessage::V0(message),
+                &[&genesis.mint_keypair],
+            )
+            .unwrap();
+
+            let BanksTransactionResultWithSimulation {
+                result,
+                simulation_details,
+            } = banks_client
+                .simulate_transaction(transaction.clone())
+                .await?;
+            assert_eq!(result, Some(Ok(())));
+            let simulation_details = simulation_details.unwrap();
+            assert_eq!(simulation_details.inner_instructions, Some(vec![vec![]]));
+            let (_, bob_account) = simulation_details
+                .post_simulation_accounts
+                .iter()
+                .find(|(pubkey, _)| *pubkey == bob_pubkey)
+                .unwrap();
+            assert_eq!(bob_account.lamports, 1);
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 0);
+
+            banks_client.process_transaction(transaction).await.unwrap();
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 1);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_banks_server_process_transaction_with_report() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(sol_to_lamports(1.0));
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = Arc::new(RwLock::new(BankForks::new(bank)));
+
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let new_keypair = Keypair::new();
+        let new_pubkey = new_keypair.pubkey();
+        let space = 10;
+        let instruction = system_instruction::create_account(
+            &mint_pubkey,
+            &new_pubkey,
+            sol_to_lamports(0.1),
+            space,
+            &system_program::id(),
+        );
+        let message = Message::new(&[instruction], Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport =
+                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
+                    .await;
+            let mut banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let transaction = Transaction::new(
+                &[&genesis.mint_keypair, &new_keypair],
+                message,
+                recent_blockhash,
+            );
+            let BanksTransactionResultWithReport { result, report } = banks_client
+                .process_transaction_with_report(transaction)
+                .await?;
+            assert_eq!(result, Some(Ok(())));
+            let report = report.unwrap();
+            assert_eq!(
+                report.invocations,
+                vec![InvocationReport {
+                    program_id: system_program::id(),
+                    stack_height: 1,
+                    units_consumed: 0,
+                }]
+            );
+            assert_eq!(report.accounts_data_len_delta, space as i64);
+
+            let new_account_diff = report
+                .account_diffs
+                .iter()
+                .find(|diff| diff.pubkey == new_pubkey)
+                .unwrap();
+            assert_eq!(new_account_diff.pre_lamports, 0);
+            assert_eq!(new_account_diff.post_lamports, sol_to_lamports(0.1));
+            assert_eq!(new_account_diff.post_data_len, space as usize);
+            assert_eq!(
+                new_account_diff.changed_data_ranges,
+                vec![0..space as usize]
+            );
+            assert!(report
+                .account_diffs
+                .iter()
+                .any(|diff| diff.pubkey == mint_pubkey));
+
+            assert_eq!(
+                banks_client.get_balance(new_pubkey).await?,
+                sol_to_lamports(0.1)
+            );
+            Ok(())
+        })
+    }
 }
@@ -8,12 +8,14 @@ use {
         commitment_config::CommitmentLevel,
         fee_calculator::FeeCalculator,
         hash::Hash,
+        instruction::CompiledInstruction,
         message::Message,
         pubkey::Pubkey,
         signature::Signature,
-        transaction::{self, Transaction, TransactionError},
+        transaction::{self, TransactionError, VersionedTransaction},
         transaction_context::TransactionReturnData,
     },
+    std::ops::Range,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,6 +39,10 @@ pub struct TransactionSimulationDetails {
     pub logs: Vec<String>,
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
+    /// Instructions invoked through CPI, one list per top-level instruction
+    pub inner_instructions: Option<Vec<Vec<CompiledInstruction>>>,
+    /// The state of each of the transaction's static account keys after simulation
+    pub post_simulation_accounts: Vec<(Pubkey, Account)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -45,9 +51,51 @@ pub struct BanksTransactionResultWithSimulation {
     pub simulation_details: Option<TransactionSimulationDetails>,
 }
 
+/// A program invocation, in the order the invocations started
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationReport {
+    pub program_id: Pubkey,
+    /// 1 for a top-level instruction, and one more for each level of CPI
+    pub stack_height: usize,
+    /// Units consumed by the invocation, including the CPIs it made
+    pub units_consumed: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    pub pubkey: Pubkey,
+    pub pre_lamports: u64,
+    pub post_lamports: u64,
+    pub pre_owner: Pubkey,
+    pub post_owner: Pubkey,
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+    /// Byte ranges of the post-execution data that differ from the pre-execution data
+    pub changed_data_ranges: Vec<Range<usize>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionExecutionReport {
+    pub units_consumed: u64,
+    pub invocations: Vec<InvocationReport>,
+    /// Only the accounts the transaction modified
+    pub account_diffs: Vec<AccountDiff>,
+    /// Accounts data growth charged to the accounts data meter, 0 if the transaction failed
+    pub accounts_data_len_delta: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanksTransactionResultWithReport {
+    pub result: Option<transaction::Result<()>>,
+    pub report: Option<TransactionExecutionReport>,
+}
+
 #[tarpc::service]
 pub trait Banks {
-    async fn send_transaction_with_context(transaction: Transaction);
+    async fn send_transaction_with_context(transaction: VersionedTransaction);
     #[deprecated(
         since = "1.9.0",
         note = "Please use `get_fee_for_message_with_commitment_and_context` instead"
@@ -60,13 +108,21 @@ pub trait Banks {
     async fn get_slot_with_context(commitment: CommitmentLevel) -> Slot;
     async fn get_block_height_with_context(commitment: CommitmentLevel) -> u64;
     async fn process_transaction_with_preflight_and_commitment_and_context(
-        transaction: Transaction,
+        transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> BanksTransactionResultWithSimulation;
     async fn process_transaction_with_commitment_and_context(
-        transaction: Transaction,
+        transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> Option<transaction::Result<()>>;
+    async fn process_transaction_with_report_and_commitment_and_context(
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithReport;
+    async fn simulate_transaction_with_commitment_and_context(
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithSimulation;
     async fn get_account_with_commitment_and_context(
         address: Pubkey,
         commitment: CommitmentLevel,
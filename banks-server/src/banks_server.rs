@@ -3,8 +3,9 @@ use {
     crossbeam_channel::{unbounded, Receiver, Sender},
     futures::{future, prelude::stream::StreamExt},
     solana_banks_interface::{
-        Banks, BanksRequest, BanksResponse, BanksTransactionResultWithSimulation,
-        TransactionConfirmationStatus, TransactionSimulationDetails, TransactionStatus,
+        AccountDiff, Banks, BanksRequest, BanksResponse, BanksTransactionResultWithReport,
+        BanksTransactionResultWithSimulation, InvocationReport, TransactionConfirmationStatus,
+        TransactionExecutionReport, TransactionSimulationDetails, TransactionStatus,
     },
     solana_runtime::{
         bank::{Bank, TransactionSimulationResult},
@@ -12,16 +13,16 @@ use {
         commitment::BlockCommitmentCache,
     },
     solana_sdk::{
-        account::Account,
+        account::{Account, AccountSharedData, ReadableAccount},
         clock::Slot,
         commitment_config::CommitmentLevel,
-        feature_set::FeatureSet,
+        feature_set::{self, FeatureSet},
         fee_calculator::FeeCalculator,
         hash::Hash,
         message::{Message, SanitizedMessage},
         pubkey::Pubkey,
         signature::Signature,
-        transaction::{self, SanitizedTransaction, Transaction},
+        transaction::{self, MessageHash, SanitizedTransaction, VersionedTransaction},
     },
     solana_send_transaction_service::{
         send_transaction_service::{SendTransactionService, TransactionInfo, DEFAULT_TPU_USE_QUIC},
@@ -31,6 +32,7 @@ use {
         convert::TryFrom,
         io,
         net::{Ipv4Addr, SocketAddr},
+        ops::Range,
         sync::{Arc, RwLock},
         thread::Builder,
         time::Duration,
@@ -84,7 +86,7 @@ impl BanksServer {
                 .map(|info| deserialize(&info.wire_transaction).unwrap())
                 .collect();
             let bank = bank_forks.read().unwrap().working_bank();
-            let _ = bank.try_process_transactions(transactions.iter());
+            let _ = bank.try_process_entry_transactions(transactions);
         }
     }
 
@@ -146,10 +148,101 @@ impl BanksServer {
         }
         status
     }
+
+    fn simulate_transaction(
+        bank: &Bank,
+        transaction: VersionedTransaction,
+    ) -> BanksTransactionResultWithSimulation {
+        let sanitized_transaction = match sanitize_transaction(transaction, bank) {
+            Err(err) => {
+                return BanksTransactionResultWithSimulation {
+                    result: Some(Err(err)),
+                    simulation_details: None,
+                };
+            }
+            Ok(tx) => tx,
+        };
+        let TransactionSimulationResult {
+            result,
+            logs,
+            post_simulation_accounts,
+            units_consumed,
+            return_data,
+            inner_instructions,
+            accounts_data_len_delta: _,
+            instruction_compute_units: _,
+        } = bank.simulate_transaction_unchecked(sanitized_transaction, true);
+        BanksTransactionResultWithSimulation {
+            result: Some(result),
+            simulation_details: Some(TransactionSimulationDetails {
+                logs,
+                units_consumed,
+                return_data,
+                inner_instructions,
+                post_simulation_accounts: post_simulation_accounts
+                    .into_iter()
+                    .map(|(pubkey, account)| (pubkey, Account::from(account)))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Byte ranges of `post` that differ from `pre`
+fn changed_data_ranges(pre: &[u8], post: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for (index, byte) in post.iter().enumerate() {
+        if pre.get(index) == Some(byte) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end += 1,
+            _ => ranges.push(index..index + 1),
+        }
+    }
+    ranges
+}
+
+fn account_diff(
+    pubkey: Pubkey,
+    pre_account: &AccountSharedData,
+    post_account: &AccountSharedData,
+) -> Option<AccountDiff> {
+    if pre_account.lamports() == post_account.lamports()
+        && pre_account.owner() == post_account.owner()
+        && pre_account.data() == post_account.data()
+    {
+        return None;
+    }
+    Some(AccountDiff {
+        pubkey,
+        pre_lamports: pre_account.lamports(),
+        post_lamports: post_account.lamports(),
+        pre_owner: *pre_account.owner(),
+        post_owner: *post_account.owner(),
+        pre_data_len: pre_account.data().len(),
+        post_data_len: post_account.data().len(),
+        changed_data_ranges: changed_data_ranges(pre_account.data(), post_account.data()),
+    })
+}
+
+/// Sanitize a transaction against the given bank, resolving any address lookup tables
+fn sanitize_transaction(
+    transaction: VersionedTransaction,
+    bank: &Bank,
+) -> transaction::Result<SanitizedTransaction> {
+    SanitizedTransaction::try_create(
+        transaction,
+        MessageHash::Compute,
+        Some(false),
+        bank,
+        bank.feature_set
+            .is_active(&feature_set::require_static_program_ids_in_transaction::id()),
+    )
 }
 
 fn verify_transaction(
-    transaction: &Transaction,
+    transaction: &SanitizedTransaction,
     feature_set: &Arc<FeatureSet>,
 ) -> transaction::Result<()> {
     if let Err(err) = transaction.verify() {
@@ -163,8 +256,8 @@ fn verify_transaction(
 
 #[tarpc::server]
 impl Banks for BanksServer {
-    async fn send_transaction_with_context(self, _: Context, transaction: Transaction) {
-        let blockhash = &transaction.message.recent_blockhash;
+    async fn send_transaction_with_context(self, _: Context, transaction: VersionedTransaction) {
+        let blockhash = transaction.message.recent_blockhash();
         let last_valid_block_height = self
             .bank_forks
             .read()
@@ -249,37 +342,17 @@ impl Banks for BanksServer {
     async fn process_transaction_with_preflight_and_commitment_and_context(
         self,
         ctx: Context,
-        transaction: Transaction,
+        transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> BanksTransactionResultWithSimulation {
-        let sanitized_transaction =
-            match SanitizedTransaction::try_from_legacy_transaction(transaction.clone()) {
-                Err(err) => {
-                    return BanksTransactionResultWithSimulation {
-                        result: Some(Err(err)),
-                        simulation_details: None,
-                    };
-                }
-                Ok(tx) => tx,
-            };
-        if let TransactionSimulationResult {
-            result: Err(err),
-            logs,
-            post_simulation_accounts: _,
-            units_consumed,
-            return_data,
-        } = self
-            .bank(commitment)
-            .simulate_transaction_unchecked(sanitized_transaction)
+        let simulation_result =
+            Self::simulate_transaction(&self.bank(commitment), transaction.clone());
+        if let BanksTransactionResultWithSimulation {
+            result: Some(Err(_)),
+            ..
+        } = simulation_result
         {
-            return BanksTransactionResultWithSimulation {
-                result: Some(Err(err)),
-                simulation_details: Some(TransactionSimulationDetails {
-                    logs,
-                    units_consumed,
-                    return_data,
-                }),
-            };
+            return simulation_result;
         }
         BanksTransactionResultWithSimulation {
             result: self
@@ -292,16 +365,18 @@ impl Banks for BanksServer {
     async fn process_transaction_with_commitment_and_context(
         self,
         _: Context,
-        transaction: Transaction,
+        transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> Option<transaction::Result<()>> {
-        if let Err(err) = verify_transaction(&transaction, &self.bank(commitment).feature_set) {
+        let bank = self.bank(commitment);
+        if let Err(err) = sanitize_transaction(transaction.clone(), &bank)
+            .and_then(|tx| verify_transaction(&tx, &bank.feature_set))
+        {
             return Some(Err(err));
         }
 
-        let blockhash = &transaction.message.recent_blockhash;
-        let last_valid_block_height = self
-            .bank(commitment)
+        let blockhash = transaction.message.recent_blockhash();
+        let last_valid_block_height = bank
             .get_blockhash_last_valid_block_height(blockhash)
             .unwrap();
         let signature = transaction.signatures.get(0).cloned().unwrap_or_default();
@@ -318,6 +393,77 @@ impl Banks for BanksServer {
             .await
     }
 
+    async fn process_transaction_with_report_and_commitment_and_context(
+        self,
+        ctx: Context,
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithReport {
+        // The report comes from simulating the transaction against the same bank it is about to
+        // be processed on, since execution details aren't kept once a transaction is committed
+        let bank = self.bank(commitment);
+        let sanitized_transaction = match sanitize_transaction(transaction.clone(), &bank) {
+            Err(err) => {
+                return BanksTransactionResultWithReport {
+                    result: Some(Err(err)),
+                    report: None,
+                };
+            }
+            Ok(tx) => tx,
+        };
+        let TransactionSimulationResult {
+            result,
+            post_simulation_accounts,
+            units_consumed,
+            accounts_data_len_delta,
+            instruction_compute_units,
+            ..
+        } = bank.simulate_transaction_unchecked(sanitized_transaction, true);
+        let account_diffs = post_simulation_accounts
+            .iter()
+            .filter_map(|(pubkey, post_account)| {
+                let pre_account = bank.get_account(pubkey).unwrap_or_default();
+                account_diff(*pubkey, &pre_account, post_account)
+            })
+            .collect();
+        let report = TransactionExecutionReport {
+            units_consumed,
+            invocations: instruction_compute_units
+                .unwrap_or_default()
+                .into_iter()
+                .map(|instruction| InvocationReport {
+                    program_id: instruction.program_id,
+                    stack_height: instruction.stack_height,
+                    units_consumed: instruction.units_consumed,
+                })
+                .collect(),
+            account_diffs,
+            accounts_data_len_delta,
+        };
+
+        if let Err(err) = result {
+            return BanksTransactionResultWithReport {
+                result: Some(Err(err)),
+                report: Some(report),
+            };
+        }
+        BanksTransactionResultWithReport {
+            result: self
+                .process_transaction_with_commitment_and_context(ctx, transaction, commitment)
+                .await,
+            report: Some(report),
+        }
+    }
+
+    async fn simulate_transaction_with_commitment_and_context(
+        self,
+        _: Context,
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithSimulation {
+        Self::simulate_transaction(&self.bank(commitment), transaction)
+    }
+
     async fn get_account_with_commitment_and_context(
         self,
         _: Context,
@@ -905,11 +905,19 @@ fn show_votes_and_credits(
     )?;
 
     for vote in votes.iter().rev() {
-        writeln!(
-            f,
-            "- slot: {} (confirmation count: {})",
-            vote.slot, vote.confirmation_count
-        )?;
+        if let Some(latency) = vote.latency {
+            writeln!(
+                f,
+                "- slot: {} (confirmation count: {}, latency: {})",
+                vote.slot, vote.confirmation_count, latency
+            )?;
+        } else {
+            writeln!(
+                f,
+                "- slot: {} (confirmation count: {})",
+                vote.slot, vote.confirmation_count
+            )?;
+        }
     }
     if let Some(newest) = newest_history_entry {
         writeln!(
@@ -1470,6 +1478,8 @@ pub struct CliEpochVotingHistory {
 pub struct CliLockout {
     pub slot: Slot,
     pub confirmation_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<u8>,
 }
 
 impl From<&Lockout> for CliLockout {
@@ -1477,6 +1487,7 @@ impl From<&Lockout> for CliLockout {
         Self {
             slot: lockout.slot,
             confirmation_count: lockout.confirmation_count,
+            latency: None,
         }
     }
 }
@@ -2039,6 +2050,26 @@ impl fmt::Display for CliUpgradeableProgramClosed {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliUpgradeableProgramExtended {
+    pub program_id: String,
+    pub additional_bytes: u32,
+}
+impl QuietDisplay for CliUpgradeableProgramExtended {}
+impl VerboseDisplay for CliUpgradeableProgramExtended {}
+impl fmt::Display for CliUpgradeableProgramExtended {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Extended Program Id {} by {} bytes",
+            &self.program_id, self.additional_bytes,
+        )?;
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliUpgradeableBuffer {
@@ -41,6 +41,7 @@ use {
 pub const DEFAULT_RPC_TIMEOUT_SECONDS: &str = "30";
 pub const DEFAULT_CONFIRM_TX_TIMEOUT_SECONDS: &str = "5";
 const CHECKED: bool = true;
+const MOVE_LAMPORTS: bool = true;
 
 #[derive(Debug, PartialEq)]
 #[allow(clippy::large_enum_variant)]
@@ -209,6 +210,7 @@ pub enum CliCommand {
         nonce_authority: SignerIndex,
         memo: Option<String>,
         fee_payer: SignerIndex,
+        redelegation_stake_account_pubkey: Option<Pubkey>,
     },
     SplitStake {
         stake_account_pubkey: Pubkey,
@@ -236,6 +238,20 @@ pub enum CliCommand {
         memo: Option<String>,
         fee_payer: SignerIndex,
     },
+    MoveStake {
+        source_stake_account_pubkey: Pubkey,
+        destination_stake_account_pubkey: Pubkey,
+        stake_authority: SignerIndex,
+        lamports: u64,
+        move_lamports: bool,
+        sign_only: bool,
+        dump_transaction_message: bool,
+        blockhash_query: BlockhashQuery,
+        nonce_account: Option<Pubkey>,
+        nonce_authority: SignerIndex,
+        memo: Option<String>,
+        fee_payer: SignerIndex,
+    },
     ShowStakeHistory {
         use_lamports_unit: bool,
         limit_results: usize,
@@ -674,6 +690,9 @@ pub fn parse_command(
         ("delegate-stake", Some(matches)) => {
             parse_stake_delegate_stake(matches, default_signer, wallet_manager)
         }
+        ("redelegate-stake", Some(matches)) => {
+            parse_stake_delegate_stake(matches, default_signer, wallet_manager)
+        }
         ("withdraw-stake", Some(matches)) => {
             parse_stake_withdraw_stake(matches, default_signer, wallet_manager)
         }
@@ -686,6 +705,12 @@ pub fn parse_command(
         ("merge-stake", Some(matches)) => {
             parse_merge_stake(matches, default_signer, wallet_manager)
         }
+        ("move-stake", Some(matches)) => {
+            parse_move_stake(matches, default_signer, wallet_manager, !MOVE_LAMPORTS)
+        }
+        ("move-stake-lamports", Some(matches)) => {
+            parse_move_stake(matches, default_signer, wallet_manager, MOVE_LAMPORTS)
+        }
         ("stake-authorize", Some(matches)) => {
             parse_stake_authorize(matches, default_signer, wallet_manager, !CHECKED)
         }
@@ -1119,6 +1144,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             nonce_authority,
             memo,
             fee_payer,
+            redelegation_stake_account_pubkey,
         } => process_delegate_stake(
             &rpc_client,
             config,
@@ -1133,6 +1159,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *nonce_authority,
             memo.as_ref(),
             *fee_payer,
+            redelegation_stake_account_pubkey.as_ref(),
         ),
         CliCommand::SplitStake {
             stake_account_pubkey,
@@ -1188,6 +1215,35 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             memo.as_ref(),
             *fee_payer,
         ),
+        CliCommand::MoveStake {
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+            stake_authority,
+            lamports,
+            move_lamports,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority,
+            memo,
+            fee_payer,
+        } => process_move_stake(
+            &rpc_client,
+            config,
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+            *stake_authority,
+            *lamports,
+            *move_lamports,
+            *sign_only,
+            *dump_transaction_message,
+            blockhash_query,
+            *nonce_account,
+            *nonce_authority,
+            memo.as_ref(),
+            *fee_payer,
+        ),
         CliCommand::ShowStakeAccount {
             pubkey: stake_account_pubkey,
             use_lamports_unit,
@@ -2136,7 +2136,7 @@ impl RentLengthValue {
             Self::Nonce => NonceState::size(),
             Self::Stake => StakeState::size_of(),
             Self::System => 0,
-            Self::Vote => VoteState::size_of(),
+            Self::Vote => VoteState::size_of_with_vote_latencies(),
             Self::Bytes(l) => *l,
         }
     }
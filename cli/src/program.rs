@@ -15,7 +15,7 @@ use {
     solana_cli_output::{
         CliProgram, CliProgramAccountType, CliProgramAuthority, CliProgramBuffer, CliProgramId,
         CliUpgradeableBuffer, CliUpgradeableBuffers, CliUpgradeableProgram,
-        CliUpgradeableProgramClosed, CliUpgradeablePrograms,
+        CliUpgradeableProgramClosed, CliUpgradeableProgramExtended, CliUpgradeablePrograms,
     },
     solana_client::{
         client_error::ClientErrorKind,
@@ -104,6 +104,10 @@ pub enum ProgramCliCommand {
         authority_index: SignerIndex,
         use_lamports_unit: bool,
     },
+    ExtendProgram {
+        program_pubkey: Pubkey,
+        additional_bytes: u32,
+    },
 }
 
 pub trait ProgramSubCommands {
@@ -382,6 +386,28 @@ impl ProgramSubCommands for App<'_, '_> {
                                 .help("Display balance in lamports instead of SOL"),
                         ),
                 )
+                .subcommand(
+                    SubCommand::with_name("extend")
+                        .about("Extend the length of an upgradeable program to deploy larger programs")
+                        .arg(
+                            Arg::with_name("program_id")
+                                .index(1)
+                                .value_name("PROGRAM_ID")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Address of the program to extend")
+                        )
+                        .arg(
+                            Arg::with_name("additional_bytes")
+                                .index(2)
+                                .value_name("ADDITIONAL_BYTES")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_parsable::<u32>)
+                                .help("Number of bytes that will be allocated for the program's data account")
+                        )
+                )
         )
         .subcommand(
             SubCommand::with_name("deploy")
@@ -672,6 +698,26 @@ pub fn parse_program_subcommand(
                 signers: signer_info.signers,
             }
         }
+        ("extend", Some(matches)) => {
+            let program_pubkey = pubkey_of(matches, "program_id").unwrap();
+            let additional_bytes = value_of(matches, "additional_bytes").unwrap();
+
+            let signer_info = default_signer.generate_unique_signers(
+                vec![Some(
+                    default_signer.signer_from_path(matches, wallet_manager)?,
+                )],
+                matches,
+                wallet_manager,
+            )?;
+
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::ExtendProgram {
+                    program_pubkey,
+                    additional_bytes,
+                }),
+                signers: signer_info.signers,
+            }
+        }
         _ => unreachable!(),
     };
     Ok(response)
@@ -783,6 +829,10 @@ pub fn process_program_subcommand(
             *authority_index,
             *use_lamports_unit,
         ),
+        ProgramCliCommand::ExtendProgram {
+            program_pubkey,
+            additional_bytes,
+        } => process_extend_program(&rpc_client, config, *program_pubkey, *additional_bytes),
     }
 }
 
@@ -1188,6 +1238,100 @@ fn process_set_authority(
     Ok(config.output_format.formatted_string(&authority))
 }
 
+fn process_extend_program(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    program_pubkey: Pubkey,
+    additional_bytes: u32,
+) -> ProcessResult {
+    let payer_pubkey = config.signers[0].pubkey();
+
+    if additional_bytes == 0 {
+        return Err("Additional bytes must be greater than zero".into());
+    }
+
+    let program_account = match rpc_client
+        .get_account_with_commitment(&program_pubkey, config.commitment)?
+        .value
+    {
+        Some(program_account) => Ok(program_account),
+        None => Err(format!("Unable to find program {}", program_pubkey)),
+    }?;
+
+    if !bpf_loader_upgradeable::check_id(&program_account.owner) {
+        return Err(format!("Account {} is not an upgradeable program", program_pubkey).into());
+    }
+
+    let programdata_pubkey = match program_account.state() {
+        Ok(UpgradeableLoaderState::Program {
+            programdata_address: programdata_pubkey,
+        }) => Ok(programdata_pubkey),
+        _ => Err(format!(
+            "Account {} is not an upgradeable program",
+            program_pubkey
+        )),
+    }?;
+
+    let programdata_account = match rpc_client
+        .get_account_with_commitment(&programdata_pubkey, config.commitment)?
+        .value
+    {
+        Some(programdata_account) => Ok(programdata_account),
+        None => Err(format!("Program {} is closed", program_pubkey)),
+    }?;
+
+    match programdata_account.state() {
+        Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address: Some(_),
+            ..
+        }) => {}
+        Ok(UpgradeableLoaderState::ProgramData { .. }) => {
+            return Err(format!("Program {} is not upgradeable", program_pubkey).into());
+        }
+        _ => return Err(format!("Program {} is closed", program_pubkey).into()),
+    }
+
+    let blockhash = rpc_client.get_latest_blockhash()?;
+
+    let mut tx = Transaction::new_unsigned(Message::new(
+        &[bpf_loader_upgradeable::extend_program(
+            &program_pubkey,
+            Some(&payer_pubkey),
+            additional_bytes,
+        )],
+        Some(&payer_pubkey),
+    ));
+
+    tx.try_sign(&[config.signers[0]], blockhash)?;
+    let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+        &tx,
+        config.commitment,
+        RpcSendTransactionConfig {
+            skip_preflight: false,
+            preflight_commitment: Some(config.commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        },
+    );
+    if let Err(err) = result {
+        if let ClientErrorKind::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::InvalidInstructionData,
+        )) = err.kind()
+        {
+            return Err("Extending a program is not supported by the cluster".into());
+        } else {
+            return Err(format!("Extend program failed: {}", err).into());
+        }
+    }
+
+    Ok(config
+        .output_format
+        .formatted_string(&CliUpgradeableProgramExtended {
+            program_id: program_pubkey.to_string(),
+            additional_bytes,
+        }))
+}
+
 const ACCOUNT_TYPE_SIZE: usize = 4;
 const SLOT_SIZE: usize = size_of::<u64>();
 const OPTION_SIZE: usize = 1;
@@ -3092,6 +3236,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cli_parse_extend_program() {
+        let test_commands = get_clap_app("test", "desc", "version");
+
+        let default_keypair = Keypair::new();
+        let keypair_file = make_tmp_path("keypair_file");
+        write_keypair_file(&default_keypair, &keypair_file).unwrap();
+        let default_signer = DefaultSigner::new("", &keypair_file);
+
+        let program_pubkey = Pubkey::new_unique();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "extend",
+            &program_pubkey.to_string(),
+            "1024",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::ExtendProgram {
+                    program_pubkey,
+                    additional_bytes: 1024,
+                }),
+                signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
+            }
+        );
+
+        // additional bytes must fit in a u32
+        assert!(test_commands
+            .clone()
+            .get_matches_from_safe(vec![
+                "test",
+                "program",
+                "extend",
+                &program_pubkey.to_string(),
+                "4294967296",
+            ])
+            .is_err());
+    }
+
     #[test]
     fn test_cli_keypair_file() {
         solana_logger::setup();
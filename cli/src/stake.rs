@@ -284,6 +284,50 @@ impl StakeSubCommands for App<'_, '_> {
                 .arg(fee_payer_arg())
                 .arg(memo_arg())
         )
+        .subcommand(
+            SubCommand::with_name("redelegate-stake")
+                .about("Redelegate active stake to another vote account")
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .takes_value(false)
+                        .hidden(true) // Don't document this argument to discourage its use
+                        .help("Override vote account sanity checks (use carefully!)")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("stake_account_pubkey")
+                        .index(1)
+                        .value_name("STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Existing delegated stake account that has been fully activated. \
+                        On success this stake account will be scheduled for deactivation and the rent-exempt balance \
+                        may be withdrawn once fully deactivated")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("vote_account_pubkey")
+                        .index(2)
+                        .value_name("REDELEGATED_VOTE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "The vote account to which the stake will be redelegated")
+                )
+                .arg(
+                    Arg::with_name("redelegation_stake_account")
+                        .index(3)
+                        .value_name("REDELEGATION_STAKE_ACCOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_signer)
+                        .help("Stake account to create for the redelegation. \
+                               On success this stake account will be created and scheduled for activation with all \
+                               the stake in the existing stake account, exclusive of the rent-exempt balance retained \
+                               in the existing account")
+                )
+                .arg(stake_authority_arg())
+                .offline_args()
+                .nonce_args(false)
+                .arg(fee_payer_arg())
+                .arg(memo_arg())
+        )
         .subcommand(
             SubCommand::with_name("stake-authorize")
                 .about("Authorize a new signing keypair for the given stake account")
@@ -459,6 +503,72 @@ impl StakeSubCommands for App<'_, '_> {
                 .arg(fee_payer_arg())
                 .arg(memo_arg())
         )
+        .subcommand(
+            SubCommand::with_name("move-stake")
+                .about("Move active stake to another stake account with the same authorities")
+                .arg(
+                    pubkey!(Arg::with_name("source_stake_account_pubkey")
+                        .index(1)
+                        .value_name("SOURCE_STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Fully active stake account to move stake from. \
+                         If all of its stake is moved, the account becomes inactive")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("destination_stake_account_pubkey")
+                        .index(2)
+                        .value_name("DESTINATION_STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Stake account to move stake to. It must be fully active and delegated \
+                         to the same vote account as the source, or fully inactive")
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .index(3)
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .validator(is_amount)
+                        .required(true)
+                        .help("The amount of stake to move, in SOL")
+                )
+                .arg(stake_authority_arg())
+                .offline_args()
+                .nonce_args(false)
+                .arg(fee_payer_arg())
+                .arg(memo_arg())
+        )
+        .subcommand(
+            SubCommand::with_name("move-stake-lamports")
+                .about("Move undelegated SOL to another stake account with the same authorities")
+                .arg(
+                    pubkey!(Arg::with_name("source_stake_account_pubkey")
+                        .index(1)
+                        .value_name("SOURCE_STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Fully active or inactive stake account to move SOL from")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("destination_stake_account_pubkey")
+                        .index(2)
+                        .value_name("DESTINATION_STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Stake account to move SOL to")
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .index(3)
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .validator(is_amount)
+                        .required(true)
+                        .help("The amount of undelegated SOL to move, in SOL")
+                )
+                .arg(stake_authority_arg())
+                .offline_args()
+                .nonce_args(false)
+                .arg(fee_payer_arg())
+                .arg(memo_arg())
+        )
         .subcommand(
             SubCommand::with_name("withdraw-stake")
                 .about("Withdraw the unstaked SOL from the stake account")
@@ -753,11 +863,16 @@ pub fn parse_stake_delegate_stake(
     let (nonce_authority, nonce_authority_pubkey) =
         signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
     let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
+    let (redelegation_stake_account, redelegation_stake_account_pubkey) =
+        signer_of(matches, "redelegation_stake_account", wallet_manager)?;
 
     let mut bulk_signers = vec![stake_authority, fee_payer];
     if nonce_account.is_some() {
         bulk_signers.push(nonce_authority);
     }
+    if redelegation_stake_account.is_some() {
+        bulk_signers.push(redelegation_stake_account);
+    }
     let signer_info =
         default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
 
@@ -774,6 +889,7 @@ pub fn parse_stake_delegate_stake(
             nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
             memo,
             fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
+            redelegation_stake_account_pubkey,
         },
         signers: signer_info.signers,
     })
@@ -995,6 +1111,55 @@ pub fn parse_merge_stake(
     })
 }
 
+pub fn parse_move_stake(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    move_lamports: bool,
+) -> Result<CliCommandInfo, CliError> {
+    let source_stake_account_pubkey =
+        pubkey_of_signer(matches, "source_stake_account_pubkey", wallet_manager)?.unwrap();
+    let destination_stake_account_pubkey =
+        pubkey_of_signer(matches, "destination_stake_account_pubkey", wallet_manager)?.unwrap();
+    let lamports = lamports_of_sol(matches, "amount").unwrap();
+
+    let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+    let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
+    let blockhash_query = BlockhashQuery::new_from_matches(matches);
+    let nonce_account = pubkey_of(matches, NONCE_ARG.name);
+    let memo = matches.value_of(MEMO_ARG.name).map(String::from);
+    let (stake_authority, stake_authority_pubkey) =
+        signer_of(matches, STAKE_AUTHORITY_ARG.name, wallet_manager)?;
+    let (nonce_authority, nonce_authority_pubkey) =
+        signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
+    let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
+
+    let mut bulk_signers = vec![stake_authority, fee_payer];
+    if nonce_account.is_some() {
+        bulk_signers.push(nonce_authority);
+    }
+    let signer_info =
+        default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
+
+    Ok(CliCommandInfo {
+        command: CliCommand::MoveStake {
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+            stake_authority: signer_info.index_of(stake_authority_pubkey).unwrap(),
+            lamports,
+            move_lamports,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
+            memo,
+            fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
+        },
+        signers: signer_info.signers,
+    })
+}
+
 pub fn parse_stake_deactivate_stake(
     matches: &ArgMatches<'_>,
     default_signer: &DefaultSigner,
@@ -1970,6 +2135,136 @@ pub fn process_merge_stake(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn process_move_stake(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    source_stake_account_pubkey: &Pubkey,
+    destination_stake_account_pubkey: &Pubkey,
+    stake_authority: SignerIndex,
+    lamports: u64,
+    move_lamports: bool,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    blockhash_query: &BlockhashQuery,
+    nonce_account: Option<Pubkey>,
+    nonce_authority: SignerIndex,
+    memo: Option<&String>,
+    fee_payer: SignerIndex,
+) -> ProcessResult {
+    let fee_payer = config.signers[fee_payer];
+
+    check_unique_pubkeys(
+        (&fee_payer.pubkey(), "fee-payer keypair".to_string()),
+        (
+            source_stake_account_pubkey,
+            "source_stake_account".to_string(),
+        ),
+    )?;
+    check_unique_pubkeys(
+        (&fee_payer.pubkey(), "fee-payer keypair".to_string()),
+        (
+            destination_stake_account_pubkey,
+            "destination_stake_account".to_string(),
+        ),
+    )?;
+    check_unique_pubkeys(
+        (
+            source_stake_account_pubkey,
+            "source_stake_account".to_string(),
+        ),
+        (
+            destination_stake_account_pubkey,
+            "destination_stake_account".to_string(),
+        ),
+    )?;
+
+    let stake_authority = config.signers[stake_authority];
+
+    if !sign_only {
+        for stake_account_address in &[
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+        ] {
+            if let Ok(stake_account) = rpc_client.get_account(stake_account_address) {
+                if stake_account.owner != stake::program::id() {
+                    return Err(CliError::BadParameter(format!(
+                        "Account {} is not a stake account",
+                        stake_account_address
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+
+    let recent_blockhash = blockhash_query.get_blockhash(rpc_client, config.commitment)?;
+
+    let ixs = vec![if move_lamports {
+        stake_instruction::move_lamports(
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+            &stake_authority.pubkey(),
+            lamports,
+        )
+    } else {
+        stake_instruction::move_stake(
+            source_stake_account_pubkey,
+            destination_stake_account_pubkey,
+            &stake_authority.pubkey(),
+            lamports,
+        )
+    }]
+    .with_memo(memo);
+
+    let nonce_authority = config.signers[nonce_authority];
+
+    let message = if let Some(nonce_account) = &nonce_account {
+        Message::new_with_nonce(
+            ixs,
+            Some(&fee_payer.pubkey()),
+            nonce_account,
+            &nonce_authority.pubkey(),
+        )
+    } else {
+        Message::new(&ixs, Some(&fee_payer.pubkey()))
+    };
+    let mut tx = Transaction::new_unsigned(message);
+
+    if sign_only {
+        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        return_signers_with_config(
+            &tx,
+            &config.output_format,
+            &ReturnSignersConfig {
+                dump_transaction_message,
+            },
+        )
+    } else {
+        tx.try_sign(&config.signers, recent_blockhash)?;
+        if let Some(nonce_account) = &nonce_account {
+            let nonce_account = nonce_utils::get_account_with_commitment(
+                rpc_client,
+                nonce_account,
+                config.commitment,
+            )?;
+            check_nonce_account(&nonce_account, &nonce_authority.pubkey(), &recent_blockhash)?;
+        }
+        check_account_for_fee_with_commitment(
+            rpc_client,
+            &tx.message.account_keys[0],
+            &tx.message,
+            config.commitment,
+        )?;
+        let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+            &tx,
+            config.commitment,
+            config.send_transaction_config,
+        );
+        log_instruction_custom_error::<StakeError>(result, config)
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_stake_set_lockup(
     rpc_client: &RpcClient,
@@ -2393,11 +2688,21 @@ pub fn process_delegate_stake(
     nonce_authority: SignerIndex,
     memo: Option<&String>,
     fee_payer: SignerIndex,
+    redelegation_stake_account_pubkey: Option<&Pubkey>,
 ) -> ProcessResult {
     check_unique_pubkeys(
         (&config.signers[0].pubkey(), "cli keypair".to_string()),
         (stake_account_pubkey, "stake_account_pubkey".to_string()),
     )?;
+    if let Some(redelegation_stake_account_pubkey) = redelegation_stake_account_pubkey {
+        check_unique_pubkeys(
+            (stake_account_pubkey, "stake_account_pubkey".to_string()),
+            (
+                redelegation_stake_account_pubkey,
+                "redelegation_stake_account".to_string(),
+            ),
+        )?;
+    }
     let stake_authority = config.signers[stake_authority];
 
     if !sign_only {
@@ -2450,11 +2755,20 @@ pub fn process_delegate_stake(
 
     let recent_blockhash = blockhash_query.get_blockhash(rpc_client, config.commitment)?;
 
-    let ixs = vec![stake_instruction::delegate_stake(
-        stake_account_pubkey,
-        &stake_authority.pubkey(),
-        vote_account_pubkey,
-    )]
+    let ixs = if let Some(redelegation_stake_account_pubkey) = redelegation_stake_account_pubkey {
+        stake_instruction::redelegate(
+            stake_account_pubkey,
+            &stake_authority.pubkey(),
+            vote_account_pubkey,
+            redelegation_stake_account_pubkey,
+        )
+    } else {
+        vec![stake_instruction::delegate_stake(
+            stake_account_pubkey,
+            &stake_authority.pubkey(),
+            vote_account_pubkey,
+        )]
+    }
     .with_memo(memo);
     let nonce_authority = config.signers[nonce_authority];
     let fee_payer = config.signers[fee_payer];
@@ -3824,6 +4138,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
             }
@@ -3855,6 +4170,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -3888,6 +4204,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
             }
@@ -3922,6 +4239,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
             }
@@ -3951,6 +4269,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
             }
@@ -3990,6 +4309,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 1,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -4038,6 +4358,7 @@ mod tests {
                     nonce_authority: 2,
                     memo: None,
                     fee_payer: 1,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -4074,6 +4395,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     fee_payer: 1,
+                    redelegation_stake_account_pubkey: None,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -4082,6 +4404,50 @@ mod tests {
             }
         );
 
+        // Test RedelegateStake Subcommand
+        let (redelegation_stake_account_keypair_file, mut redelegation_stake_account_tmp_file) =
+            make_tmp_file();
+        let redelegation_stake_account_keypair = Keypair::new();
+        write_keypair(
+            &redelegation_stake_account_keypair,
+            redelegation_stake_account_tmp_file.as_file_mut(),
+        )
+        .unwrap();
+        let test_redelegate_stake = test_commands.clone().get_matches_from(vec![
+            "test",
+            "redelegate-stake",
+            &stake_account_string,
+            &vote_account_string,
+            &redelegation_stake_account_keypair_file,
+        ]);
+        assert_eq!(
+            parse_command(&test_redelegate_stake, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::DelegateStake {
+                    stake_account_pubkey,
+                    vote_account_pubkey,
+                    stake_authority: 0,
+                    force: false,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::default(),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    fee_payer: 0,
+                    redelegation_stake_account_pubkey: Some(
+                        redelegation_stake_account_keypair.pubkey()
+                    ),
+                },
+                signers: vec![
+                    read_keypair_file(&default_keypair_file).unwrap().into(),
+                    read_keypair_file(&redelegation_stake_account_keypair_file)
+                        .unwrap()
+                        .into()
+                ],
+            }
+        );
+
         // Test WithdrawStake Subcommand
         let test_withdraw_stake = test_commands.clone().get_matches_from(vec![
             "test",
@@ -4643,5 +5009,38 @@ mod tests {
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into(),],
             }
         );
+
+        // Test MoveStake and MoveStakeLamports SubCommands
+        let source_stake_account_pubkey = solana_sdk::pubkey::new_rand();
+        let destination_stake_account_pubkey = solana_sdk::pubkey::new_rand();
+        for (subcommand, move_lamports) in [("move-stake", false), ("move-stake-lamports", true)] {
+            let test_move_stake = test_commands.clone().get_matches_from(vec![
+                "test",
+                subcommand,
+                &source_stake_account_pubkey.to_string(),
+                &destination_stake_account_pubkey.to_string(),
+                "1.5",
+            ]);
+            assert_eq!(
+                parse_command(&test_move_stake, &default_signer, &mut None).unwrap(),
+                CliCommandInfo {
+                    command: CliCommand::MoveStake {
+                        source_stake_account_pubkey,
+                        destination_stake_account_pubkey,
+                        stake_authority: 0,
+                        lamports: 1_500_000_000,
+                        move_lamports,
+                        sign_only: false,
+                        dump_transaction_message: false,
+                        blockhash_query: BlockhashQuery::default(),
+                        nonce_account: None,
+                        nonce_authority: 0,
+                        memo: None,
+                        fee_payer: 0,
+                    },
+                    signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
+                }
+            );
+        }
     }
 }
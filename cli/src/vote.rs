@@ -770,7 +770,7 @@ pub fn process_create_vote_account(
     )?;
 
     let required_balance = rpc_client
-        .get_minimum_balance_for_rent_exemption(VoteState::size_of())?
+        .get_minimum_balance_for_rent_exemption(VoteState::size_of_with_vote_latencies())?
         .max(1);
     let amount = SpendAmount::Some(required_balance);
 
@@ -1183,8 +1183,13 @@ pub fn process_show_vote_account(
     let mut votes: Vec<CliLockout> = vec![];
     let mut epoch_voting_history: Vec<CliEpochVotingHistory> = vec![];
     if !vote_state.votes.is_empty() {
-        for vote in &vote_state.votes {
-            votes.push(vote.into());
+        let has_latencies = vote_state.vote_latencies.len() == vote_state.votes.len();
+        for (i, vote) in vote_state.votes.iter().enumerate() {
+            let mut vote: CliLockout = vote.into();
+            if has_latencies {
+                vote.latency = vote_state.vote_latencies.get(i).copied();
+            }
+            votes.push(vote);
         }
         for (epoch, credits, prev_credits) in vote_state.epoch_credits().iter().copied() {
             let credits_earned = credits - prev_credits;
@@ -1284,8 +1289,8 @@ pub fn process_withdraw_from_vote_account(
 
     if !sign_only {
         let current_balance = rpc_client.get_balance(vote_account_pubkey)?;
-        let minimum_balance =
-            rpc_client.get_minimum_balance_for_rent_exemption(VoteState::size_of())?;
+        let minimum_balance = rpc_client
+            .get_minimum_balance_for_rent_exemption(VoteState::size_of_with_vote_latencies())?;
         if let SpendAmount::Some(withdraw_amount) = withdraw_amount {
             let balance_remaining = current_balance.saturating_sub(withdraw_amount);
             if balance_remaining < minimum_balance && balance_remaining != 0 {
@@ -113,6 +113,7 @@ fn test_stake_delegation_force() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config).unwrap_err();
 
@@ -129,6 +130,7 @@ fn test_stake_delegation_force() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config).unwrap();
 }
@@ -205,6 +207,7 @@ fn test_seed_stake_delegation_and_deactivation() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config_validator).unwrap();
 
@@ -293,6 +296,7 @@ fn test_stake_delegation_and_deactivation() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config_validator).unwrap();
 
@@ -405,6 +409,7 @@ fn test_offline_stake_delegation_and_deactivation() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     config_offline.output_format = OutputFormat::JsonCompact;
     let sig_response = process_command(&config_offline).unwrap();
@@ -426,6 +431,7 @@ fn test_offline_stake_delegation_and_deactivation() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config_payer).unwrap();
 
@@ -558,6 +564,7 @@ fn test_nonced_stake_delegation_and_deactivation() {
         nonce_authority: 0,
         memo: None,
         fee_payer: 0,
+        redelegation_stake_account_pubkey: None,
     };
     process_command(&config).unwrap();
 
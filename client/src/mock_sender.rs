@@ -320,6 +320,7 @@ impl RpcSender for MockSender {
                         epoch_credits: vec![],
                         last_vote: 0,
                         root_slot: Slot::default(),
+                        vote_latencies: vec![],
                     }],
                 })
             }
@@ -405,6 +406,7 @@ impl RpcSender for MockSender {
                 }])?
             }
             "getBlockTime" => serde_json::to_value(UnixTimestamp::default())?,
+            "getAccountHistory" => json!([]),
             "getEpochSchedule" => serde_json::to_value(EpochSchedule::default())?,
             "getRecentPerformanceSamples" => serde_json::to_value(vec![RpcPerfSample {
                 slot: 347873,
@@ -3334,6 +3334,36 @@ impl RpcClient {
             .map_err(|err| err.into_with_request(request))?
     }
 
+    /// Returns the cost summary recorded for a block.
+    ///
+    /// Cost summaries are recorded for blocks the queried node produced or
+    /// replayed; `None` is returned for blocks without transactions.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getBlockCostInfo`] RPC method.
+    ///
+    /// [`getBlockCostInfo`]: https://docs.solana.com/developing/clients/jsonrpc-api#getblockcostinfo
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     nonblocking::rpc_client::RpcClient,
+    /// # };
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot().await?;
+    /// let block_cost_info = rpc_client.get_block_cost_info(slot).await?;
+    /// #     Ok::<(), ClientError>(())
+    /// # })?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub async fn get_block_cost_info(&self, slot: Slot) -> ClientResult<Option<RpcBlockCostInfo>> {
+        self.send(RpcRequest::GetBlockCostInfo, json!([slot])).await
+    }
+
     /// Returns information about the current epoch.
     ///
     /// This method uses the configured default [commitment level][cl].
@@ -3994,6 +4024,155 @@ impl RpcClient {
             })?
     }
 
+    /// Returns the state of an account as of a past slot.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`, and `slot` must be within the range of
+    /// slots it has recorded account history for.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getAccountInfo`] RPC method.
+    ///
+    /// [`getAccountInfo`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccountinfo
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     nonblocking::rpc_client::RpcClient,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot().await?;
+    /// let account = rpc_client.get_account_at_slot(&alice.pubkey(), slot).await?;
+    /// #     Ok::<(), ClientError>(())
+    /// # })?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub async fn get_account_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> RpcResult<Option<Account>> {
+        let config = RpcGetAccountInfoConfig {
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64Zstd),
+                ..RpcAccountInfoConfig::default()
+            },
+            slot: Some(slot),
+        };
+        let Response {
+            context,
+            value: rpc_account,
+        } = self
+            .send::<Response<Option<UiAccount>>>(
+                RpcRequest::GetAccountInfo,
+                json!([pubkey.to_string(), config]),
+            )
+            .await?;
+        Ok(Response {
+            context,
+            value: rpc_account.and_then(|rpc_account| rpc_account.decode()),
+        })
+    }
+
+    /// Returns the recorded versions of an account, newest first.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`. Up to 1000 versions are returned.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getAccountHistory`] RPC method.
+    ///
+    /// [`getAccountHistory`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccounthistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     nonblocking::rpc_client::RpcClient,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let history = rpc_client.get_account_history(&alice.pubkey()).await?;
+    /// #     Ok::<(), ClientError>(())
+    /// # })?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub async fn get_account_history(
+        &self,
+        pubkey: &Pubkey,
+    ) -> ClientResult<Vec<RpcAccountHistoryEntry>> {
+        self.get_account_history_with_config(pubkey, RpcAccountHistoryConfig::default())
+            .await
+    }
+
+    /// Returns the recorded versions of an account, newest first.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getAccountHistory`] RPC method.
+    ///
+    /// [`getAccountHistory`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccounthistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     nonblocking::rpc_client::RpcClient,
+    /// #     rpc_config::RpcAccountHistoryConfig,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot().await?;
+    /// let config = RpcAccountHistoryConfig {
+    ///     before: Some(slot),
+    ///     limit: Some(10),
+    ///     ..RpcAccountHistoryConfig::default()
+    /// };
+    /// let history = rpc_client.get_account_history_with_config(
+    ///     &alice.pubkey(),
+    ///     config,
+    /// ).await?;
+    /// #     Ok::<(), ClientError>(())
+    /// # })?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub async fn get_account_history_with_config(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcAccountHistoryConfig,
+    ) -> ClientResult<Vec<RpcAccountHistoryEntry>> {
+        self.send(
+            RpcRequest::GetAccountHistory,
+            json!([pubkey.to_string(), config]),
+        )
+        .await
+    }
+
     /// Get the max slot seen from retransmit stage.
     ///
     /// # RPC Reference
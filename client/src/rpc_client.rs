@@ -2757,6 +2757,33 @@ impl RpcClient {
         self.invoke(self.rpc_client.get_block_time(slot))
     }
 
+    /// Returns the cost summary recorded for a block.
+    ///
+    /// Cost summaries are recorded for blocks the queried node produced or
+    /// replayed; `None` is returned for blocks without transactions.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getBlockCostInfo`] RPC method.
+    ///
+    /// [`getBlockCostInfo`]: https://docs.solana.com/developing/clients/jsonrpc-api#getblockcostinfo
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     rpc_client::RpcClient,
+    /// # };
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot()?;
+    /// let block_cost_info = rpc_client.get_block_cost_info(slot)?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub fn get_block_cost_info(&self, slot: Slot) -> ClientResult<Option<RpcBlockCostInfo>> {
+        self.invoke(self.rpc_client.get_block_cost_info(slot))
+    }
+
     /// Returns information about the current epoch.
     ///
     /// This method uses the configured default [commitment level][cl].
@@ -3304,6 +3331,121 @@ impl RpcClient {
         self.invoke(self.rpc_client.get_account_with_config(pubkey, config))
     }
 
+    /// Returns the state of an account as of a past slot.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`, and `slot` must be within the range of
+    /// slots it has recorded account history for.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getAccountInfo`] RPC method.
+    ///
+    /// [`getAccountInfo`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccountinfo
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     rpc_client::RpcClient,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot()?;
+    /// let account = rpc_client.get_account_at_slot(&alice.pubkey(), slot)?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub fn get_account_at_slot(&self, pubkey: &Pubkey, slot: Slot) -> RpcResult<Option<Account>> {
+        self.invoke(self.rpc_client.get_account_at_slot(pubkey, slot))
+    }
+
+    /// Returns the recorded versions of an account, newest first.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`. Up to 1000 versions are returned.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getAccountHistory`] RPC method.
+    ///
+    /// [`getAccountHistory`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccounthistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     rpc_client::RpcClient,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let history = rpc_client.get_account_history(&alice.pubkey())?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub fn get_account_history(
+        &self,
+        pubkey: &Pubkey,
+    ) -> ClientResult<Vec<RpcAccountHistoryEntry>> {
+        self.invoke(self.rpc_client.get_account_history(pubkey))
+    }
+
+    /// Returns the recorded versions of an account, newest first.
+    ///
+    /// The queried node must have been started with
+    /// `--enable-rpc-account-history`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getAccountHistory`] RPC method.
+    ///
+    /// [`getAccountHistory`]: https://docs.solana.com/developing/clients/jsonrpc-api#getaccounthistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_client::{
+    /// #     client_error::ClientError,
+    /// #     rpc_client::RpcClient,
+    /// #     rpc_config::RpcAccountHistoryConfig,
+    /// # };
+    /// # use solana_sdk::{
+    /// #     signature::Signer,
+    /// #     signer::keypair::Keypair,
+    /// # };
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot()?;
+    /// let config = RpcAccountHistoryConfig {
+    ///     before: Some(slot),
+    ///     limit: Some(10),
+    ///     ..RpcAccountHistoryConfig::default()
+    /// };
+    /// let history = rpc_client.get_account_history_with_config(
+    ///     &alice.pubkey(),
+    ///     config,
+    /// )?;
+    /// # Ok::<(), ClientError>(())
+    /// ```
+    pub fn get_account_history_with_config(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcAccountHistoryConfig,
+    ) -> ClientResult<Vec<RpcAccountHistoryEntry>> {
+        self.invoke(
+            self.rpc_client
+                .get_account_history_with_config(pubkey, config),
+        )
+    }
+
     /// Get the max slot seen from retransmit stage.
     ///
     /// # RPC Reference
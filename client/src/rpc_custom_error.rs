@@ -23,6 +23,8 @@ pub const JSON_RPC_SERVER_ERROR_TRANSACTION_SIGNATURE_LEN_MISMATCH: i64 = -32013
 pub const JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET: i64 = -32014;
 pub const JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION: i64 = -32015;
 pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
+pub const JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE: i64 = -32017;
+pub const JSON_RPC_SERVER_ERROR_ACCOUNT_STATE_NOT_AVAILABLE: i64 = -32018;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -64,6 +66,10 @@ pub enum RpcCustomError {
     UnsupportedTransactionVersion(u8),
     #[error("MinContextSlotNotReached")]
     MinContextSlotNotReached { context_slot: Slot },
+    #[error("AccountHistoryNotAvailable")]
+    AccountHistoryNotAvailable,
+    #[error("AccountStateNotAvailable")]
+    AccountStateNotAvailable { slot: Slot },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -204,6 +210,16 @@ impl From<RpcCustomError> for Error {
                     context_slot,
                 })),
             },
+            RpcCustomError::AccountHistoryNotAvailable => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE),
+                message: "Account history is not available from this node".to_string(),
+                data: None,
+            },
+            RpcCustomError::AccountStateNotAvailable { slot } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_ACCOUNT_STATE_NOT_AVAILABLE),
+                message: format!("Account state not available for slot {}", slot),
+                data: None,
+            },
         }
     }
 }
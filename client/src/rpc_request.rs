@@ -12,6 +12,7 @@ pub enum RpcRequest {
         method: &'static str,
     },
     DeregisterNode,
+    GetAccountHistory,
     GetAccountInfo,
     GetBalance,
     GetBlock,
@@ -20,6 +21,7 @@ pub enum RpcRequest {
     GetBlocks,
     GetBlocksWithLimit,
     GetBlockTime,
+    GetBlockCostInfo,
     GetClusterNodes,
     #[deprecated(since = "1.7.0", note = "Please use RpcRequest::GetBlock instead")]
     GetConfirmedBlock,
@@ -119,6 +121,7 @@ impl fmt::Display for RpcRequest {
         let method = match self {
             RpcRequest::Custom { method } => method,
             RpcRequest::DeregisterNode => "deregisterNode",
+            RpcRequest::GetAccountHistory => "getAccountHistory",
             RpcRequest::GetAccountInfo => "getAccountInfo",
             RpcRequest::GetBalance => "getBalance",
             RpcRequest::GetBlock => "getBlock",
@@ -127,6 +130,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetBlocks => "getBlocks",
             RpcRequest::GetBlocksWithLimit => "getBlocksWithLimit",
             RpcRequest::GetBlockTime => "getBlockTime",
+            RpcRequest::GetBlockCostInfo => "getBlockCostInfo",
             RpcRequest::GetClusterNodes => "getClusterNodes",
             RpcRequest::GetConfirmedBlock => "getConfirmedBlock",
             RpcRequest::GetConfirmedBlocks => "getConfirmedBlocks",
@@ -195,6 +199,7 @@ pub const MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS: usize = 256;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE: u64 = 10_000;
 pub const MAX_GET_CONFIRMED_BLOCKS_RANGE: u64 = 500_000;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT: usize = 1_000;
+pub const MAX_GET_ACCOUNT_HISTORY_LIMIT: usize = 1_000;
 pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
 pub const NUM_LARGEST_ACCOUNTS: usize = 20;
 pub const MAX_GET_PROGRAM_ACCOUNT_FILTERS: usize = 4;
@@ -383,6 +383,11 @@ pub struct RpcVoteAccountInfo {
 
     /// Current root slot for this vote account (0 if not root slot exists)
     pub root_slot: Slot,
+
+    /// Landing latency, in slots, of each vote in the vote account's lockout
+    /// stack (empty unless vote credits are awarded by vote latency)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vote_latencies: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -483,6 +488,42 @@ pub struct RpcConfirmedTransactionStatusWithSignature {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountHistoryEntry {
+    pub slot: Slot,
+    pub account: UiAccount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockCostInfo {
+    pub slot: Slot,
+    pub block_cost: u64,
+    pub vote_cost: u64,
+    pub transaction_count: u64,
+    pub account_data_size: u64,
+    pub costliest_accounts: Vec<RpcAccountCost>,
+    pub rejected_transaction_counts: RpcRejectedTransactionCounts,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountCost {
+    pub pubkey: String,
+    pub cost: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRejectedTransactionCounts {
+    pub would_exceed_block_max_limit: u64,
+    pub would_exceed_vote_max_limit: u64,
+    pub would_exceed_account_max_limit: u64,
+    pub would_exceed_account_data_block_limit: u64,
+    pub would_exceed_account_data_total_limit: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcPerfSample {
@@ -0,0 +1,112 @@
+//! The `account_history_service` records every account version written by a
+//! rooted bank into the blockstore, so RPC can serve account state at past slots.
+use {
+    crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
+    solana_ledger::{blockstore::Blockstore, blockstore_meta::AccountHistoryMeta},
+    solana_measure::measure::Measure,
+    solana_runtime::bank::Bank,
+    solana_sdk::{account::Account, clock::Slot, pubkey::Pubkey},
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+/// A newly rooted slot and the accounts it wrote
+pub type RootedSlotAccounts = (Slot, Vec<(Pubkey, AccountHistoryMeta)>);
+pub type AccountHistoryReceiver = Receiver<Vec<RootedSlotAccounts>>;
+pub type AccountHistorySender = Sender<Vec<RootedSlotAccounts>>;
+
+pub struct AccountHistoryService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl AccountHistoryService {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        account_history_receiver: AccountHistoryReceiver,
+        blockstore: Arc<Blockstore>,
+        exit: &Arc<AtomicBool>,
+    ) -> Self {
+        let exit = exit.clone();
+        let thread_hdl = Builder::new()
+            .name("solana-account-history".to_string())
+            .spawn(move || loop {
+                if exit.load(Ordering::Relaxed) {
+                    break;
+                }
+                match account_history_receiver.recv_timeout(Duration::from_secs(1)) {
+                    Err(RecvTimeoutError::Disconnected) => {
+                        break;
+                    }
+                    Ok(rooted_slots_accounts) => {
+                        for (slot, accounts) in rooted_slots_accounts {
+                            Self::record_account_history(slot, accounts, &blockstore);
+                        }
+                    }
+                    _ => {}
+                }
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    /// Collect the accounts written by the banks that are about to be rooted, and whether each
+    /// of them was created by its bank.
+    ///
+    /// This must be called before the banks are rooted in `BankForks`: once their slots are
+    /// roots, clean and shrink may drop the versions a newer root has overwritten.
+    pub fn collect_rooted_slots_accounts(
+        newly_rooted_banks: &[&Arc<Bank>],
+    ) -> Vec<RootedSlotAccounts> {
+        newly_rooted_banks
+            .iter()
+            .map(|bank| {
+                let parent = bank.parent();
+                let accounts = bank
+                    .get_all_accounts_modified_since_parent()
+                    .into_iter()
+                    .map(|(pubkey, account)| {
+                        let created = parent
+                            .as_ref()
+                            .and_then(|parent| parent.get_account_with_fixed_root(&pubkey))
+                            .is_none();
+                        let meta = AccountHistoryMeta {
+                            account: Account::from(account),
+                            created,
+                        };
+                        (pubkey, meta)
+                    })
+                    .collect();
+                (bank.slot(), accounts)
+            })
+            .collect()
+    }
+
+    fn record_account_history(
+        slot: Slot,
+        accounts: Vec<(Pubkey, AccountHistoryMeta)>,
+        blockstore: &Blockstore,
+    ) {
+        let mut measure = Measure::start("record_account_history");
+        let num_accounts = accounts.len();
+        if let Err(e) = blockstore.write_account_history(slot, accounts) {
+            error!("write_account_history failed: slot {:?} {:?}", slot, e);
+        }
+        measure.stop();
+        datapoint_info!(
+            "account_history_service",
+            ("slot", slot, i64),
+            ("num_accounts", num_accounts, i64),
+            ("record_us", measure.as_us(), i64),
+        );
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
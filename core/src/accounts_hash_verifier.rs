@@ -141,6 +141,8 @@ impl AccountsHashVerifier {
                     use_write_cache: false,
                     epoch_schedule: &accounts_package.epoch_schedule,
                     rent_collector: &accounts_package.rent_collector,
+                    // a validator restarting from this snapshot recalculates this hash
+                    retain_hash_cache: accounts_package.snapshot_type.is_some(),
                 },
                 &sorted_storages,
                 timings,
@@ -1447,7 +1447,8 @@ impl BankingStage {
     ) -> ProcessTransactionBatchOutput {
         let mut cost_model_time = Measure::start("cost_model");
 
-        let transaction_costs = qos_service.compute_transaction_costs(txs.iter());
+        let transaction_costs =
+            qos_service.compute_transaction_costs(txs.iter(), &bank.feature_set);
 
         let (transactions_qos_results, num_included) =
             qos_service.select_transactions_per_cost(txs.iter(), transaction_costs.iter(), bank);
@@ -1,21 +1,27 @@
 //! this service receives instruction ExecuteTimings from replay_stage,
 //! update cost_model which is shared with banking_stage to optimize
-//! packing transactions into block; it also triggers persisting cost
-//! table to blockstore.
+//! packing transactions into block; it also persists the cost summary
+//! of each frozen bank to blockstore.
 
 use {
     crossbeam_channel::Receiver,
-    solana_ledger::blockstore::Blockstore,
+    solana_ledger::{
+        blockstore::Blockstore,
+        blockstore_meta::{BlockCostInfo, RejectedTransactionCounts},
+    },
     solana_measure::measure::Measure,
     solana_program_runtime::timings::ExecuteTimings,
-    solana_runtime::{bank::Bank, cost_model::CostModel},
-    solana_sdk::timing::timestamp,
+    solana_runtime::{bank::Bank, cost_model::CostModel, cost_tracker::CostTracker},
+    solana_sdk::{clock::Slot, timing::timestamp},
     std::{
         sync::{Arc, RwLock},
         thread::{self, Builder, JoinHandle},
     },
 };
 
+// Number of costliest writable accounts persisted per block
+const MAX_BLOCK_COST_ACCOUNTS: usize = 10;
+
 #[derive(Default)]
 pub struct CostUpdateServiceTiming {
     last_print: u64,
@@ -89,7 +95,7 @@ impl CostUpdateService {
     }
 
     fn service_loop(
-        _blockstore: Arc<Blockstore>,
+        blockstore: Arc<Blockstore>,
         cost_model: Arc<RwLock<CostModel>>,
         cost_update_receiver: CostUpdateReceiver,
     ) {
@@ -97,7 +103,12 @@ impl CostUpdateService {
         for cost_update in cost_update_receiver.iter() {
             match cost_update {
                 CostUpdate::FrozenBank { bank } => {
-                    bank.read_cost_tracker().unwrap().report_stats(bank.slot());
+                    let block_cost_info = {
+                        let cost_tracker = bank.read_cost_tracker().unwrap();
+                        cost_tracker.report_stats(bank.slot());
+                        Self::block_cost_info(&cost_tracker)
+                    };
+                    Self::persist_block_cost_info(&blockstore, bank.slot(), &block_cost_info);
                 }
                 CostUpdate::ExecuteTiming {
                     mut execute_timings,
@@ -113,6 +124,46 @@ impl CostUpdateService {
         }
     }
 
+    /// The cost trackers of produced and replayed banks alike track their
+    /// transactions, so only banks without any have nothing worth persisting
+    fn persist_block_cost_info(
+        blockstore: &Blockstore,
+        slot: Slot,
+        block_cost_info: &BlockCostInfo,
+    ) {
+        if block_cost_info.transaction_count == 0
+            && block_cost_info.rejected_transaction_counts == RejectedTransactionCounts::default()
+        {
+            return;
+        }
+        if let Err(err) = blockstore.write_block_cost_info(slot, block_cost_info) {
+            warn!(
+                "failed to write block cost info for slot {}: {:?}",
+                slot, err
+            );
+        }
+    }
+
+    fn block_cost_info(cost_tracker: &CostTracker) -> BlockCostInfo {
+        let error_counts = cost_tracker.error_counts();
+        BlockCostInfo {
+            block_cost: cost_tracker.block_cost(),
+            vote_cost: cost_tracker.vote_cost(),
+            transaction_count: cost_tracker.transaction_count(),
+            account_data_size: cost_tracker.account_data_size(),
+            costliest_accounts: cost_tracker.costliest_accounts(MAX_BLOCK_COST_ACCOUNTS),
+            rejected_transaction_counts: RejectedTransactionCounts {
+                would_exceed_block_max_limit: error_counts.would_exceed_block_max_limit,
+                would_exceed_vote_max_limit: error_counts.would_exceed_vote_max_limit,
+                would_exceed_account_max_limit: error_counts.would_exceed_account_max_limit,
+                would_exceed_account_data_block_limit: error_counts
+                    .would_exceed_account_data_block_limit,
+                would_exceed_account_data_total_limit: error_counts
+                    .would_exceed_account_data_total_limit,
+            },
+        }
+    }
+
     fn update_cost_model(
         cost_model: &RwLock<CostModel>,
         execute_timings: &mut ExecuteTimings,
@@ -140,7 +191,51 @@ impl CostUpdateService {
 
 #[cfg(test)]
 mod tests {
-    use {super::*, solana_program_runtime::timings::ProgramTiming, solana_sdk::pubkey::Pubkey};
+    use {
+        super::*, solana_ledger::get_tmp_ledger_path_auto_delete,
+        solana_program_runtime::timings::ProgramTiming,
+        solana_runtime::cost_model::TransactionCost, solana_sdk::pubkey::Pubkey,
+    };
+
+    #[test]
+    fn test_persist_block_cost_info() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        // nothing tracked, nothing persisted
+        let mut cost_tracker = CostTracker::default();
+        let block_cost_info = CostUpdateService::block_cost_info(&cost_tracker);
+        CostUpdateService::persist_block_cost_info(&blockstore, 1, &block_cost_info);
+        assert_eq!(blockstore.get_block_cost_info(1).unwrap(), None);
+
+        let writable_accounts: Vec<_> = (0..MAX_BLOCK_COST_ACCOUNTS + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let tx_cost = TransactionCost {
+            writable_accounts: writable_accounts.clone(),
+            bpf_execution_cost: 100,
+            account_data_size: 10,
+            ..TransactionCost::default()
+        };
+        cost_tracker.try_add(&tx_cost).unwrap();
+        let block_cost_info = CostUpdateService::block_cost_info(&cost_tracker);
+        CostUpdateService::persist_block_cost_info(&blockstore, 2, &block_cost_info);
+
+        let persisted = blockstore.get_block_cost_info(2).unwrap().unwrap();
+        assert_eq!(persisted.block_cost, tx_cost.sum());
+        assert_eq!(persisted.vote_cost, 0);
+        assert_eq!(persisted.transaction_count, 1);
+        assert_eq!(persisted.account_data_size, 10);
+        assert_eq!(persisted.costliest_accounts.len(), MAX_BLOCK_COST_ACCOUNTS);
+        assert!(persisted
+            .costliest_accounts
+            .iter()
+            .all(|(key, cost)| writable_accounts.contains(key) && *cost == tx_cost.sum()));
+        assert_eq!(
+            persisted.rejected_transaction_counts,
+            RejectedTransactionCounts::default()
+        );
+    }
 
     #[test]
     fn test_update_cost_model_with_empty_execute_timings() {
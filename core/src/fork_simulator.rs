@@ -0,0 +1,117 @@
+//! Deterministic fork simulation for single node clusters
+//!
+//! A lone validator never forks, so clients never see the rollbacks of a real cluster. Once
+//! enabled, the validator periodically builds a fork without voting on it: each of the fork's
+//! slots becomes the processed slot, then the fork is abandoned. Its slots are marked dead and the
+//! validator resets to the fork's parent, so the slots the fork took are skipped.
+
+use solana_sdk::clock::Slot;
+
+/// Schedule of the forks a validator builds and abandons
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkSimulationConfig {
+    /// Number of slots voted on before each fork
+    pub interval: u64,
+    /// Number of slots of each fork that are processed before it's abandoned
+    ///
+    /// The fork is abandoned once the slot that follows them is frozen, which is never processed.
+    pub depth: u64,
+}
+
+/// What replay does with a bank it could vote on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkSimulationAction {
+    /// The bank isn't on a simulated fork
+    Vote,
+    /// The bank extends a simulated fork, make it the processed slot instead of voting on it
+    Process,
+    /// The bank is on a simulated fork and was already processed
+    Hold,
+    /// The bank completes a simulated fork, abandon the fork that starts at the given slot
+    Abandon(Slot),
+}
+
+#[derive(Debug)]
+pub struct ForkSimulator {
+    config: ForkSimulationConfig,
+    slots_until_fork: u64,
+    fork_slots: Vec<Slot>,
+}
+
+impl ForkSimulator {
+    pub fn new(config: ForkSimulationConfig) -> Self {
+        Self {
+            config,
+            slots_until_fork: config.interval,
+            fork_slots: vec![],
+        }
+    }
+
+    /// Decide what to do with the bank of `slot`, which replay selected to vote on
+    pub fn votable_bank_action(&mut self, slot: Slot) -> ForkSimulationAction {
+        if self.fork_slots.contains(&slot) {
+            return ForkSimulationAction::Hold;
+        }
+        if self.fork_slots.is_empty() && self.slots_until_fork > 0 {
+            self.slots_until_fork -= 1;
+            return ForkSimulationAction::Vote;
+        }
+
+        self.fork_slots.push(slot);
+        if (self.fork_slots.len() as u64) <= self.config.depth {
+            return ForkSimulationAction::Process;
+        }
+        let fork_slot = self.fork_slots[0];
+        self.fork_slots.clear();
+        self.slots_until_fork = self.config.interval;
+        ForkSimulationAction::Abandon(fork_slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_votable_bank_action() {
+        let config = ForkSimulationConfig {
+            interval: 4,
+            depth: 2,
+        };
+        let mut fork_simulator = ForkSimulator::new(config);
+        for slot in 10..14 {
+            assert_eq!(
+                fork_simulator.votable_bank_action(slot),
+                ForkSimulationAction::Vote
+            );
+        }
+        assert_eq!(
+            fork_simulator.votable_bank_action(14),
+            ForkSimulationAction::Process
+        );
+        assert_eq!(
+            fork_simulator.votable_bank_action(14),
+            ForkSimulationAction::Hold
+        );
+        assert_eq!(
+            fork_simulator.votable_bank_action(15),
+            ForkSimulationAction::Process
+        );
+        assert_eq!(
+            fork_simulator.votable_bank_action(16),
+            ForkSimulationAction::Abandon(14)
+        );
+
+        // The validator resets to slot 13 and skips the fork's slots
+        for slot in [17, 18, 19, 20] {
+            assert_eq!(
+                fork_simulator.votable_bank_action(slot),
+                ForkSimulationAction::Vote
+            );
+        }
+        assert_eq!(
+            fork_simulator.votable_bank_action(21),
+            ForkSimulationAction::Process
+        );
+    }
+}
@@ -7,6 +7,7 @@
 //! command-line tools to spin up validators and a Rust library
 //!
 
+pub mod account_history_service;
 pub mod accounts_hash_verifier;
 pub mod ancestor_hashes_service;
 pub mod banking_stage;
@@ -26,6 +27,7 @@ pub mod duplicate_repair_status;
 pub mod fetch_stage;
 pub mod find_packet_sender_stake_stage;
 pub mod fork_choice;
+pub mod fork_simulator;
 pub mod gen_keys;
 pub mod heaviest_subtree_fork_choice;
 pub mod latest_validator_votes_for_frozen_banks;
@@ -13,6 +13,7 @@ use {
     },
     solana_sdk::{
         clock::Slot,
+        feature_set::FeatureSet,
         transaction::{self, SanitizedTransaction, TransactionError},
     },
     std::{
@@ -92,12 +93,13 @@ impl QosService {
     pub fn compute_transaction_costs<'a>(
         &self,
         transactions: impl Iterator<Item = &'a SanitizedTransaction>,
+        feature_set: &FeatureSet,
     ) -> Vec<TransactionCost> {
         let mut compute_cost_time = Measure::start("compute_cost_time");
         let cost_model = self.cost_model.read().unwrap();
         let txs_costs: Vec<_> = transactions
             .map(|tx| {
-                let cost = cost_model.calculate_cost(tx);
+                let cost = cost_model.calculate_cost(tx, feature_set);
                 debug!(
                     "transaction {:?}, cost {:?}, cost sum {}",
                     tx,
@@ -584,7 +586,8 @@ mod tests {
 
         let cost_model = Arc::new(RwLock::new(CostModel::default()));
         let qos_service = QosService::new(cost_model.clone(), 1);
-        let txs_costs = qos_service.compute_transaction_costs(txs.iter());
+        let txs_costs =
+            qos_service.compute_transaction_costs(txs.iter(), &FeatureSet::all_enabled());
 
         // verify the size of txs_costs and its contents
         assert_eq!(txs_costs.len(), txs.len());
@@ -594,7 +597,11 @@ mod tests {
             .map(|(index, cost)| {
                 assert_eq!(
                     cost.sum(),
-                    cost_model.read().unwrap().calculate_cost(&txs[index]).sum()
+                    cost_model
+                        .read()
+                        .unwrap()
+                        .calculate_cost(&txs[index], &FeatureSet::all_enabled())
+                        .sum()
                 );
             })
             .collect_vec();
@@ -625,15 +632,20 @@ mod tests {
         let transfer_tx_cost = cost_model
             .read()
             .unwrap()
-            .calculate_cost(&transfer_tx)
+            .calculate_cost(&transfer_tx, &FeatureSet::all_enabled())
+            .sum();
+        let vote_tx_cost = cost_model
+            .read()
+            .unwrap()
+            .calculate_cost(&vote_tx, &FeatureSet::all_enabled())
             .sum();
-        let vote_tx_cost = cost_model.read().unwrap().calculate_cost(&vote_tx).sum();
 
         // make a vec of txs
         let txs = vec![transfer_tx.clone(), vote_tx.clone(), transfer_tx, vote_tx];
 
         let qos_service = QosService::new(cost_model, 1);
-        let txs_costs = qos_service.compute_transaction_costs(txs.iter());
+        let txs_costs =
+            qos_service.compute_transaction_costs(txs.iter(), &FeatureSet::all_enabled());
 
         // set cost tracker limit to fit 1 transfer tx and 1 vote tx
         let cost_limit = transfer_tx_cost + vote_tx_cost;
@@ -673,7 +685,8 @@ mod tests {
         // assert all tx_costs should be applied to cost_tracker if all execution_results are all committed
         {
             let qos_service = QosService::new(Arc::new(RwLock::new(CostModel::default())), 1);
-            let txs_costs = qos_service.compute_transaction_costs(txs.iter());
+            let txs_costs =
+                qos_service.compute_transaction_costs(txs.iter(), &FeatureSet::all_enabled());
             let total_txs_cost: u64 = txs_costs.iter().map(|cost| cost.sum()).sum();
             let (qos_results, _num_included) =
                 qos_service.select_transactions_per_cost(txs.iter(), txs_costs.iter(), &bank);
@@ -726,7 +739,8 @@ mod tests {
         // assert all tx_costs should be removed from cost_tracker if all execution_results are all Not Committed
         {
             let qos_service = QosService::new(Arc::new(RwLock::new(CostModel::default())), 1);
-            let txs_costs = qos_service.compute_transaction_costs(txs.iter());
+            let txs_costs =
+                qos_service.compute_transaction_costs(txs.iter(), &FeatureSet::all_enabled());
             let total_txs_cost: u64 = txs_costs.iter().map(|cost| cost.sum()).sum();
             let (qos_results, _num_included) =
                 qos_service.select_transactions_per_cost(txs.iter(), txs_costs.iter(), &bank);
@@ -766,7 +780,8 @@ mod tests {
         // assert only commited tx_costs are applied cost_tracker
         {
             let qos_service = QosService::new(Arc::new(RwLock::new(CostModel::default())), 1);
-            let txs_costs = qos_service.compute_transaction_costs(txs.iter());
+            let txs_costs =
+                qos_service.compute_transaction_costs(txs.iter(), &FeatureSet::all_enabled());
             let total_txs_cost: u64 = txs_costs.iter().map(|cost| cost.sum()).sum();
             let (qos_results, _num_included) =
                 qos_service.select_transactions_per_cost(txs.iter(), txs_costs.iter(), &bank);
@@ -1,6 +1,7 @@
 //! The `replay_stage` replays transactions broadcast by the leader.
 use {
     crate::{
+        account_history_service::{AccountHistorySender, AccountHistoryService},
         ancestor_hashes_service::AncestorHashesReplayUpdateSender,
         broadcast_stage::RetransmitSlotsSender,
         cache_block_meta_service::CacheBlockMetaSender,
@@ -16,6 +17,7 @@ use {
         },
         cost_update_service::CostUpdate,
         fork_choice::{ForkChoice, SelectVoteAndResetForkResult},
+        fork_simulator::{ForkSimulationAction, ForkSimulationConfig, ForkSimulator},
         heaviest_subtree_fork_choice::HeaviestSubtreeForkChoice,
         latest_validator_votes_for_frozen_banks::LatestValidatorVotesForFrozenBanks,
         progress_map::{ForkProgress, ProgressMap, PropagatedStats},
@@ -135,6 +137,7 @@ pub struct ReplayStageConfig {
     pub transaction_status_sender: Option<TransactionStatusSender>,
     pub rewards_recorder_sender: Option<RewardsRecorderSender>,
     pub cache_block_meta_sender: Option<CacheBlockMetaSender>,
+    pub account_history_sender: Option<AccountHistorySender>,
     pub bank_notification_sender: Option<BankNotificationSender>,
     pub wait_for_vote_to_start_leader: bool,
     pub ancestor_hashes_replay_update_sender: AncestorHashesReplayUpdateSender,
@@ -142,6 +145,8 @@ pub struct ReplayStageConfig {
     // Stops voting until this slot has been reached. Should be used to avoid
     // duplicate voting which can lead to slashing.
     pub wait_to_vote_slot: Option<Slot>,
+    // Builds and abandons forks on this schedule instead of voting on them, see `ForkSimulator`
+    pub fork_simulation: Option<ForkSimulationConfig>,
 }
 
 #[derive(Default)]
@@ -390,11 +395,13 @@ impl ReplayStage {
             transaction_status_sender,
             rewards_recorder_sender,
             cache_block_meta_sender,
+            account_history_sender,
             bank_notification_sender,
             wait_for_vote_to_start_leader,
             ancestor_hashes_replay_update_sender,
             tower_storage,
             wait_to_vote_slot,
+            fork_simulation,
         } = config;
 
         trace!("replay stage");
@@ -434,6 +441,7 @@ impl ReplayStage {
                 let mut latest_validator_votes_for_frozen_banks: LatestValidatorVotesForFrozenBanks = LatestValidatorVotesForFrozenBanks::default();
                 let mut voted_signatures = Vec::new();
                 let mut has_new_vote_been_rooted = !wait_for_vote_to_start_leader;
+                let mut fork_simulator = fork_simulation.map(ForkSimulator::new);
                 let mut last_vote_refresh_time = LastVoteRefreshTime {
                     last_refresh_time: Instant::now(),
                     last_print_time: Instant::now(),
@@ -640,8 +648,8 @@ impl ReplayStage {
                     let mut select_vote_and_reset_forks_time =
                         Measure::start("select_vote_and_reset_forks");
                     let SelectVoteAndResetForkResult {
-                        vote_bank,
-                        reset_bank,
+                        mut vote_bank,
+                        mut reset_bank,
                         heaviest_fork_failures,
                     } = Self::select_vote_and_reset_forks(
                         &heaviest_bank,
@@ -675,6 +683,22 @@ impl ReplayStage {
                     }
                     heaviest_fork_failures_time.stop();
 
+                    if let Some(fork_simulator) = fork_simulator.as_mut() {
+                        Self::simulate_fork(
+                            fork_simulator,
+                            &mut vote_bank,
+                            &mut reset_bank,
+                            &mut ancestors,
+                            &mut descendants,
+                            &mut progress,
+                            &mut heaviest_subtree_fork_choice,
+                            &bank_forks,
+                            &blockstore,
+                            &rpc_subscriptions,
+                            &lockouts_sender,
+                        );
+                    }
+
                     let mut voting_time = Measure::start("voting_time");
                     // Vote on a fork
                     if let Some((ref vote_bank, ref switch_fork_decision)) = vote_bank {
@@ -707,6 +731,7 @@ impl ReplayStage {
                             &block_commitment_cache,
                             &mut heaviest_subtree_fork_choice,
                             &bank_notification_sender,
+                            &account_history_sender,
                             &mut duplicate_slots_tracker,
                             &mut gossip_duplicate_confirmed_slots,
                             &mut unfrozen_gossip_verified_vote_hashes,
@@ -1230,27 +1255,47 @@ impl ReplayStage {
     ) {
         warn!("purging slot {}", duplicate_slot);
 
-        // Doesn't need to be root bank, just needs a common bank to
-        // access the status cache and accounts
-        let slot_descendants = descendants.get(&duplicate_slot).cloned();
-        if slot_descendants.is_none() {
-            // Root has already moved past this slot, no need to purge it
-            if root_bank.slot() <= duplicate_slot {
-                blockstore.clear_unconfirmed_slot(duplicate_slot);
+        match Self::purge_slot_and_descendants(
+            duplicate_slot,
+            ancestors,
+            descendants,
+            progress,
+            root_bank,
+            bank_forks,
+        ) {
+            None => {
+                // Root has already moved past this slot, no need to purge it
+                if root_bank.slot() <= duplicate_slot {
+                    blockstore.clear_unconfirmed_slot(duplicate_slot);
+                }
+            }
+            Some(purged_slots) => {
+                for slot in purged_slots {
+                    // Clear the slot-related data in blockstore. This will:
+                    // 1) Clear old shreds allowing new ones to be inserted
+                    // 2) Clear the "dead" flag allowing ReplayStage to start replaying
+                    // this slot
+                    blockstore.clear_unconfirmed_slot(slot);
+                }
             }
-
-            return;
         }
+    }
+
+    /// Remove the banks and progress of `slot` and its descendants, returns the purged slots, or
+    /// `None` if the slot isn't in `descendants` anymore
+    fn purge_slot_and_descendants(
+        slot: Slot,
+        ancestors: &mut HashMap<Slot, HashSet<Slot>>,
+        descendants: &mut HashMap<Slot, HashSet<Slot>>,
+        progress: &mut ProgressMap,
+        root_bank: &Bank,
+        bank_forks: &RwLock<BankForks>,
+    ) -> Option<Vec<Slot>> {
+        let slot_descendants = descendants.get(&slot).cloned()?;
 
         // Clear the ancestors/descendants map to keep them
         // consistent
-        let slot_descendants = slot_descendants.unwrap();
-        Self::purge_ancestors_descendants(
-            duplicate_slot,
-            &slot_descendants,
-            ancestors,
-            descendants,
-        );
+        Self::purge_ancestors_descendants(slot, &slot_descendants, ancestors, descendants);
 
         // Grab the Slot and BankId's of the banks we need to purge, then clear the banks
         // from BankForks
@@ -1258,9 +1303,9 @@ impl ReplayStage {
             let mut w_bank_forks = bank_forks.write().unwrap();
             slot_descendants
                 .iter()
-                .chain(std::iter::once(&duplicate_slot))
+                .chain(std::iter::once(&slot))
                 .map(|slot| {
-                    // Clear the duplicate banks from BankForks
+                    // Clear the purged banks from BankForks
                     let bank = w_bank_forks
                         .remove(*slot)
                         .expect("BankForks should not have been purged yet");
@@ -1271,7 +1316,8 @@ impl ReplayStage {
 
         // Clear the accounts for these slots so that any ongoing RPC scans fail.
         // These have to be atomically cleared together in the same batch, in order
-        // to prevent RPC from seeing inconsistent results in scans.
+        // to prevent RPC from seeing inconsistent results in scans. Doesn't need to be
+        // root bank, just needs a common bank to access the status cache and accounts
         root_bank.remove_unrooted_slots(&slots_to_purge);
 
         // Once the slots above have been purged, now it's safe to remove the banks from
@@ -1279,24 +1325,114 @@ impl ReplayStage {
         // `remove_unrooted_slots()` call.
         drop(removed_banks);
 
-        for (slot, slot_id) in slots_to_purge {
-            warn!(
-                "purging descendant: {} with slot_id {}, of slot {}",
-                slot, slot_id, duplicate_slot
-            );
-            // Clear the slot signatures from status cache for this slot.
-            // TODO: What about RPC queries that had already cloned the Bank for this slot
-            // and are looking up the signature for this slot?
-            root_bank.clear_slot_signatures(slot);
-
-            // Clear the slot-related data in blockstore. This will:
-            // 1) Clear old shreds allowing new ones to be inserted
-            // 2) Clear the "dead" flag allowing ReplayStage to start replaying
-            // this slot
-            blockstore.clear_unconfirmed_slot(slot);
-
-            // Clear the progress map of these forks
-            let _ = progress.remove(&slot);
+        Some(
+            slots_to_purge
+                .into_iter()
+                .map(|(purged_slot, slot_id)| {
+                    warn!(
+                        "purging descendant: {} with slot_id {}, of slot {}",
+                        purged_slot, slot_id, slot
+                    );
+                    // Clear the slot signatures from status cache for this slot.
+                    // TODO: What about RPC queries that had already cloned the Bank for this slot
+                    // and are looking up the signature for this slot?
+                    root_bank.clear_slot_signatures(purged_slot);
+
+                    // Clear the progress map of these forks
+                    let _ = progress.remove(&purged_slot);
+                    purged_slot
+                })
+                .collect(),
+        )
+    }
+
+    /// Apply the fork simulator's decision on `vote_bank`: update the processed commitment
+    /// instead of voting on simulated forks, and abandon them once they're complete
+    #[allow(clippy::too_many_arguments)]
+    fn simulate_fork(
+        fork_simulator: &mut ForkSimulator,
+        vote_bank: &mut Option<(Arc<Bank>, SwitchForkDecision)>,
+        reset_bank: &mut Option<Arc<Bank>>,
+        ancestors: &mut HashMap<Slot, HashSet<Slot>>,
+        descendants: &mut HashMap<Slot, HashSet<Slot>>,
+        progress: &mut ProgressMap,
+        heaviest_subtree_fork_choice: &mut HeaviestSubtreeForkChoice,
+        bank_forks: &RwLock<BankForks>,
+        blockstore: &Blockstore,
+        rpc_subscriptions: &RpcSubscriptions,
+        lockouts_sender: &Sender<CommitmentAggregationData>,
+    ) {
+        let bank = match vote_bank {
+            Some((bank, _)) => bank.clone(),
+            None => return,
+        };
+        let action = fork_simulator.votable_bank_action(bank.slot());
+        if action == ForkSimulationAction::Vote {
+            return;
+        }
+        *vote_bank = None;
+
+        let (root_bank, fork_parent) = {
+            let r_bank_forks = bank_forks.read().unwrap();
+            let fork_parent = match action {
+                ForkSimulationAction::Abandon(fork_slot) => r_bank_forks
+                    .get(fork_slot)
+                    .and_then(|fork_bank| r_bank_forks.get(fork_bank.parent_slot())),
+                _ => None,
+            };
+            (r_bank_forks.root_bank(), fork_parent)
+        };
+        let total_stake = progress
+            .get_fork_stats(bank.slot())
+            .expect("All frozen banks must exist in the Progress map")
+            .total_stake;
+        match action {
+            ForkSimulationAction::Vote | ForkSimulationAction::Hold => {}
+            ForkSimulationAction::Process => {
+                Self::update_commitment_cache(bank, root_bank.slot(), total_stake, lockouts_sender);
+            }
+            ForkSimulationAction::Abandon(fork_slot) => {
+                let fork_parent = fork_parent.expect("simulated fork must have a parent bank");
+                warn!(
+                    "abandoning simulated fork of slots {}..={}, resetting to slot {}",
+                    fork_slot,
+                    bank.slot(),
+                    fork_parent.slot()
+                );
+                if let Some(fork_hash) = progress.get_hash(fork_slot) {
+                    heaviest_subtree_fork_choice
+                        .mark_fork_invalid_candidate(&(fork_slot, fork_hash));
+                }
+                let abandoned_slots = Self::purge_slot_and_descendants(
+                    fork_slot,
+                    ancestors,
+                    descendants,
+                    progress,
+                    &root_bank,
+                    bank_forks,
+                )
+                .unwrap_or_default();
+                for slot in abandoned_slots {
+                    // Keep the slot's shreds, so that the leader skips the slot instead of
+                    // producing it again
+                    blockstore
+                        .set_dead_slot(slot)
+                        .expect("Failed to mark slot as dead in blockstore");
+                    rpc_subscriptions.notify_slot_update(SlotUpdate::Dead {
+                        slot,
+                        err: "abandoned by fork simulation".to_string(),
+                        timestamp: timestamp(),
+                    });
+                }
+
+                Self::update_commitment_cache(
+                    fork_parent.clone(),
+                    root_bank.slot(),
+                    total_stake,
+                    lockouts_sender,
+                );
+                *reset_bank = Some(fork_parent);
+            }
         }
     }
 
@@ -1786,6 +1922,7 @@ impl ReplayStage {
         block_commitment_cache: &Arc<RwLock<BlockCommitmentCache>>,
         heaviest_subtree_fork_choice: &mut HeaviestSubtreeForkChoice,
         bank_notification_sender: &Option<BankNotificationSender>,
+        account_history_sender: &Option<AccountHistorySender>,
         duplicate_slots_tracker: &mut DuplicateSlotsTracker,
         gossip_duplicate_confirmed_slots: &mut GossipDuplicateConfirmedSlots,
         unfrozen_gossip_verified_vote_hashes: &mut UnfrozenGossipVerifiedVoteHashes,
@@ -1821,6 +1958,20 @@ impl ReplayStage {
             blockstore
                 .set_roots(rooted_slots.iter())
                 .expect("Ledger set roots failed");
+            if let Some(sender) = account_history_sender {
+                // `handle_new_root()` below roots these slots, after which clean may drop the
+                // versions they wrote, so their accounts are collected here
+                let old_root = bank_forks.read().unwrap().root();
+                let newly_rooted_banks = rooted_banks
+                    .iter()
+                    .filter(|bank| bank.slot() > old_root)
+                    .collect::<Vec<_>>();
+                sender
+                    .send(AccountHistoryService::collect_rooted_slots_accounts(
+                        &newly_rooted_banks,
+                    ))
+                    .unwrap_or_else(|err| warn!("account_history_sender failed: {:?}", err));
+            }
             let highest_confirmed_root = Some(
                 block_commitment_cache
                     .read()
@@ -5131,6 +5282,131 @@ pub(crate) mod tests {
         assert!(progress.get(&0).is_some());
     }
 
+    #[test]
+    fn test_simulate_fork_abandon() {
+        let (vote_simulator, blockstore) = setup_default_forks(1, None::<GenerateVotes>);
+        let VoteSimulator {
+            bank_forks,
+            mut progress,
+            mut heaviest_subtree_fork_choice,
+            ..
+        } = vote_simulator;
+        let mut descendants = bank_forks.read().unwrap().descendants();
+        let mut ancestors = bank_forks.read().unwrap().ancestors();
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let block_commitment_cache = Arc::new(RwLock::new(BlockCommitmentCache::default()));
+        let rpc_subscriptions = RpcSubscriptions::new_for_tests(
+            &exit,
+            Arc::new(AtomicU64::default()),
+            bank_forks.clone(),
+            block_commitment_cache,
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        );
+        let (lockouts_sender, lockouts_receiver) = unbounded();
+
+        // Fork off at slot 5 and abandon the fork once slot 6 is frozen
+        let mut fork_simulator = ForkSimulator::new(ForkSimulationConfig {
+            interval: 0,
+            depth: 1,
+        });
+        let mut simulate_fork = |slot: Slot,
+                                 ancestors: &mut HashMap<Slot, HashSet<Slot>>,
+                                 descendants: &mut HashMap<Slot, HashSet<Slot>>,
+                                 progress: &mut ProgressMap| {
+            let bank = bank_forks.read().unwrap().get(slot).unwrap();
+            let mut vote_bank = Some((bank.clone(), SwitchForkDecision::SameFork));
+            let mut reset_bank = Some(bank);
+            ReplayStage::simulate_fork(
+                &mut fork_simulator,
+                &mut vote_bank,
+                &mut reset_bank,
+                ancestors,
+                descendants,
+                progress,
+                &mut heaviest_subtree_fork_choice,
+                &bank_forks,
+                &blockstore,
+                &rpc_subscriptions,
+                &lockouts_sender,
+            );
+            // Simulated forks are never voted on
+            assert!(vote_bank.is_none());
+            reset_bank.map(|bank| bank.slot())
+        };
+
+        // Slot 5 becomes the processed slot
+        assert_eq!(
+            simulate_fork(5, &mut ancestors, &mut descendants, &mut progress),
+            Some(5)
+        );
+        assert_eq!(lockouts_receiver.try_iter().count(), 1);
+        assert!(bank_forks.read().unwrap().get(5).is_some());
+
+        // Slot 6 completes the fork, which is abandoned: replay resets to the fork's parent,
+        // and the processed slot rolls back to it
+        let fork_hash = progress.get_hash(5).unwrap();
+        assert_eq!(
+            simulate_fork(6, &mut ancestors, &mut descendants, &mut progress),
+            Some(3)
+        );
+        assert_eq!(lockouts_receiver.try_iter().count(), 1);
+        assert_eq!(
+            heaviest_subtree_fork_choice.is_candidate(&(5, fork_hash)),
+            Some(false)
+        );
+
+        // The fork's banks and progress are purged like those of a duplicate slot, and the
+        // ancestors and descendants are kept consistent with BankForks
+        for slot in 5..=6 {
+            assert!(bank_forks.read().unwrap().get(slot).is_none());
+            assert!(progress.get(&slot).is_none());
+        }
+        for slot in 0..=4 {
+            assert!(bank_forks.read().unwrap().get(slot).is_some());
+            assert!(progress.get(&slot).is_some());
+        }
+        assert!(check_map_eq(
+            &ancestors,
+            &bank_forks.read().unwrap().ancestors()
+        ));
+        assert!(check_map_eq(
+            &descendants,
+            &bank_forks.read().unwrap().descendants()
+        ));
+
+        // Unlike purged duplicate slots, the fork's slots are left dead with their shreds, so
+        // they're skipped instead of being replayed again
+        for slot in 5..=6 {
+            assert!(blockstore.is_dead(slot));
+            assert!(blockstore.is_full(slot));
+        }
+
+        // Purging a duplicate slot still clears the blockstore of the slot and its remaining
+        // descendants
+        let root_bank = bank_forks.read().unwrap().root_bank();
+        ReplayStage::purge_unconfirmed_duplicate_slot(
+            3,
+            &mut ancestors,
+            &mut descendants,
+            &mut progress,
+            &root_bank,
+            &bank_forks,
+            &blockstore,
+        );
+        assert!(bank_forks.read().unwrap().get(3).is_none());
+        assert!(progress.get(&3).is_none());
+        assert!(blockstore.get_slot_entries(3, 0).unwrap().is_empty());
+        assert!(check_map_eq(
+            &ancestors,
+            &bank_forks.read().unwrap().ancestors()
+        ));
+        assert!(check_map_eq(
+            &descendants,
+            &bank_forks.read().unwrap().descendants()
+        ));
+    }
+
     #[test]
     fn test_purge_ancestors_descendants() {
         let (VoteSimulator { bank_forks, .. }, _) = setup_default_forks(1, None::<GenerateVotes>);
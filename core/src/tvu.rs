@@ -3,6 +3,7 @@
 
 use {
     crate::{
+        account_history_service::AccountHistorySender,
         broadcast_stage::RetransmitSlotsSender,
         cache_block_meta_service::CacheBlockMetaSender,
         cluster_info_vote_listener::{
@@ -14,6 +15,7 @@ use {
         consensus::Tower,
         cost_update_service::CostUpdateService,
         drop_bank_service::DropBankService,
+        fork_simulator::ForkSimulationConfig,
         ledger_cleanup_service::LedgerCleanupService,
         replay_stage::{ReplayStage, ReplayStageConfig},
         retransmit_stage::RetransmitStage,
@@ -89,6 +91,7 @@ pub struct TvuConfig {
     pub rocksdb_compaction_interval: Option<u64>,
     pub rocksdb_max_compaction_jitter: Option<u64>,
     pub wait_for_vote_to_start_leader: bool,
+    pub fork_simulation: Option<ForkSimulationConfig>,
 }
 
 impl Tvu {
@@ -118,6 +121,7 @@ impl Tvu {
         transaction_status_sender: Option<TransactionStatusSender>,
         rewards_recorder_sender: Option<RewardsRecorderSender>,
         cache_block_meta_sender: Option<CacheBlockMetaSender>,
+        account_history_sender: Option<AccountHistorySender>,
         vote_tracker: Arc<VoteTracker>,
         retransmit_slots_sender: RetransmitSlotsSender,
         gossip_verified_vote_hash_receiver: GossipVerifiedVoteHashReceiver,
@@ -213,11 +217,13 @@ impl Tvu {
             transaction_status_sender,
             rewards_recorder_sender,
             cache_block_meta_sender,
+            account_history_sender,
             bank_notification_sender,
             wait_for_vote_to_start_leader: tvu_config.wait_for_vote_to_start_leader,
             ancestor_hashes_replay_update_sender,
             tower_storage: tower_storage.clone(),
             wait_to_vote_slot,
+            fork_simulation: tvu_config.fork_simulation,
         };
 
         let (voting_sender, voting_receiver) = unbounded();
@@ -437,6 +443,7 @@ pub mod tests {
             None,
             None,
             None,
+            None,
             Arc::<VoteTracker>::default(),
             retransmit_slots_sender,
             gossip_verified_vote_hash_receiver,
@@ -387,6 +387,7 @@ fn get_priority_details(message: &SanitizedVersionedMessage) -> Option<Transacti
             true, // don't reject txs that use request heap size ix
             true, // use default units per instruction
             true, // don't reject txs that use set compute unit price ix
+            true, // don't reject txs that use set loaded accounts data size limit ix
         )
         .ok()?;
     Some(TransactionPriorityDetails {
@@ -3,12 +3,14 @@
 pub use solana_perf::report_target_features;
 use {
     crate::{
+        account_history_service::{AccountHistorySender, AccountHistoryService},
         accounts_hash_verifier::AccountsHashVerifier,
         broadcast_stage::BroadcastStageType,
         cache_block_meta_service::{CacheBlockMetaSender, CacheBlockMetaService},
         cluster_info_vote_listener::VoteTracker,
         completed_data_sets_service::CompletedDataSetsService,
         consensus::{reconcile_blockstore_roots_with_tower, Tower},
+        fork_simulator::ForkSimulationConfig,
         ledger_metric_report_service::LedgerMetricReportService,
         poh_timing_report_service::PohTimingReportService,
         rewards_recorder_service::{RewardsRecorderSender, RewardsRecorderService},
@@ -172,6 +174,7 @@ pub struct ValidatorConfig {
     pub no_wait_for_vote_to_start_leader: bool,
     pub accounts_shrink_ratio: AccountShrinkThreshold,
     pub wait_to_vote_slot: Option<Slot>,
+    pub fork_simulation: Option<ForkSimulationConfig>,
     pub ledger_column_options: LedgerColumnOptions,
     pub runtime_config: RuntimeConfig,
 }
@@ -232,6 +235,7 @@ impl Default for ValidatorConfig {
             accounts_shrink_ratio: AccountShrinkThreshold::default(),
             accounts_db_config: None,
             wait_to_vote_slot: None,
+            fork_simulation: None,
             ledger_column_options: LedgerColumnOptions::default(),
             runtime_config: RuntimeConfig::default(),
         }
@@ -317,6 +321,8 @@ struct TransactionHistoryServices {
     rewards_recorder_service: Option<RewardsRecorderService>,
     cache_block_meta_sender: Option<CacheBlockMetaSender>,
     cache_block_meta_service: Option<CacheBlockMetaService>,
+    account_history_sender: Option<AccountHistorySender>,
+    account_history_service: Option<AccountHistoryService>,
 }
 
 pub struct Validator {
@@ -328,6 +334,7 @@ pub struct Validator {
     transaction_status_service: Option<TransactionStatusService>,
     rewards_recorder_service: Option<RewardsRecorderService>,
     cache_block_meta_service: Option<CacheBlockMetaService>,
+    account_history_service: Option<AccountHistoryService>,
     system_monitor_service: Option<SystemMonitorService>,
     sample_performance_service: Option<SamplePerformanceService>,
     poh_timing_report_service: PohTimingReportService,
@@ -519,6 +526,8 @@ impl Validator {
                 rewards_recorder_service,
                 cache_block_meta_sender,
                 cache_block_meta_service,
+                account_history_sender,
+                account_history_service,
             },
             blockstore_process_options,
             blockstore_root_scan,
@@ -948,6 +957,7 @@ impl Validator {
             transaction_status_sender.clone(),
             rewards_recorder_sender,
             cache_block_meta_sender,
+            account_history_sender,
             vote_tracker.clone(),
             retransmit_slots_sender,
             gossip_verified_vote_hash_receiver,
@@ -963,6 +973,7 @@ impl Validator {
                 rocksdb_compaction_interval: config.rocksdb_compaction_interval,
                 rocksdb_max_compaction_jitter: config.rocksdb_compaction_interval,
                 wait_for_vote_to_start_leader,
+                fork_simulation: config.fork_simulation,
             },
             &max_slots,
             &cost_model,
@@ -1022,6 +1033,7 @@ impl Validator {
             transaction_status_service,
             rewards_recorder_service,
             cache_block_meta_service,
+            account_history_service,
             system_monitor_service,
             sample_performance_service,
             poh_timing_report_service,
@@ -1126,6 +1138,12 @@ impl Validator {
                 .expect("cache_block_meta_service");
         }
 
+        if let Some(account_history_service) = self.account_history_service {
+            account_history_service
+                .join()
+                .expect("account_history_service");
+        }
+
         if let Some(system_monitor_service) = self.system_monitor_service {
             system_monitor_service
                 .join()
@@ -1392,7 +1410,7 @@ fn load_blockstore(
     let enable_rpc_transaction_history =
         config.rpc_addrs.is_some() && config.rpc_config.enable_rpc_transaction_history;
     let is_plugin_transaction_history_required = transaction_notifier.as_ref().is_some();
-    let transaction_history_services =
+    let mut transaction_history_services =
         if enable_rpc_transaction_history || is_plugin_transaction_history_required {
             initialize_rpc_transaction_history_services(
                 blockstore.clone(),
@@ -1404,6 +1422,15 @@ fn load_blockstore(
         } else {
             TransactionHistoryServices::default()
         };
+    if config.rpc_addrs.is_some() && config.rpc_config.enable_rpc_account_history {
+        let (account_history_sender, account_history_receiver) = unbounded();
+        transaction_history_services.account_history_sender = Some(account_history_sender);
+        transaction_history_services.account_history_service = Some(AccountHistoryService::new(
+            account_history_receiver,
+            blockstore.clone(),
+            exit,
+        ));
+    }
 
     let (bank_forks, mut leader_schedule_cache, starting_snapshot_hashes) =
         bank_forks_utils::load_bank_forks(
@@ -1822,6 +1849,7 @@ fn initialize_rpc_transaction_history_services(
         rewards_recorder_service,
         cache_block_meta_sender,
         cache_block_meta_service,
+        ..TransactionHistoryServices::default()
     }
 }
 
@@ -12,6 +12,7 @@ use {
     regex::Regex,
     serde::Serialize,
     serde_json::json,
+    solana_bpf_loader_program::profiler,
     solana_clap_utils::{
         input_parsers::{cluster_type_of, pubkey_of, pubkeys_of},
         input_validators::{
@@ -55,7 +56,7 @@ use {
         account_utils::StateMut,
         clock::{Epoch, Slot},
         feature::{self, Feature},
-        feature_set,
+        feature_set::{self, FeatureSet},
         genesis_config::{ClusterType, GenesisConfig},
         hash::Hash,
         inflation::Inflation,
@@ -717,6 +718,9 @@ fn analyze_storage(database: &Database) {
     analyze_column::<BlockHeight>(database, "BlockHeight");
     analyze_column::<ProgramCosts>(database, "ProgramCosts");
     analyze_column::<OptimisticSlots>(database, "OptimisticSlots");
+    analyze_column::<BlockCosts>(database, "BlockCosts");
+    analyze_column::<AccountHistory>(database, "AccountHistory");
+    analyze_column::<AccountHistorySlots>(database, "AccountHistorySlots");
 }
 
 fn open_blockstore(
@@ -890,7 +894,7 @@ fn compute_slot_cost(blockstore: &Blockstore, slot: Slot) -> Result<(), String>
             .for_each(|transaction| {
                 num_programs += transaction.message().instructions().len();
 
-                let tx_cost = cost_model.calculate_cost(&transaction);
+                let tx_cost = cost_model.calculate_cost(&transaction, &FeatureSet::all_enabled());
                 let result = cost_tracker.try_add(&tx_cost);
                 if result.is_err() {
                     println!(
@@ -911,6 +915,36 @@ fn compute_slot_cost(blockstore: &Blockstore, slot: Slot) -> Result<(), String>
     );
     println!("  Programs: {:?}", program_ids);
 
+    // Blocks produced or replayed by the node that owns this ledger have the
+    // cost tracker state recorded when the bank was frozen
+    if let Some(block_cost_info) = blockstore.get_block_cost_info(slot).map_err(|err| {
+        format!(
+            " Slot: {}, Failed to load block cost info, err {:?}",
+            slot, err
+        )
+    })? {
+        let rejected = &block_cost_info.rejected_transaction_counts;
+        println!(
+            "  Recorded cost: block {}, vote {}, transactions {}, account data size {}",
+            block_cost_info.block_cost,
+            block_cost_info.vote_cost,
+            block_cost_info.transaction_count,
+            block_cost_info.account_data_size,
+        );
+        println!(
+            "  Recorded rejections: block limit {}, vote limit {}, account limit {}, \
+             account data block limit {}, account data total limit {}",
+            rejected.would_exceed_block_max_limit,
+            rejected.would_exceed_vote_max_limit,
+            rejected.would_exceed_account_max_limit,
+            rejected.would_exceed_account_data_block_limit,
+            rejected.would_exceed_account_data_total_limit,
+        );
+        for (pubkey, cost) in &block_cost_info.costliest_accounts {
+            println!("    {}: {}", pubkey, cost);
+        }
+    }
+
     Ok(())
 }
 
@@ -1415,6 +1449,14 @@ fn main() {
                     .takes_value(false)
                     .help("Skip ledger PoH verification"),
             )
+            .arg(
+                Arg::with_name("profile_file")
+                    .long("profile-file")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help("Profile the compute units of the replayed BPF programs, and write \
+                           the profile to FILE as collapsed stacks for flamegraph tools"),
+            )
             .arg(
                 Arg::with_name("print_accounts_stats")
                     .long("print-accounts-stats")
@@ -2254,6 +2296,10 @@ fn main() {
                     ..ProcessOptions::default()
                 };
                 let print_accounts_stats = arg_matches.is_present("print_accounts_stats");
+                let profile_file = value_t!(arg_matches, "profile_file", PathBuf).ok();
+                if profile_file.is_some() {
+                    profiler::enable_profiler();
+                }
                 println!(
                     "genesis hash: {}",
                     open_genesis_config_by(&ledger_path, arg_matches).hash()
@@ -2277,6 +2323,17 @@ fn main() {
                     let working_bank = bank_forks.read().unwrap().working_bank();
                     working_bank.print_accounts_stats();
                 }
+                if let Some(profile_file) = profile_file {
+                    profiler::write_profile_file(&profile_file).unwrap_or_else(|err| {
+                        eprintln!(
+                            "Unable to write profile to {}: {}",
+                            profile_file.display(),
+                            err
+                        );
+                        exit(1);
+                    });
+                    println!("Profile written to {}", profile_file.display());
+                }
                 exit_signal.store(true, Ordering::Relaxed);
                 system_monitor_service.join().unwrap();
                 println!("Ok");
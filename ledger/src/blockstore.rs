@@ -170,6 +170,9 @@ pub struct Blockstore {
     program_costs_cf: LedgerColumn<cf::ProgramCosts>,
     bank_hash_cf: LedgerColumn<cf::BankHash>,
     optimistic_slots_cf: LedgerColumn<cf::OptimisticSlots>,
+    block_costs_cf: LedgerColumn<cf::BlockCosts>,
+    account_history_cf: LedgerColumn<cf::AccountHistory>,
+    account_history_slots_cf: LedgerColumn<cf::AccountHistorySlots>,
     last_root: RwLock<Slot>,
     insert_shreds_lock: Mutex<()>,
     new_shreds_signals: Mutex<Vec<Sender<bool>>>,
@@ -281,6 +284,9 @@ impl Blockstore {
         let program_costs_cf = db.column();
         let bank_hash_cf = db.column();
         let optimistic_slots_cf = db.column();
+        let block_costs_cf = db.column();
+        let account_history_cf = db.column();
+        let account_history_slots_cf = db.column();
 
         let db = Arc::new(db);
 
@@ -333,6 +339,9 @@ impl Blockstore {
             program_costs_cf,
             bank_hash_cf,
             optimistic_slots_cf,
+            block_costs_cf,
+            account_history_cf,
+            account_history_slots_cf,
             new_shreds_signals: Mutex::default(),
             completed_slots_senders: Mutex::default(),
             shred_timing_point_sender: None,
@@ -674,6 +683,9 @@ impl Blockstore {
         self.program_costs_cf.submit_rocksdb_cf_metrics();
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
+        self.block_costs_cf.submit_rocksdb_cf_metrics();
+        self.account_history_cf.submit_rocksdb_cf_metrics();
+        self.account_history_slots_cf.submit_rocksdb_cf_metrics();
     }
 
     fn try_shred_recovery(
@@ -1845,6 +1857,119 @@ impl Blockstore {
         self.block_height_cf.put(slot, &block_height)
     }
 
+    pub fn get_block_cost_info(&self, slot: Slot) -> Result<Option<BlockCostInfo>> {
+        datapoint_info!(
+            "blockstore-rpc-api",
+            ("method", "get_block_cost_info", String)
+        );
+        let _lock = self.check_lowest_cleanup_slot(slot)?;
+        self.block_costs_cf.get(slot)
+    }
+
+    pub fn write_block_cost_info(&self, slot: Slot, block_cost_info: &BlockCostInfo) -> Result<()> {
+        self.block_costs_cf.put(slot, block_cost_info)
+    }
+
+    /// Records the versions of `accounts` written by the rooted bank at `slot`
+    pub fn write_account_history(
+        &self,
+        slot: Slot,
+        accounts: Vec<(Pubkey, AccountHistoryMeta)>,
+    ) -> Result<()> {
+        let mut write_batch = self.db.batch()?;
+        let mut pubkeys = Vec::with_capacity(accounts.len());
+        for (pubkey, meta) in accounts {
+            write_batch.put::<cf::AccountHistory>((pubkey, slot), &meta)?;
+            pubkeys.push(pubkey);
+        }
+        write_batch.put::<cf::AccountHistorySlots>(slot, &pubkeys)?;
+        self.db.write(write_batch)
+    }
+
+    /// Returns the lowest and highest slots for which account history has been recorded
+    pub fn get_account_history_slot_range(&self) -> Result<Option<(Slot, Slot)>> {
+        let (lock, lowest_available_slot) = self.ensure_lowest_cleanup_slot();
+        let lowest_slot = self
+            .account_history_slots_cf
+            .iter(IteratorMode::From(
+                lowest_available_slot,
+                IteratorDirection::Forward,
+            ))?
+            .next()
+            .map(|(slot, _)| slot);
+        let highest_slot = self
+            .account_history_slots_cf
+            .iter(IteratorMode::End)?
+            .next()
+            .map(|(slot, _)| slot);
+        drop(lock);
+        Ok(lowest_slot.zip(highest_slot))
+    }
+
+    /// Returns up to `limit` recorded versions of `pubkey`, newest first, along with the slot
+    /// that wrote each of them. Both `before` and `until` are exclusive bounds.
+    pub fn get_account_history(
+        &self,
+        pubkey: Pubkey,
+        before: Option<Slot>,
+        until: Option<Slot>,
+        limit: usize,
+    ) -> Result<Vec<(Slot, AccountHistoryMeta)>> {
+        datapoint_info!(
+            "blockstore-rpc-api",
+            ("method", "get_account_history", String)
+        );
+        let start_slot = match before {
+            Some(0) => return Ok(vec![]),
+            Some(before) => before - 1,
+            None => Slot::MAX,
+        };
+        let (lock, lowest_available_slot) = self.ensure_lowest_cleanup_slot();
+        let mut history = vec![];
+        let iterator = self.account_history_cf.iter(IteratorMode::From(
+            (pubkey, start_slot),
+            IteratorDirection::Reverse,
+        ))?;
+        for ((address, slot), data) in iterator {
+            if history.len() >= limit
+                || address != pubkey
+                || slot < lowest_available_slot
+                || until.map(|until| slot <= until).unwrap_or(false)
+            {
+                break;
+            }
+            history.push((slot, deserialize::<AccountHistoryMeta>(&data)?));
+        }
+        drop(lock);
+        Ok(history)
+    }
+
+    /// Returns the oldest recorded version of `pubkey` written after `slot`, along with the slot
+    /// that wrote it
+    pub fn get_oldest_account_history_after(
+        &self,
+        pubkey: Pubkey,
+        slot: Slot,
+    ) -> Result<Option<(Slot, AccountHistoryMeta)>> {
+        let start_slot = match slot.checked_add(1) {
+            Some(start_slot) => start_slot,
+            None => return Ok(None),
+        };
+        let (lock, lowest_available_slot) = self.ensure_lowest_cleanup_slot();
+        let mut iterator = self.account_history_cf.iter(IteratorMode::From(
+            (pubkey, start_slot.max(lowest_available_slot)),
+            IteratorDirection::Forward,
+        ))?;
+        let entry = match iterator.next() {
+            Some(((address, slot), data)) if address == pubkey => {
+                Some((slot, deserialize::<AccountHistoryMeta>(&data)?))
+            }
+            _ => None,
+        };
+        drop(lock);
+        Ok(entry)
+    }
+
     /// The first complete block that is available in the Blockstore ledger
     pub fn get_first_available_block(&self) -> Result<Slot> {
         let mut root_iterator = self.rooted_slot_iterator(self.lowest_slot_with_genesis())?;
@@ -4241,6 +4366,7 @@ pub mod tests {
         solana_entry::entry::{next_entry, next_entry_mut},
         solana_runtime::bank::{Bank, RewardType},
         solana_sdk::{
+            account::{Account, AccountSharedData},
             hash::{self, hash, Hash},
             instruction::CompiledInstruction,
             message::v0::LoadedAddresses,
@@ -8228,6 +8354,154 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_write_get_block_cost_info() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let slot = 7;
+        assert_eq!(blockstore.get_block_cost_info(slot).unwrap(), None);
+
+        let block_cost_info = BlockCostInfo {
+            block_cost: 1_000,
+            vote_cost: 100,
+            transaction_count: 3,
+            account_data_size: 42,
+            costliest_accounts: vec![(Pubkey::new_unique(), 900), (Pubkey::new_unique(), 100)],
+            rejected_transaction_counts: RejectedTransactionCounts {
+                would_exceed_account_max_limit: 2,
+                ..RejectedTransactionCounts::default()
+            },
+        };
+        blockstore
+            .write_block_cost_info(slot, &block_cost_info)
+            .unwrap();
+        assert_eq!(
+            blockstore.get_block_cost_info(slot).unwrap(),
+            Some(block_cost_info)
+        );
+
+        blockstore.purge_slots(slot, slot, PurgeType::Exact);
+        assert_eq!(blockstore.get_block_cost_info(slot).unwrap(), None);
+    }
+
+    #[test]
+    fn test_write_get_account_history() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let pubkey = Pubkey::new_unique();
+        let other_pubkey = Pubkey::new_unique();
+        assert_eq!(blockstore.get_account_history_slot_range().unwrap(), None);
+        assert!(blockstore
+            .get_account_history(pubkey, None, None, 10)
+            .unwrap()
+            .is_empty());
+
+        let owner = Pubkey::new_unique();
+        let versions: Vec<_> = (1..=3)
+            .map(|lamports| AccountHistoryMeta {
+                account: Account::from(AccountSharedData::new(lamports, 0, &owner)),
+                created: lamports == 1,
+            })
+            .collect();
+        let other_version = AccountHistoryMeta {
+            account: Account::default(),
+            created: false,
+        };
+        for (slot, meta) in [(5, &versions[0]), (7, &versions[1]), (9, &versions[2])] {
+            blockstore
+                .write_account_history(
+                    slot,
+                    vec![
+                        (pubkey, meta.clone()),
+                        (other_pubkey, other_version.clone()),
+                    ],
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            blockstore.get_account_history_slot_range().unwrap(),
+            Some((5, 9))
+        );
+
+        let history = blockstore
+            .get_account_history(pubkey, None, None, 10)
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                (9, versions[2].clone()),
+                (7, versions[1].clone()),
+                (5, versions[0].clone()),
+            ]
+        );
+
+        // the version at a slot is the latest one written at or before it
+        assert_eq!(
+            blockstore
+                .get_account_history(pubkey, Some(9), None, 1)
+                .unwrap(),
+            vec![(7, versions[1].clone())]
+        );
+        assert_eq!(
+            blockstore
+                .get_account_history(pubkey, Some(9), Some(5), 10)
+                .unwrap(),
+            vec![(7, versions[1].clone())]
+        );
+        assert!(blockstore
+            .get_account_history(pubkey, Some(5), None, 10)
+            .unwrap()
+            .is_empty());
+
+        // the oldest version after a slot
+        assert_eq!(
+            blockstore
+                .get_oldest_account_history_after(pubkey, 4)
+                .unwrap(),
+            Some((5, versions[0].clone()))
+        );
+        assert_eq!(
+            blockstore
+                .get_oldest_account_history_after(pubkey, 7)
+                .unwrap(),
+            Some((9, versions[2].clone()))
+        );
+        assert_eq!(
+            blockstore
+                .get_oldest_account_history_after(pubkey, 9)
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            blockstore
+                .get_oldest_account_history_after(Pubkey::new_unique(), 0)
+                .unwrap(),
+            None
+        );
+
+        blockstore.purge_slots(7, 7, PurgeType::Exact);
+        assert_eq!(
+            blockstore
+                .get_account_history(pubkey, None, None, 10)
+                .unwrap()
+                .into_iter()
+                .map(|(slot, _)| slot)
+                .collect::<Vec<_>>(),
+            vec![9, 5]
+        );
+        assert_eq!(
+            blockstore
+                .get_account_history(other_pubkey, None, None, 10)
+                .unwrap()
+                .into_iter()
+                .map(|(slot, _)| slot)
+                .collect::<Vec<_>>(),
+            vec![9, 5]
+        );
+    }
+
     #[test]
     fn test_lowest_slot() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
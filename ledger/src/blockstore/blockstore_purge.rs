@@ -215,6 +215,14 @@ impl Blockstore {
             & self
                 .db
                 .delete_range_cf::<cf::OptimisticSlots>(&mut write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .db
+                .delete_range_cf::<cf::BlockCosts>(&mut write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .db
+                .delete_range_cf::<cf::AccountHistorySlots>(&mut write_batch, from_slot, to_slot)
                 .is_ok();
         let mut w_active_transaction_status_index =
             self.active_transaction_status_index.write().unwrap();
@@ -336,6 +344,14 @@ impl Blockstore {
             && self
                 .optimistic_slots_cf
                 .compact_range(from_slot, to_slot)
+                .unwrap_or(false)
+            && self
+                .block_costs_cf
+                .compact_range(from_slot, to_slot)
+                .unwrap_or(false)
+            && self
+                .account_history_slots_cf
+                .compact_range(from_slot, to_slot)
                 .unwrap_or(false);
         compact_timer.stop();
         if !result {
@@ -385,6 +401,17 @@ impl Blockstore {
                 }
             }
         }
+        for (slot, pubkeys) in self
+            .account_history_slots_cf
+            .iter(IteratorMode::From(from_slot, IteratorDirection::Forward))?
+        {
+            if slot >= to_slot {
+                break;
+            }
+            for pubkey in deserialize::<Vec<Pubkey>>(&pubkeys)? {
+                batch.delete::<cf::AccountHistory>((pubkey, slot))?;
+            }
+        }
         if index0.max_slot >= from_slot && index0.max_slot <= to_slot {
             index0.max_slot = from_slot.saturating_sub(1);
             batch.put::<cf::TransactionStatusIndex>(0, &index0)?;
@@ -97,6 +97,12 @@ const BLOCK_HEIGHT_CF: &str = "block_height";
 const PROGRAM_COSTS_CF: &str = "program_costs";
 /// Column family for optimistic slots
 const OPTIMISTIC_SLOTS_CF: &str = "optimistic_slots";
+/// Column family for BlockCosts
+const BLOCK_COSTS_CF: &str = "block_costs";
+/// Column family for AccountHistory
+const ACCOUNT_HISTORY_CF: &str = "account_history";
+/// Column family for AccountHistorySlots
+const ACCOUNT_HISTORY_SLOTS_CF: &str = "account_history_slots";
 
 // 1 day is chosen for the same reasoning of DEFAULT_COMPACTION_SLOT_INTERVAL
 const PERIODIC_COMPACTION_SECONDS: u64 = 60 * 60 * 24;
@@ -219,6 +225,18 @@ pub mod columns {
     /// The optimistic slot column
     pub struct OptimisticSlots;
 
+    #[derive(Debug)]
+    /// The block costs column
+    pub struct BlockCosts;
+
+    #[derive(Debug)]
+    /// The account history column
+    pub struct AccountHistory;
+
+    #[derive(Debug)]
+    /// The account history slots column
+    pub struct AccountHistorySlots;
+
     // When adding a new column ...
     // - Add struct below and implement `Column` and `ColumnName` traits
     // - Add descriptor in Rocks::cf_descriptors() and name in Rocks::columns()
@@ -402,6 +420,9 @@ impl Rocks {
             new_cf_descriptor::<BlockHeight>(options, oldest_slot),
             new_cf_descriptor::<ProgramCosts>(options, oldest_slot),
             new_cf_descriptor::<OptimisticSlots>(options, oldest_slot),
+            new_cf_descriptor::<BlockCosts>(options, oldest_slot),
+            new_cf_descriptor::<AccountHistory>(options, oldest_slot),
+            new_cf_descriptor::<AccountHistorySlots>(options, oldest_slot),
         ]
     }
 
@@ -429,6 +450,9 @@ impl Rocks {
             BlockHeight::NAME,
             ProgramCosts::NAME,
             OptimisticSlots::NAME,
+            BlockCosts::NAME,
+            AccountHistory::NAME,
+            AccountHistorySlots::NAME,
         ]
     }
 
@@ -943,6 +967,54 @@ impl TypedColumn for columns::OptimisticSlots {
     type Type = blockstore_meta::OptimisticSlotMetaVersioned;
 }
 
+impl SlotColumn for columns::BlockCosts {}
+impl ColumnName for columns::BlockCosts {
+    const NAME: &'static str = BLOCK_COSTS_CF;
+}
+impl TypedColumn for columns::BlockCosts {
+    type Type = blockstore_meta::BlockCostInfo;
+}
+
+impl Column for columns::AccountHistory {
+    type Index = (Pubkey, Slot);
+
+    fn key((pubkey, slot): (Pubkey, Slot)) -> Vec<u8> {
+        let mut key = vec![0; 32 + 8]; // size_of Pubkey + size_of Slot
+        key[0..32].clone_from_slice(&pubkey.as_ref()[0..32]);
+        BigEndian::write_u64(&mut key[32..40], slot);
+        key
+    }
+
+    fn index(key: &[u8]) -> (Pubkey, Slot) {
+        let pubkey = Pubkey::new(&key[0..32]);
+        let slot = BigEndian::read_u64(&key[32..40]);
+        (pubkey, slot)
+    }
+
+    fn primary_index(index: Self::Index) -> Slot {
+        index.1
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn as_index(slot: Slot) -> Self::Index {
+        (Pubkey::default(), slot)
+    }
+}
+impl ColumnName for columns::AccountHistory {
+    const NAME: &'static str = ACCOUNT_HISTORY_CF;
+}
+impl TypedColumn for columns::AccountHistory {
+    type Type = blockstore_meta::AccountHistoryMeta;
+}
+
+impl SlotColumn for columns::AccountHistorySlots {}
+impl ColumnName for columns::AccountHistorySlots {
+    const NAME: &'static str = ACCOUNT_HISTORY_SLOTS_CF;
+}
+impl TypedColumn for columns::AccountHistorySlots {
+    type Type = Vec<Pubkey>;
+}
+
 #[derive(Debug)]
 pub struct Database {
     backend: Arc<Rocks>,
@@ -1713,6 +1785,15 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_account_history_index() {
+        let index = (Pubkey::new_unique(), 42);
+        let key = columns::AccountHistory::key(index);
+        assert_eq!(columns::AccountHistory::index(&key), index);
+        assert_eq!(columns::AccountHistory::primary_index(index), 42);
+        assert_eq!(columns::AccountHistory::slot(index), 42);
+    }
+
     #[test]
     fn test_cf_names_and_descriptors_equal_length() {
         let options = BlockstoreOptions::default();
@@ -2,8 +2,10 @@ use {
     crate::shred::{Shred, ShredType},
     serde::{Deserialize, Deserializer, Serialize, Serializer},
     solana_sdk::{
+        account::Account,
         clock::{Slot, UnixTimestamp},
         hash::Hash,
+        pubkey::Pubkey,
     },
     std::{
         collections::BTreeSet,
@@ -330,6 +332,38 @@ pub struct ProgramCost {
     pub cost: u64,
 }
 
+/// A version of an account written by a rooted slot
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AccountHistoryMeta {
+    pub account: Account,
+    /// true if the account did not exist before the slot that wrote this version
+    pub created: bool,
+}
+
+/// Cost summary of a frozen block, as tracked by the bank's `CostTracker`
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BlockCostInfo {
+    pub block_cost: u64,
+    pub vote_cost: u64,
+    pub transaction_count: u64,
+    pub account_data_size: u64,
+    /// Costliest writable accounts and their accumulated cost, costliest first
+    pub costliest_accounts: Vec<(Pubkey, u64)>,
+    pub rejected_transaction_counts: RejectedTransactionCounts,
+}
+
+/// Number of rejected attempts to add a transaction to the block's cost tracker, by reason.
+/// Transactions are only rejected while this node produces the block, and a transaction that is
+/// retried is counted at each rejected attempt.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RejectedTransactionCounts {
+    pub would_exceed_block_max_limit: u64,
+    pub would_exceed_vote_max_limit: u64,
+    pub would_exceed_account_max_limit: u64,
+    pub would_exceed_account_data_block_limit: u64,
+    pub would_exceed_account_data_total_limit: u64,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct OptimisticSlotMetaV0 {
     pub hash: Hash,
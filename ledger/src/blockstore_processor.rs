@@ -156,6 +156,7 @@ fn aggregate_total_execution_units(execute_timings: &ExecuteTimings) -> u64 {
     execute_cost_units
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_batch(
     batch: &TransactionBatch,
     bank: &Arc<Bank>,
@@ -164,6 +165,7 @@ fn execute_batch(
     timings: &mut ExecuteTimings,
     cost_capacity_meter: Arc<RwLock<BlockCostCapacityMeter>>,
     tx_cost: u64,
+    cost_model: &CostModel,
 ) -> Result<()> {
     let record_token_balances = transaction_status_sender.is_some();
 
@@ -224,6 +226,13 @@ fn execute_batch(
         ..
     } = tx_results;
 
+    track_committed_transaction_costs(
+        bank,
+        batch.sanitized_transactions(),
+        &execution_results,
+        cost_model,
+    );
+
     if bank
         .feature_set
         .is_active(&feature_set::cap_accounts_data_len::id())
@@ -256,6 +265,31 @@ fn execute_batch(
     first_err.map(|(result, _)| result).unwrap_or(Ok(()))
 }
 
+/// Add the cost of the transactions committed to `bank` to its cost tracker, as banking stage does
+/// for the blocks this node produces, so that the cost info of replayed blocks is recorded too.
+/// Transactions are already part of the block, so they are added regardless of the cost limits.
+fn track_committed_transaction_costs(
+    bank: &Bank,
+    transactions: &[SanitizedTransaction],
+    execution_results: &[TransactionExecutionResult],
+    cost_model: &CostModel,
+) {
+    let committed_costs: Vec<_> = transactions
+        .iter()
+        .zip(execution_results)
+        .filter_map(|(transaction, execution_result)| {
+            let details = execution_result.details()?;
+            let tx_cost = cost_model.calculate_cost(transaction, &bank.feature_set);
+            Some((tx_cost, details.executed_units))
+        })
+        .collect();
+    let mut cost_tracker = bank.write_cost_tracker().unwrap();
+    for (tx_cost, executed_units) in &committed_costs {
+        cost_tracker.add_committed_transaction(tx_cost, *executed_units);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn execute_batches_internal(
     bank: &Arc<Bank>,
     batches: &[TransactionBatch],
@@ -265,6 +299,7 @@ fn execute_batches_internal(
     timings: &mut ExecuteTimings,
     cost_capacity_meter: Arc<RwLock<BlockCostCapacityMeter>>,
     tx_costs: &[u64],
+    cost_model: &CostModel,
 ) -> Result<()> {
     inc_new_counter_debug!("bank-par_execute_entries-count", batches.len());
     let (results, new_timings): (Vec<Result<()>>, Vec<ExecuteTimings>) =
@@ -282,6 +317,7 @@ fn execute_batches_internal(
                         &mut timings,
                         cost_capacity_meter.clone(),
                         tx_costs[index],
+                        cost_model,
                     );
                     if let Some(entry_callback) = entry_callback {
                         entry_callback(bank);
@@ -343,7 +379,7 @@ fn execute_batches(
     let tx_costs = sanitized_txs
         .iter()
         .map(|tx| {
-            let tx_cost = cost_model.calculate_cost(tx);
+            let tx_cost = cost_model.calculate_cost(tx, &bank.feature_set);
             let cost = tx_cost.sum();
             let cost_without_bpf = tx_cost.sum_without_bpf();
             minimal_tx_cost = std::cmp::min(minimal_tx_cost, cost);
@@ -403,6 +439,7 @@ fn execute_batches(
         timings,
         cost_capacity_meter,
         &tx_batch_costs,
+        cost_model,
     )
 }
 
@@ -2865,6 +2902,47 @@ pub mod tests {
         assert_eq!(bank.last_blockhash(), blockhash);
     }
 
+    #[test]
+    fn test_process_entries_tracks_transaction_costs() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000);
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(
+            &mint_keypair,
+            &keypair.pubkey(),
+            1_000_000,
+            bank.last_blockhash(),
+        );
+        let entry = next_entry(&bank.last_blockhash(), 1, vec![tx.clone()]);
+        assert_eq!(
+            process_entries_for_tests(&bank, vec![entry], true, None, None),
+            Ok(())
+        );
+
+        let tx_cost = CostModel::new().calculate_cost(
+            &SanitizedTransaction::from_transaction_for_tests(tx),
+            &bank.feature_set,
+        );
+        let cost_tracker = bank.read_cost_tracker().unwrap();
+        assert_eq!(cost_tracker.transaction_count(), 1);
+        // the estimated execution cost is replaced with the executed units
+        let block_cost = cost_tracker.block_cost();
+        assert!(block_cost >= tx_cost.sum() - tx_cost.bpf_execution_cost);
+        let mut writable_accounts = vec![mint_keypair.pubkey(), keypair.pubkey()];
+        writable_accounts.sort();
+        assert_eq!(
+            cost_tracker.costliest_accounts(2),
+            writable_accounts
+                .into_iter()
+                .map(|pubkey| (pubkey, block_cost))
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_process_entry_tx_random_execution_with_error() {
         let GenesisConfigInfo {
@@ -0,0 +1,17 @@
+#![no_main]
+use {
+    lazy_static::lazy_static,
+    libfuzzer_sys::fuzz_target,
+    solana_program_fuzz::{programs, FuzzBank, ProgramSchema},
+};
+
+lazy_static! {
+    static ref FUZZ_BANK: FuzzBank = FuzzBank::new();
+    static ref SCHEMA: ProgramSchema = programs::vote_program_schema();
+}
+
+fuzz_target!(|input: &[u8]| {
+    if let Err(violation) = FUZZ_BANK.run(&SCHEMA, input) {
+        panic!("{}", violation);
+    }
+});
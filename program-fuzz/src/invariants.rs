@@ -0,0 +1,222 @@
+//! Runtime invariants that must hold for every processed instruction
+
+use {
+    solana_runtime::account_rent_state::RentState,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        instruction::AccountMeta,
+        pubkey::Pubkey,
+        rent::Rent,
+        system_program,
+    },
+    std::collections::HashMap,
+    thiserror::Error,
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    #[error("lamports not conserved: {pre} before the instruction, {post} after")]
+    LamportsNotConserved { pre: u128, post: u128 },
+    #[error("failed instruction modified account {0}")]
+    FailedInstructionModifiedAccount(Pubkey),
+    #[error("read-only account {0} modified")]
+    ReadonlyAccountModified(Pubkey),
+    #[error("account {address} modified by program {program_id}, which doesn't own it")]
+    UnauthorizedModification { address: Pubkey, program_id: Pubkey },
+    #[error("account {0} made a disallowed rent state transition")]
+    InvalidRentStateTransition(Pubkey),
+}
+
+/// An instruction account before and after the instruction was processed
+#[derive(Clone, Debug)]
+pub struct AccountTransition {
+    pub address: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub pre: AccountSharedData,
+    pub post: AccountSharedData,
+}
+
+/// Merge the accounts an instruction references more than once, with the union of their
+/// privileges
+pub fn account_transitions(
+    account_metas: &[AccountMeta],
+    pre_accounts: &HashMap<Pubkey, AccountSharedData>,
+    post_accounts: &HashMap<Pubkey, AccountSharedData>,
+) -> Vec<AccountTransition> {
+    let mut transitions = Vec::<AccountTransition>::new();
+    for account_meta in account_metas {
+        match transitions
+            .iter_mut()
+            .find(|transition| transition.address == account_meta.pubkey)
+        {
+            Some(transition) => {
+                transition.is_signer |= account_meta.is_signer;
+                transition.is_writable |= account_meta.is_writable;
+            }
+            None => transitions.push(AccountTransition {
+                address: account_meta.pubkey,
+                is_signer: account_meta.is_signer,
+                is_writable: account_meta.is_writable,
+                pre: pre_accounts
+                    .get(&account_meta.pubkey)
+                    .cloned()
+                    .unwrap_or_default(),
+                post: post_accounts
+                    .get(&account_meta.pubkey)
+                    .cloned()
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+    transitions
+}
+
+fn modified(pre: &AccountSharedData, post: &AccountSharedData) -> bool {
+    pre.lamports() != post.lamports()
+        || pre.data() != post.data()
+        || pre.owner() != post.owner()
+        || pre.executable() != post.executable()
+}
+
+/// Check the invariants of an instruction of `program_id` that ran with `transitions`
+///
+/// Besides the program's own accounts, the program may only debit system accounts that signed
+/// the instruction, and only allocate or assign system accounts without data, which covers what
+/// it can do through the system program.
+pub fn check_invariants(
+    program_id: &Pubkey,
+    rent: &Rent,
+    transitions: &[AccountTransition],
+    succeeded: bool,
+) -> Result<(), InvariantViolation> {
+    let pre = transitions
+        .iter()
+        .map(|transition| u128::from(transition.pre.lamports()))
+        .sum();
+    let post = transitions
+        .iter()
+        .map(|transition| u128::from(transition.post.lamports()))
+        .sum();
+    if pre != post {
+        return Err(InvariantViolation::LamportsNotConserved { pre, post });
+    }
+
+    for transition in transitions {
+        let AccountTransition {
+            address,
+            is_signer,
+            is_writable,
+            pre,
+            post,
+        } = transition;
+        if !modified(pre, post) {
+            continue;
+        }
+        if !succeeded {
+            return Err(InvariantViolation::FailedInstructionModifiedAccount(
+                *address,
+            ));
+        }
+        if !is_writable {
+            return Err(InvariantViolation::ReadonlyAccountModified(*address));
+        }
+
+        let owned = pre.owner() == program_id;
+        let system_owned = *pre.owner() == system_program::id();
+        let debit_allowed = owned || (system_owned && *is_signer);
+        let reassign_allowed = owned || (system_owned && pre.data().is_empty());
+        let debited = post.lamports() < pre.lamports();
+        let reassigned = pre.data() != post.data() || pre.owner() != post.owner();
+        if (debited && !debit_allowed) || (reassigned && !reassign_allowed) {
+            return Err(InvariantViolation::UnauthorizedModification {
+                address: *address,
+                program_id: *program_id,
+            });
+        }
+
+        let pre_rent_state = RentState::from_account(pre, rent);
+        let post_rent_state = RentState::from_account(post, rent);
+        if !post_rent_state.transition_allowed_from(&pre_rent_state, true) {
+            return Err(InvariantViolation::InvalidRentStateTransition(*address));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::WritableAccount};
+
+    fn transition(owner: &Pubkey, pre_lamports: u64, post_lamports: u64) -> AccountTransition {
+        let rent = Rent::default();
+        AccountTransition {
+            address: Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: true,
+            pre: AccountSharedData::new(pre_lamports + rent.minimum_balance(0), 0, owner),
+            post: AccountSharedData::new(post_lamports + rent.minimum_balance(0), 0, owner),
+        }
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let program_id = Pubkey::new_unique();
+        let rent = Rent::default();
+
+        let transfer = [
+            transition(&program_id, 10, 0),
+            transition(&program_id, 0, 10),
+        ];
+        assert_eq!(
+            check_invariants(&program_id, &rent, &transfer, true),
+            Ok(())
+        );
+        assert_eq!(
+            check_invariants(&program_id, &rent, &transfer, false),
+            Err(InvariantViolation::FailedInstructionModifiedAccount(
+                transfer[0].address
+            ))
+        );
+
+        let minted = [transition(&program_id, 0, 10)];
+        assert!(matches!(
+            check_invariants(&program_id, &rent, &minted, true),
+            Err(InvariantViolation::LamportsNotConserved { .. })
+        ));
+
+        let foreign_debit = [
+            transition(&Pubkey::new_unique(), 10, 0),
+            transition(&program_id, 0, 10),
+        ];
+        assert_eq!(
+            check_invariants(&program_id, &rent, &foreign_debit, true),
+            Err(InvariantViolation::UnauthorizedModification {
+                address: foreign_debit[0].address,
+                program_id,
+            })
+        );
+
+        let mut unsigned_system_debit = [
+            transition(&system_program::id(), 10, 0),
+            transition(&program_id, 0, 10),
+        ];
+        assert!(check_invariants(&program_id, &rent, &unsigned_system_debit, true).is_err());
+        unsigned_system_debit[0].is_signer = true;
+        assert_eq!(
+            check_invariants(&program_id, &rent, &unsigned_system_debit, true),
+            Ok(())
+        );
+
+        let mut rent_paying = [transition(&program_id, 0, 0)];
+        rent_paying[0].post.set_lamports(1);
+        rent_paying[0].pre.set_lamports(1);
+        rent_paying[0].post.set_data(vec![0; 10]);
+        assert_eq!(
+            check_invariants(&program_id, &rent, &rent_paying, true),
+            Err(InvariantViolation::InvalidRentStateTransition(
+                rent_paying[0].address
+            ))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Structured fuzzing of builtin and BPF programs
+//!
+//! A [`ProgramSchema`] declares the instruction data and accounts a program accepts.
+//! [`FuzzBank::run`] turns fuzzer input into an instruction that follows the schema, processes it
+//! in a bank and checks the runtime [invariants] on the accounts the instruction referenced.
+//!
+//! The `fuzz` directory has `cargo fuzz` targets for the system, stake, vote and address lookup
+//! table programs, e.g. `cd program-fuzz/fuzz && cargo +nightly fuzz run stake`.
+#![allow(clippy::integer_arithmetic)]
+
+pub mod invariants;
+pub mod programs;
+pub mod schema;
+
+use {
+    arbitrary::Unstructured,
+    invariants::{account_transitions, check_invariants},
+    schema::{generated_keypair, GENERATED_ADDRESSES},
+    solana_bpf_loader_program::solana_bpf_loader_program,
+    solana_program_runtime::timings::ExecuteTimings,
+    solana_runtime::{bank::Bank, genesis_utils::create_genesis_config},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        bpf_loader,
+        clock::MAX_PROCESSING_AGE,
+        instruction::Instruction,
+        message::Message,
+        native_token::sol_to_lamports,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{Keypair, Signer},
+        transaction::{self, Transaction, VersionedTransaction},
+    },
+    std::{collections::HashMap, sync::Arc},
+};
+pub use {
+    invariants::InvariantViolation,
+    schema::{AccountSchema, DataSchema, FuzzCase, ProgramSchema},
+};
+
+/// What processing a fuzzed instruction did
+#[derive(Debug, Default)]
+pub struct FuzzOutcome {
+    /// Status of the transaction, `None` if the input didn't generate an instruction
+    pub status: Option<transaction::Result<()>>,
+    /// Log messages of the transaction, empty unless it was executed
+    pub log_messages: Vec<String>,
+}
+
+impl FuzzOutcome {
+    /// Whether the program was invoked, whatever the result
+    pub fn invoked(&self, program_id: &Pubkey) -> bool {
+        let invoke = format!("Program {} invoke [1]", program_id);
+        self.log_messages.iter().any(|log| *log == invoke)
+    }
+}
+
+/// A bank that processes every fuzzed instruction in a fresh child bank
+pub struct FuzzBank {
+    bank: Arc<Bank>,
+    payer: Keypair,
+    generated_keypairs: Vec<Keypair>,
+}
+
+impl Default for FuzzBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FuzzBank {
+    /// Create a bank with all features active, and the builtin programs and BPF loader
+    pub fn new() -> Self {
+        let genesis_config_info = create_genesis_config(sol_to_lamports(1_000_000.0));
+        let mut bank = Bank::new_for_tests(&genesis_config_info.genesis_config);
+        let (name, program_id, process_instruction) = solana_bpf_loader_program!();
+        bank.add_builtin(&name, &program_id, process_instruction);
+        Self {
+            bank: Arc::new(bank),
+            payer: genesis_config_info.mint_keypair,
+            generated_keypairs: (0..GENERATED_ADDRESSES).map(generated_keypair).collect(),
+        }
+    }
+
+    /// Add a BPF program to fuzz
+    ///
+    /// Programs must be added before the first run, which freezes the bank they're stored in.
+    pub fn add_bpf_program(&self, program_id: &Pubkey, elf: &[u8]) {
+        let account = Account {
+            lamports: self.rent().minimum_balance(elf.len()).max(1),
+            data: elf.to_vec(),
+            owner: bpf_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        };
+        self.bank
+            .store_account(program_id, &AccountSharedData::from(account));
+    }
+
+    pub fn rent(&self) -> Rent {
+        self.bank.rent_collector().rent
+    }
+
+    fn instruction_accounts(
+        bank: &Bank,
+        instruction: &Instruction,
+    ) -> HashMap<Pubkey, AccountSharedData> {
+        instruction
+            .accounts
+            .iter()
+            .filter_map(|account_meta| {
+                let account = bank.get_account(&account_meta.pubkey)?;
+                Some((account_meta.pubkey, account))
+            })
+            .collect()
+    }
+
+    /// Process the instruction that `input` generates from `schema`, and check the invariants
+    ///
+    /// Inputs that can't generate an instruction are ignored.
+    pub fn run(
+        &self,
+        schema: &ProgramSchema,
+        input: &[u8],
+    ) -> Result<FuzzOutcome, InvariantViolation> {
+        match schema.generate(&mut Unstructured::new(input), &self.rent()) {
+            Ok(case) => self.run_case(schema, &case),
+            Err(_) => Ok(FuzzOutcome::default()),
+        }
+    }
+
+    /// Process the instruction of `case`, and check the invariants
+    pub fn run_case(
+        &self,
+        schema: &ProgramSchema,
+        case: &FuzzCase,
+    ) -> Result<FuzzOutcome, InvariantViolation> {
+        let bank = Bank::new_from_parent(&self.bank, &Pubkey::default(), self.bank.slot() + 1);
+        // Every run processes its transaction in a bank of the same slot
+        bank.clear_signatures();
+        for (address, account) in &case.accounts {
+            bank.store_account(address, account);
+        }
+
+        let pre_accounts = Self::instruction_accounts(&bank, &case.instruction);
+        let outcome = self.process_instruction(&bank, &case.instruction);
+        let post_accounts = Self::instruction_accounts(&bank, &case.instruction);
+
+        check_invariants(
+            &schema.program_id,
+            &self.rent(),
+            &account_transitions(&case.instruction.accounts, &pre_accounts, &post_accounts),
+            matches!(outcome.status, Some(Ok(()))),
+        )?;
+        Ok(outcome)
+    }
+
+    fn process_instruction(&self, bank: &Bank, instruction: &Instruction) -> FuzzOutcome {
+        let mut signers = vec![&self.payer];
+        for account_meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+            if let Some(keypair) = self
+                .generated_keypairs
+                .iter()
+                .find(|keypair| keypair.pubkey() == account_meta.pubkey)
+            {
+                if signers
+                    .iter()
+                    .all(|signer| signer.pubkey() != keypair.pubkey())
+                {
+                    signers.push(keypair);
+                }
+            }
+        }
+        let message = Message::new(&[instruction.clone()], Some(&self.payer.pubkey()));
+        let transaction = Transaction::new(&signers, message, bank.last_blockhash());
+
+        let batch = match bank.prepare_entry_batch(vec![VersionedTransaction::from(transaction)]) {
+            Ok(batch) => batch,
+            Err(err) => {
+                return FuzzOutcome {
+                    status: Some(Err(err)),
+                    ..FuzzOutcome::default()
+                }
+            }
+        };
+        let (results, _balances) = bank.load_execute_and_commit_transactions(
+            &batch,
+            MAX_PROCESSING_AGE,
+            false,
+            false,
+            true,
+            false,
+            &mut ExecuteTimings::default(),
+        );
+        let execution_result = &results.execution_results[0];
+        FuzzOutcome {
+            status: Some(execution_result.flattened_result()),
+            log_messages: execution_result
+                .details()
+                .and_then(|details| details.log_messages.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Schemas of the builtin programs
+
+use {
+    crate::schema::{AccountSchema, DataSchema, ProgramSchema},
+    solana_address_lookup_table_program::state::LOOKUP_TABLE_META_SIZE,
+    solana_sdk::{
+        nonce,
+        pubkey::Pubkey,
+        stake::{self, state::StakeState},
+        system_program, sysvar,
+    },
+    solana_vote_program::vote_state::VoteState,
+};
+
+/// Owner of generated accounts that belong to none of the fuzzed programs
+pub const FOREIGN_OWNER: Pubkey = Pubkey::new_from_array([0xfe; 32]);
+
+fn system_account() -> AccountSchema {
+    AccountSchema::Generated {
+        owners: vec![system_program::id(), FOREIGN_OWNER],
+        data: DataSchema::Bytes { len: 0..=0 },
+    }
+}
+
+fn sysvars(addresses: Vec<Pubkey>) -> AccountSchema {
+    AccountSchema::Existing {
+        addresses,
+        is_writable: false,
+    }
+}
+
+fn stake_account() -> AccountSchema {
+    AccountSchema::Generated {
+        owners: vec![stake::program::id(), FOREIGN_OWNER],
+        // `StakeState` has 4 variants
+        data: DataSchema::Enum {
+            variants: 4,
+            len: StakeState::size_of()..=StakeState::size_of(),
+        },
+    }
+}
+
+fn vote_account() -> AccountSchema {
+    AccountSchema::Generated {
+        owners: vec![solana_vote_program::id(), FOREIGN_OWNER],
+        // `VoteStateVersions` has 2 variants
+        data: DataSchema::Enum {
+            variants: 2,
+            len: VoteState::size_of()..=VoteState::size_of(),
+        },
+    }
+}
+
+/// The system program, with nonce accounts
+pub fn system_program_schema() -> ProgramSchema {
+    #[allow(deprecated)]
+    let sysvar_account = sysvars(vec![sysvar::recent_blockhashes::id(), sysvar::rent::id()]);
+    let nonce_account = AccountSchema::Generated {
+        owners: vec![system_program::id()],
+        // `nonce::state::Versions` has 1 variant
+        data: DataSchema::Enum {
+            variants: 1,
+            len: nonce::State::size()..=nonce::State::size(),
+        },
+    };
+    let account = vec![system_account(), nonce_account, sysvar_account];
+    ProgramSchema {
+        program_id: system_program::id(),
+        accounts: vec![account; 5],
+        // `SystemInstruction` has 12 variants
+        instruction_data: DataSchema::Enum {
+            variants: 12,
+            len: 4..=128,
+        },
+    }
+}
+
+pub fn stake_program_schema() -> ProgramSchema {
+    let sysvar_account = sysvars(vec![
+        sysvar::clock::id(),
+        sysvar::rent::id(),
+        sysvar::stake_history::id(),
+        stake::config::id(),
+    ]);
+    let account = vec![
+        stake_account(),
+        vote_account(),
+        system_account(),
+        sysvar_account,
+    ];
+    ProgramSchema {
+        program_id: stake::program::id(),
+        accounts: vec![account; 6],
+        // `StakeInstruction` has 18 variants
+        instruction_data: DataSchema::Enum {
+            variants: 18,
+            len: 4..=160,
+        },
+    }
+}
+
+pub fn vote_program_schema() -> ProgramSchema {
+    let sysvar_account = sysvars(vec![
+        sysvar::clock::id(),
+        sysvar::rent::id(),
+        sysvar::slot_hashes::id(),
+    ]);
+    let account = vec![vote_account(), system_account(), sysvar_account];
+    ProgramSchema {
+        program_id: solana_vote_program::id(),
+        accounts: vec![account; 5],
+        // `VoteInstruction` has 10 variants
+        instruction_data: DataSchema::Enum {
+            variants: 10,
+            len: 4..=512,
+        },
+    }
+}
+
+pub fn address_lookup_table_program_schema() -> ProgramSchema {
+    let lookup_table_account = AccountSchema::Generated {
+        owners: vec![solana_address_lookup_table_program::id(), FOREIGN_OWNER],
+        // `ProgramState` has 2 variants, tables hold up to 8 addresses
+        data: DataSchema::Enum {
+            variants: 2,
+            len: LOOKUP_TABLE_META_SIZE..=LOOKUP_TABLE_META_SIZE + 8 * 32,
+        },
+    };
+    let system_program_account = AccountSchema::Existing {
+        addresses: vec![system_program::id()],
+        is_writable: false,
+    };
+    let account = vec![
+        lookup_table_account,
+        system_account(),
+        system_program_account,
+    ];
+    ProgramSchema {
+        program_id: solana_address_lookup_table_program::id(),
+        accounts: vec![account; 4],
+        // `ProgramInstruction` has 5 variants
+        instruction_data: DataSchema::Enum {
+            variants: 5,
+            len: 4..=4 + 8 + 8 * 32,
+        },
+    }
+}
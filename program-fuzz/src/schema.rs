@@ -0,0 +1,159 @@
+//! Declarative description of the instructions a program accepts
+
+use {
+    arbitrary::{Result, Unstructured},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        clock::Epoch,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{keypair_from_seed, Keypair, Signer},
+    },
+    std::ops::RangeInclusive,
+};
+
+/// Number of distinct addresses generated accounts are drawn from, so that an instruction can
+/// reference the same account more than once
+pub const GENERATED_ADDRESSES: u8 = 6;
+
+/// Bytes of instruction or account data
+#[derive(Clone, Debug)]
+pub enum DataSchema {
+    /// Arbitrary bytes, of a length within `len`
+    Bytes { len: RangeInclusive<usize> },
+    /// A bincode-serialized enum with `variants` variants: a valid variant index followed by
+    /// arbitrary bytes, for a total length within `len`
+    Enum {
+        variants: u32,
+        len: RangeInclusive<usize>,
+    },
+}
+
+impl DataSchema {
+    pub fn generate(&self, u: &mut Unstructured) -> Result<Vec<u8>> {
+        match self {
+            Self::Bytes { len } => {
+                let mut data = vec![0; u.int_in_range(len.clone())?];
+                u.fill_buffer(&mut data)?;
+                Ok(data)
+            }
+            Self::Enum { variants, len } => {
+                let variant = u.int_in_range(0..=variants.saturating_sub(1))?;
+                let mut data = variant.to_le_bytes().to_vec();
+                let len = u.int_in_range(len.clone())?.max(data.len());
+                let mut payload = vec![0; len - data.len()];
+                u.fill_buffer(&mut payload)?;
+                data.extend(payload);
+                Ok(data)
+            }
+        }
+    }
+}
+
+/// An account passed to an instruction
+#[derive(Clone, Debug)]
+pub enum AccountSchema {
+    /// One of `addresses`, such as a sysvar or a program, used as it is in the bank
+    Existing {
+        addresses: Vec<Pubkey>,
+        is_writable: bool,
+    },
+    /// An account generated for the instruction and stored in the bank before it's processed
+    ///
+    /// Its address, signer and writable flags and lamports are arbitrary.
+    Generated {
+        owners: Vec<Pubkey>,
+        data: DataSchema,
+    },
+}
+
+/// The instructions of a program
+#[derive(Clone, Debug)]
+pub struct ProgramSchema {
+    pub program_id: Pubkey,
+    /// The candidates for each instruction account, in order
+    ///
+    /// Instructions are generated with any number of accounts up to the number of entries.
+    pub accounts: Vec<Vec<AccountSchema>>,
+    pub instruction_data: DataSchema,
+}
+
+/// An instruction generated from a `ProgramSchema`, and the accounts to store for it
+#[derive(Clone, Debug)]
+pub struct FuzzCase {
+    pub instruction: Instruction,
+    pub accounts: Vec<(Pubkey, AccountSharedData)>,
+}
+
+/// The keypair of a generated address, which signs for it when it's an instruction signer
+pub fn generated_keypair(index: u8) -> Keypair {
+    keypair_from_seed(&[index.saturating_add(1); 32]).unwrap()
+}
+
+pub fn generated_address(index: u8) -> Pubkey {
+    generated_keypair(index).pubkey()
+}
+
+fn generate_lamports(u: &mut Unstructured, rent: &Rent, data_len: usize) -> Result<u64> {
+    let rent_exempt_minimum = rent.minimum_balance(data_len);
+    Ok(match u.int_in_range(0..=3)? {
+        0 => 0,
+        1 => rent_exempt_minimum,
+        2 => rent_exempt_minimum.saturating_sub(1),
+        _ => u.int_in_range(1..=1_000_000_000_000)?,
+    })
+}
+
+impl ProgramSchema {
+    pub fn generate(&self, u: &mut Unstructured, rent: &Rent) -> Result<FuzzCase> {
+        let num_accounts = u.int_in_range(0..=self.accounts.len())?;
+        let mut account_metas = Vec::with_capacity(num_accounts);
+        let mut accounts = Vec::<(Pubkey, AccountSharedData)>::new();
+        for candidates in self.accounts.iter().take(num_accounts) {
+            match u.choose(candidates)? {
+                AccountSchema::Existing {
+                    addresses,
+                    is_writable,
+                } => account_metas.push(AccountMeta {
+                    pubkey: *u.choose(addresses)?,
+                    is_signer: false,
+                    is_writable: *is_writable,
+                }),
+                AccountSchema::Generated { owners, data } => {
+                    let address = generated_address(u.int_in_range(0..=GENERATED_ADDRESSES - 1)?);
+                    account_metas.push(AccountMeta {
+                        pubkey: address,
+                        is_signer: u.arbitrary()?,
+                        is_writable: u.arbitrary()?,
+                    });
+                    // An address drawn again refers to the account already generated for it
+                    if accounts.iter().all(|(generated, _)| *generated != address) {
+                        let owner = *u.choose(owners)?;
+                        let data = data.generate(u)?;
+                        let lamports = generate_lamports(u, rent, data.len())?;
+                        let account = Account {
+                            lamports,
+                            data,
+                            owner,
+                            executable: false,
+                            // Exempt the account from rent collection, which would take lamports
+                            // out of the instruction's accounts
+                            rent_epoch: Epoch::MAX,
+                        };
+                        accounts.push((address, AccountSharedData::from(account)));
+                    }
+                }
+            }
+        }
+
+        Ok(FuzzCase {
+            instruction: Instruction {
+                program_id: self.program_id,
+                accounts: account_metas,
+                data: self.instruction_data.generate(u)?,
+            },
+            accounts,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    solana_program_fuzz::{programs, schema::generated_address, FuzzBank, FuzzCase, ProgramSchema},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        clock::Epoch,
+        system_instruction, system_program,
+    },
+};
+
+fn fuzz_with_random_inputs(schema: ProgramSchema) {
+    let fuzz_bank = FuzzBank::new();
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut invocations = 0;
+    for _ in 0..200 {
+        let mut input = vec![0u8; rng.gen_range(0, 4096)];
+        rng.fill(input.as_mut_slice());
+        let outcome = fuzz_bank.run(&schema, &input).unwrap();
+        if outcome.invoked(&schema.program_id) {
+            invocations += 1;
+        }
+    }
+    // Transactions that fail before reaching the program don't exercise it
+    assert!(invocations > 0);
+}
+
+#[test]
+fn test_known_good_input() {
+    let fuzz_bank = FuzzBank::new();
+    let schema = programs::system_program_schema();
+    let from = generated_address(0);
+    let to = generated_address(1);
+    let account = AccountSharedData::from(Account {
+        lamports: 1_000_000,
+        data: vec![],
+        owner: system_program::id(),
+        executable: false,
+        rent_epoch: Epoch::MAX,
+    });
+    let case = FuzzCase {
+        instruction: system_instruction::transfer(&from, &to, 1_000),
+        accounts: vec![(from, account.clone()), (to, account)],
+    };
+
+    let outcome = fuzz_bank.run_case(&schema, &case).unwrap();
+    assert_eq!(outcome.status, Some(Ok(())));
+    assert!(outcome.invoked(&schema.program_id));
+    assert!(outcome
+        .log_messages
+        .contains(&format!("Program {} success", schema.program_id)));
+}
+
+#[test]
+fn test_system_program() {
+    fuzz_with_random_inputs(programs::system_program_schema());
+}
+
+#[test]
+fn test_stake_program() {
+    fuzz_with_random_inputs(programs::stake_program_schema());
+}
+
+#[test]
+fn test_vote_program() {
+    fuzz_with_random_inputs(programs::vote_program_schema());
+}
+
+#[test]
+fn test_address_lookup_table_program() {
+    fuzz_with_random_inputs(programs::address_lookup_table_program_schema());
+}
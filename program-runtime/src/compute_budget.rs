@@ -13,6 +13,7 @@ use {
 pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
 pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+pub const MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES: u32 = 64 * 1024 * 1024;
 
 #[cfg(RUSTC_WITH_SPECIALIZATION)]
 impl ::solana_frozen_abi::abi_example::AbiExample for ComputeBudget {
@@ -69,6 +70,12 @@ pub struct ComputeBudget {
     pub curve25519_edwards_subtract_cost: u64,
     /// Number of compute units consumed to multiply a curve25519 edwards point
     pub curve25519_edwards_multiply_cost: u64,
+    /// Number of compute units consumed for a multiscalar multiplication (msm) of edwards points.
+    /// The total cost is calculated as `msm_base_cost + (length - 1) * msm_incremental_cost`.
+    pub curve25519_edwards_msm_base_cost: u64,
+    /// Number of compute units consumed for a multiscalar multiplication (msm) of edwards points.
+    /// The total cost is calculated as `msm_base_cost + (length - 1) * msm_incremental_cost`.
+    pub curve25519_edwards_msm_incremental_cost: u64,
     /// Number of compute units consumed to validate a curve25519 ristretto point
     pub curve25519_ristretto_validate_point_cost: u64,
     /// Number of compute units consumed to add two curve25519 ristretto points
@@ -77,6 +84,24 @@ pub struct ComputeBudget {
     pub curve25519_ristretto_subtract_cost: u64,
     /// Number of compute units consumed to multiply a curve25519 ristretto point
     pub curve25519_ristretto_multiply_cost: u64,
+    /// Number of compute units consumed for a multiscalar multiplication (msm) of ristretto points.
+    /// The total cost is calculated as `msm_base_cost + (length - 1) * msm_incremental_cost`.
+    pub curve25519_ristretto_msm_base_cost: u64,
+    /// Number of compute units consumed for a multiscalar multiplication (msm) of ristretto points.
+    /// The total cost is calculated as `msm_base_cost + (length - 1) * msm_incremental_cost`.
+    pub curve25519_ristretto_msm_incremental_cost: u64,
+    /// Number of compute units consumed to add two alt_bn128 G1 points
+    pub alt_bn128_addition_cost: u64,
+    /// Number of compute units consumed to multiply an alt_bn128 G1 point by a scalar
+    pub alt_bn128_multiplication_cost: u64,
+    /// Number of compute units consumed by the first pair of an alt_bn128 pairing check
+    pub alt_bn128_pairing_one_pair_cost_first: u64,
+    /// Number of compute units consumed by each additional pair of an alt_bn128 pairing check
+    pub alt_bn128_pairing_one_pair_cost_other: u64,
+    /// Base number of compute units consumed to call Poseidon
+    pub poseidon_base_cost: u64,
+    /// Incremental number of compute units consumed by Poseidon for each input
+    pub poseidon_cost_per_input: u64,
     /// Optional program heap region size, if `None` then loader default
     pub heap_size: Option<usize>,
     /// Number of compute units per additional 32k heap above the default (~.5
@@ -84,6 +109,9 @@ pub struct ComputeBudget {
     pub heap_cost: u64,
     /// Memory operation syscall base cost
     pub mem_op_base_cost: u64,
+    /// Maximum total data size, in bytes, of the accounts a transaction is
+    /// allowed to load
+    pub loaded_accounts_data_size_limit: usize,
 }
 
 impl Default for ComputeBudget {
@@ -112,17 +140,28 @@ impl ComputeBudget {
             secp256k1_recover_cost: 25_000,
             syscall_base_cost: 100,
             zk_token_elgamal_op_cost: 25_000,
-            curve25519_edwards_validate_point_cost: 25_000, // TODO: precisely determine curve25519 costs
-            curve25519_edwards_add_cost: 25_000,
-            curve25519_edwards_subtract_cost: 25_000,
-            curve25519_edwards_multiply_cost: 25_000,
-            curve25519_ristretto_validate_point_cost: 25_000,
-            curve25519_ristretto_add_cost: 25_000,
-            curve25519_ristretto_subtract_cost: 25_000,
-            curve25519_ristretto_multiply_cost: 25_000,
+            curve25519_edwards_validate_point_cost: 159,
+            curve25519_edwards_add_cost: 473,
+            curve25519_edwards_subtract_cost: 475,
+            curve25519_edwards_multiply_cost: 2_177,
+            curve25519_edwards_msm_base_cost: 2_273,
+            curve25519_edwards_msm_incremental_cost: 758,
+            curve25519_ristretto_validate_point_cost: 169,
+            curve25519_ristretto_add_cost: 521,
+            curve25519_ristretto_subtract_cost: 519,
+            curve25519_ristretto_multiply_cost: 2_208,
+            curve25519_ristretto_msm_base_cost: 2_303,
+            curve25519_ristretto_msm_incremental_cost: 788,
+            alt_bn128_addition_cost: 334,
+            alt_bn128_multiplication_cost: 3_840,
+            alt_bn128_pairing_one_pair_cost_first: 36_364,
+            alt_bn128_pairing_one_pair_cost_other: 12_121,
+            poseidon_base_cost: 542,
+            poseidon_cost_per_input: 732,
             heap_size: None,
             heap_cost: 8,
             mem_op_base_cost: 10,
+            loaded_accounts_data_size_limit: MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES as usize,
         }
     }
 
@@ -132,11 +171,13 @@ impl ComputeBudget {
         requestable_heap_size: bool,
         default_units_per_instruction: bool,
         support_set_compute_unit_price_ix: bool,
+        support_set_loaded_accounts_data_size_limit_ix: bool,
     ) -> Result<PrioritizationFeeDetails, TransactionError> {
         let mut num_non_compute_budget_instructions: usize = 0;
         let mut updated_compute_unit_limit = None;
         let mut requested_heap_size = None;
         let mut prioritization_fee = None;
+        let mut updated_loaded_accounts_data_size_limit = None;
 
         for (i, (program_id, instruction)) in instructions.enumerate() {
             if compute_budget::check_id(program_id) {
@@ -182,6 +223,14 @@ impl ComputeBudget {
                             prioritization_fee =
                                 Some(PrioritizationFeeType::ComputeUnitPrice(micro_lamports));
                         }
+                        Ok(ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes))
+                            if support_set_loaded_accounts_data_size_limit_ix =>
+                        {
+                            if updated_loaded_accounts_data_size_limit.is_some() {
+                                return Err(duplicate_instruction_error);
+                            }
+                            updated_loaded_accounts_data_size_limit = Some(bytes);
+                        }
                         _ => return Err(invalid_instruction_data_error),
                     }
                 } else if i < 3 {
@@ -239,6 +288,11 @@ impl ComputeBudget {
         .unwrap_or(MAX_COMPUTE_UNIT_LIMIT)
         .min(MAX_COMPUTE_UNIT_LIMIT) as u64;
 
+        self.loaded_accounts_data_size_limit = updated_loaded_accounts_data_size_limit
+            .unwrap_or(MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES)
+            .min(MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES)
+            as usize;
+
         Ok(prioritization_fee
             .map(|fee_type| PrioritizationFeeDetails::new(fee_type, self.compute_unit_limit))
             .unwrap_or_default())
@@ -285,6 +339,7 @@ mod tests {
                 true,
                 true,
                 $type_change,
+                true,
             );
             assert_eq!($expected_result, result);
             assert_eq!(compute_budget, $expected_budget);
@@ -608,4 +663,72 @@ mod tests {
             ComputeBudget::default()
         );
     }
+
+    #[test]
+    fn test_process_loaded_accounts_data_size_limit_instruction() {
+        test!(
+            &[
+                ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(1024),
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0, vec![]),
+            ],
+            Ok(PrioritizationFeeDetails::default()),
+            ComputeBudget {
+                compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT as u64,
+                loaded_accounts_data_size_limit: 1024,
+                ..ComputeBudget::default()
+            }
+        );
+
+        // Limits above the maximum are capped
+        test!(
+            &[
+                ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(u32::MAX),
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0, vec![]),
+            ],
+            Ok(PrioritizationFeeDetails::default()),
+            ComputeBudget {
+                compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT as u64,
+                loaded_accounts_data_size_limit: MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES as usize,
+                ..ComputeBudget::default()
+            }
+        );
+
+        test!(
+            &[
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0, vec![]),
+                ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(1024),
+                ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(2048),
+            ],
+            Err(TransactionError::DuplicateInstruction(2)),
+            ComputeBudget::default()
+        );
+
+        // The instruction is invalid until it is supported
+        let payer_keypair = Keypair::new();
+        let tx = SanitizedTransaction::from_transaction_for_tests(Transaction::new(
+            &[&payer_keypair],
+            Message::new(
+                &[
+                    ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(1024),
+                    Instruction::new_with_bincode(Pubkey::new_unique(), &0, vec![]),
+                ],
+                Some(&payer_keypair.pubkey()),
+            ),
+            Hash::default(),
+        ));
+        let mut compute_budget = ComputeBudget::default();
+        assert_eq!(
+            compute_budget.process_instructions(
+                tx.message().program_instructions_iter(),
+                true,
+                true,
+                true,
+                false,
+            ),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+    }
 }
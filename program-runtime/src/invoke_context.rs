@@ -947,6 +947,8 @@ impl<'a> InvokeContext<'a> {
                 let execution_result = self.process_executable_chain();
                 let post_remaining_units = self.compute_meter.borrow().get_remaining();
                 *compute_units_consumed = pre_remaining_units.saturating_sub(post_remaining_units);
+                self.transaction_context
+                    .set_compute_units_consumed(*compute_units_consumed)?;
                 process_executable_chain_time.stop();
 
                 // Verify the called program has not misbehaved
@@ -1696,6 +1698,16 @@ mod tests {
             assert!(compute_units_consumed > 0);
             assert_eq!(compute_units_consumed, compute_units_to_consume);
             assert_eq!(result, expected_result);
+            let recorded_instruction = invoke_context
+                .transaction_context
+                .get_instruction_trace()
+                .last()
+                .and_then(|instruction_trace| instruction_trace.last())
+                .unwrap();
+            assert_eq!(
+                recorded_instruction.get_compute_units_consumed(),
+                compute_units_to_consume
+            );
 
             invoke_context.pop().unwrap();
         }
@@ -0,0 +1,83 @@
+//! lcov coverage reports of the BPF programs loaded into a `ProgramTest`
+
+use {
+    log::*,
+    solana_bpf_loader_program::coverage::{coverage, write_lcov},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        fs::{self, File},
+        io::{self, BufWriter, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// A BPF program added with `ProgramTest::add_program`
+#[derive(Clone, Debug)]
+pub(crate) struct CoverageProgram {
+    pub name: String,
+    pub program_id: Pubkey,
+    pub program_file: PathBuf,
+}
+
+/// Writes the coverage collected so far of each program as `<DIR>/<PROGRAM_NAME>-<PID>.lcov`
+/// when dropped
+///
+/// The counts are process-wide, so every report written by a test binary includes the runs of
+/// the tests that finished before it, and the last one written is complete.
+pub(crate) struct CoverageReport {
+    pub dir: PathBuf,
+    pub programs: Vec<CoverageProgram>,
+}
+
+/// The unstripped build of `program_file`, which `cargo-build-sbf` leaves next to the deploy
+/// directory, or `program_file` itself if there is none
+fn unstripped_program_file(program_file: &Path) -> PathBuf {
+    let target_dir = program_file.parent().and_then(Path::parent);
+    let file_name = program_file.file_name();
+    target_dir
+        .zip(file_name)
+        .and_then(|(target_dir, file_name)| {
+            ["sbf-solana-solana", "bpf-solana-solana"]
+                .iter()
+                .map(|triple| target_dir.join(triple).join("release").join(file_name))
+                .find(|candidate| candidate.exists())
+        })
+        .unwrap_or_else(|| program_file.to_path_buf())
+}
+
+impl CoverageReport {
+    fn write(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let coverage = coverage();
+        for program in &self.programs {
+            let hits = match coverage.get(&program.program_id) {
+                Some(hits) => hits,
+                None => continue,
+            };
+            let program_file = unstripped_program_file(&program.program_file);
+            let elf = fs::read(&program_file)?;
+            let lcov_file = self
+                .dir
+                .join(format!("{}-{}.lcov", program.name, std::process::id()));
+            let mut writer = BufWriter::new(File::create(&lcov_file)?);
+            write_lcov(&mut writer, &elf, hits).map_err(|err| {
+                io::Error::new(err.kind(), format!("{}: {}", program_file.display(), err))
+            })?;
+            writer.flush()?;
+            info!(
+                "\"{}\" BPF program coverage written to {}",
+                program.name,
+                lcov_file.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Drop for CoverageReport {
+    fn drop(&mut self) {
+        if let Err(err) = self.write() {
+            warn!("Failed to write BPF program coverage: {}", err);
+        }
+    }
+}
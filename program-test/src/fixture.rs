@@ -0,0 +1,255 @@
+//! Snapshots of real-world accounts and programs that can be loaded into a `ProgramTest`
+//!
+//! A fixture is a directory containing
+//! * `<ADDRESS>.json` files, one per account, in the format written by
+//!   `solana account --output json <ADDRESS>`
+//! * `<PROGRAM_ID>.so` files, one per upgradeable program, holding the program's ELF as written
+//!   by `solana program dump <PROGRAM_ID>`
+//! * optional `<PROGRAM_ID>.programdata.json` files next to them, holding the deploy slot and
+//!   upgrade authority of the program. Without one, the program is loaded as deployed at slot 0
+//!   and not upgradeable
+//!
+//! `solana-test-validator --dump-fixture <DIR>` writes the accounts copied with `--clone` in this
+//! format.
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_account_decoder::{UiAccount, UiAccountEncoding},
+    solana_sdk::{
+        account::{Account, AccountSharedData, ReadableAccount},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Slot,
+        pubkey::Pubkey,
+        rent::Rent,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        fs::{self, File},
+        io::{self, BufReader, BufWriter},
+        path::Path,
+        str::FromStr,
+    },
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+/// Extension of the file holding the program data metadata of a `<PROGRAM_ID>.so` file
+const PROGRAMDATA_EXTENSION: &str = "programdata.json";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureProgramData {
+    slot: Slot,
+    upgrade_authority: Option<String>,
+}
+
+fn invalid_data(path: &Path, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), msg),
+    )
+}
+
+fn read_account_file(path: &Path) -> io::Result<(Pubkey, AccountSharedData)> {
+    let file = File::open(path)?;
+    let fixture_account: FixtureAccount =
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| invalid_data(path, err))?;
+    let address = Pubkey::from_str(&fixture_account.pubkey)
+        .map_err(|err| invalid_data(path, format!("invalid pubkey: {}", err)))?;
+    let account = fixture_account
+        .account
+        .decode::<AccountSharedData>()
+        .ok_or_else(|| invalid_data(path, "unable to decode account data"))?;
+    Ok((address, account))
+}
+
+fn read_programdata_file(path: &Path) -> io::Result<(Slot, Option<Pubkey>)> {
+    let file = File::open(path)?;
+    let programdata: FixtureProgramData =
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| invalid_data(path, err))?;
+    let upgrade_authority_address = programdata
+        .upgrade_authority
+        .map(|authority| Pubkey::from_str(&authority))
+        .transpose()
+        .map_err(|err| invalid_data(path, format!("invalid upgrade authority: {}", err)))?;
+    Ok((programdata.slot, upgrade_authority_address))
+}
+
+/// Build the program and program data accounts of an upgradeable program from its ELF, deployed
+/// at `slot` by `upgrade_authority_address`.
+pub fn upgradeable_program_accounts(
+    program_id: &Pubkey,
+    elf: &[u8],
+    slot: Slot,
+    upgrade_authority_address: Option<Pubkey>,
+    rent: &Rent,
+) -> [(Pubkey, AccountSharedData); 2] {
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+    let mut program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+    program_data.resize(UpgradeableLoaderState::size_of_program(), 0);
+
+    let mut programdata_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot,
+        upgrade_authority_address,
+    })
+    .unwrap();
+    programdata_data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+    programdata_data.extend_from_slice(elf);
+
+    [
+        (
+            *program_id,
+            AccountSharedData::from(Account {
+                lamports: rent.minimum_balance(program_data.len()).max(1),
+                data: program_data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: true,
+                rent_epoch: 0,
+            }),
+        ),
+        (
+            programdata_address,
+            AccountSharedData::from(Account {
+                lamports: rent.minimum_balance(programdata_data.len()).max(1),
+                data: programdata_data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+                rent_epoch: 0,
+            }),
+        ),
+    ]
+}
+
+/// Read every account and program in the fixture directory `dir`.
+///
+/// Files with other extensions are ignored.
+pub fn read_fixture<P: AsRef<Path>>(
+    dir: P,
+    rent: &Rent,
+) -> io::Result<Vec<(Pubkey, AccountSharedData)>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    // Keep the load order, and so which file wins a duplicate address, stable across platforms
+    paths.sort();
+
+    let mut accounts = vec![];
+    for path in paths {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default();
+        if file_name.ends_with(&format!(".{}", PROGRAMDATA_EXTENSION)) {
+            // Read along with the program's ELF
+            continue;
+        }
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => accounts.push(read_account_file(&path)?),
+            Some("so") => {
+                let program_id = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| Pubkey::from_str(stem).ok())
+                    .ok_or_else(|| invalid_data(&path, "file name is not a program id"))?;
+                let elf = fs::read(&path)?;
+                let programdata_path = path.with_extension(PROGRAMDATA_EXTENSION);
+                let (slot, upgrade_authority_address) = if programdata_path.exists() {
+                    read_programdata_file(&programdata_path)?
+                } else {
+                    (0, None)
+                };
+                accounts.extend(upgradeable_program_accounts(
+                    &program_id,
+                    &elf,
+                    slot,
+                    upgrade_authority_address,
+                    rent,
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(accounts)
+}
+
+/// Write `accounts` into the fixture directory `dir`, creating it if needed.
+///
+/// An upgradeable program whose program data account is also in `accounts` is written as an ELF
+/// dump along with its deploy slot and upgrade authority; every other account is written as a
+/// JSON file.
+pub fn write_fixture<'a, P, T>(dir: P, accounts: T) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    T: IntoIterator<Item = (&'a Pubkey, &'a AccountSharedData)>,
+{
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let accounts: HashMap<_, _> = accounts.into_iter().collect();
+    let upgradeable_state = |account: &AccountSharedData| {
+        if *account.owner() == bpf_loader_upgradeable::id() {
+            bincode::deserialize::<UpgradeableLoaderState>(account.data()).ok()
+        } else {
+            None
+        }
+    };
+
+    // Upgradeable programs whose program data is available are dumped as ELF files, which stand
+    // in for both the program and the program data account
+    let mut dumped_addresses = HashSet::new();
+    for (program_id, account) in &accounts {
+        if let Some(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = upgradeable_state(account)
+        {
+            if let Some(programdata) = accounts.get(&programdata_address) {
+                if let Some(UpgradeableLoaderState::ProgramData {
+                    slot,
+                    upgrade_authority_address,
+                }) = upgradeable_state(programdata)
+                {
+                    let elf = programdata
+                        .data()
+                        .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+                        .unwrap_or_default();
+                    let elf_path = dir.join(format!("{}.so", program_id));
+                    fs::write(&elf_path, elf)?;
+                    let fixture_programdata = FixtureProgramData {
+                        slot,
+                        upgrade_authority: upgrade_authority_address
+                            .map(|authority| authority.to_string()),
+                    };
+                    let file = File::create(elf_path.with_extension(PROGRAMDATA_EXTENSION))?;
+                    serde_json::to_writer_pretty(BufWriter::new(file), &fixture_programdata)
+                        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+                    dumped_addresses.insert(**program_id);
+                    dumped_addresses.insert(programdata_address);
+                }
+            }
+        }
+    }
+
+    for (address, account) in accounts {
+        if dumped_addresses.contains(address) {
+            continue;
+        }
+        let fixture_account = FixtureAccount {
+            pubkey: address.to_string(),
+            account: UiAccount::encode(address, account, UiAccountEncoding::Base64, None, None),
+        };
+        let file = File::create(dir.join(format!("{}.json", address)))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &fixture_account)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    }
+    Ok(())
+}
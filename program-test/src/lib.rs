@@ -4,6 +4,10 @@
 // Export tokio for test clients
 pub use tokio;
 use {
+    crate::{
+        coverage::{CoverageProgram, CoverageReport},
+        profile::ProfileReport,
+    },
     async_trait::async_trait,
     chrono_humanize::{Accuracy, HumanTime, Tense},
     log::*,
@@ -23,7 +27,7 @@ use {
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
         account_info::AccountInfo,
-        clock::Slot,
+        clock::{Clock, Epoch, Slot, UnixTimestamp, DEFAULT_MS_PER_SLOT},
         entrypoint::{ProgramResult, SUCCESS},
         feature_set::FEATURE_NAMES,
         fee_calculator::{FeeCalculator, FeeRateGovernor, DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE},
@@ -63,6 +67,9 @@ pub use {
     solana_program_runtime::invoke_context::InvokeContext,
 };
 
+mod coverage;
+pub mod fixture;
+mod profile;
 pub mod programs;
 
 #[macro_use]
@@ -74,6 +81,9 @@ pub enum ProgramTestError {
     /// The chosen warp slot is not in the future, so warp is not performed
     #[error("Warp slot not in the future")]
     InvalidWarpSlot,
+    /// The chosen warp epoch is not in the future, so warp is not performed
+    #[error("Warp epoch not in the future")]
+    InvalidWarpEpoch,
 }
 
 thread_local! {
@@ -430,6 +440,9 @@ pub struct ProgramTest {
     prefer_bpf: bool,
     use_bpf_jit: bool,
     deactivate_feature_set: HashSet<Pubkey>,
+    coverage_dir: Option<PathBuf>,
+    coverage_programs: Vec<CoverageProgram>,
+    profile_dir: Option<PathBuf>,
 }
 
 impl Default for ProgramTest {
@@ -445,6 +458,12 @@ impl Default for ProgramTest {
     /// * the `tests/fixtures` sub-directory
     /// * the current working directory
     ///
+    /// If the `SBF_COVERAGE_DIR` environment variable is defined, lcov coverage reports of the BPF
+    /// programs are written to that directory.  See `ProgramTest::set_coverage_dir()`.
+    ///
+    /// If the `SBF_PROFILE_DIR` environment variable is defined, a compute unit profile of the BPF
+    /// programs is written to that directory.  See `ProgramTest::set_profile_dir()`.
+    ///
     fn default() -> Self {
         solana_logger::setup_with_default(
             "solana_rbpf::vm=debug,\
@@ -454,6 +473,8 @@ impl Default for ProgramTest {
         );
         let prefer_bpf =
             std::env::var("BPF_OUT_DIR").is_ok() || std::env::var("SBF_OUT_DIR").is_ok();
+        let coverage_dir = std::env::var_os("SBF_COVERAGE_DIR").map(PathBuf::from);
+        let profile_dir = std::env::var_os("SBF_PROFILE_DIR").map(PathBuf::from);
 
         Self {
             accounts: vec![],
@@ -462,6 +483,9 @@ impl Default for ProgramTest {
             prefer_bpf,
             use_bpf_jit: false,
             deactivate_feature_set: HashSet::default(),
+            coverage_dir,
+            coverage_programs: vec![],
+            profile_dir,
         }
     }
 }
@@ -506,6 +530,25 @@ impl ProgramTest {
         self.use_bpf_jit = use_bpf_jit;
     }
 
+    /// Collect the coverage of the BPF programs added with `add_program`, and write an lcov report
+    /// of each to `coverage_dir` when the test environment is dropped
+    ///
+    /// Source lines are resolved with the DWARF line info of the unstripped program that
+    /// `cargo build-sbf` leaves in `target/sbf-solana-solana/release`, falling back to the loaded
+    /// program file.
+    pub fn set_coverage_dir<P: Into<PathBuf>>(&mut self, coverage_dir: P) {
+        self.coverage_dir = Some(coverage_dir.into());
+    }
+
+    /// Profile the compute units of every BPF program run, including programs invoked via CPI,
+    /// and write the profile to `profile_dir` when the test environment is dropped
+    ///
+    /// The profile is written as collapsed stacks, e.g. for `inferno-flamegraph` or
+    /// `flamegraph.pl`.
+    pub fn set_profile_dir<P: Into<PathBuf>>(&mut self, profile_dir: P) {
+        self.profile_dir = Some(profile_dir.into());
+    }
+
     /// Add an account to the test environment
     pub fn add_account(&mut self, address: Pubkey, account: Account) {
         self.accounts
@@ -556,6 +599,17 @@ impl ProgramTest {
         );
     }
 
+    /// Add every account and upgradeable program of the fixture directory `dirname` to the test
+    /// environment. See the [`fixture`] module for the directory layout.
+    pub fn add_accounts_from_fixture(&mut self, dirname: &str) {
+        let dir = find_file(dirname).unwrap_or_else(|| {
+            panic!("Unable to locate {}", dirname);
+        });
+        let accounts = fixture::read_fixture(&dir, &Rent::default())
+            .unwrap_or_else(|err| panic!("Failed to load fixture \"{}\": {}", dir.display(), err));
+        self.accounts.extend(accounts);
+    }
+
     /// Add a BPF program to the test environment.
     ///
     /// `program_name` will also be used to locate the BPF shared object in the current or fixtures
@@ -593,6 +647,11 @@ impl ProgramTest {
                     .unwrap_or_else(|| "".to_string())
             );
 
+            this.coverage_programs.push(CoverageProgram {
+                name: program_name.to_string(),
+                program_id,
+                program_file,
+            });
             this.add_account(
                 program_id,
                 Account {
@@ -698,6 +757,23 @@ impl ProgramTest {
         self.deactivate_feature_set.insert(feature_id);
     }
 
+    /// Enable coverage collection and profiling, if requested, before any program runs
+    fn reports(&self) -> TestReports {
+        TestReports {
+            _coverage: self.coverage_dir.as_ref().map(|dir| {
+                solana_bpf_loader_program::coverage::enable_coverage_collection();
+                CoverageReport {
+                    dir: dir.clone(),
+                    programs: self.coverage_programs.clone(),
+                }
+            }),
+            _profile: self.profile_dir.as_ref().map(|dir| {
+                solana_bpf_loader_program::profiler::enable_profiler();
+                ProfileReport { dir: dir.clone() }
+            }),
+        }
+    }
+
     fn setup_bank(
         &self,
     ) -> (
@@ -838,6 +914,7 @@ impl ProgramTest {
     }
 
     pub async fn start(self) -> (BanksClient, Keypair, Hash) {
+        let reports = self.reports();
         let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
         let target_slot_duration = target_tick_duration * gci.genesis_config.ticks_per_slot as u32;
@@ -853,8 +930,9 @@ impl ProgramTest {
 
         // Run a simulated PohService to provide the client with new blockhashes.  New blockhashes
         // are required when sending multiple otherwise identical transactions in series from a
-        // test
+        // test.  The task also holds the reports, which are written when the runtime shuts down
         tokio::spawn(async move {
+            let _reports = reports;
             loop {
                 tokio::time::sleep(target_slot_duration).await;
                 bank_forks
@@ -873,6 +951,7 @@ impl ProgramTest {
     /// Returns a `BanksClient` interface into the test environment as well as a payer `Keypair`
     /// with SOL for sending transactions
     pub async fn start_with_context(self) -> ProgramTestContext {
+        let reports = self.reports();
         let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
         let transport = start_local_server(
@@ -891,6 +970,7 @@ impl ProgramTest {
             banks_client,
             last_blockhash,
             gci,
+            reports,
         )
     }
 }
@@ -964,6 +1044,12 @@ impl ProgramTestBanksClientExt for BanksClient {
     }
 }
 
+/// Reports written when the test environment is dropped
+struct TestReports {
+    _coverage: Option<CoverageReport>,
+    _profile: Option<ProfileReport>,
+}
+
 struct DroppableTask<T>(Arc<AtomicBool>, JoinHandle<T>);
 
 impl<T> Drop for DroppableTask<T> {
@@ -980,6 +1066,7 @@ pub struct ProgramTestContext {
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     _bank_task: DroppableTask<()>,
+    _reports: TestReports,
 }
 
 impl ProgramTestContext {
@@ -989,6 +1076,7 @@ impl ProgramTestContext {
         banks_client: BanksClient,
         last_blockhash: Hash,
         genesis_config_info: GenesisConfigInfo,
+        reports: TestReports,
     ) -> Self {
         // Run a simulated PohService to provide the client with new blockhashes.  New blockhashes
         // are required when sending multiple otherwise identical transactions in series from a
@@ -1026,6 +1114,7 @@ impl ProgramTestContext {
             bank_forks,
             block_commitment_cache,
             _bank_task: bank_task,
+            _reports: reports,
         }
     }
 
@@ -1134,4 +1223,75 @@ impl ProgramTestContext {
         self.last_blockhash = bank.last_blockhash();
         Ok(())
     }
+
+    /// Force the working bank ahead to the first slot of `warp_epoch`.
+    ///
+    /// Unlike a single `warp_to_slot`, each epoch boundary along the way is processed in turn, as
+    /// on a real cluster: stake history gains an entry per epoch, so stake activates and
+    /// deactivates at the expected rate.
+    ///
+    /// No validator votes in the test environment, so vote accounts earn no credits and no
+    /// inflation rewards are paid out unless credits are added with
+    /// `increment_vote_account_credits`.
+    pub fn warp_to_epoch(&mut self, warp_epoch: Epoch) -> Result<(), ProgramTestError> {
+        let (working_epoch, epoch_schedule) = {
+            let bank = self.bank_forks.read().unwrap().working_bank();
+            (bank.epoch(), bank.epoch_schedule().clone())
+        };
+        if warp_epoch <= working_epoch {
+            return Err(ProgramTestError::InvalidWarpEpoch);
+        }
+        for epoch in working_epoch + 1..=warp_epoch {
+            self.warp_to_slot(epoch_schedule.get_first_slot_in_epoch(epoch))?;
+        }
+        Ok(())
+    }
+
+    /// Move the test cluster `seconds` into the future.
+    ///
+    /// The working bank is warped ahead by the number of slots a cluster produces in that time,
+    /// processing each epoch boundary crossed as `warp_to_epoch` does, so `SlotHashes` and stake
+    /// history are updated along the way. `Clock::unix_timestamp` advances by exactly `seconds`,
+    /// and the start of each new epoch is timestamped in proportion to its slot.
+    pub fn advance_clock(&mut self, seconds: u64) -> Result<(), ProgramTestError> {
+        let (start_clock, epoch_schedule) = {
+            let bank = self.bank_forks.read().unwrap().working_bank();
+            (bank.clock(), bank.epoch_schedule().clone())
+        };
+        let num_slots = seconds.saturating_mul(1_000) / DEFAULT_MS_PER_SLOT;
+        if num_slots == 0 {
+            return Ok(());
+        }
+        let warp_slot = start_clock.slot + num_slots;
+        let timestamp_at_slot = |slot: Slot| -> UnixTimestamp {
+            let elapsed = (slot - start_clock.slot) as u128 * seconds as u128 / num_slots as u128;
+            start_clock.unix_timestamp + elapsed as UnixTimestamp
+        };
+
+        for epoch in start_clock.epoch + 1..=epoch_schedule.get_epoch(warp_slot) {
+            let first_slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+            self.warp_to_slot(first_slot)?;
+            let epoch_start_timestamp = timestamp_at_slot(first_slot);
+            self.set_sysvar(&Clock {
+                epoch_start_timestamp,
+                unix_timestamp: epoch_start_timestamp,
+                ..self.clock()
+            });
+        }
+        // Warping resets the epoch start timestamp, so carry it over from before the warp
+        let epoch_start_timestamp = self.clock().epoch_start_timestamp;
+        if self.clock().slot < warp_slot {
+            self.warp_to_slot(warp_slot)?;
+        }
+        self.set_sysvar(&Clock {
+            epoch_start_timestamp,
+            unix_timestamp: timestamp_at_slot(warp_slot),
+            ..self.clock()
+        });
+        Ok(())
+    }
+
+    fn clock(&self) -> Clock {
+        self.bank_forks.read().unwrap().working_bank().clock()
+    }
 }
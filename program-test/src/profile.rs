@@ -0,0 +1,34 @@
+//! Compute unit profiles of the BPF programs run by a `ProgramTest`
+
+use {
+    log::*,
+    solana_bpf_loader_program::profiler::write_profile_file,
+    std::{fs, io, path::PathBuf},
+};
+
+/// Writes the profile collected so far as `<DIR>/profile-<PID>.folded` when dropped
+///
+/// The profile is process-wide, so the last report written by a test binary is complete.
+pub(crate) struct ProfileReport {
+    pub dir: PathBuf,
+}
+
+impl ProfileReport {
+    fn write(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let profile_file = self
+            .dir
+            .join(format!("profile-{}.folded", std::process::id()));
+        write_profile_file(&profile_file)?;
+        info!("BPF program profile written to {}", profile_file.display());
+        Ok(())
+    }
+}
+
+impl Drop for ProfileReport {
+    fn drop(&mut self) {
+        if let Err(err) = self.write() {
+            warn!("Failed to write BPF program profile: {}", err);
+        }
+    }
+}
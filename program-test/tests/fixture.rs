@@ -0,0 +1,89 @@
+use {
+    solana_program_test::{fixture, ProgramTest},
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        pubkey::Pubkey,
+        rent::Rent,
+        system_program,
+    },
+};
+
+#[tokio::test]
+async fn accounts_from_fixture() {
+    let fixture_dir = tempfile::tempdir().unwrap();
+
+    let wallet_address = Pubkey::new_unique();
+    let wallet = AccountSharedData::new(42, 3, &system_program::id());
+    let program_id = Pubkey::new_unique();
+    let upgrade_authority_address = Pubkey::new_unique();
+    let [program, programdata] = fixture::upgradeable_program_accounts(
+        &program_id,
+        &[0x7f, b'E', b'L', b'F'],
+        42,
+        Some(upgrade_authority_address),
+        &Rent::default(),
+    );
+    let accounts = vec![(wallet_address, wallet.clone()), program, programdata];
+    fixture::write_fixture(
+        fixture_dir.path(),
+        accounts.iter().map(|(address, account)| (address, account)),
+    )
+    .unwrap();
+    assert!(fixture_dir
+        .path()
+        .join(format!("{}.json", wallet_address))
+        .exists());
+    assert!(fixture_dir
+        .path()
+        .join(format!("{}.so", program_id))
+        .exists());
+
+    let mut program_test = ProgramTest::default();
+    program_test.add_accounts_from_fixture(fixture_dir.path().to_str().unwrap());
+    let mut context = program_test.start_with_context().await;
+
+    for (address, expected_account) in accounts {
+        let account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.lamports, expected_account.lamports());
+        assert_eq!(account.data, expected_account.data());
+        assert_eq!(account.owner, *expected_account.owner());
+        assert_eq!(account.executable, expected_account.executable());
+    }
+
+    // The program data metadata survives the ELF dump
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let programdata = context
+        .banks_client
+        .get_account(programdata_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        bincode::deserialize::<UpgradeableLoaderState>(&programdata.data).unwrap(),
+        UpgradeableLoaderState::ProgramData {
+            slot: 42,
+            upgrade_authority_address: Some(upgrade_authority_address),
+        }
+    );
+}
+
+#[test]
+fn program_without_programdata_file_from_fixture() {
+    let fixture_dir = tempfile::tempdir().unwrap();
+    let program_id = Pubkey::new_unique();
+    let elf = [0x7f, b'E', b'L', b'F'];
+    std::fs::write(fixture_dir.path().join(format!("{}.so", program_id)), elf).unwrap();
+
+    let rent = Rent::default();
+    let accounts = fixture::read_fixture(fixture_dir.path(), &rent).unwrap();
+    let expected_accounts =
+        fixture::upgradeable_program_accounts(&program_id, &elf, 0, None, &rent);
+    assert_eq!(accounts, expected_accounts.to_vec());
+}
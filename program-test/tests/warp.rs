@@ -7,13 +7,15 @@ use {
     },
     solana_sdk::{
         account_info::{next_account_info, AccountInfo},
-        clock::Clock,
+        clock::{Clock, DEFAULT_MS_PER_SLOT},
         entrypoint::ProgramResult,
         instruction::{AccountMeta, Instruction, InstructionError},
+        native_token::sol_to_lamports,
         program_error::ProgramError,
         pubkey::Pubkey,
         rent::Rent,
         signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
         stake::{
             instruction as stake_instruction,
             state::{Authorized, Lockup, StakeActivationStatus, StakeState},
@@ -411,3 +413,157 @@ async fn get_blockhash_post_warp() {
     tx.sign(&[&context.payer], new_blockhash);
     context.banks_client.process_transaction(tx).await.unwrap();
 }
+
+#[tokio::test]
+async fn warp_to_epoch_processes_each_epoch_boundary() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    let start_epoch = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .epoch;
+    let warp_epoch = start_epoch + 3;
+    context.warp_to_epoch(warp_epoch).unwrap();
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(clock.epoch, warp_epoch);
+    let stake_history = context
+        .banks_client
+        .get_sysvar::<StakeHistory>()
+        .await
+        .unwrap();
+    for epoch in start_epoch..warp_epoch {
+        assert!(stake_history.get(epoch).is_some());
+    }
+
+    assert_eq!(
+        context.warp_to_epoch(warp_epoch).unwrap_err(),
+        ProgramTestError::InvalidWarpEpoch,
+    );
+}
+
+async fn get_stake_activation_status(
+    context: &mut ProgramTestContext,
+    stake_address: Pubkey,
+) -> (u64, StakeActivationStatus) {
+    let stake_account = context
+        .banks_client
+        .get_account(stake_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let stake_state: StakeState = deserialize(&stake_account.data).unwrap();
+    let delegation = stake_state.delegation().unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let stake_history = context
+        .banks_client
+        .get_sysvar::<StakeHistory>()
+        .await
+        .unwrap();
+    (
+        delegation.stake,
+        delegation.stake_activating_and_deactivating(clock.epoch, Some(&stake_history)),
+    )
+}
+
+#[tokio::test]
+async fn warp_to_epoch_activates_stake_gradually() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+    let vote_address = setup_vote(&mut context).await;
+
+    // more than the bootstrap stake can warm up in a single epoch
+    let user_keypair = Keypair::new();
+    let stake_lamports = sol_to_lamports(600_000.0);
+    let stake_address =
+        setup_stake(&mut context, &user_keypair, &vote_address, stake_lamports).await;
+    let (stake, status) = get_stake_activation_status(&mut context, stake_address).await;
+    assert_eq!(
+        status,
+        StakeActivationStatus::with_effective_and_activating(0, stake)
+    );
+
+    let activation_epoch = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .epoch;
+    context.warp_to_epoch(activation_epoch + 1).unwrap();
+    let (_, status) = get_stake_activation_status(&mut context, stake_address).await;
+    assert!(status.effective > 0);
+    assert!(status.effective < stake);
+    assert_eq!(status.activating, stake - status.effective);
+
+    context.warp_to_epoch(activation_epoch + 3).unwrap();
+    let (_, status) = get_stake_activation_status(&mut context, stake_address).await;
+    assert_eq!(status, StakeActivationStatus::with_effective(stake));
+}
+
+#[tokio::test]
+async fn warp_to_epoch_pays_rewards_for_vote_credits() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+    let vote_address = setup_vote(&mut context).await;
+
+    let user_keypair = Keypair::new();
+    let stake_lamports = 1_000_000_000_000;
+    let stake_address =
+        setup_stake(&mut context, &user_keypair, &vote_address, stake_lamports).await;
+
+    // the stake is active by the first normal epoch, but no credits were earned
+    let first_normal_epoch = context.genesis_config().epoch_schedule.first_normal_epoch;
+    context.warp_to_epoch(first_normal_epoch).unwrap();
+    let (stake, status) = get_stake_activation_status(&mut context, stake_address).await;
+    assert_eq!(status, StakeActivationStatus::with_effective(stake));
+    let account = context
+        .banks_client
+        .get_account(stake_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.lamports, stake_lamports);
+
+    context.increment_vote_account_credits(&vote_address, 100);
+    context.warp_to_epoch(first_normal_epoch + 1).unwrap();
+    let account = context
+        .banks_client
+        .get_account(stake_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(account.lamports > stake_lamports);
+}
+
+#[tokio::test]
+async fn advance_clock_moves_slot_and_timestamp() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    let start_clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let seconds = 3_600;
+    context.advance_clock(seconds).unwrap();
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(
+        clock.unix_timestamp,
+        start_clock.unix_timestamp + seconds as i64
+    );
+    assert_eq!(
+        clock.slot,
+        start_clock.slot + seconds * 1_000 / DEFAULT_MS_PER_SLOT
+    );
+    assert!(clock.epoch > start_clock.epoch);
+    assert!(clock.epoch_start_timestamp > start_clock.unix_timestamp);
+    assert!(clock.epoch_start_timestamp <= clock.unix_timestamp);
+
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    assert!(slot_hashes.get(&(clock.slot - 1)).is_some());
+}
@@ -915,7 +915,7 @@ fn test_return_data_and_log_data_syscall() {
         let transaction = Transaction::new(&[&mint_keypair], message, blockhash);
         let sanitized_tx = SanitizedTransaction::from_transaction_for_tests(transaction);
 
-        let result = bank.simulate_transaction(sanitized_tx);
+        let result = bank.simulate_transaction(sanitized_tx, false);
 
         assert!(result.result.is_ok());
 
@@ -3586,6 +3586,8 @@ fn test_program_fees() {
         &fee_structure,
         true,
         true,
+        true,
+        true,
     );
     bank_client
         .send_and_confirm_message(&[&mint_keypair], message)
@@ -3608,6 +3610,8 @@ fn test_program_fees() {
         &fee_structure,
         true,
         true,
+        true,
+        true,
     );
     assert!(expected_normal_fee < expected_prioritized_fee);
 
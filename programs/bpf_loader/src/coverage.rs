@@ -0,0 +1,165 @@
+//! Instruction-trace based code coverage of BPF programs
+//!
+//! Once collection is enabled, every BPF program run is traced and the number of times each
+//! instruction executed is accumulated per program id. Instructions are keyed by their address in
+//! the program's ELF, so that [`write_lcov`] can map them back to source lines through the DWARF
+//! line info of the unstripped shared object that `cargo-build-sbf` leaves in
+//! `target/sbf-solana-solana/release`.
+
+#[cfg(feature = "coverage")]
+use std::io::{self, Write};
+use {
+    lazy_static::lazy_static,
+    solana_rbpf::{ebpf, vm::Tracer},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
+    },
+};
+
+/// Number of times each instruction of a program executed, keyed by the instruction's ELF address
+pub type InstructionHits = BTreeMap<u64, u64>;
+
+/// Index of the program counter in the register state logged by the tracer
+const TRACER_PC_INDEX: usize = 11;
+
+static COLLECTION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref COVERAGE: Mutex<HashMap<Pubkey, InstructionHits>> = Mutex::new(HashMap::new());
+}
+
+/// Collect the coverage of every BPF program run in this process from now on
+///
+/// Tracing is configured when a program's executor is created, so collection must be enabled
+/// before the program is first invoked.
+pub fn enable_coverage_collection() {
+    COLLECTION_ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_coverage_collection_enabled() -> bool {
+    COLLECTION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Count the instructions executed in `tracer`
+///
+/// `text_vaddr` is the VM address of the program's text section, as returned by
+/// `Executable::get_text_bytes()`.
+pub fn instruction_hits(text_vaddr: u64, tracer: &Tracer) -> InstructionHits {
+    let text_address = text_vaddr.saturating_sub(ebpf::MM_PROGRAM_START);
+    let mut hits = InstructionHits::new();
+    for pc in tracer
+        .log
+        .iter()
+        .filter_map(|state| state.get(TRACER_PC_INDEX))
+    {
+        let address = text_address.saturating_add(pc.saturating_mul(ebpf::INSN_SIZE as u64));
+        let count = hits.entry(address).or_default();
+        *count = count.saturating_add(1);
+    }
+    hits
+}
+
+/// Add the instructions executed in `tracer` to the coverage collected for `program_id`
+pub fn record_trace(program_id: &Pubkey, text_vaddr: u64, tracer: &Tracer) {
+    let hits = instruction_hits(text_vaddr, tracer);
+    let mut coverage = COVERAGE.lock().unwrap();
+    let program_hits = coverage.entry(*program_id).or_default();
+    for (address, count) in hits {
+        let total = program_hits.entry(address).or_default();
+        *total = total.saturating_add(count);
+    }
+}
+
+/// The coverage collected so far, per program id
+pub fn coverage() -> HashMap<Pubkey, InstructionHits> {
+    COVERAGE.lock().unwrap().clone()
+}
+
+/// Write `hits` as an lcov tracefile, using the DWARF line info of the program's unstripped `elf`
+///
+/// Every line with debug info is reported, so that lines which never executed count as missed.
+#[cfg(feature = "coverage")]
+pub fn write_lcov<W: Write>(writer: &mut W, elf: &[u8], hits: &InstructionHits) -> io::Result<()> {
+    use addr2line::object::{self, Object, ObjectSection};
+
+    fn invalid_data(err: impl std::fmt::Display) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+    }
+
+    let file = object::File::parse(elf).map_err(invalid_data)?;
+    let text = file
+        .section_by_name(".text")
+        .ok_or_else(|| invalid_data("no .text section"))?;
+    let context = addr2line::Context::new(&file).map_err(invalid_data)?;
+
+    let mut source_files = BTreeMap::<String, BTreeMap<u32, u64>>::new();
+    let locations = context
+        .find_location_range(text.address(), text.address().saturating_add(text.size()))
+        .map_err(invalid_data)?;
+    for (address, size, location) in locations {
+        if let (Some(source_file), Some(line)) = (location.file, location.line) {
+            // A line compiles to several instructions, it ran as often as the most executed one
+            let count = hits
+                .range(address..address.saturating_add(size))
+                .map(|(_, count)| *count)
+                .max()
+                .unwrap_or(0);
+            let line_count = source_files
+                .entry(source_file.to_string())
+                .or_default()
+                .entry(line)
+                .or_default();
+            *line_count = (*line_count).max(count);
+        }
+    }
+    if source_files.is_empty() {
+        return Err(invalid_data(
+            "no DWARF line info, use the unstripped shared object",
+        ));
+    }
+
+    for (source_file, lines) in source_files {
+        writeln!(writer, "SF:{}", source_file)?;
+        for (line, count) in &lines {
+            writeln!(writer, "DA:{},{}", line, count)?;
+        }
+        writeln!(writer, "LF:{}", lines.len())?;
+        writeln!(
+            writer,
+            "LH:{}",
+            lines.values().filter(|count| **count > 0).count()
+        )?;
+        writeln!(writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_trace() {
+        let text_vaddr = ebpf::MM_PROGRAM_START.saturating_add(0x120);
+        let mut tracer = Tracer::default();
+        for pc in [0, 1, 2, 1, 2, 3] {
+            let mut state = [0; 12];
+            *state.get_mut(TRACER_PC_INDEX).unwrap() = pc;
+            tracer.log.push(state);
+        }
+
+        let program_id = Pubkey::new_unique();
+        record_trace(&program_id, text_vaddr, &tracer);
+        record_trace(&program_id, text_vaddr, &tracer);
+
+        let expected: InstructionHits = [(0x120, 2), (0x128, 4), (0x130, 4), (0x138, 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(coverage().get(&program_id), Some(&expected));
+    }
+}
@@ -2,7 +2,9 @@
 #![deny(clippy::indexing_slicing)]
 
 pub mod allocator_bump;
+pub mod coverage;
 pub mod deprecated;
+pub mod profiler;
 pub mod serialization;
 pub mod syscalls;
 pub mod upgradeable;
@@ -43,7 +45,7 @@ use {
         feature_set::{
             cap_accounts_data_len, disable_bpf_deprecated_load_instructions,
             disable_bpf_unresolved_symbols_at_runtime, disable_deploy_of_alloc_free_syscall,
-            disable_deprecated_loader, do_support_realloc,
+            disable_deprecated_loader, do_support_realloc, enable_bpf_loader_extend_program_ix,
             error_on_syscall_bpf_function_hash_collisions, reduce_required_deploy_balance,
             reject_callx_r10, requestable_heap_size,
         },
@@ -131,8 +133,10 @@ pub fn create_executor(
         enable_stack_frame_gaps: true,
         instruction_meter_checkpoint_distance: 10000,
         enable_instruction_meter: true,
-        enable_instruction_tracing: log_enabled!(Trace),
-        enable_symbol_and_section_labels: false,
+        enable_instruction_tracing: log_enabled!(Trace)
+            || coverage::is_coverage_collection_enabled()
+            || profiler::is_profiler_enabled(),
+        enable_symbol_and_section_labels: profiler::is_profiler_enabled(),
         disable_unresolved_symbols_at_runtime: invoke_context
             .feature_set
             .is_active(&disable_bpf_unresolved_symbols_at_runtime::id()),
@@ -1035,6 +1039,130 @@ fn process_loader_upgradeable_instruction(
                 }
             }
         }
+        UpgradeableLoaderInstruction::ExtendProgram { additional_bytes } => {
+            if !invoke_context
+                .feature_set
+                .is_active(&enable_bpf_loader_extend_program_ix::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            if additional_bytes == 0 {
+                ic_logger_msg!(log_collector, "Additional bytes must be greater than 0");
+                return Err(InstructionError::InvalidInstructionData);
+            }
+
+            const PROGRAM_DATA_ACCOUNT_INDEX: usize = 0;
+            const PROGRAM_ACCOUNT_INDEX: usize = 1;
+            // The system program at index 2 is only required when the payer is debited
+            const OPTIONAL_PAYER_ACCOUNT_INDEX: usize = 3;
+
+            instruction_context.check_number_of_instruction_accounts(2)?;
+            let programdata_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_DATA_ACCOUNT_INDEX)?;
+            let programdata_key = *programdata_account.get_key();
+
+            if program_id != programdata_account.get_owner() {
+                ic_logger_msg!(log_collector, "ProgramData owner is invalid");
+                return Err(InstructionError::InvalidAccountOwner);
+            }
+            if !programdata_account.is_writable() {
+                ic_logger_msg!(log_collector, "ProgramData is not writable");
+                return Err(InstructionError::InvalidArgument);
+            }
+
+            let program_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_ACCOUNT_INDEX)?;
+            if !program_account.is_writable() {
+                ic_logger_msg!(log_collector, "Program account is not writable");
+                return Err(InstructionError::InvalidArgument);
+            }
+            if program_account.get_owner() != program_id {
+                ic_logger_msg!(log_collector, "Program account not owned by loader");
+                return Err(InstructionError::InvalidAccountOwner);
+            }
+            match program_account.get_state()? {
+                UpgradeableLoaderState::Program {
+                    programdata_address,
+                } => {
+                    if programdata_address != programdata_key {
+                        ic_logger_msg!(
+                            log_collector,
+                            "Program account does not match ProgramData account"
+                        );
+                        return Err(InstructionError::InvalidArgument);
+                    }
+                }
+                _ => {
+                    ic_logger_msg!(log_collector, "Invalid Program account");
+                    return Err(InstructionError::InvalidAccountData);
+                }
+            }
+            drop(program_account);
+
+            let old_len = programdata_account.get_data().len();
+            let new_len = old_len.saturating_add(additional_bytes as usize);
+            if new_len > MAX_PERMITTED_DATA_LENGTH as usize {
+                ic_logger_msg!(
+                    log_collector,
+                    "Extended ProgramData length of {} bytes exceeds max account data length of {} bytes",
+                    new_len,
+                    MAX_PERMITTED_DATA_LENGTH
+                );
+                return Err(InstructionError::InvalidRealloc);
+            }
+
+            if let UpgradeableLoaderState::ProgramData {
+                slot: _,
+                upgrade_authority_address,
+            } = programdata_account.get_state()?
+            {
+                if upgrade_authority_address.is_none() {
+                    ic_logger_msg!(
+                        log_collector,
+                        "Cannot extend ProgramData accounts that are not upgradeable"
+                    );
+                    return Err(InstructionError::Immutable);
+                }
+            } else {
+                ic_logger_msg!(log_collector, "ProgramData state is invalid");
+                return Err(InstructionError::InvalidAccountData);
+            }
+
+            let required_payment = {
+                let balance = programdata_account.get_lamports();
+                let rent = invoke_context.get_sysvar_cache().get_rent()?;
+                let min_balance = rent.minimum_balance(new_len).max(1);
+                min_balance.saturating_sub(balance)
+            };
+
+            // Borrowed accounts need to be dropped before native_invoke
+            drop(programdata_account);
+
+            if required_payment > 0 {
+                let payer_key = *transaction_context.get_key_of_account_at_index(
+                    instruction_context.get_index_in_transaction(
+                        first_instruction_account.saturating_add(OPTIONAL_PAYER_ACCOUNT_INDEX),
+                    )?,
+                )?;
+
+                invoke_context.native_invoke(
+                    system_instruction::transfer(&payer_key, &programdata_key, required_payment),
+                    &[],
+                )?;
+            }
+
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context.get_current_instruction_context()?;
+            let mut programdata_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_DATA_ACCOUNT_INDEX)?;
+            programdata_account.set_data_length(new_len)?;
+
+            ic_logger_msg!(
+                log_collector,
+                "Extended ProgramData account by {} bytes",
+                additional_bytes
+            );
+        }
     }
 
     Ok(())
@@ -1180,6 +1308,11 @@ impl Executor for BpfExecutor {
         let transaction_context = &invoke_context.transaction_context;
         let instruction_context = transaction_context.get_current_instruction_context()?;
         let program_id = *instruction_context.get_program_key(transaction_context)?;
+        let profiler_invocation_stack = if profiler::is_profiler_enabled() {
+            Some(profiler::invocation_stack(transaction_context)?)
+        } else {
+            None
+        };
 
         let mut serialize_time = Measure::start("serialize");
         let (mut parameter_bytes, account_lengths) =
@@ -1207,12 +1340,23 @@ impl Executor for BpfExecutor {
             stable_log::program_invoke(&log_collector, &program_id, stack_height);
             let mut instruction_meter = ThisInstructionMeter::new(compute_meter.clone());
             let before = compute_meter.borrow().get_remaining();
+            if profiler_invocation_stack.is_some() {
+                profiler::begin_run();
+            }
             let result = if self.use_jit {
                 vm.execute_program_jit(&mut instruction_meter)
             } else {
                 vm.execute_program_interpreted(&mut instruction_meter)
             };
             let after = compute_meter.borrow().get_remaining();
+            if let Some(invocation_stack) = &profiler_invocation_stack {
+                profiler::end_run(
+                    invocation_stack,
+                    &self.executable,
+                    vm.get_tracer(),
+                    before.saturating_sub(after),
+                );
+            }
             ic_logger_msg!(
                 log_collector,
                 "Program {} consumed {} of {} compute units",
@@ -1227,6 +1371,13 @@ impl Executor for BpfExecutor {
                 let trace_string = String::from_utf8(trace_buffer).unwrap();
                 trace!("BPF Program Instruction Trace:\n{}", trace_string);
             }
+            if coverage::is_coverage_collection_enabled() {
+                coverage::record_trace(
+                    &program_id,
+                    self.executable.get_text_bytes().0,
+                    vm.get_tracer(),
+                );
+            }
             drop(vm);
             let (_returned_from_program_id, return_data) =
                 invoke_context.transaction_context.get_return_data();
@@ -3756,6 +3907,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bpf_loader_upgradeable_extend_program() {
+        let loader_id = bpf_loader_upgradeable::id();
+        let rent = Rent::default();
+        let authority_address = Pubkey::new_unique();
+        let program_address = Pubkey::new_unique();
+        let (programdata_address, _) =
+            Pubkey::find_program_address(&[program_address.as_ref()], &loader_id);
+        let programdata_len = UpgradeableLoaderState::size_of_programdata(10);
+        let mut programdata_account = AccountSharedData::new(
+            rent.minimum_balance(programdata_len.saturating_add(20)),
+            programdata_len,
+            &loader_id,
+        );
+        programdata_account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(authority_address),
+            })
+            .unwrap();
+        let mut program_account = AccountSharedData::new(
+            rent.minimum_balance(UpgradeableLoaderState::size_of_program()),
+            UpgradeableLoaderState::size_of_program(),
+            &loader_id,
+        );
+        program_account.set_executable(true);
+        program_account
+            .set_state(&UpgradeableLoaderState::Program {
+                programdata_address,
+            })
+            .unwrap();
+        let transaction_accounts = |programdata_account: &AccountSharedData| {
+            vec![
+                (programdata_address, programdata_account.clone()),
+                (program_address, program_account.clone()),
+                (sysvar::rent::id(), create_account_for_test(&rent)),
+            ]
+        };
+        let instruction_accounts = vec![
+            AccountMeta::new(programdata_address, false),
+            AccountMeta::new(program_address, false),
+        ];
+        let extend_program = |additional_bytes: u32| {
+            bincode::serialize(&UpgradeableLoaderInstruction::ExtendProgram { additional_bytes })
+                .unwrap()
+        };
+
+        // Case: the ProgramData account already pays for the extension
+        let accounts = process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(20),
+            transaction_accounts(&programdata_account),
+            instruction_accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            accounts.first().unwrap().data().len(),
+            programdata_len.saturating_add(20)
+        );
+
+        // Case: zero additional bytes
+        process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(0),
+            transaction_accounts(&programdata_account),
+            instruction_accounts.clone(),
+            Err(InstructionError::InvalidInstructionData),
+        );
+
+        // Case: the extension needs a payer, which is missing
+        process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(21),
+            transaction_accounts(&programdata_account),
+            instruction_accounts.clone(),
+            Err(InstructionError::NotEnoughAccountKeys),
+        );
+
+        // Case: beyond the max account data length
+        process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(MAX_PERMITTED_DATA_LENGTH as u32),
+            transaction_accounts(&programdata_account),
+            instruction_accounts.clone(),
+            Err(InstructionError::InvalidRealloc),
+        );
+
+        // Case: Program account doesn't match the ProgramData account
+        let other_programdata_address = Pubkey::new_unique();
+        process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(20),
+            vec![
+                (other_programdata_address, programdata_account.clone()),
+                (program_address, program_account.clone()),
+                (sysvar::rent::id(), create_account_for_test(&rent)),
+            ],
+            vec![
+                AccountMeta::new(other_programdata_address, false),
+                AccountMeta::new(program_address, false),
+            ],
+            Err(InstructionError::InvalidArgument),
+        );
+
+        // Case: the program isn't upgradeable
+        programdata_account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: None,
+            })
+            .unwrap();
+        process_instruction(
+            &loader_id,
+            &[],
+            &extend_program(20),
+            transaction_accounts(&programdata_account),
+            instruction_accounts,
+            Err(InstructionError::Immutable),
+        );
+    }
+
     #[test]
     #[ignore]
     fn test_fuzz() {
@@ -0,0 +1,295 @@
+//! Compute unit profiler of BPF programs
+//!
+//! Once enabled, every BPF program run is traced and each executed instruction's compute unit is
+//! attributed to the call stack it ran in, with functions named after the ELF symbol table.
+//! Stacks start with the program ids of the invocation, so programs invoked via CPI appear under
+//! their callers. The units a run consumed in syscalls are attributed to a `[syscalls]` frame
+//! under the program, as the trace doesn't tell which instruction they were charged to.
+//!
+//! The profile is written in the collapsed stack format read by flamegraph tools.
+
+use {
+    crate::{BpfError, ThisInstructionMeter},
+    lazy_static::lazy_static,
+    solana_rbpf::{ebpf, elf::Executable, vm::Tracer},
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, transaction_context::TransactionContext,
+    },
+    std::{
+        cell::RefCell,
+        collections::{BTreeMap, HashMap},
+        fs::{self, File},
+        io::{self, BufWriter, Write},
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
+    },
+};
+
+/// Index of the program counter in the register state logged by the tracer
+const TRACER_PC_INDEX: usize = 11;
+
+static PROFILER_ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref PROFILE: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+thread_local! {
+    /// Units consumed by the CPIs of each program run in progress on this thread
+    static NESTED_UNITS: RefCell<Vec<u64>> = RefCell::new(vec![]);
+}
+
+/// Profile every BPF program run in this process from now on
+///
+/// Tracing is configured when a program's executor is created, so the profiler must be enabled
+/// before the program is first invoked.
+pub fn enable_profiler() {
+    PROFILER_ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_profiler_enabled() -> bool {
+    PROFILER_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The program ids of the current instruction and of the instructions that invoked it
+pub(crate) fn invocation_stack(
+    transaction_context: &TransactionContext,
+) -> Result<Vec<Pubkey>, InstructionError> {
+    (0..transaction_context.get_instruction_context_stack_height())
+        .map(|level| {
+            transaction_context
+                .get_instruction_context_at(level)?
+                .get_program_key(transaction_context)
+                .copied()
+        })
+        .collect()
+}
+
+/// Start profiling a program run, before the program executes
+pub(crate) fn begin_run() {
+    NESTED_UNITS.with(|nested_units| nested_units.borrow_mut().push(0));
+}
+
+/// Finish profiling a program run that consumed `units_consumed`, including its CPIs
+pub(crate) fn end_run(
+    invocation_stack: &[Pubkey],
+    executable: &Executable<BpfError, ThisInstructionMeter>,
+    tracer: &Tracer,
+    units_consumed: u64,
+) {
+    let nested_units = NESTED_UNITS.with(|nested_units| {
+        let mut nested_units = nested_units.borrow_mut();
+        let run_nested_units = nested_units.pop().unwrap_or_default();
+        if let Some(caller_nested_units) = nested_units.last_mut() {
+            *caller_nested_units = caller_nested_units.saturating_add(units_consumed);
+        }
+        run_nested_units
+    });
+    let syscall_units = units_consumed
+        .saturating_sub(tracer.log.len() as u64)
+        .saturating_sub(nested_units);
+
+    record_run(
+        invocation_stack,
+        sample_trace(executable, tracer),
+        syscall_units,
+    );
+}
+
+/// Add the units of a program run to the profile, under the program ids of `invocation_stack`
+fn record_run(invocation_stack: &[Pubkey], samples: HashMap<String, u64>, syscall_units: u64) {
+    let prefix = invocation_stack
+        .iter()
+        .map(|program_id| program_id.to_string())
+        .collect::<Vec<_>>()
+        .join(";");
+    let mut profile = PROFILE.lock().unwrap();
+    let mut add_units = |stack: String, units: u64| {
+        let total = profile.entry(stack).or_default();
+        *total = total.saturating_add(units);
+    };
+    for (stack, units) in samples {
+        add_units(format!("{};{}", prefix, stack), units);
+    }
+    if syscall_units > 0 {
+        add_units(format!("{};[syscalls]", prefix), syscall_units);
+    }
+}
+
+/// Count the instructions executed in each call stack of `tracer`
+fn sample_trace(
+    executable: &Executable<BpfError, ThisInstructionMeter>,
+    tracer: &Tracer,
+) -> HashMap<String, u64> {
+    let (_, text) = executable.get_text_bytes();
+    let functions = executable
+        .get_function_registry()
+        .values()
+        .map(|(pc, name)| (*pc, name.as_str()))
+        .collect::<BTreeMap<_, _>>();
+    let pcs = tracer
+        .log
+        .iter()
+        .filter_map(|state| state.get(TRACER_PC_INDEX))
+        .map(|pc| *pc as usize)
+        .collect::<Vec<_>>();
+    sample_pcs(text, &functions, &pcs)
+}
+
+/// Count the instructions executed in each call stack, given the program counters `pcs` of the
+/// instructions of `text` in the order they were executed, and the names of the `functions`
+/// starting at each program counter
+fn sample_pcs(
+    text: &[u8],
+    functions: &BTreeMap<usize, &str>,
+    pcs: &[usize],
+) -> HashMap<String, u64> {
+    let function_at = |pc: usize| match functions.range(..=pc).next_back() {
+        // `;` separates the frames of collapsed stacks
+        Some((_, name)) if !name.is_empty() => name.replace(';', ","),
+        Some((start, _)) => format!("function_{}", start),
+        None => "[unknown]".to_string(),
+    };
+
+    let mut samples = HashMap::<String, u64>::new();
+    let mut stack = vec![];
+    let mut stack_key = String::new();
+    for (index, pc) in pcs.iter().enumerate() {
+        if stack.is_empty() {
+            stack.push(function_at(*pc));
+            stack_key = stack.join(";");
+        }
+        match samples.get_mut(&stack_key) {
+            Some(units) => *units = units.saturating_add(1),
+            None => {
+                samples.insert(stack_key.clone(), 1);
+            }
+        }
+
+        let next_pc = pcs.get(index.saturating_add(1));
+        match text.get(pc.saturating_mul(ebpf::INSN_SIZE)).copied() {
+            // Syscalls return to the next instruction, function calls jump elsewhere
+            Some(ebpf::CALL_IMM | ebpf::CALL_REG) => {
+                if let Some(next_pc) = next_pc.filter(|next_pc| **next_pc != pc.saturating_add(1)) {
+                    stack.push(function_at(*next_pc));
+                    stack_key = stack.join(";");
+                }
+            }
+            Some(ebpf::EXIT) => {
+                stack.pop();
+                stack_key = stack.join(";");
+            }
+            _ => {}
+        }
+    }
+    samples
+}
+
+/// Write the profile collected so far as collapsed stacks, one `<STACK> <UNITS>` line per stack
+pub fn write_profile<W: Write>(writer: &mut W) -> io::Result<()> {
+    let profile = PROFILE
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(stack, units)| (stack.clone(), *units))
+        .collect::<BTreeMap<_, _>>();
+    for (stack, units) in profile {
+        writeln!(writer, "{} {}", stack, units)?;
+    }
+    writer.flush()
+}
+
+/// Write the profile collected so far to `path`, see `write_profile`
+///
+/// The profile is written to a temporary file that then replaces `path`, so that `path` always
+/// holds a complete profile even if the process is killed while the profile is written.
+pub fn write_profile_file(path: &Path) -> io::Result<()> {
+    let mut temp_file_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_file_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_file_name);
+    write_profile(&mut BufWriter::new(File::create(&temp_path)?))?;
+    fs::rename(temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_pcs() {
+        let mut text = vec![0; ebpf::INSN_SIZE.saturating_mul(12)];
+        let mut set_opcode = |pc: usize, opc: u8| {
+            *text.get_mut(pc.saturating_mul(ebpf::INSN_SIZE)).unwrap() = opc;
+        };
+        set_opcode(1, ebpf::CALL_IMM);
+        set_opcode(2, ebpf::CALL_IMM);
+        set_opcode(4, ebpf::CALL_REG);
+        set_opcode(5, ebpf::EXIT);
+        set_opcode(7, ebpf::EXIT);
+        set_opcode(10, ebpf::EXIT);
+        let functions = [(0, "entrypoint"), (6, "a;b"), (9, "")]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        // entrypoint calls `a;b`, makes a syscall, then calls the unnamed function at 9
+        let pcs = [0, 1, 6, 7, 2, 3, 4, 9, 10, 5];
+        let samples = sample_pcs(&text, &functions, &pcs);
+        let expected = [
+            ("entrypoint", 6),
+            ("entrypoint;a,b", 2),
+            ("entrypoint;function_9", 2),
+        ]
+        .into_iter()
+        .map(|(stack, units)| (stack.to_string(), units))
+        .collect::<HashMap<_, _>>();
+        assert_eq!(samples, expected);
+
+        // instructions outside of any known function
+        let functions = [(4, "f")].into_iter().collect::<BTreeMap<_, _>>();
+        let samples = sample_pcs(&text, &functions, &[0, 3]);
+        assert_eq!(
+            samples,
+            [("[unknown]".to_string(), 2)].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_write_profile() {
+        let caller = Pubkey::new_unique();
+        let callee = Pubkey::new_unique();
+        let samples = |stacks: &[(&str, u64)]| {
+            stacks
+                .iter()
+                .map(|(stack, units)| (stack.to_string(), *units))
+                .collect::<HashMap<_, _>>()
+        };
+        record_run(
+            &[caller, callee],
+            samples(&[("entrypoint", 10), ("entrypoint;f", 5)]),
+            0,
+        );
+        record_run(&[caller], samples(&[("entrypoint", 20)]), 7);
+        record_run(&[caller, callee], samples(&[("entrypoint", 1)]), 3);
+
+        let mut profile = vec![];
+        write_profile(&mut profile).unwrap();
+        let mut expected = vec![
+            format!("{};entrypoint 20", caller),
+            format!("{};[syscalls] 7", caller),
+            format!("{};{};entrypoint 11", caller, callee),
+            format!("{};{};entrypoint;f 5", caller, callee),
+            format!("{};{};[syscalls] 3", caller, callee),
+        ];
+        expected.sort();
+        assert_eq!(
+            String::from_utf8(profile).unwrap(),
+            expected
+                .into_iter()
+                .map(|line| line + "\n")
+                .collect::<String>()
+        );
+    }
+}
@@ -23,7 +23,8 @@ use {
         feature_set::{
             add_get_processed_sibling_instruction_syscall, blake3_syscall_enabled,
             check_physical_overlapping, check_slice_translation_size, curve25519_syscall_enabled,
-            disable_fees_sysvar, do_support_realloc, executables_incur_cpi_data_cost,
+            disable_fees_sysvar, do_support_realloc, enable_alt_bn128_syscall,
+            enable_poseidon_syscall, executables_incur_cpi_data_cost,
             fixed_memcpy_nonoverlapping_check, libsecp256k1_0_5_upgrade_enabled,
             limit_secp256k1_recovery_id, prevent_calling_precompiles_as_programs,
             return_data_syscall_enabled, secp256k1_recover_syscall_enabled,
@@ -35,7 +36,7 @@ use {
             AccountMeta, Instruction, InstructionError, ProcessedSiblingInstruction,
             TRANSACTION_LEVEL_STACK_HEIGHT,
         },
-        keccak, native_loader,
+        keccak, native_loader, poseidon,
         precompiles::is_precompile,
         program::MAX_RETURN_DATA,
         program_stubs::is_nonoverlapping,
@@ -142,6 +143,12 @@ pub fn register_syscalls(
     let curve25519_syscall_enabled = invoke_context
         .feature_set
         .is_active(&curve25519_syscall_enabled::id());
+    let enable_alt_bn128_syscall = invoke_context
+        .feature_set
+        .is_active(&enable_alt_bn128_syscall::id());
+    let enable_poseidon_syscall = invoke_context
+        .feature_set
+        .is_active(&enable_poseidon_syscall::id());
     let disable_fees_sysvar = invoke_context
         .feature_set
         .is_active(&disable_fees_sysvar::id());
@@ -229,6 +236,15 @@ pub fn register_syscalls(
         SyscallBlake3::call,
     )?;
 
+    // Poseidon
+    register_feature_gated_syscall!(
+        syscall_registry,
+        enable_poseidon_syscall,
+        b"sol_poseidon",
+        SyscallPoseidon::init,
+        SyscallPoseidon::call,
+    )?;
+
     // ZK Token
     register_feature_gated_syscall!(
         syscall_registry,
@@ -252,23 +268,37 @@ pub fn register_syscalls(
         SyscallZkTokenElgamalOpWithScalar::call,
     )?;
 
-    // Elliptic Curve Point Validation
-    //
-    // TODO: add group operations and multiscalar multiplications
+    // Elliptic Curve Operations
     register_feature_gated_syscall!(
         syscall_registry,
         curve25519_syscall_enabled,
-        b"sol_curve25519_point_validation",
+        b"sol_curve_validate_point",
         SyscallCurvePointValidation::init,
         SyscallCurvePointValidation::call,
     )?;
     register_feature_gated_syscall!(
         syscall_registry,
         curve25519_syscall_enabled,
-        b"sol_curve25519_point_validation",
+        b"sol_curve_group_op",
         SyscallCurveGroupOps::init,
         SyscallCurveGroupOps::call,
     )?;
+    register_feature_gated_syscall!(
+        syscall_registry,
+        curve25519_syscall_enabled,
+        b"sol_curve_multiscalar_mul",
+        SyscallCurveMultiscalarMultiplication::init,
+        SyscallCurveMultiscalarMultiplication::call,
+    )?;
+
+    // alt_bn128
+    register_feature_gated_syscall!(
+        syscall_registry,
+        enable_alt_bn128_syscall,
+        b"sol_alt_bn128_group_op",
+        SyscallAltBn128::init,
+        SyscallAltBn128::call,
+    )?;
 
     // Sysvars
     syscall_registry.register_syscall_by_name(
@@ -2048,6 +2078,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 SUB => {
@@ -2083,6 +2115,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 MUL => {
@@ -2118,6 +2152,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 _ => {
@@ -2159,6 +2195,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 SUB => {
@@ -2196,6 +2234,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 MUL => {
@@ -2231,6 +2271,8 @@ declare_syscall!(
                             result
                         ) = result_point;
                         *result = Ok(0);
+                    } else {
+                        *result = Ok(1);
                     }
                 }
                 _ => {
@@ -2245,6 +2287,234 @@ declare_syscall!(
     }
 );
 
+declare_syscall!(
+    // Elliptic Curve Multiscalar Multiplication
+    //
+    // Currently, only curve25519 Edwards and Ristretto representations are supported
+    SyscallCurveMultiscalarMultiplication,
+    fn call(
+        &mut self,
+        curve_id: u64,
+        scalars_addr: u64,
+        points_addr: u64,
+        points_len: u64,
+        result_point_addr: u64,
+        memory_mapping: &mut MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        use solana_zk_token_sdk::curve25519::{
+            curve_syscall_traits::*, edwards, ristretto, scalar,
+        };
+
+        let invoke_context = question_mark!(
+            self.invoke_context
+                .try_borrow()
+                .map_err(|_| SyscallError::InvokeContextBorrowFailed),
+            result
+        );
+
+        match curve_id {
+            CURVE25519_EDWARDS => {
+                let cost = invoke_context
+                    .get_compute_budget()
+                    .curve25519_edwards_msm_base_cost
+                    .saturating_add(
+                        invoke_context
+                            .get_compute_budget()
+                            .curve25519_edwards_msm_incremental_cost
+                            .saturating_mul(points_len.saturating_sub(1)),
+                    );
+                question_mark!(invoke_context.get_compute_meter().consume(cost), result);
+
+                let scalars = question_mark!(
+                    translate_slice::<scalar::PodScalar>(
+                        memory_mapping,
+                        scalars_addr,
+                        points_len,
+                        invoke_context.get_check_aligned(),
+                        invoke_context.get_check_size(),
+                    ),
+                    result
+                );
+                let points = question_mark!(
+                    translate_slice::<edwards::PodEdwardsPoint>(
+                        memory_mapping,
+                        points_addr,
+                        points_len,
+                        invoke_context.get_check_aligned(),
+                        invoke_context.get_check_size(),
+                    ),
+                    result
+                );
+
+                if let Some(result_point) = edwards::multiscalar_multiply_edwards(scalars, points) {
+                    *question_mark!(
+                        translate_type_mut::<edwards::PodEdwardsPoint>(
+                            memory_mapping,
+                            result_point_addr,
+                            invoke_context.get_check_aligned(),
+                        ),
+                        result
+                    ) = result_point;
+                    *result = Ok(0);
+                } else {
+                    *result = Ok(1);
+                }
+            }
+
+            CURVE25519_RISTRETTO => {
+                let cost = invoke_context
+                    .get_compute_budget()
+                    .curve25519_ristretto_msm_base_cost
+                    .saturating_add(
+                        invoke_context
+                            .get_compute_budget()
+                            .curve25519_ristretto_msm_incremental_cost
+                            .saturating_mul(points_len.saturating_sub(1)),
+                    );
+                question_mark!(invoke_context.get_compute_meter().consume(cost), result);
+
+                let scalars = question_mark!(
+                    translate_slice::<scalar::PodScalar>(
+                        memory_mapping,
+                        scalars_addr,
+                        points_len,
+                        invoke_context.get_check_aligned(),
+                        invoke_context.get_check_size(),
+                    ),
+                    result
+                );
+                let points = question_mark!(
+                    translate_slice::<ristretto::PodRistrettoPoint>(
+                        memory_mapping,
+                        points_addr,
+                        points_len,
+                        invoke_context.get_check_aligned(),
+                        invoke_context.get_check_size(),
+                    ),
+                    result
+                );
+
+                if let Some(result_point) =
+                    ristretto::multiscalar_multiply_ristretto(scalars, points)
+                {
+                    *question_mark!(
+                        translate_type_mut::<ristretto::PodRistrettoPoint>(
+                            memory_mapping,
+                            result_point_addr,
+                            invoke_context.get_check_aligned(),
+                        ),
+                        result
+                    ) = result_point;
+                    *result = Ok(0);
+                } else {
+                    *result = Ok(1);
+                }
+            }
+
+            _ => {
+                *result = Ok(1);
+            }
+        }
+    }
+);
+
+declare_syscall!(
+    // alt_bn128 (BN254) group operations
+    //
+    // Addition and scalar multiplication of G1 points, and pairing checks
+    SyscallAltBn128,
+    fn call(
+        &mut self,
+        group_op: u64,
+        input_addr: u64,
+        input_size: u64,
+        result_addr: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        use solana_sdk::alt_bn128::{
+            alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing, AltBn128Error,
+            ALT_BN128_ADD, ALT_BN128_ADDITION_OUTPUT_LEN, ALT_BN128_MUL,
+            ALT_BN128_MULTIPLICATION_OUTPUT_LEN, ALT_BN128_PAIRING, ALT_BN128_PAIRING_ELEMENT_LEN,
+            ALT_BN128_PAIRING_OUTPUT_LEN,
+        };
+
+        let invoke_context = question_mark!(
+            self.invoke_context
+                .try_borrow()
+                .map_err(|_| SyscallError::InvokeContextBorrowFailed),
+            result
+        );
+        let budget = invoke_context.get_compute_budget();
+
+        let (cost, output_len, calculation): (_, _, fn(&[u8]) -> Result<Vec<u8>, AltBn128Error>) =
+            match group_op {
+                ALT_BN128_ADD => (
+                    budget.alt_bn128_addition_cost,
+                    ALT_BN128_ADDITION_OUTPUT_LEN,
+                    alt_bn128_addition,
+                ),
+                ALT_BN128_MUL => (
+                    budget.alt_bn128_multiplication_cost,
+                    ALT_BN128_MULTIPLICATION_OUTPUT_LEN,
+                    alt_bn128_multiplication,
+                ),
+                ALT_BN128_PAIRING => {
+                    let additional_pairs = input_size
+                        .saturating_div(ALT_BN128_PAIRING_ELEMENT_LEN as u64)
+                        .saturating_sub(1);
+                    (
+                        budget.alt_bn128_pairing_one_pair_cost_first.saturating_add(
+                            budget
+                                .alt_bn128_pairing_one_pair_cost_other
+                                .saturating_mul(additional_pairs),
+                        ),
+                        ALT_BN128_PAIRING_OUTPUT_LEN,
+                        alt_bn128_pairing,
+                    )
+                }
+                _ => {
+                    *result = Ok(AltBn128Error::InvalidInputData.into());
+                    return;
+                }
+            };
+        question_mark!(invoke_context.get_compute_meter().consume(cost), result);
+
+        let input = question_mark!(
+            translate_slice::<u8>(
+                memory_mapping,
+                input_addr,
+                input_size,
+                invoke_context.get_check_aligned(),
+                invoke_context.get_check_size(),
+            ),
+            result
+        );
+        let call_result = question_mark!(
+            translate_slice_mut::<u8>(
+                memory_mapping,
+                result_addr,
+                output_len as u64,
+                invoke_context.get_check_aligned(),
+                invoke_context.get_check_size(),
+            ),
+            result
+        );
+
+        match calculation(input) {
+            Ok(result_point) => {
+                call_result.copy_from_slice(&result_point);
+                *result = Ok(SUCCESS);
+            }
+            Err(error) => {
+                *result = Ok(error.into());
+            }
+        }
+    }
+);
+
 declare_syscall!(
     // Blake3
     SyscallBlake3,
@@ -2350,6 +2620,99 @@ declare_syscall!(
     }
 );
 
+declare_syscall!(
+    // Poseidon
+    SyscallPoseidon,
+    fn call(
+        &mut self,
+        parameters: u64,
+        endianness: u64,
+        vals_addr: u64,
+        vals_len: u64,
+        result_addr: u64,
+        memory_mapping: &mut MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        let invoke_context = question_mark!(
+            self.invoke_context
+                .try_borrow()
+                .map_err(|_| SyscallError::InvokeContextBorrowFailed),
+            result
+        );
+        let parameters = match poseidon::Parameters::try_from(parameters) {
+            Ok(parameters) => parameters,
+            Err(err) => {
+                *result = Ok(err.into());
+                return;
+            }
+        };
+        let endianness = match poseidon::Endianness::try_from(endianness) {
+            Ok(endianness) => endianness,
+            Err(err) => {
+                *result = Ok(err.into());
+                return;
+            }
+        };
+        if vals_len == 0 || vals_len > poseidon::MAX_INPUTS as u64 {
+            *result = Ok(poseidon::PoseidonSyscallError::InvalidNumberOfInputs.into());
+            return;
+        }
+
+        let compute_budget = invoke_context.get_compute_budget();
+        let cost = compute_budget.poseidon_base_cost.saturating_add(
+            compute_budget
+                .poseidon_cost_per_input
+                .saturating_mul(vals_len),
+        );
+        question_mark!(invoke_context.get_compute_meter().consume(cost), result);
+
+        let hash_result = question_mark!(
+            translate_slice_mut::<u8>(
+                memory_mapping,
+                result_addr,
+                poseidon::HASH_BYTES as u64,
+                invoke_context.get_check_aligned(),
+                invoke_context.get_check_size(),
+            ),
+            result
+        );
+        let vals = question_mark!(
+            translate_slice::<&[u8]>(
+                memory_mapping,
+                vals_addr,
+                vals_len,
+                invoke_context.get_check_aligned(),
+                invoke_context.get_check_size(),
+            ),
+            result
+        );
+        let inputs = question_mark!(
+            vals.iter()
+                .map(|val| {
+                    translate_slice::<u8>(
+                        memory_mapping,
+                        val.as_ptr() as u64,
+                        val.len() as u64,
+                        invoke_context.get_check_aligned(),
+                        invoke_context.get_check_size(),
+                    )
+                })
+                .collect::<Result<Vec<_>, EbpfError<BpfError>>>(),
+            result
+        );
+
+        match poseidon::hashv(parameters, endianness, &inputs) {
+            Ok(hash) => {
+                hash_result.copy_from_slice(&hash.to_bytes());
+                *result = Ok(0);
+            }
+            Err(err) => {
+                *result = Ok(err.into());
+            }
+        }
+    }
+);
+
 // Cross-program invocation syscalls
 
 struct CallerAccount<'a> {
@@ -4601,6 +4964,522 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_alt_bn128() {
+        use solana_sdk::alt_bn128::{AltBn128Error, ALT_BN128_ADD, ALT_BN128_G1_POINT_SIZE};
+
+        let config = Config::default();
+        prepare_mockup!(
+            invoke_context,
+            transaction_context,
+            program_id,
+            bpf_loader::id(),
+        );
+
+        // The generator of G1, (1, 2), added to itself
+        let mut input = [0u8; ALT_BN128_G1_POINT_SIZE * 2];
+        for point in input.chunks_mut(ALT_BN128_G1_POINT_SIZE) {
+            point[31] = 1;
+            point[63] = 2;
+        }
+        let expected = solana_sdk::alt_bn128::alt_bn128_addition(&input).unwrap();
+        let output = [0u8; ALT_BN128_G1_POINT_SIZE];
+        let ro_va = 0x100000000;
+        let rw_va = 0x200000000;
+        let mut memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::default(),
+                MemoryRegion {
+                    host_addr: input.as_ptr() as *const _ as u64,
+                    vm_addr: ro_va,
+                    len: input.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: output.as_ptr() as *const _ as u64,
+                    vm_addr: rw_va,
+                    len: output.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &config,
+        )
+        .unwrap();
+
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .mock_set_remaining(invoke_context.get_compute_budget().alt_bn128_addition_cost * 2);
+        let mut syscall = SyscallAltBn128 {
+            invoke_context: Rc::new(RefCell::new(&mut invoke_context)),
+        };
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            ALT_BN128_ADD,
+            ro_va,
+            input.len() as u64,
+            rw_va,
+            0,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(output.to_vec(), expected);
+
+        // Unknown operations fail without consuming compute units
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            u64::MAX,
+            ro_va,
+            input.len() as u64,
+            rw_va,
+            0,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), u64::from(AltBn128Error::InvalidInputData));
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            ALT_BN128_ADD,
+            ro_va,
+            input.len() as u64 + 1, // AccessViolation
+            rw_va,
+            0,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_access_violation!(result, ro_va, input.len() as u64 + 1);
+
+        syscall.call(
+            ALT_BN128_ADD,
+            ro_va,
+            input.len() as u64,
+            rw_va,
+            0,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_syscall_poseidon() {
+        use solana_sdk::poseidon::{Endianness, Parameters, PoseidonSyscallError};
+
+        let config = Config::default();
+        prepare_mockup!(
+            invoke_context,
+            transaction_context,
+            program_id,
+            bpf_loader::id(),
+        );
+
+        let input = [1u8; 32];
+        let mock_slices = [
+            MockSlice {
+                vm_addr: 0x300000000,
+                len: input.len(),
+            },
+            MockSlice {
+                vm_addr: 0x300000000,
+                len: input.len(),
+            },
+        ];
+        let hash_result = [0u8; poseidon::HASH_BYTES];
+        let ro_va = 0x100000000;
+        let rw_va = 0x200000000;
+        let mut memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::default(),
+                MemoryRegion {
+                    host_addr: mock_slices.as_ptr() as *const _ as u64,
+                    vm_addr: ro_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: hash_result.as_ptr() as *const _ as u64,
+                    vm_addr: rw_va,
+                    len: poseidon::HASH_BYTES as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+                MemoryRegion {
+                    host_addr: input.as_ptr() as *const _ as u64,
+                    vm_addr: 0x300000000,
+                    len: input.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+            ],
+            &config,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .mock_set_remaining(budget.poseidon_base_cost + budget.poseidon_cost_per_input * 2);
+        let mut syscall = SyscallPoseidon {
+            invoke_context: Rc::new(RefCell::new(&mut invoke_context)),
+        };
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            Parameters::Bn254X5.into(),
+            Endianness::BigEndian.into(),
+            ro_va,
+            2,
+            rw_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        let expected = poseidon::hashv(
+            Parameters::Bn254X5,
+            Endianness::BigEndian,
+            &[&input, &input],
+        )
+        .unwrap();
+        assert_eq!(hash_result, expected.to_bytes());
+
+        // Invalid arguments fail without consuming compute units
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            u64::MAX,
+            Endianness::BigEndian.into(),
+            ro_va,
+            2,
+            rw_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            result.unwrap(),
+            u64::from(PoseidonSyscallError::InvalidParameters)
+        );
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            Parameters::Bn254X5.into(),
+            Endianness::BigEndian.into(),
+            ro_va,
+            poseidon::MAX_INPUTS as u64 + 1,
+            rw_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            result.unwrap(),
+            u64::from(PoseidonSyscallError::InvalidNumberOfInputs)
+        );
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            Parameters::Bn254X5.into(),
+            Endianness::BigEndian.into(),
+            ro_va,
+            1,
+            rw_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_syscall_edwards_curve_group_ops() {
+        use solana_zk_token_sdk::curve25519::{
+            curve_syscall_traits::{ADD, CURVE25519_EDWARDS, MUL, SUB},
+            edwards::{self, PodEdwardsPoint},
+            scalar::PodScalar,
+        };
+
+        let config = Config::default();
+        prepare_mockup!(
+            invoke_context,
+            transaction_context,
+            program_id,
+            bpf_loader::id(),
+        );
+
+        // The compressed ed25519 basepoint
+        let mut basepoint = [0x66u8; 32];
+        basepoint[0] = 0x58;
+        let point = PodEdwardsPoint(basepoint);
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes[0] = 2;
+        let scalar = PodScalar(scalar_bytes);
+        let doubled = edwards::multiply_edwards(&scalar, &point).unwrap();
+
+        let point_va = 0x100000000;
+        let scalar_va = 0x200000000;
+        let result_va = 0x300000000;
+        let result_point = PodEdwardsPoint::default();
+        let mut memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::default(),
+                MemoryRegion {
+                    host_addr: &point as *const _ as u64,
+                    vm_addr: point_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: &scalar as *const _ as u64,
+                    vm_addr: scalar_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: &result_point as *const _ as u64,
+                    vm_addr: result_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &config,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .mock_set_remaining(
+                budget.curve25519_edwards_add_cost
+                    + budget.curve25519_edwards_subtract_cost
+                    + budget.curve25519_edwards_multiply_cost,
+            );
+        let mut syscall = SyscallCurveGroupOps {
+            invoke_context: Rc::new(RefCell::new(&mut invoke_context)),
+        };
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            ADD,
+            point_va,
+            point_va,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(result_point, doubled);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            SUB,
+            result_va,
+            point_va,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(result_point, point);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            MUL,
+            scalar_va,
+            point_va,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(result_point, doubled);
+
+        // Unknown operations fail without consuming compute units
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            u64::MAX,
+            point_va,
+            point_va,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), 1);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            ADD,
+            point_va,
+            point_va,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_syscall_multiscalar_multiplication() {
+        use solana_zk_token_sdk::curve25519::{
+            curve_syscall_traits::{CURVE25519_EDWARDS, CURVE25519_RISTRETTO},
+            edwards::{self, PodEdwardsPoint},
+            ristretto::{self, PodRistrettoPoint},
+            scalar::PodScalar,
+        };
+
+        let config = Config::default();
+        prepare_mockup!(
+            invoke_context,
+            transaction_context,
+            program_id,
+            bpf_loader::id(),
+        );
+
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        let mut three = [0u8; 32];
+        three[0] = 3;
+        let mut five = [0u8; 32];
+        five[0] = 5;
+        let scalars = [PodScalar(two), PodScalar(three)];
+
+        // The compressed ed25519 and ristretto255 basepoints
+        let mut edwards_basepoint = [0x66u8; 32];
+        edwards_basepoint[0] = 0x58;
+        let edwards_points = [PodEdwardsPoint(edwards_basepoint); 2];
+        let ristretto_points = [PodRistrettoPoint([
+            226, 242, 174, 10, 106, 188, 78, 113, 168, 132, 169, 97, 197, 0, 81, 95, 88, 227, 11,
+            106, 165, 130, 221, 141, 182, 166, 89, 69, 224, 141, 45, 118,
+        ]); 2];
+        let expected_edwards =
+            edwards::multiply_edwards(&PodScalar(five), &edwards_points[0]).unwrap();
+        let expected_ristretto =
+            ristretto::multiply_ristretto(&PodScalar(five), &ristretto_points[0]).unwrap();
+
+        let scalars_va = 0x100000000;
+        let edwards_points_va = 0x200000000;
+        let ristretto_points_va = 0x300000000;
+        let result_va = 0x400000000;
+        let result_point = [0u8; 32];
+        let mut memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::default(),
+                MemoryRegion {
+                    host_addr: scalars.as_ptr() as *const _ as u64,
+                    vm_addr: scalars_va,
+                    len: 64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: edwards_points.as_ptr() as *const _ as u64,
+                    vm_addr: edwards_points_va,
+                    len: 64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: ristretto_points.as_ptr() as *const _ as u64,
+                    vm_addr: ristretto_points_va,
+                    len: 64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: result_point.as_ptr() as *const _ as u64,
+                    vm_addr: result_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &config,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .mock_set_remaining(
+                budget.curve25519_edwards_msm_base_cost
+                    + budget.curve25519_edwards_msm_incremental_cost
+                    + budget.curve25519_ristretto_msm_base_cost
+                    + budget.curve25519_ristretto_msm_incremental_cost,
+            );
+        let mut syscall = SyscallCurveMultiscalarMultiplication {
+            invoke_context: Rc::new(RefCell::new(&mut invoke_context)),
+        };
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            scalars_va,
+            edwards_points_va,
+            2,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(result_point, expected_edwards.0);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_RISTRETTO,
+            scalars_va,
+            ristretto_points_va,
+            2,
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), SUCCESS);
+        assert_eq!(result_point, expected_ristretto.0);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            CURVE25519_EDWARDS,
+            scalars_va,
+            edwards_points_va,
+            3, // exceeds the remaining compute budget
+            result_va,
+            &mut memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
     fn create_filled_type<T: Default>(zero_init: bool) -> T {
         let mut val = T::default();
         let p = &mut val as *mut _ as *mut u8;
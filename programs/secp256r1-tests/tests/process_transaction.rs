@@ -0,0 +1,101 @@
+use {
+    assert_matches::assert_matches,
+    openssl::{
+        ec::{EcGroup, EcKey},
+        nid::Nid,
+        pkey::Private,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        feature_set,
+        secp256r1_instruction::new_secp256r1_instruction,
+        signature::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+fn new_signing_key() -> EcKey<Private> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    EcKey::generate(&group).unwrap()
+}
+
+#[tokio::test]
+async fn test_success() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+
+    let signing_key = new_signing_key();
+    let message_arr = b"hello";
+    let instruction = new_secp256r1_instruction(&signing_key, message_arr).unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+}
+
+#[tokio::test]
+async fn test_failure() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+
+    let signing_key = new_signing_key();
+    let message_arr = b"hello";
+    let mut instruction = new_secp256r1_instruction(&signing_key, message_arr).unwrap();
+
+    let message_index = instruction.data.len() - 1;
+    instruction.data[message_index] += 1;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        client.process_transaction(transaction).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InvalidAccountIndex
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_feature_disabled() {
+    let mut program_test = ProgramTest::default();
+    program_test.deactivate_feature(feature_set::enable_secp256r1_precompile::id());
+    let mut context = program_test.start_with_context().await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+
+    let signing_key = new_signing_key();
+    let message_arr = b"hello";
+    let instruction = new_secp256r1_instruction(&signing_key, message_arr).unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        client.process_transaction(transaction).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::ProgramAccountNotFound
+        ))
+    );
+}
@@ -3,7 +3,7 @@ use {
         config,
         stake_state::{
             authorize, authorize_with_seed, deactivate, deactivate_delinquent, delegate,
-            initialize, merge, set_lockup, split, withdraw,
+            initialize, merge, move_lamports, move_stake, redelegate, set_lockup, split, withdraw,
         },
     },
     log::*,
@@ -438,6 +438,82 @@ pub fn process_instruction(
                 Err(InstructionError::InvalidInstructionData)
             }
         }
+        Ok(StakeInstruction::Redelegate) => {
+            let mut me = get_stake_account()?;
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_redelegate_instruction::id())
+            {
+                instruction_context.check_number_of_instruction_accounts(4)?;
+                let config_account =
+                    instruction_context.try_borrow_instruction_account(transaction_context, 3)?;
+                if !config::check_id(config_account.get_key()) {
+                    return Err(InstructionError::InvalidArgument);
+                }
+                let config =
+                    config::from(&config_account).ok_or(InstructionError::InvalidArgument)?;
+                drop(config_account);
+
+                redelegate(
+                    invoke_context,
+                    transaction_context,
+                    instruction_context,
+                    &mut me,
+                    1,
+                    2,
+                    &config,
+                    &signers,
+                )
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
+        Ok(StakeInstruction::MoveStake(lamports)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::move_stake_and_move_lamports_ixs::id())
+            {
+                instruction_context.check_number_of_instruction_accounts(3)?;
+                let clock = invoke_context.get_sysvar_cache().get_clock()?;
+                let stake_history = invoke_context.get_sysvar_cache().get_stake_history()?;
+                move_stake(
+                    invoke_context,
+                    transaction_context,
+                    instruction_context,
+                    first_instruction_account,
+                    lamports,
+                    first_instruction_account + 1,
+                    &clock,
+                    &stake_history,
+                    &signers,
+                )
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
+        Ok(StakeInstruction::MoveLamports(lamports)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::move_stake_and_move_lamports_ixs::id())
+            {
+                instruction_context.check_number_of_instruction_accounts(3)?;
+                let clock = invoke_context.get_sysvar_cache().get_clock()?;
+                let stake_history = invoke_context.get_sysvar_cache().get_stake_history()?;
+                move_lamports(
+                    invoke_context,
+                    transaction_context,
+                    instruction_context,
+                    first_instruction_account,
+                    lamports,
+                    first_instruction_account + 1,
+                    &clock,
+                    &stake_history,
+                    &signers,
+                )
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
         Err(err) => {
             if !invoke_context.feature_set.is_active(
                 &feature_set::add_get_minimum_delegation_instruction_to_stake_program::id(),
@@ -6719,6 +6795,445 @@ mod tests {
         );
     }
 
+    fn do_test_redelegate(feature_set: FeatureSet) {
+        let feature_set = Arc::new(feature_set);
+        let rent = Rent::default();
+        let rent_exempt_reserve = rent.minimum_balance(StakeState::size_of());
+        let minimum_delegation = crate::get_minimum_delegation(&feature_set);
+        let current_epoch = 10;
+
+        let mut sysvar_cache_override = SysvarCache::default();
+        sysvar_cache_override.set_clock(Clock {
+            epoch: current_epoch,
+            ..Clock::default()
+        });
+        sysvar_cache_override.set_stake_history(StakeHistory::default());
+        sysvar_cache_override.set_rent(rent);
+
+        let authority_address = Pubkey::new_unique();
+        let vote_address = Pubkey::new_unique();
+        let new_vote_address = Pubkey::new_unique();
+        let stake_address = Pubkey::new_unique();
+        let uninitialized_stake_address = Pubkey::new_unique();
+
+        let vote_account = AccountSharedData::new_data_with_space(
+            1, /* lamports */
+            &VoteStateVersions::new_current(VoteState::default()),
+            VoteState::size_of(),
+            &solana_vote_program::id(),
+        )
+        .unwrap();
+        let stake_account = AccountSharedData::new_data_with_space(
+            rent_exempt_reserve + minimum_delegation,
+            &StakeState::Stake(
+                Meta {
+                    rent_exempt_reserve,
+                    authorized: Authorized::auto(&authority_address),
+                    lockup: Lockup::default(),
+                },
+                new_stake(
+                    minimum_delegation,
+                    &vote_address,
+                    &VoteState::default(),
+                    0, /* activation_epoch */
+                    &stake_config::Config::default(),
+                ),
+            ),
+            StakeState::size_of(),
+            &id(),
+        )
+        .unwrap();
+        let uninitialized_stake_account =
+            AccountSharedData::new(rent_exempt_reserve, StakeState::size_of(), &id());
+
+        let process_instruction_redelegate =
+            |stake_account: &AccountSharedData,
+             uninitialized_stake_account: &AccountSharedData,
+             vote_address: &Pubkey,
+             is_signer: bool,
+             feature_set: Arc<FeatureSet>,
+             expected_result| {
+                process_instruction_with_overrides(
+                    &serialize(&StakeInstruction::Redelegate).unwrap(),
+                    vec![
+                        (stake_address, stake_account.clone()),
+                        (
+                            uninitialized_stake_address,
+                            uninitialized_stake_account.clone(),
+                        ),
+                        (*vote_address, vote_account.clone()),
+                        (
+                            stake_config::id(),
+                            config::create_account(0, &stake_config::Config::default()),
+                        ),
+                        (authority_address, AccountSharedData::default()),
+                    ],
+                    vec![
+                        AccountMeta::new(stake_address, false),
+                        AccountMeta::new(uninitialized_stake_address, false),
+                        AccountMeta::new_readonly(*vote_address, false),
+                        AccountMeta::new_readonly(stake_config::id(), false),
+                        AccountMeta::new_readonly(authority_address, is_signer),
+                    ],
+                    Some(&sysvar_cache_override),
+                    Some(feature_set),
+                    expected_result,
+                )
+            };
+
+        // The instruction is feature gated
+        let mut inactive_feature_set = FeatureSet::clone(&feature_set);
+        inactive_feature_set.deactivate(&feature_set::stake_redelegate_instruction::id());
+        process_instruction_redelegate(
+            &stake_account,
+            &uninitialized_stake_account,
+            &new_vote_address,
+            true,
+            Arc::new(inactive_feature_set),
+            Err(InstructionError::InvalidInstructionData),
+        );
+
+        // The stake authority must sign
+        process_instruction_redelegate(
+            &stake_account,
+            &uninitialized_stake_account,
+            &new_vote_address,
+            false,
+            feature_set.clone(),
+            Err(InstructionError::MissingRequiredSignature),
+        );
+
+        // Redelegating to the same vote account isn't permitted
+        process_instruction_redelegate(
+            &stake_account,
+            &uninitialized_stake_account,
+            &vote_address,
+            true,
+            feature_set.clone(),
+            Err(StakeError::RedelegateToSameVoteAccount.into()),
+        );
+
+        // The active stake moves to the uninitialized stake account, which starts activating
+        let accounts = process_instruction_redelegate(
+            &stake_account,
+            &uninitialized_stake_account,
+            &new_vote_address,
+            true,
+            feature_set.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[0].lamports(), rent_exempt_reserve);
+        let stake = stake_from(&accounts[0]).unwrap();
+        assert_eq!(stake.delegation.voter_pubkey, vote_address);
+        assert_eq!(stake.delegation.deactivation_epoch, current_epoch);
+        assert_eq!(
+            accounts[1].lamports(),
+            rent_exempt_reserve + minimum_delegation
+        );
+        let redelegated_stake = stake_from(&accounts[1]).unwrap();
+        assert_eq!(redelegated_stake.delegation.voter_pubkey, new_vote_address);
+        assert_eq!(redelegated_stake.delegation.stake, minimum_delegation);
+        assert_eq!(redelegated_stake.delegation.activation_epoch, current_epoch);
+        assert_eq!(
+            redelegated_stake.delegation.deactivation_epoch,
+            std::u64::MAX
+        );
+        assert_eq!(
+            authorized_from(&accounts[1]).unwrap(),
+            Authorized::auto(&authority_address)
+        );
+
+        // Deactivating stake can't be redelegated
+        process_instruction_redelegate(
+            &accounts[0],
+            &uninitialized_stake_account,
+            &new_vote_address,
+            true,
+            feature_set.clone(),
+            Err(StakeError::RedelegateTransientOrInactiveStake.into()),
+        );
+
+        // The destination stake account must be uninitialized
+        process_instruction_redelegate(
+            &stake_account,
+            &accounts[1],
+            &new_vote_address,
+            true,
+            feature_set,
+            Err(InstructionError::AccountAlreadyInitialized),
+        );
+    }
+
+    fn do_test_move_stake_and_lamports(feature_set: FeatureSet) {
+        let feature_set = Arc::new(feature_set);
+        let rent = Rent::default();
+        let rent_exempt_reserve = rent.minimum_balance(StakeState::size_of());
+        let minimum_delegation = crate::get_minimum_delegation(&feature_set);
+        let free_lamports = 42;
+
+        let mut sysvar_cache_override = SysvarCache::default();
+        sysvar_cache_override.set_clock(Clock {
+            epoch: 10,
+            ..Clock::default()
+        });
+        sysvar_cache_override.set_stake_history(StakeHistory::default());
+        sysvar_cache_override.set_rent(rent);
+
+        let authority_address = Pubkey::new_unique();
+        let vote_address = Pubkey::new_unique();
+        let other_vote_address = Pubkey::new_unique();
+        let source_address = Pubkey::new_unique();
+        let active_address = Pubkey::new_unique();
+        let inactive_address = Pubkey::new_unique();
+        let other_vote_active_address = Pubkey::new_unique();
+        let other_authority_address = Pubkey::new_unique();
+
+        let meta = Meta {
+            rent_exempt_reserve,
+            authorized: Authorized::auto(&authority_address),
+            lockup: Lockup::default(),
+        };
+        let active_stake_account = |meta: Meta, vote_address: &Pubkey, stake: u64, extra: u64| {
+            AccountSharedData::new_data_with_space(
+                rent_exempt_reserve + stake + extra,
+                &StakeState::Stake(
+                    meta,
+                    new_stake(
+                        stake,
+                        vote_address,
+                        &VoteState::default(),
+                        0, /* activation_epoch */
+                        &stake_config::Config::default(),
+                    ),
+                ),
+                StakeState::size_of(),
+                &id(),
+            )
+            .unwrap()
+        };
+        let source_account =
+            active_stake_account(meta, &vote_address, 2 * minimum_delegation, free_lamports);
+        let transaction_accounts = vec![
+            (source_address, source_account.clone()),
+            (
+                active_address,
+                active_stake_account(meta, &vote_address, minimum_delegation, 0),
+            ),
+            (
+                inactive_address,
+                AccountSharedData::new_data_with_space(
+                    rent_exempt_reserve,
+                    &StakeState::Initialized(meta),
+                    StakeState::size_of(),
+                    &id(),
+                )
+                .unwrap(),
+            ),
+            (
+                other_vote_active_address,
+                active_stake_account(meta, &other_vote_address, minimum_delegation, 0),
+            ),
+            (
+                other_authority_address,
+                active_stake_account(
+                    Meta {
+                        authorized: Authorized::auto(&Pubkey::new_unique()),
+                        ..meta
+                    },
+                    &vote_address,
+                    minimum_delegation,
+                    0,
+                ),
+            ),
+            (authority_address, AccountSharedData::default()),
+        ];
+
+        let process_instruction_move =
+            |instruction: StakeInstruction,
+             destination_address: &Pubkey,
+             is_signer: bool,
+             feature_set: Arc<FeatureSet>,
+             expected_result| {
+                process_instruction_with_overrides(
+                    &serialize(&instruction).unwrap(),
+                    transaction_accounts.clone(),
+                    vec![
+                        AccountMeta::new(source_address, false),
+                        AccountMeta::new(*destination_address, false),
+                        AccountMeta::new_readonly(authority_address, is_signer),
+                    ],
+                    Some(&sysvar_cache_override),
+                    Some(feature_set),
+                    expected_result,
+                )
+            };
+
+        // The instructions are feature gated
+        let mut inactive_feature_set = FeatureSet::clone(&feature_set);
+        inactive_feature_set.deactivate(&feature_set::move_stake_and_move_lamports_ixs::id());
+        let inactive_feature_set = Arc::new(inactive_feature_set);
+        for instruction in [
+            StakeInstruction::MoveStake(minimum_delegation),
+            StakeInstruction::MoveLamports(free_lamports),
+        ] {
+            process_instruction_move(
+                instruction,
+                &active_address,
+                true,
+                inactive_feature_set.clone(),
+                Err(InstructionError::InvalidInstructionData),
+            );
+        }
+
+        // The stake authority must sign
+        for instruction in [
+            StakeInstruction::MoveStake(minimum_delegation),
+            StakeInstruction::MoveLamports(free_lamports),
+        ] {
+            process_instruction_move(
+                instruction,
+                &active_address,
+                false,
+                feature_set.clone(),
+                Err(InstructionError::MissingRequiredSignature),
+            );
+        }
+
+        // Something must be moved
+        for instruction in [
+            StakeInstruction::MoveStake(0),
+            StakeInstruction::MoveLamports(0),
+        ] {
+            process_instruction_move(
+                instruction,
+                &active_address,
+                true,
+                feature_set.clone(),
+                Err(InstructionError::InvalidArgument),
+            );
+        }
+
+        // The source and destination must differ
+        for instruction in [
+            StakeInstruction::MoveStake(minimum_delegation),
+            StakeInstruction::MoveLamports(free_lamports),
+        ] {
+            process_instruction_move(
+                instruction,
+                &source_address,
+                true,
+                feature_set.clone(),
+                Err(InstructionError::InvalidInstructionData),
+            );
+        }
+
+        // The authorities must match
+        for instruction in [
+            StakeInstruction::MoveStake(minimum_delegation),
+            StakeInstruction::MoveLamports(free_lamports),
+        ] {
+            process_instruction_move(
+                instruction,
+                &other_authority_address,
+                true,
+                feature_set.clone(),
+                Err(StakeError::MergeMismatch.into()),
+            );
+        }
+
+        // Active stake can only move to stake delegated to the same vote account
+        process_instruction_move(
+            StakeInstruction::MoveStake(minimum_delegation),
+            &other_vote_active_address,
+            true,
+            feature_set.clone(),
+            Err(StakeError::VoteAddressMismatch.into()),
+        );
+
+        // More stake than is delegated can't be moved
+        process_instruction_move(
+            StakeInstruction::MoveStake(2 * minimum_delegation + 1),
+            &active_address,
+            true,
+            feature_set.clone(),
+            Err(InstructionError::InvalidArgument),
+        );
+
+        // Part of the stake moves to an active account
+        let accounts = process_instruction_move(
+            StakeInstruction::MoveStake(minimum_delegation),
+            &active_address,
+            true,
+            feature_set.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            accounts[0].lamports(),
+            rent_exempt_reserve + minimum_delegation + free_lamports
+        );
+        assert_eq!(
+            stake_from(&accounts[0]).unwrap().delegation.stake,
+            minimum_delegation
+        );
+        assert_eq!(
+            accounts[1].lamports(),
+            rent_exempt_reserve + 2 * minimum_delegation
+        );
+        assert_eq!(
+            stake_from(&accounts[1]).unwrap().delegation.stake,
+            2 * minimum_delegation
+        );
+
+        // All of the stake moves to an inactive account, which becomes active
+        let accounts = process_instruction_move(
+            StakeInstruction::MoveStake(2 * minimum_delegation),
+            &inactive_address,
+            true,
+            feature_set.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[0].lamports(), rent_exempt_reserve + free_lamports);
+        assert_eq!(from(&accounts[0]).unwrap(), StakeState::Initialized(meta));
+        assert_eq!(
+            accounts[2].lamports(),
+            rent_exempt_reserve + 2 * minimum_delegation
+        );
+        let stake = stake_from(&accounts[2]).unwrap();
+        assert_eq!(stake.delegation.voter_pubkey, vote_address);
+        assert_eq!(stake.delegation.stake, 2 * minimum_delegation);
+        assert_eq!(stake.delegation.activation_epoch, 0);
+        assert_eq!(stake.delegation.deactivation_epoch, std::u64::MAX);
+
+        // Only undelegated lamports above the rent exempt reserve can be moved
+        process_instruction_move(
+            StakeInstruction::MoveLamports(free_lamports + 1),
+            &other_vote_active_address,
+            true,
+            feature_set.clone(),
+            Err(InstructionError::InvalidArgument),
+        );
+        let accounts = process_instruction_move(
+            StakeInstruction::MoveLamports(free_lamports),
+            &other_vote_active_address,
+            true,
+            feature_set,
+            Ok(()),
+        );
+        assert_eq!(
+            accounts[0].lamports(),
+            rent_exempt_reserve + 2 * minimum_delegation
+        );
+        assert_eq!(from(&accounts[0]).unwrap(), from(&source_account).unwrap());
+        assert_eq!(
+            accounts[3].lamports(),
+            rent_exempt_reserve + minimum_delegation + free_lamports
+        );
+        assert_eq!(
+            stake_from(&accounts[3]).unwrap().delegation.stake,
+            minimum_delegation
+        );
+    }
+
     mod old_behavior {
         use super::*;
 
@@ -6913,6 +7428,14 @@ mod tests {
         fn test_deactivate_delinquent() {
             do_test_deactivate_delinquent(new_feature_set());
         }
+        #[test]
+        fn test_redelegate() {
+            do_test_redelegate(new_feature_set());
+        }
+        #[test]
+        fn test_move_stake_and_lamports() {
+            do_test_move_stake_and_lamports(new_feature_set());
+        }
     }
 
     mod new_behavior {
@@ -7111,5 +7634,13 @@ mod tests {
         fn test_deactivate_delinquent() {
             do_test_deactivate_delinquent(new_feature_set());
         }
+        #[test]
+        fn test_redelegate() {
+            do_test_redelegate(new_feature_set());
+        }
+        #[test]
+        fn test_move_stake_and_lamports() {
+            do_test_move_stake_and_lamports(new_feature_set());
+        }
     }
 }
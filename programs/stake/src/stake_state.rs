@@ -94,7 +94,7 @@ pub fn meta_from(account: &AccountSharedData) -> Option<Meta> {
     from(account).and_then(|state: StakeState| state.meta())
 }
 
-fn redelegate(
+fn redelegate_stake(
     stake: &mut Stake,
     stake_lamports: u64,
     voter_pubkey: &Pubkey,
@@ -595,7 +595,7 @@ pub fn delegate(
             meta.authorized.check(signers, StakeAuthorize::Staker)?;
             let ValidatedDelegatedInfo { stake_amount } =
                 validate_delegated_amount(&stake_account, &meta, feature_set)?;
-            redelegate(
+            redelegate_stake(
                 &mut stake,
                 stake_amount,
                 &vote_pubkey,
@@ -625,6 +625,130 @@ pub fn deactivate(
     }
 }
 
+/// Move the active stake of `stake_account` into the uninitialized stake account, delegated to
+/// another vote account, and deactivate `stake_account`
+#[allow(clippy::too_many_arguments)]
+pub fn redelegate(
+    invoke_context: &InvokeContext,
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+    stake_account: &mut BorrowedAccount,
+    uninitialized_stake_account_index: usize,
+    vote_account_index: usize,
+    config: &Config,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+
+    // ensure `uninitialized_stake_account_index` is in the uninitialized state
+    let mut uninitialized_stake_account = instruction_context
+        .try_borrow_instruction_account(transaction_context, uninitialized_stake_account_index)?;
+    if *uninitialized_stake_account.get_owner() != id() {
+        ic_msg!(
+            invoke_context,
+            "expected uninitialized stake account owner to be {}, not {}",
+            id(),
+            *uninitialized_stake_account.get_owner()
+        );
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    if uninitialized_stake_account.get_data().len() != StakeState::size_of() {
+        ic_msg!(
+            invoke_context,
+            "expected uninitialized stake account data len to be {}, not {}",
+            StakeState::size_of(),
+            uninitialized_stake_account.get_data().len()
+        );
+        return Err(InstructionError::InvalidAccountData);
+    }
+    if !matches!(
+        uninitialized_stake_account.get_state()?,
+        StakeState::Uninitialized
+    ) {
+        ic_msg!(
+            invoke_context,
+            "expected uninitialized stake account to be uninitialized",
+        );
+        return Err(InstructionError::AccountAlreadyInitialized);
+    }
+
+    // validate the provided vote account
+    let vote_account = instruction_context
+        .try_borrow_instruction_account(transaction_context, vote_account_index)?;
+    if *vote_account.get_owner() != solana_vote_program::id() {
+        ic_msg!(
+            invoke_context,
+            "expected vote account owner to be {}, not {}",
+            solana_vote_program::id(),
+            *vote_account.get_owner()
+        );
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    let vote_pubkey = *vote_account.get_key();
+    let vote_state = vote_account.get_state::<VoteStateVersions>()?;
+    drop(vote_account);
+
+    let (stake_meta, effective_stake) =
+        if let StakeState::Stake(meta, stake) = stake_account.get_state()? {
+            let stake_history = invoke_context.get_sysvar_cache().get_stake_history()?;
+            let status = stake
+                .delegation
+                .stake_activating_and_deactivating(clock.epoch, Some(&stake_history));
+            if status.effective == 0 || status.activating != 0 || status.deactivating != 0 {
+                ic_msg!(invoke_context, "stake is not active");
+                return Err(StakeError::RedelegateTransientOrInactiveStake.into());
+            }
+
+            // Deny redelegating to the same vote account. This is nonsensical and could be used to
+            // grief the global stake warm-up/cool-down rate
+            if stake.delegation.voter_pubkey == vote_pubkey {
+                ic_msg!(
+                    invoke_context,
+                    "redelegating to the same vote account not permitted"
+                );
+                return Err(StakeError::RedelegateToSameVoteAccount.into());
+            }
+
+            (meta, status.effective)
+        } else {
+            ic_msg!(invoke_context, "invalid stake account data",);
+            return Err(InstructionError::InvalidAccountData);
+        };
+
+    // deactivate `stake_account`
+    //
+    // Note: This function also ensures `signers` contains the `StakeAuthorize::Staker`
+    deactivate(stake_account, &clock, signers)?;
+
+    // transfer the effective stake to the uninitialized stake account
+    stake_account.checked_sub_lamports(effective_stake)?;
+    uninitialized_stake_account.checked_add_lamports(effective_stake)?;
+
+    // initialize and schedule `uninitialized_stake_account` for activation
+    let rent = invoke_context.get_sysvar_cache().get_rent()?;
+    let mut uninitialized_stake_meta = stake_meta;
+    uninitialized_stake_meta.rent_exempt_reserve =
+        rent.minimum_balance(uninitialized_stake_account.get_data().len());
+
+    let ValidatedDelegatedInfo { stake_amount } = validate_delegated_amount(
+        &uninitialized_stake_account,
+        &uninitialized_stake_meta,
+        &invoke_context.feature_set,
+    )?;
+    uninitialized_stake_account.set_state(&StakeState::Stake(
+        uninitialized_stake_meta,
+        new_stake(
+            stake_amount,
+            &vote_pubkey,
+            &vote_state.convert_to_current(),
+            clock.epoch,
+            config,
+        ),
+    ))?;
+
+    Ok(())
+}
+
 pub fn set_lockup(
     stake_account: &mut BorrowedAccount,
     lockup: &LockupArgs,
@@ -851,6 +975,259 @@ pub fn merge(
     Ok(())
 }
 
+/// Checks shared by `move_stake()` and `move_lamports()`: both accounts must be writable, owned
+/// by the stake program, mergeable and share authorities and lockups
+fn move_stake_or_lamports_shared_checks(
+    invoke_context: &InvokeContext,
+    source_account: &BorrowedAccount,
+    lamports: u64,
+    destination_account: &BorrowedAccount,
+    clock: &Clock,
+    stake_history: &StakeHistory,
+    signers: &HashSet<Pubkey>,
+) -> Result<(MergeKind, MergeKind), InstructionError> {
+    if *source_account.get_owner() != id() || *destination_account.get_owner() != id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    if !source_account.is_writable() || !destination_account.is_writable() {
+        return Err(InstructionError::InvalidInstructionData);
+    }
+    if lamports == 0 {
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    // `get_if_mergeable()` rejects accounts that are partially activated or deactivating
+    ic_msg!(invoke_context, "Checking if source stake is mergeable");
+    let source_merge_kind = MergeKind::get_if_mergeable(
+        invoke_context,
+        &source_account.get_state()?,
+        source_account.get_lamports(),
+        clock,
+        stake_history,
+    )?;
+
+    // Authorized staker is allowed to move stake and lamports
+    source_merge_kind
+        .meta()
+        .authorized
+        .check(signers, StakeAuthorize::Staker)?;
+
+    ic_msg!(invoke_context, "Checking if destination stake is mergeable");
+    let destination_merge_kind = MergeKind::get_if_mergeable(
+        invoke_context,
+        &destination_account.get_state()?,
+        destination_account.get_lamports(),
+        clock,
+        stake_history,
+    )?;
+
+    // Authorities must match, and so must lockups while either is in force
+    MergeKind::metas_can_merge(
+        invoke_context,
+        source_merge_kind.meta(),
+        destination_merge_kind.meta(),
+        clock,
+    )?;
+
+    Ok((source_merge_kind, destination_merge_kind))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn move_stake(
+    invoke_context: &InvokeContext,
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+    source_account_index: usize,
+    lamports: u64,
+    destination_account_index: usize,
+    clock: &Clock,
+    stake_history: &StakeHistory,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    let mut source_account =
+        instruction_context.try_borrow_account(transaction_context, source_account_index)?;
+    if instruction_context.get_index_in_transaction(source_account_index)?
+        == instruction_context.get_index_in_transaction(destination_account_index)?
+    {
+        return Err(InstructionError::InvalidInstructionData);
+    }
+    let mut destination_account =
+        instruction_context.try_borrow_account(transaction_context, destination_account_index)?;
+
+    let (source_merge_kind, destination_merge_kind) = move_stake_or_lamports_shared_checks(
+        invoke_context,
+        &source_account,
+        lamports,
+        &destination_account,
+        clock,
+        stake_history,
+        signers,
+    )?;
+
+    // Both accounts must be able to hold a `StakeState::Stake`
+    if source_account.get_data().len() != StakeState::size_of()
+        || destination_account.get_data().len() != StakeState::size_of()
+    {
+        return Err(InstructionError::InvalidAccountData);
+    }
+
+    let (source_meta, mut source_stake) = match source_merge_kind {
+        MergeKind::FullyActive(meta, stake) => (meta, stake),
+        _ => {
+            ic_msg!(invoke_context, "source stake is not fully active");
+            return Err(InstructionError::InvalidAccountData);
+        }
+    };
+
+    let minimum_delegation = crate::get_minimum_delegation(&invoke_context.feature_set);
+
+    // The source can't move more stake than is delegated, whatever its balance
+    let source_final_stake = source_stake
+        .delegation
+        .stake
+        .checked_sub(lamports)
+        .ok_or(InstructionError::InvalidArgument)?;
+
+    // Unless all of it is moved, the source must keep at least the minimum delegation
+    if source_final_stake != 0 && source_final_stake < minimum_delegation {
+        return Err(StakeError::InsufficientDelegation.into());
+    }
+
+    let destination_meta = match destination_merge_kind {
+        MergeKind::FullyActive(destination_meta, mut destination_stake) => {
+            if source_stake.delegation.voter_pubkey != destination_stake.delegation.voter_pubkey {
+                ic_msg!(
+                    invoke_context,
+                    "destination stake is delegated to a different vote account"
+                );
+                return Err(StakeError::VoteAddressMismatch.into());
+            }
+            // Moved stake keeps its rewards, so weigh in the source's `credits_observed`
+            destination_stake.credits_observed = stake_weighted_credits_observed(
+                &destination_stake,
+                lamports,
+                source_stake.credits_observed,
+            )
+            .ok_or(InstructionError::ArithmeticOverflow)?;
+            destination_stake.delegation.stake =
+                checked_add(destination_stake.delegation.stake, lamports)?;
+            // Only relevant if the minimum delegation was raised since the destination delegated
+            if destination_stake.delegation.stake < minimum_delegation {
+                return Err(StakeError::InsufficientDelegation.into());
+            }
+            destination_account
+                .set_state(&StakeState::Stake(destination_meta, destination_stake))?;
+            destination_meta
+        }
+        MergeKind::Inactive(destination_meta, _) => {
+            if lamports < minimum_delegation {
+                return Err(StakeError::InsufficientDelegation.into());
+            }
+            let mut destination_stake = source_stake;
+            destination_stake.delegation.stake = lamports;
+            destination_account
+                .set_state(&StakeState::Stake(destination_meta, destination_stake))?;
+            destination_meta
+        }
+        _ => {
+            ic_msg!(
+                invoke_context,
+                "destination stake is neither fully active nor inactive"
+            );
+            return Err(InstructionError::InvalidAccountData);
+        }
+    };
+
+    if source_final_stake == 0 {
+        source_account.set_state(&StakeState::Initialized(source_meta))?;
+    } else {
+        source_stake.delegation.stake = source_final_stake;
+        source_account.set_state(&StakeState::Stake(source_meta, source_stake))?;
+    }
+
+    source_account.checked_sub_lamports(lamports)?;
+    destination_account.checked_add_lamports(lamports)?;
+
+    // Delegations never exceed the balance above the rent exempt minimum, so this can't happen,
+    // but the accounting is done with delegations so guard against it anyway
+    if source_account.get_lamports() < source_meta.rent_exempt_reserve
+        || destination_account.get_lamports() < destination_meta.rent_exempt_reserve
+    {
+        ic_msg!(
+            invoke_context,
+            "delegation calculations violated lamport balance assumptions"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn move_lamports(
+    invoke_context: &InvokeContext,
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+    source_account_index: usize,
+    lamports: u64,
+    destination_account_index: usize,
+    clock: &Clock,
+    stake_history: &StakeHistory,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    let mut source_account =
+        instruction_context.try_borrow_account(transaction_context, source_account_index)?;
+    if instruction_context.get_index_in_transaction(source_account_index)?
+        == instruction_context.get_index_in_transaction(destination_account_index)?
+    {
+        return Err(InstructionError::InvalidInstructionData);
+    }
+    let mut destination_account =
+        instruction_context.try_borrow_account(transaction_context, destination_account_index)?;
+
+    let (source_merge_kind, _) = move_stake_or_lamports_shared_checks(
+        invoke_context,
+        &source_account,
+        lamports,
+        &destination_account,
+        clock,
+        stake_history,
+        signers,
+    )?;
+
+    // Only lamports that neither back a delegation nor the rent exempt minimum are free to move
+    let source_free_lamports = match source_merge_kind {
+        MergeKind::FullyActive(source_meta, source_stake) => source_account
+            .get_lamports()
+            .saturating_sub(source_stake.delegation.stake)
+            .saturating_sub(source_meta.rent_exempt_reserve),
+        MergeKind::Inactive(source_meta, source_lamports) => {
+            source_lamports.saturating_sub(source_meta.rent_exempt_reserve)
+        }
+        _ => {
+            ic_msg!(
+                invoke_context,
+                "source stake is neither fully active nor inactive"
+            );
+            return Err(InstructionError::InvalidAccountData);
+        }
+    };
+
+    if lamports > source_free_lamports {
+        ic_msg!(
+            invoke_context,
+            "only {} undelegated lamports are available to move",
+            source_free_lamports
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    source_account.checked_sub_lamports(lamports)?;
+    destination_account.checked_add_lamports(lamports)?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn withdraw(
     transaction_context: &TransactionContext,
@@ -9,6 +9,7 @@ use {
         clock::{Clock, Slot},
         hash::Hash,
         pubkey::Pubkey,
+        rent::Rent,
         slot_hashes::{SlotHashes, MAX_ENTRIES},
         sysvar,
         transaction_context::{InstructionAccount, TransactionAccount, TransactionContext},
@@ -32,6 +33,7 @@ fn create_accounts() -> (
     let num_initial_votes = MAX_LOCKOUT_HISTORY as Slot;
 
     let clock = Clock::default();
+    let rent = Rent::default();
     let mut slot_hashes = SlotHashes::new(&[]);
     for i in 0..MAX_ENTRIES {
         // slot hashes is full in normal operation
@@ -52,14 +54,14 @@ fn create_accounts() -> (
         );
 
         for next_vote_slot in 0..num_initial_votes {
-            vote_state.process_next_vote_slot(next_vote_slot, 0);
+            vote_state.process_next_vote_slot(next_vote_slot, 0, 0, false);
         }
         let mut vote_account_data: Vec<u8> = vec![0; VoteState::size_of()];
         let versioned = VoteStateVersions::new_current(vote_state);
         VoteState::serialize(&versioned, &mut vote_account_data).unwrap();
 
         Account {
-            lamports: 1,
+            lamports: rent.minimum_balance(VoteState::size_of_with_vote_latencies()),
             data: vote_account_data,
             owner: solana_vote_program::id(),
             executable: false,
@@ -79,6 +81,10 @@ fn create_accounts() -> (
             AccountSharedData::from(create_account_for_test(&clock)),
         ),
         (authority_pubkey, AccountSharedData::default()),
+        (
+            sysvar::rent::id(),
+            AccountSharedData::from(create_account_for_test(&rent)),
+        ),
     ];
     let mut instruction_accounts = (0..4)
         .map(|index_in_instruction| InstructionAccount {
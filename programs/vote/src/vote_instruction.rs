@@ -122,6 +122,9 @@ pub fn create_account(
     vote_init: &VoteInit,
     lamports: u64,
 ) -> Vec<Instruction> {
+    // Vote accounts are initialized at `VoteState::size_of()` and grow to
+    // `VoteState::size_of_with_vote_latencies()` once they record vote latencies, which they can
+    // only do if `lamports` covers the rent exemption of the larger size
     let space = VoteState::size_of() as u64;
     let create_ix =
         system_instruction::create_account(from_pubkey, vote_pubkey, lamports, space, &id());
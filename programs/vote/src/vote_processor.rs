@@ -6,9 +6,27 @@ use {
     solana_program_runtime::{
         invoke_context::InvokeContext, sysvar_cache::get_sysvar_with_account_check,
     },
-    solana_sdk::{feature_set, instruction::InstructionError, program_utils::limited_deserialize},
+    solana_sdk::{
+        feature_set, instruction::InstructionError, program_utils::limited_deserialize,
+        rent::Rent,
+    },
+    std::sync::Arc,
 };
 
+/// The rent sysvar is needed to resize vote accounts that start recording vote latencies
+fn get_rent_if_timely_vote_credits(
+    invoke_context: &InvokeContext,
+) -> Result<Option<Arc<Rent>>, InstructionError> {
+    if invoke_context
+        .feature_set
+        .is_active(&feature_set::timely_vote_credits::id())
+    {
+        invoke_context.get_sysvar_cache().get_rent().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn process_instruction(
     first_instruction_account: usize,
     invoke_context: &mut InvokeContext,
@@ -63,6 +81,7 @@ pub fn process_instruction(
                 get_sysvar_with_account_check::slot_hashes(invoke_context, instruction_context, 1)?;
             let clock =
                 get_sysvar_with_account_check::clock(invoke_context, instruction_context, 2)?;
+            let rent = get_rent_if_timely_vote_credits(invoke_context)?;
             vote_state::process_vote(
                 &mut me,
                 &slot_hashes,
@@ -70,6 +89,7 @@ pub fn process_instruction(
                 &vote,
                 &signers,
                 &invoke_context.feature_set,
+                rent.as_deref(),
             )
         }
         VoteInstruction::UpdateVoteState(vote_state_update)
@@ -81,12 +101,15 @@ pub fn process_instruction(
                 let sysvar_cache = invoke_context.get_sysvar_cache();
                 let slot_hashes = sysvar_cache.get_slot_hashes()?;
                 let clock = sysvar_cache.get_clock()?;
+                let rent = get_rent_if_timely_vote_credits(invoke_context)?;
                 vote_state::process_vote_state_update(
                     &mut me,
                     slot_hashes.slot_hashes(),
                     &clock,
                     vote_state_update,
                     &signers,
+                    &invoke_context.feature_set,
+                    rent.as_deref(),
                 )
             } else {
                 Err(InstructionError::InvalidInstructionData)
@@ -166,13 +189,14 @@ mod tests {
             },
             vote_state::{
                 Lockout, Vote, VoteAuthorize, VoteInit, VoteState, VoteStateUpdate,
-                VoteStateVersions,
+                VoteStateVersions, MAX_EPOCH_CREDITS_HISTORY, MAX_LOCKOUT_HISTORY,
+                VOTE_CREDITS_MAXIMUM_PER_SLOT,
             },
         },
         bincode::serialize,
         solana_program_runtime::invoke_context::mock_process_instruction,
         solana_sdk::{
-            account::{self, Account, AccountSharedData, ReadableAccount},
+            account::{self, Account, AccountSharedData, ReadableAccount, WritableAccount},
             account_utils::StateMut,
             feature_set::FeatureSet,
             hash::Hash,
@@ -586,6 +610,7 @@ mod tests {
             (vote_pubkey, vote_account),
             (sysvar::slot_hashes::id(), slot_hashes_account.clone()),
             (sysvar::clock::id(), create_default_clock_account()),
+            (sysvar::rent::id(), create_default_rent_account()),
         ];
         let mut instruction_accounts = vec![
             AccountMeta {
@@ -682,6 +707,178 @@ mod tests {
         );
     }
 
+    /// Votes for slots `0..=MAX_LOCKOUT_HISTORY`, each landing in the slot after the one it votes
+    /// for, and returns the resulting vote account
+    fn process_timely_votes(
+        vote_pubkey: Pubkey,
+        mut vote_account: AccountSharedData,
+        authorized_voter: Pubkey,
+        rent: &Rent,
+        feature_set: Arc<FeatureSet>,
+        expected_result: Result<(), InstructionError>,
+    ) -> AccountSharedData {
+        let instruction_accounts = vec![
+            AccountMeta {
+                pubkey: vote_pubkey,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: sysvar::slot_hashes::id(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: sysvar::clock::id(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: authorized_voter,
+                is_signer: true,
+                is_writable: false,
+            },
+        ];
+        for slot in 0..=MAX_LOCKOUT_HISTORY as u64 {
+            let vote = Vote::new(vec![slot], Hash::new_unique());
+            let clock = Clock {
+                slot: slot + 1,
+                ..Clock::default()
+            };
+            let transaction_accounts = vec![
+                (vote_pubkey, vote_account),
+                (
+                    sysvar::slot_hashes::id(),
+                    account::create_account_shared_data_for_test(&SlotHashes::new(&[(
+                        slot, vote.hash,
+                    )])),
+                ),
+                (
+                    sysvar::clock::id(),
+                    account::create_account_shared_data_for_test(&clock),
+                ),
+                (authorized_voter, AccountSharedData::default()),
+                (
+                    sysvar::rent::id(),
+                    account::create_account_shared_data_for_test(rent),
+                ),
+            ];
+            let accounts = mock_process_instruction(
+                &id(),
+                Vec::new(),
+                &serialize(&VoteInstruction::Vote(vote)).unwrap(),
+                transaction_accounts,
+                instruction_accounts.clone(),
+                None,
+                Some(feature_set.clone()),
+                expected_result.clone(),
+                super::process_instruction,
+            );
+            vote_account = accounts[0].clone();
+            if expected_result.is_err() {
+                break;
+            }
+        }
+        vote_account
+    }
+
+    #[test]
+    fn test_vote_timely_vote_credits_full_size_account() {
+        let rent = Rent::default();
+        let lamports = rent.minimum_balance(VoteState::size_of_with_vote_latencies());
+
+        // A vote account whose vote state grows to the maximum size
+        let mut full_vote_state = VoteState::get_max_sized_vote_state();
+        full_vote_state.votes.clear();
+        full_vote_state.root_slot = None;
+        full_vote_state.epoch_credits = vec![(0, 0, 0); MAX_EPOCH_CREDITS_HISTORY];
+        let authorized_voter = full_vote_state
+            .authorized_voters()
+            .get_authorized_voter(0)
+            .unwrap();
+        let full_vote_pubkey = solana_sdk::pubkey::new_rand();
+        let mut full_vote_account = AccountSharedData::new(lamports, VoteState::size_of(), &id());
+        VoteState::to(
+            &VoteStateVersions::new_current(full_vote_state),
+            &mut full_vote_account,
+        )
+        .unwrap();
+
+        // A newly created vote account
+        let new_vote_pubkey = solana_sdk::pubkey::new_rand();
+        let new_vote_account = vote_state::create_account_with_authorized(
+            &solana_sdk::pubkey::new_rand(),
+            &authorized_voter,
+            &authorized_voter,
+            0,
+            lamports,
+        );
+
+        // Without timely vote credits, vote latencies are not recorded and each rooted vote earns
+        // a single credit
+        let vote_account = process_timely_votes(
+            full_vote_pubkey,
+            full_vote_account.clone(),
+            authorized_voter,
+            &rent,
+            Arc::new(FeatureSet::default()),
+            Ok(()),
+        );
+        assert_eq!(vote_account.data().len(), VoteState::size_of());
+        assert!(matches!(
+            StateMut::<VoteStateVersions>::state(&vote_account).unwrap(),
+            VoteStateVersions::Current(_)
+        ));
+        assert_eq!(VoteState::from(&vote_account).unwrap().credits(), 1);
+
+        // With timely vote credits, both accounts are resized to hold vote latencies and earn the
+        // same credits
+        for (vote_pubkey, vote_account) in [
+            (full_vote_pubkey, full_vote_account.clone()),
+            (new_vote_pubkey, new_vote_account),
+        ] {
+            let vote_account = process_timely_votes(
+                vote_pubkey,
+                vote_account,
+                authorized_voter,
+                &rent,
+                Arc::new(FeatureSet::all_enabled()),
+                Ok(()),
+            );
+            assert_eq!(
+                vote_account.data().len(),
+                VoteState::size_of_with_vote_latencies()
+            );
+            assert!(VoteState::is_correct_size_and_initialized(
+                vote_account.data()
+            ));
+            let vote_state = VoteState::from(&vote_account).unwrap();
+            assert_eq!(vote_state.vote_latencies.len(), MAX_LOCKOUT_HISTORY);
+            assert_eq!(
+                vote_state.credits(),
+                u64::from(VOTE_CREDITS_MAXIMUM_PER_SLOT)
+            );
+        }
+
+        // An account that is not rent exempt at the larger size keeps voting without vote
+        // latencies, and each rooted vote earns a single credit
+        full_vote_account.set_lamports(rent.minimum_balance(VoteState::size_of()));
+        let vote_account = process_timely_votes(
+            full_vote_pubkey,
+            full_vote_account,
+            authorized_voter,
+            &rent,
+            Arc::new(FeatureSet::all_enabled()),
+            Ok(()),
+        );
+        assert_eq!(vote_account.data().len(), VoteState::size_of());
+        assert!(matches!(
+            StateMut::<VoteStateVersions>::state(&vote_account).unwrap(),
+            VoteStateVersions::Current(_)
+        ));
+        assert_eq!(VoteState::from(&vote_account).unwrap().credits(), 1);
+    }
+
     #[test]
     fn test_authorize_voter() {
         let (vote_pubkey, vote_account) = create_test_account();
@@ -773,6 +970,7 @@ mod tests {
         let slot_hashes_account = account::create_account_shared_data_for_test(&slot_hashes);
         let instruction_data = serialize(&VoteInstruction::Vote(vote)).unwrap();
         transaction_accounts.push((sysvar::slot_hashes::id(), slot_hashes_account));
+        transaction_accounts.push((sysvar::rent::id(), create_default_rent_account()));
         instruction_accounts.insert(
             1,
             AccountMeta {
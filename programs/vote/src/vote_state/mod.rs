@@ -11,7 +11,7 @@ use {
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount, WritableAccount},
         clock::{Epoch, Slot, UnixTimestamp},
-        feature_set::{self, filter_votes_outside_slot_hashes, FeatureSet},
+        feature_set::{self, filter_votes_outside_slot_hashes, timely_vote_credits, FeatureSet},
         hash::Hash,
         instruction::InstructionError,
         pubkey::Pubkey,
@@ -38,6 +38,12 @@ pub const INITIAL_LOCKOUT: usize = 2;
 // Maximum number of credits history to keep around
 pub const MAX_EPOCH_CREDITS_HISTORY: usize = 64;
 
+// Number of slots a vote can take to land and still earn the maximum credits once rooted
+pub const VOTE_CREDITS_GRACE_SLOTS: u8 = 2;
+
+// Maximum number of credits a rooted vote earns, when vote latencies are used to award credits
+pub const VOTE_CREDITS_MAXIMUM_PER_SLOT: u8 = 16;
+
 // Offset of VoteState::prior_voters, for determining initialization status without deserialization
 const DEFAULT_PRIOR_VOTERS_OFFSET: usize = 82;
 
@@ -322,6 +328,13 @@ pub struct VoteState {
 
     /// most recent timestamp submitted with a vote
     pub last_timestamp: BlockTimestamp,
+
+    /// number of slots each of `votes` took to land, empty if vote latencies aren't recorded
+    ///
+    /// Serialized by `VoteStateVersions::CurrentWithVoteLatencies`, outside of `VoteState`, so
+    /// the layout of `VoteStateVersions::Current` is unchanged. A latency of 0 is unknown.
+    #[serde(skip)]
+    pub vote_latencies: VecDeque<u8>,
 }
 
 impl VoteState {
@@ -357,6 +370,11 @@ impl VoteState {
         3731 // see test_vote_state_size_of.
     }
 
+    /// Upper limit on the size of the Vote State, including the latency of each of its votes
+    pub const fn size_of_with_vote_latencies() -> usize {
+        3770 // see test_vote_state_size_of_with_vote_latencies.
+    }
+
     // utility function, used by Stakes, tests
     pub fn from<T: ReadableAccount>(account: &T) -> Option<VoteState> {
         Self::deserialize(account.data()).ok()
@@ -418,7 +436,7 @@ impl VoteState {
     }
 
     #[cfg(test)]
-    fn get_max_sized_vote_state() -> VoteState {
+    pub(crate) fn get_max_sized_vote_state() -> VoteState {
         let mut authorized_voters = AuthorizedVoters::default();
         for i in 0..=MAX_LEADER_SCHEDULE_EPOCH_OFFSET {
             authorized_voters.insert(i, solana_sdk::pubkey::new_rand());
@@ -763,6 +781,8 @@ impl VoteState {
         new_root: Option<Slot>,
         timestamp: Option<i64>,
         epoch: Epoch,
+        current_slot: Slot,
+        timely_vote_credits: bool,
     ) -> Result<(), VoteError> {
         assert!(!new_state.is_empty());
         if new_state.len() > MAX_LOCKOUT_HISTORY {
@@ -817,16 +837,23 @@ impl VoteState {
             previous_vote = Some(vote);
         }
 
+        self.sync_vote_latencies(timely_vote_credits);
+
         // Find the first vote in the current vote state for a slot greater
         // than the new proposed root
         let mut current_vote_state_index = 0;
         let mut new_vote_state_index = 0;
 
+        // Credits earned by the votes that the new proposed root roots
+        let mut earned_credits = 0;
+
         for current_vote in &self.votes {
             // Find the first vote in the current vote state for a slot greater
             // than the new proposed root
             if let Some(new_root) = new_root {
                 if current_vote.slot <= new_root {
+                    earned_credits += self
+                        .credits_for_vote_at_index(current_vote_state_index, timely_vote_credits);
                     current_vote_state_index += 1;
                     continue;
                 }
@@ -871,14 +898,34 @@ impl VoteState {
         // `new_vote_state` passed all the checks, finalize the change by rewriting
         // our state.
         if self.root_slot != new_root {
-            // TODO to think about: Note, people may be incentivized to set more
-            // roots to get more credits, but I think they can already do this...
-            self.increment_credits(epoch);
+            if timely_vote_credits {
+                // Each vote that was rooted earns credits according to how quickly it landed
+                if earned_credits > 0 {
+                    self.increment_credits_by(epoch, earned_credits);
+                }
+            } else {
+                // TODO to think about: Note, people may be incentivized to set more
+                // roots to get more credits, but I think they can already do this...
+                self.increment_credits(epoch);
+            }
         }
         if let Some(timestamp) = timestamp {
             let last_slot = new_state.back().unwrap().slot;
             self.process_timestamp(last_slot, timestamp)?;
         }
+        if timely_vote_credits {
+            // Votes already in the vote state keep their latency, new ones land now
+            self.vote_latencies = new_state
+                .iter()
+                .map(|new_vote| {
+                    self.votes
+                        .binary_search_by(|lockout| lockout.slot.cmp(&new_vote.slot))
+                        .ok()
+                        .and_then(|index| self.vote_latencies.get(index).copied())
+                        .unwrap_or_else(|| Self::compute_vote_latency(new_vote.slot, current_slot))
+                })
+                .collect();
+        }
         self.root_slot = new_root;
         self.votes = new_state;
         Ok(())
@@ -889,7 +936,32 @@ impl VoteState {
         vote: &Vote,
         slot_hashes: &[SlotHash],
         epoch: Epoch,
+        current_slot: Slot,
         feature_set: Option<&FeatureSet>,
+    ) -> Result<(), VoteError> {
+        let timely_vote_credits = feature_set.map_or(false, |feature_set| {
+            feature_set.is_active(&timely_vote_credits::id())
+        });
+        self.process_vote_with_vote_latencies(
+            vote,
+            slot_hashes,
+            epoch,
+            current_slot,
+            feature_set,
+            timely_vote_credits,
+        )
+    }
+
+    /// `process_vote()`, recording vote latencies and awarding credits by them only if
+    /// `timely_vote_credits` is set
+    fn process_vote_with_vote_latencies(
+        &mut self,
+        vote: &Vote,
+        slot_hashes: &[SlotHash],
+        epoch: Epoch,
+        current_slot: Slot,
+        feature_set: Option<&FeatureSet>,
+        timely_vote_credits: bool,
     ) -> Result<(), VoteError> {
         if vote.slots.is_empty() {
             return Err(VoteError::EmptySlots);
@@ -917,13 +989,20 @@ impl VoteState {
 
         self.check_slots_are_valid(vote_slots, &vote.hash, slot_hashes)?;
 
-        vote_slots
-            .iter()
-            .for_each(|s| self.process_next_vote_slot(*s, epoch));
+        self.sync_vote_latencies(timely_vote_credits);
+        vote_slots.iter().for_each(|s| {
+            self.process_next_vote_slot(*s, epoch, current_slot, timely_vote_credits)
+        });
         Ok(())
     }
 
-    pub fn process_next_vote_slot(&mut self, next_vote_slot: Slot, epoch: Epoch) {
+    pub fn process_next_vote_slot(
+        &mut self,
+        next_vote_slot: Slot,
+        epoch: Epoch,
+        current_slot: Slot,
+        timely_vote_credits: bool,
+    ) {
         // Ignore votes for slots earlier than we already have votes for
         if self
             .last_voted_slot()
@@ -938,17 +1017,66 @@ impl VoteState {
 
         // Once the stack is full, pop the oldest lockout and distribute rewards
         if self.votes.len() == MAX_LOCKOUT_HISTORY {
+            let credits = self.credits_for_vote_at_index(0, timely_vote_credits);
             let vote = self.votes.pop_front().unwrap();
+            self.vote_latencies.pop_front();
             self.root_slot = Some(vote.slot);
 
-            self.increment_credits(epoch);
+            self.increment_credits_by(epoch, credits);
         }
         self.votes.push_back(vote);
+        if timely_vote_credits {
+            self.vote_latencies
+                .push_back(Self::compute_vote_latency(next_vote_slot, current_slot));
+        }
         self.double_lockouts();
     }
 
+    /// Number of slots a vote for `voted_for_slot` took to land in `current_slot`
+    pub fn compute_vote_latency(voted_for_slot: Slot, current_slot: Slot) -> u8 {
+        std::cmp::min(current_slot.saturating_sub(voted_for_slot), u8::MAX as u64) as u8
+    }
+
+    /// Credits earned once the vote at `index` of `votes` is rooted
+    ///
+    /// Votes that landed within `VOTE_CREDITS_GRACE_SLOTS` earn `VOTE_CREDITS_MAXIMUM_PER_SLOT`
+    /// credits, and every slot later than that costs a credit, down to a single credit. Votes
+    /// with an unknown latency earn a single credit.
+    pub fn credits_for_vote_at_index(&self, index: usize, timely_vote_credits: bool) -> u64 {
+        if !timely_vote_credits {
+            return 1;
+        }
+        let latency = self.vote_latencies.get(index).copied().unwrap_or(0);
+        if latency == 0 {
+            return 1;
+        }
+        let late_slots = latency.saturating_sub(VOTE_CREDITS_GRACE_SLOTS);
+        u64::from(
+            VOTE_CREDITS_MAXIMUM_PER_SLOT
+                .saturating_sub(late_slots)
+                .max(1),
+        )
+    }
+
+    /// Keep `vote_latencies` in step with `votes`
+    ///
+    /// Vote latencies are only recorded while vote latencies are used to award credits. Votes
+    /// recorded without a latency get an unknown one.
+    fn sync_vote_latencies(&mut self, timely_vote_credits: bool) {
+        if !timely_vote_credits {
+            self.vote_latencies.clear();
+        } else if self.vote_latencies.len() != self.votes.len() {
+            self.vote_latencies = VecDeque::from(vec![0; self.votes.len()]);
+        }
+    }
+
     /// increment credits, record credits for last epoch if new epoch
     pub fn increment_credits(&mut self, epoch: Epoch) {
+        self.increment_credits_by(epoch, 1)
+    }
+
+    /// increase credits by `credits`, record credits for last epoch if new epoch
+    pub fn increment_credits_by(&mut self, epoch: Epoch, credits: u64) {
         // increment credits, record by epoch
 
         // never seen a credit
@@ -972,13 +1100,13 @@ impl VoteState {
             }
         }
 
-        self.epoch_credits.last_mut().unwrap().1 += 1;
+        self.epoch_credits.last_mut().unwrap().1 += credits;
     }
 
     /// "unchecked" functions used by tests and Tower
     pub fn process_vote_unchecked(&mut self, vote: Vote) {
         let slot_hashes: Vec<_> = vote.slots.iter().rev().map(|x| (*x, vote.hash)).collect();
-        let _ignored = self.process_vote(&vote, &slot_hashes, self.current_epoch(), None);
+        let _ignored = self.process_vote(&vote, &slot_hashes, self.current_epoch(), 0, None);
     }
 
     #[cfg(test)]
@@ -1121,6 +1249,7 @@ impl VoteState {
         while let Some(vote) = self.last_lockout() {
             if !vote.is_locked_out_at_slot(next_vote_slot) {
                 self.votes.pop_back();
+                self.vote_latencies.pop_back();
             } else {
                 break;
             }
@@ -1156,7 +1285,8 @@ impl VoteState {
 
     pub fn is_correct_size_and_initialized(data: &[u8]) -> bool {
         const VERSION_OFFSET: usize = 4;
-        data.len() == VoteState::size_of()
+        (data.len() == VoteState::size_of()
+            || data.len() == VoteState::size_of_with_vote_latencies())
             && data[VERSION_OFFSET..VERSION_OFFSET + DEFAULT_PRIOR_VOTERS_OFFSET]
                 != [0; DEFAULT_PRIOR_VOTERS_OFFSET]
     }
@@ -1208,7 +1338,7 @@ pub fn authorize<S: std::hash::BuildHasher>(
         }
     }
 
-    vote_account.set_state(&VoteStateVersions::new_current(vote_state))
+    set_vote_account_state(vote_account, vote_state)
 }
 
 /// Update the node_pubkey, requires signature of the authorized voter
@@ -1229,7 +1359,7 @@ pub fn update_validator_identity<S: std::hash::BuildHasher>(
 
     vote_state.node_pubkey = *node_pubkey;
 
-    vote_account.set_state(&VoteStateVersions::new_current(vote_state))
+    set_vote_account_state(vote_account, vote_state)
 }
 
 /// Update the vote account's commission
@@ -1247,7 +1377,7 @@ pub fn update_commission<S: std::hash::BuildHasher>(
 
     vote_state.commission = commission;
 
-    vote_account.set_state(&VoteStateVersions::new_current(vote_state))
+    set_vote_account_state(vote_account, vote_state)
 }
 
 fn verify_authorized_signer<S: std::hash::BuildHasher>(
@@ -1371,10 +1501,20 @@ pub fn process_vote<S: std::hash::BuildHasher>(
     vote: &Vote,
     signers: &HashSet<Pubkey, S>,
     feature_set: &FeatureSet,
+    rent: Option<&Rent>,
 ) -> Result<(), InstructionError> {
     let mut vote_state = verify_and_get_vote_state(vote_account, clock, signers)?;
+    let timely_vote_credits = feature_set.is_active(&timely_vote_credits::id())
+        && can_record_vote_latencies(vote_account, rent);
 
-    vote_state.process_vote(vote, slot_hashes, clock.epoch, Some(feature_set))?;
+    vote_state.process_vote_with_vote_latencies(
+        vote,
+        slot_hashes,
+        clock.epoch,
+        clock.slot,
+        Some(feature_set),
+        timely_vote_credits,
+    )?;
     if let Some(timestamp) = vote.timestamp {
         vote.slots
             .iter()
@@ -1382,7 +1522,7 @@ pub fn process_vote<S: std::hash::BuildHasher>(
             .ok_or(VoteError::EmptySlots)
             .and_then(|slot| vote_state.process_timestamp(*slot, timestamp))?;
     }
-    vote_account.set_state(&VoteStateVersions::new_current(vote_state))
+    set_vote_account_state(vote_account, vote_state)
 }
 
 pub fn process_vote_state_update<S: std::hash::BuildHasher>(
@@ -1391,6 +1531,8 @@ pub fn process_vote_state_update<S: std::hash::BuildHasher>(
     clock: &Clock,
     mut vote_state_update: VoteStateUpdate,
     signers: &HashSet<Pubkey, S>,
+    feature_set: &FeatureSet,
+    rent: Option<&Rent>,
 ) -> Result<(), InstructionError> {
     let mut vote_state = verify_and_get_vote_state(vote_account, clock, signers)?;
     vote_state.check_update_vote_state_slots_are_valid(&mut vote_state_update, slot_hashes)?;
@@ -1399,7 +1541,40 @@ pub fn process_vote_state_update<S: std::hash::BuildHasher>(
         vote_state_update.root,
         vote_state_update.timestamp,
         clock.epoch,
+        clock.slot,
+        feature_set.is_active(&timely_vote_credits::id())
+            && can_record_vote_latencies(vote_account, rent),
     )?;
+    set_vote_account_state(vote_account, vote_state)
+}
+
+/// Whether `vote_account` holds vote latencies, or can be resized to hold them
+///
+/// Vote latencies are only recorded once `timely_vote_credits` is active, which is when
+/// `VoteStateVersions::CurrentWithVoteLatencies` starts being written. A vote account is resized
+/// to `VoteState::size_of_with_vote_latencies()` the first time it stores vote latencies, which
+/// it must be rent exempt for. Accounts that can't be resized keep voting without vote latencies,
+/// earning a single credit per rooted vote.
+fn can_record_vote_latencies(vote_account: &BorrowedAccount, rent: Option<&Rent>) -> bool {
+    vote_account.get_data().len() >= VoteState::size_of_with_vote_latencies()
+        || rent.map_or(false, |rent| {
+            rent.is_exempt(
+                vote_account.get_lamports(),
+                VoteState::size_of_with_vote_latencies(),
+            )
+        })
+}
+
+/// Store `vote_state` in `vote_account`, resizing it if `vote_state` records vote latencies
+fn set_vote_account_state(
+    vote_account: &mut BorrowedAccount,
+    vote_state: VoteState,
+) -> Result<(), InstructionError> {
+    if !vote_state.vote_latencies.is_empty()
+        && vote_account.get_data().len() < VoteState::size_of_with_vote_latencies()
+    {
+        vote_account.set_data_length(VoteState::size_of_with_vote_latencies())?;
+    }
     vote_account.set_state(&VoteStateVersions::new_current(vote_state))
 }
 
@@ -1621,6 +1796,84 @@ mod tests {
         assert_eq!(vote_state.credits(), 3);
     }
 
+    #[test]
+    fn test_timely_vote_credits() {
+        let voter_pubkey = solana_sdk::pubkey::new_rand();
+        let mut vote_state = VoteState::new_for_test(&voter_pubkey);
+
+        // Every vote lands in the slot right after the one it votes for
+        for i in 0..MAX_LOCKOUT_HISTORY as u64 {
+            vote_state.process_next_vote_slot(i, 0, i + 1, true);
+        }
+        assert_eq!(vote_state.credits(), 0);
+        assert_eq!(vote_state.vote_latencies.len(), vote_state.votes.len());
+        assert!(vote_state
+            .vote_latencies
+            .iter()
+            .all(|latency| *latency == 1));
+
+        // Rooting a vote that landed within the grace period earns the maximum credits
+        let slot = MAX_LOCKOUT_HISTORY as u64;
+        vote_state.process_next_vote_slot(slot, 0, slot + 10, true);
+        assert_eq!(
+            vote_state.credits(),
+            u64::from(VOTE_CREDITS_MAXIMUM_PER_SLOT)
+        );
+        assert_eq!(vote_state.vote_latencies.back(), Some(&10));
+
+        // Disabling timely vote credits falls back to one credit per rooted vote
+        let credits = vote_state.credits();
+        vote_state.process_vote_unchecked(Vote::new(vec![slot + 1], Hash::default()));
+        assert!(vote_state.vote_latencies.is_empty());
+        assert_eq!(vote_state.credits(), credits + 1);
+    }
+
+    #[test]
+    fn test_credits_for_vote_at_index() {
+        let mut vote_state = VoteState::default();
+        vote_state.vote_latencies = VecDeque::from(vec![0, 1, 2, 3, 10, 17, 18, 255]);
+        let expected_credits = [1, 16, 16, 15, 8, 1, 1, 1];
+        for (index, expected) in expected_credits.iter().enumerate() {
+            assert_eq!(vote_state.credits_for_vote_at_index(index, true), *expected);
+            assert_eq!(vote_state.credits_for_vote_at_index(index, false), 1);
+        }
+        // Votes without a recorded latency earn a single credit
+        assert_eq!(
+            vote_state.credits_for_vote_at_index(expected_credits.len(), true),
+            1
+        );
+
+        assert_eq!(VoteState::compute_vote_latency(5, 4), 0);
+        assert_eq!(VoteState::compute_vote_latency(5, 7), 2);
+        assert_eq!(VoteState::compute_vote_latency(0, 1_000), u8::MAX);
+    }
+
+    #[test]
+    fn test_vote_state_versions_with_vote_latencies() {
+        let mut vote_state = VoteState::new_for_test(&solana_sdk::pubkey::new_rand());
+        for i in 0..3 {
+            vote_state.process_next_vote_slot(i, 0, i + 2, true);
+        }
+        let versioned = VoteStateVersions::new_current(vote_state.clone());
+        assert!(matches!(
+            versioned,
+            VoteStateVersions::CurrentWithVoteLatencies(_, _)
+        ));
+
+        let mut buffer = vec![0; VoteState::size_of()];
+        VoteState::serialize(&versioned, &mut buffer).unwrap();
+        let deserialized = VoteState::deserialize(&buffer).unwrap();
+        assert_eq!(deserialized, vote_state);
+        assert_eq!(deserialized.vote_latencies, VecDeque::from(vec![2, 2, 2]));
+
+        // Vote states without latencies keep the legacy layout
+        vote_state.vote_latencies.clear();
+        assert!(matches!(
+            VoteStateVersions::new_current(vote_state),
+            VoteStateVersions::Current(_)
+        ));
+    }
+
     #[test]
     fn test_duplicate_vote() {
         let voter_pubkey = solana_sdk::pubkey::new_rand();
@@ -1681,11 +1934,11 @@ mod tests {
         let slot_hashes: Vec<_> = vote.slots.iter().rev().map(|x| (*x, vote.hash)).collect();
 
         assert_eq!(
-            vote_state_a.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state_a.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
         assert_eq!(
-            vote_state_b.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state_b.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
         assert_eq!(recent_votes(&vote_state_a), recent_votes(&vote_state_b));
@@ -1698,12 +1951,12 @@ mod tests {
         let vote = Vote::new(vec![0], Hash::default());
         let slot_hashes: Vec<_> = vec![(0, vote.hash)];
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
         let recent = recent_votes(&vote_state);
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Err(VoteError::VoteTooOld)
         );
         assert_eq!(recent, recent_votes(&vote_state));
@@ -1763,7 +2016,7 @@ mod tests {
         let vote = Vote::new(vec![0], Hash::default());
         let slot_hashes: Vec<_> = vec![(*vote.slots.last().unwrap(), vote.hash)];
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
         assert_eq!(
@@ -1779,7 +2032,7 @@ mod tests {
         let vote = Vote::new(vec![0], Hash::default());
         let slot_hashes: Vec<_> = vec![(*vote.slots.last().unwrap(), vote.hash)];
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
 
@@ -1798,7 +2051,7 @@ mod tests {
         let vote = Vote::new(vec![0], Hash::default());
         let slot_hashes: Vec<_> = vec![(*vote.slots.last().unwrap(), vote.hash)];
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&FeatureSet::default())),
             Ok(())
         );
 
@@ -1815,7 +2068,7 @@ mod tests {
 
         let vote = Vote::new(vec![], Hash::default());
         assert_eq!(
-            vote_state.process_vote(&vote, &[], 0, Some(&FeatureSet::default())),
+            vote_state.process_vote(&vote, &[], 0, 0, Some(&FeatureSet::default())),
             Err(VoteError::EmptySlots)
         );
     }
@@ -2156,6 +2409,15 @@ mod tests {
         assert_eq!(VoteState::size_of() as u64, size);
     }
 
+    #[test]
+    fn test_vote_state_size_of_with_vote_latencies() {
+        let mut vote_state = VoteState::get_max_sized_vote_state();
+        vote_state.vote_latencies = VecDeque::from(vec![u8::MAX; MAX_LOCKOUT_HISTORY]);
+        let vote_state = VoteStateVersions::new_current(vote_state);
+        let size = bincode::serialized_size(&vote_state).unwrap();
+        assert_eq!(VoteState::size_of_with_vote_latencies() as u64, size);
+    }
+
     #[test]
     fn test_vote_state_max_size() {
         let mut max_sized_data = vec![0; VoteState::size_of()];
@@ -2243,7 +2505,14 @@ mod tests {
             .collect();
 
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::TooManyVotes)
         );
     }
@@ -2270,6 +2539,8 @@ mod tests {
                 lesser_root,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::RootRollBack)
         );
@@ -2282,6 +2553,8 @@ mod tests {
                 none_root,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::RootRollBack)
         );
@@ -2304,7 +2577,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ZeroConfirmations)
         );
 
@@ -2321,7 +2601,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ZeroConfirmations)
         );
     }
@@ -2338,7 +2625,14 @@ mod tests {
         .collect();
 
         vote_state1
-            .process_new_vote_state(good_votes, None, None, vote_state1.current_epoch())
+            .process_new_vote_state(
+                good_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            )
             .unwrap();
 
         let mut vote_state1 = VoteState::default();
@@ -2349,7 +2643,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ConfirmationTooLarge)
         );
     }
@@ -2377,6 +2678,8 @@ mod tests {
                 Some(root_slot),
                 None,
                 vote_state1.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::SlotSmallerThanRoot)
         );
@@ -2399,6 +2702,8 @@ mod tests {
                 Some(root_slot),
                 None,
                 vote_state1.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::SlotSmallerThanRoot)
         );
@@ -2421,7 +2726,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::SlotsNotOrdered)
         );
 
@@ -2438,7 +2750,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::SlotsNotOrdered)
         );
     }
@@ -2460,7 +2779,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ConfirmationsNotOrdered)
         );
 
@@ -2477,7 +2803,14 @@ mod tests {
         .into_iter()
         .collect();
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ConfirmationsNotOrdered)
         );
     }
@@ -2501,7 +2834,14 @@ mod tests {
 
         // Slot 7 should have expired slot 0
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::NewVoteStateLockoutMismatch)
         );
     }
@@ -2522,7 +2862,7 @@ mod tests {
         .into_iter()
         .collect();
         vote_state1
-            .process_new_vote_state(votes, None, None, vote_state1.current_epoch())
+            .process_new_vote_state(votes, None, None, vote_state1.current_epoch(), 0, false)
             .unwrap();
 
         let votes: VecDeque<Lockout> = vec![
@@ -2545,11 +2885,57 @@ mod tests {
         // Should error because newer vote state should not have lower confirmation the same slot
         // 1
         assert_eq!(
-            vote_state1.process_new_vote_state(votes, None, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                votes,
+                None,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::ConfirmationRollBack)
         );
     }
 
+    #[test]
+    fn test_process_new_vote_state_timely_vote_credits() {
+        let mut vote_state1 = VoteState::default();
+        for i in 0..MAX_LOCKOUT_HISTORY as u64 {
+            vote_state1.process_next_vote_slot(i, 0, i + 3, true);
+        }
+        let mut vote_state2 = vote_state1.clone();
+        vote_state2.process_slot_vote_unchecked(MAX_LOCKOUT_HISTORY as u64);
+        assert_eq!(vote_state2.root_slot, Some(0));
+
+        let current_slot = MAX_LOCKOUT_HISTORY as u64 + 5;
+        vote_state1
+            .process_new_vote_state(
+                vote_state2.votes.clone(),
+                vote_state2.root_slot,
+                None,
+                vote_state2.current_epoch(),
+                current_slot,
+                true,
+            )
+            .unwrap();
+        assert_eq!(vote_state1.votes, vote_state2.votes);
+
+        // The rooted vote landed one slot after the grace period
+        assert_eq!(
+            vote_state1.credits(),
+            u64::from(VOTE_CREDITS_MAXIMUM_PER_SLOT - 1)
+        );
+
+        // Votes carried over keep their latency, the new vote lands now
+        assert_eq!(vote_state1.vote_latencies.len(), vote_state1.votes.len());
+        assert!(vote_state1
+            .vote_latencies
+            .iter()
+            .take(MAX_LOCKOUT_HISTORY - 1)
+            .all(|latency| *latency == 3));
+        assert_eq!(vote_state1.vote_latencies.back(), Some(&5));
+    }
+
     #[test]
     fn test_process_new_vote_state_root_progress() {
         let mut vote_state1 = VoteState::default();
@@ -2576,6 +2962,8 @@ mod tests {
                     vote_state2.root_slot,
                     None,
                     vote_state2.current_epoch(),
+                    0,
+                    false,
                 )
                 .unwrap();
 
@@ -2633,6 +3021,8 @@ mod tests {
                 vote_state2.root_slot,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             )
             .unwrap();
 
@@ -2673,6 +3063,8 @@ mod tests {
                 vote_state2.root_slot,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::LockoutConflict)
         );
@@ -2713,6 +3105,8 @@ mod tests {
                 vote_state2.root_slot,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             ),
             Err(VoteError::LockoutConflict)
         );
@@ -2757,6 +3151,8 @@ mod tests {
                 vote_state2.root_slot,
                 None,
                 vote_state2.current_epoch(),
+                0,
+                false,
             )
             .unwrap();
         assert_eq!(vote_state1, vote_state2,);
@@ -2792,7 +3188,14 @@ mod tests {
         let root = Some(1);
 
         assert_eq!(
-            vote_state1.process_new_vote_state(bad_votes, root, None, vote_state1.current_epoch(),),
+            vote_state1.process_new_vote_state(
+                bad_votes,
+                root,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            ),
             Err(VoteError::LockoutConflict)
         );
 
@@ -2810,7 +3213,14 @@ mod tests {
         .collect();
 
         vote_state1
-            .process_new_vote_state(good_votes.clone(), root, None, vote_state1.current_epoch())
+            .process_new_vote_state(
+                good_votes.clone(),
+                root,
+                None,
+                vote_state1.current_epoch(),
+                0,
+                false,
+            )
             .unwrap();
         assert_eq!(vote_state1.votes, good_votes);
     }
@@ -2829,7 +3239,7 @@ mod tests {
         // error with `VotesTooOldAllFiltered`
         let slot_hashes = vec![(3, Hash::new_unique()), (2, Hash::new_unique())];
         assert_eq!(
-            vote_state.process_vote(&vote, &slot_hashes, 0, Some(&feature_set),),
+            vote_state.process_vote(&vote, &slot_hashes, 0, 0, Some(&feature_set),),
             Err(VoteError::VotesTooOldAllFiltered)
         );
 
@@ -2844,7 +3254,7 @@ mod tests {
 
         let vote = Vote::new(vec![old_vote_slot, vote_slot], vote_slot_hash);
         vote_state
-            .process_vote(&vote, &slot_hashes, 0, Some(&feature_set))
+            .process_vote(&vote, &slot_hashes, 0, 0, Some(&feature_set))
             .unwrap();
         assert_eq!(
             vote_state.votes.into_iter().collect::<Vec<Lockout>>(),
@@ -2873,7 +3283,7 @@ mod tests {
                 .unwrap()
                 .1;
             vote_state
-                .process_vote(&Vote::new(vote_slots, vote_hash), slot_hashes, 0, None)
+                .process_vote(&Vote::new(vote_slots, vote_hash), slot_hashes, 0, 0, None)
                 .unwrap();
         }
 
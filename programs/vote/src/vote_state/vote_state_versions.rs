@@ -4,11 +4,19 @@ use {super::*, crate::vote_state::vote_state_0_23_5::VoteState0_23_5};
 pub enum VoteStateVersions {
     V0_23_5(Box<VoteState0_23_5>),
     Current(Box<VoteState>),
+    /// `Current` followed by the landing latency of each of its votes
+    CurrentWithVoteLatencies(Box<VoteState>, VecDeque<u8>),
 }
 
 impl VoteStateVersions {
-    pub fn new_current(vote_state: VoteState) -> Self {
-        Self::Current(Box::new(vote_state))
+    /// Uses `CurrentWithVoteLatencies` whenever `vote_state` records vote latencies
+    pub fn new_current(mut vote_state: VoteState) -> Self {
+        if vote_state.vote_latencies.is_empty() {
+            Self::Current(Box::new(vote_state))
+        } else {
+            let vote_latencies = std::mem::take(&mut vote_state.vote_latencies);
+            Self::CurrentWithVoteLatencies(Box::new(vote_state), vote_latencies)
+        }
     }
 
     pub fn convert_to_current(self) -> VoteState {
@@ -45,9 +53,16 @@ impl VoteStateVersions {
 
                     /// most recent timestamp submitted with a vote
                     last_timestamp: state.last_timestamp.clone(),
+
+                    vote_latencies: VecDeque::new(),
                 }
             }
             VoteStateVersions::Current(state) => *state,
+
+            VoteStateVersions::CurrentWithVoteLatencies(mut state, vote_latencies) => {
+                state.vote_latencies = vote_latencies;
+                *state
+            }
         }
     }
 
@@ -57,7 +72,10 @@ impl VoteStateVersions {
                 vote_state.authorized_voter == Pubkey::default()
             }
 
-            VoteStateVersions::Current(vote_state) => vote_state.authorized_voters.is_empty(),
+            VoteStateVersions::Current(vote_state)
+            | VoteStateVersions::CurrentWithVoteLatencies(vote_state, _) => {
+                vote_state.authorized_voters.is_empty()
+            }
         }
     }
 }
@@ -0,0 +1,456 @@
+use {
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        feature_set,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction, system_program,
+        transaction::{Transaction, TransactionError},
+    },
+    solana_zk_token_proof_program::{
+        VERIFY_CLOSE_ACCOUNT_COMPUTE_UNITS, VERIFY_PROOF_COMPUTE_UNITS,
+    },
+    solana_zk_token_sdk::{
+        encryption::elgamal::ElGamalKeypair,
+        zk_token_elgamal::pod,
+        zk_token_proof_instruction::*,
+        zk_token_proof_program::id,
+        zk_token_proof_state::{ProofContextState, ProofType},
+    },
+    std::mem::size_of,
+};
+
+const CONTEXT_STATE_SPACE: usize = size_of::<ProofContextState<PubkeyValidityProofContext>>();
+
+async fn process_instructions(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+/// Compute units consumed by a transaction holding only `instruction`
+async fn simulate_units_consumed(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+) -> u64 {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+    assert_eq!(simulation.result, Some(Ok(())));
+    simulation.simulation_details.unwrap().units_consumed
+}
+
+/// Creates a proof context account for a `PubkeyValidityData` proof and verifies the proof into
+/// it in the same transaction
+async fn create_and_verify_proof_context(
+    context: &mut ProgramTestContext,
+    context_state_account: &Keypair,
+    context_state_authority: &Pubkey,
+    proof_data: &PubkeyValidityData,
+) -> Result<(), TransactionError> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &context_state_account.pubkey(),
+            rent.minimum_balance(CONTEXT_STATE_SPACE),
+            CONTEXT_STATE_SPACE as u64,
+            &id(),
+        ),
+        verify_pubkey_validity(
+            Some(ContextStateInfo {
+                context_state_account: &context_state_account.pubkey(),
+                context_state_authority,
+            }),
+            proof_data,
+        ),
+    ];
+    process_instructions(context, &instructions, &[context_state_account]).await
+}
+
+#[tokio::test]
+async fn test_verify_proof_without_context() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[verify_pubkey_validity(None, &proof_data)],
+            &[]
+        )
+        .await,
+        Ok(())
+    );
+
+    // a proof for another pubkey does not verify
+    let mut proof_data = proof_data;
+    proof_data.context.pubkey = pod::ElGamalPubkey(ElGamalKeypair::new_rand().public.to_bytes());
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[verify_pubkey_validity(None, &proof_data)],
+            &[]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_verify_proof_with_context() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    let context_state_account = Keypair::new();
+    let context_state_authority = Pubkey::new_unique();
+
+    assert_eq!(
+        create_and_verify_proof_context(
+            &mut context,
+            &context_state_account,
+            &context_state_authority,
+            &proof_data,
+        )
+        .await,
+        Ok(())
+    );
+
+    let account = context
+        .banks_client
+        .get_account(context_state_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.owner, id());
+    assert_eq!(
+        account.data,
+        ProofContextState::encode(
+            &context_state_authority,
+            ProofType::PubkeyValidity,
+            proof_data.context_data(),
+        )
+    );
+    let state =
+        ProofContextState::<PubkeyValidityProofContext>::try_from_bytes(&account.data).unwrap();
+    assert_eq!(state.context_state_authority, context_state_authority);
+    assert_eq!(
+        ProofType::try_from(state.proof_type),
+        Ok(ProofType::PubkeyValidity)
+    );
+    assert_eq!(state.proof_context.pubkey, proof_data.context.pubkey);
+
+    // the context of another proof cannot be written into an initialized account
+    let other_proof_data = PubkeyValidityData::new(&ElGamalKeypair::new_rand()).unwrap();
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[verify_pubkey_validity(
+                Some(ContextStateInfo {
+                    context_state_account: &context_state_account.pubkey(),
+                    context_state_authority: &context_state_authority,
+                }),
+                &other_proof_data,
+            )],
+            &[]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::AccountAlreadyInitialized
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_verify_proof_with_invalid_context_account() {
+    let mut context = ProgramTest::default().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    let context_state_authority = Pubkey::new_unique();
+
+    // the proof context account must be owned by the proof program
+    let context_state_account = Keypair::new();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &context_state_account.pubkey(),
+            rent.minimum_balance(CONTEXT_STATE_SPACE),
+            CONTEXT_STATE_SPACE as u64,
+            &system_program::id(),
+        ),
+        verify_pubkey_validity(
+            Some(ContextStateInfo {
+                context_state_account: &context_state_account.pubkey(),
+                context_state_authority: &context_state_authority,
+            }),
+            &proof_data,
+        ),
+    ];
+    assert_eq!(
+        process_instructions(&mut context, &instructions, &[&context_state_account]).await,
+        Err(TransactionError::InstructionError(
+            1,
+            InstructionError::InvalidAccountOwner
+        ))
+    );
+
+    // the proof context account must be sized for the context of the proof
+    let context_state_account = Keypair::new();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &context_state_account.pubkey(),
+            rent.minimum_balance(CONTEXT_STATE_SPACE + 1),
+            (CONTEXT_STATE_SPACE + 1) as u64,
+            &id(),
+        ),
+        verify_pubkey_validity(
+            Some(ContextStateInfo {
+                context_state_account: &context_state_account.pubkey(),
+                context_state_authority: &context_state_authority,
+            }),
+            &proof_data,
+        ),
+    ];
+    assert_eq!(
+        process_instructions(&mut context, &instructions, &[&context_state_account]).await,
+        Err(TransactionError::InstructionError(
+            1,
+            InstructionError::InvalidAccountData
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_close_context_state() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    let context_state_account = Keypair::new();
+    let context_state_authority = Keypair::new();
+    create_and_verify_proof_context(
+        &mut context,
+        &context_state_account,
+        &context_state_authority.pubkey(),
+        &proof_data,
+    )
+    .await
+    .unwrap();
+    let context_state_lamports = context
+        .banks_client
+        .get_balance(context_state_account.pubkey())
+        .await
+        .unwrap();
+
+    let destination_account = Pubkey::new_unique();
+    let close_instruction = |context_state_authority: &Pubkey| {
+        close_context_state(
+            ContextStateInfo {
+                context_state_account: &context_state_account.pubkey(),
+                context_state_authority,
+            },
+            &destination_account,
+        )
+    };
+
+    // only the context state authority can close the account
+    let incorrect_authority = Keypair::new();
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[close_instruction(&incorrect_authority.pubkey())],
+            &[&incorrect_authority]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountOwner
+        ))
+    );
+
+    // the context state authority must sign
+    let mut instruction = close_instruction(&context_state_authority.pubkey());
+    instruction.accounts[2].is_signer = false;
+    assert_eq!(
+        process_instructions(&mut context, &[instruction], &[]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    // the lamports cannot be reclaimed into the account being closed
+    let instruction = close_context_state(
+        ContextStateInfo {
+            context_state_account: &context_state_account.pubkey(),
+            context_state_authority: &context_state_authority.pubkey(),
+        },
+        &context_state_account.pubkey(),
+    );
+    assert_eq!(
+        process_instructions(&mut context, &[instruction], &[&context_state_authority]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[close_instruction(&context_state_authority.pubkey())],
+            &[&context_state_authority]
+        )
+        .await,
+        Ok(())
+    );
+    assert!(context
+        .banks_client
+        .get_account(context_state_account.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(destination_account)
+            .await
+            .unwrap(),
+        context_state_lamports
+    );
+}
+
+#[tokio::test]
+async fn test_close_context_state_not_owned_by_proof_program() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    // a valid proof context state that is owned by another program
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    let context_state_account = Pubkey::new_unique();
+    let context_state_authority = Keypair::new();
+    let data = ProofContextState::encode(
+        &context_state_authority.pubkey(),
+        ProofType::PubkeyValidity,
+        proof_data.context_data(),
+    );
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &context_state_account,
+        &Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: Pubkey::new_unique(),
+            ..Account::default()
+        }
+        .into(),
+    );
+
+    let instruction = close_context_state(
+        ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority.pubkey(),
+        },
+        &Pubkey::new_unique(),
+    );
+    assert_eq!(
+        process_instructions(&mut context, &[instruction], &[&context_state_authority]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountOwner
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_proof_instructions_without_context_state_feature() {
+    let mut program_test = ProgramTest::default();
+    program_test.deactivate_feature(feature_set::zk_token_proof_context_state::id());
+    let mut context = program_test.start_with_context().await;
+
+    // The original proofs verify at a flat cost
+    let keypair = ElGamalKeypair::new_rand();
+    let ciphertext = keypair.public.encrypt(0_u64);
+    let proof_data = CloseAccountData::new(&keypair, &ciphertext).unwrap();
+    assert_eq!(
+        simulate_units_consumed(&mut context, verify_close_account(&proof_data)).await,
+        VERIFY_PROOF_COMPUTE_UNITS
+    );
+
+    // The instructions added along with proof context accounts are rejected
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    assert_eq!(
+        process_instructions(
+            &mut context,
+            &[verify_pubkey_validity(None, &proof_data)],
+            &[]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+    let context_state_authority = Keypair::new();
+    let instruction = close_context_state(
+        ContextStateInfo {
+            context_state_account: &Pubkey::new_unique(),
+            context_state_authority: &context_state_authority.pubkey(),
+        },
+        &Pubkey::new_unique(),
+    );
+    assert_eq!(
+        process_instructions(&mut context, &[instruction], &[&context_state_authority]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_proof_compute_units_with_context_state_feature() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let keypair = ElGamalKeypair::new_rand();
+    let ciphertext = keypair.public.encrypt(0_u64);
+    let proof_data = CloseAccountData::new(&keypair, &ciphertext).unwrap();
+    assert_eq!(
+        simulate_units_consumed(&mut context, verify_close_account(&proof_data)).await,
+        VERIFY_CLOSE_ACCOUNT_COMPUTE_UNITS
+    );
+}
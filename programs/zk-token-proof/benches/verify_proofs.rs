@@ -0,0 +1,272 @@
+#![feature(test)]
+
+extern crate test;
+
+use {
+    bytemuck::Pod,
+    solana_program_runtime::invoke_context::mock_process_instruction,
+    solana_sdk::{
+        account::AccountSharedData,
+        instruction::AccountMeta,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_program,
+    },
+    solana_zk_token_proof_program::process_instruction,
+    solana_zk_token_sdk::{
+        encryption::{
+            elgamal::ElGamalKeypair,
+            pedersen::{Pedersen, PedersenOpening},
+        },
+        instruction::transfer_with_fee::FeeParameters,
+        zk_token_proof_instruction::*,
+        zk_token_proof_program::id,
+        zk_token_proof_state::{ProofContextState, ProofType},
+    },
+    std::mem::size_of,
+    test::Bencher,
+};
+
+fn bench_instruction(
+    bencher: &mut Bencher,
+    instruction_data: &[u8],
+    transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
+    instruction_accounts: Vec<AccountMeta>,
+) {
+    bencher.iter(|| {
+        mock_process_instruction(
+            &id(),
+            Vec::new(),
+            instruction_data,
+            transaction_accounts.clone(),
+            instruction_accounts.clone(),
+            None,
+            None,
+            Ok(()),
+            process_instruction,
+        )
+    });
+}
+
+fn bench_verify_proof<T: Pod>(
+    bencher: &mut Bencher,
+    proof_instruction: ProofInstruction,
+    proof_data: &T,
+) {
+    bench_instruction(
+        bencher,
+        &proof_instruction.encode(proof_data).data,
+        Vec::new(),
+        Vec::new(),
+    );
+}
+
+/// Instruction data of a `VerifyBatchedRangeProofU64` and a `VerifyBatchedRangeProofU128`
+/// instruction over `amounts`, which use half of `bit_lengths` and `bit_lengths` respectively
+fn batched_range_proof_data(amounts: Vec<u64>, bit_lengths: Vec<usize>) -> (Vec<u8>, Vec<u8>) {
+    let (commitments, openings): (Vec<_>, Vec<_>) =
+        amounts.iter().map(|amount| Pedersen::new(*amount)).unzip();
+    let commitments = commitments.iter().collect::<Vec<_>>();
+    let openings = openings.iter().collect::<Vec<_>>();
+
+    let u64_data = BatchedRangeProofU64Data::new(
+        commitments.clone(),
+        amounts.clone(),
+        bit_lengths
+            .iter()
+            .map(|bit_length| bit_length / 2)
+            .collect(),
+        openings.clone(),
+    )
+    .unwrap();
+    let u128_data =
+        BatchedRangeProofU128Data::new(commitments, amounts, bit_lengths, openings).unwrap();
+    (
+        ProofInstruction::VerifyBatchedRangeProofU64
+            .encode(&u64_data)
+            .data,
+        ProofInstruction::VerifyBatchedRangeProofU128
+            .encode(&u128_data)
+            .data,
+    )
+}
+
+// Proof verification is priced relative to this benchmark, since the cost model prices an ed25519
+// signature verification at `SIGNATURE_COST` regardless of the machine the benchmarks run on
+#[bench]
+fn bench_ed25519_signature_verification(bencher: &mut Bencher) {
+    let keypair = Keypair::new();
+    let message = [0; 128];
+    let signature = keypair.sign_message(&message);
+    bencher.iter(|| assert!(signature.verify(keypair.pubkey().as_ref(), &message)));
+}
+
+#[bench]
+fn bench_verify_close_account(bencher: &mut Bencher) {
+    let keypair = ElGamalKeypair::new_rand();
+    let ciphertext = keypair.public.encrypt(0_u64);
+    let proof_data = CloseAccountData::new(&keypair, &ciphertext).unwrap();
+    bench_verify_proof(bencher, ProofInstruction::VerifyCloseAccount, &proof_data);
+}
+
+#[bench]
+fn bench_verify_withdraw(bencher: &mut Bencher) {
+    let keypair = ElGamalKeypair::new_rand();
+    let current_balance = 77_u64;
+    let current_ciphertext = keypair.public.encrypt(current_balance);
+    let proof_data = WithdrawData::new(55, &keypair, current_balance, &current_ciphertext).unwrap();
+    bench_verify_proof(bencher, ProofInstruction::VerifyWithdraw, &proof_data);
+}
+
+#[bench]
+fn bench_verify_withdraw_withheld_tokens(bencher: &mut Bencher) {
+    let keypair = ElGamalKeypair::new_rand();
+    let destination_keypair = ElGamalKeypair::new_rand();
+    let amount = 55_u64;
+    let ciphertext = keypair.public.encrypt(amount);
+    let proof_data =
+        WithdrawWithheldTokensData::new(&keypair, &destination_keypair.public, &ciphertext, amount)
+            .unwrap();
+    bench_verify_proof(
+        bencher,
+        ProofInstruction::VerifyWithdrawWithheldTokens,
+        &proof_data,
+    );
+}
+
+#[bench]
+fn bench_verify_transfer(bencher: &mut Bencher) {
+    let source_keypair = ElGamalKeypair::new_rand();
+    let destination_pubkey = ElGamalKeypair::new_rand().public;
+    let auditor_pubkey = ElGamalKeypair::new_rand().public;
+    let spendable_balance = 77_u64;
+    let spendable_ciphertext = source_keypair.public.encrypt(spendable_balance);
+    let proof_data = TransferData::new(
+        55,
+        (spendable_balance, &spendable_ciphertext),
+        &source_keypair,
+        (&destination_pubkey, &auditor_pubkey),
+    )
+    .unwrap();
+    bench_verify_proof(bencher, ProofInstruction::VerifyTransfer, &proof_data);
+}
+
+#[bench]
+fn bench_verify_transfer_with_fee(bencher: &mut Bencher) {
+    let source_keypair = ElGamalKeypair::new_rand();
+    let destination_pubkey = ElGamalKeypair::new_rand().public;
+    let auditor_pubkey = ElGamalKeypair::new_rand().public;
+    let withdraw_withheld_authority_pubkey = ElGamalKeypair::new_rand().public;
+    let spendable_balance = 120_u64;
+    let spendable_ciphertext = source_keypair.public.encrypt(spendable_balance);
+    let fee_parameters = FeeParameters {
+        fee_rate_basis_points: 400,
+        maximum_fee: 3,
+    };
+    let proof_data = TransferWithFeeData::new(
+        100,
+        (spendable_balance, &spendable_ciphertext),
+        &source_keypair,
+        (&destination_pubkey, &auditor_pubkey),
+        fee_parameters,
+        &withdraw_withheld_authority_pubkey,
+    )
+    .unwrap();
+    bench_verify_proof(
+        bencher,
+        ProofInstruction::VerifyTransferWithFee,
+        &proof_data,
+    );
+}
+
+#[bench]
+fn bench_verify_pubkey_validity(bencher: &mut Bencher) {
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    bench_verify_proof(bencher, ProofInstruction::VerifyPubkeyValidity, &proof_data);
+}
+
+#[bench]
+fn bench_verify_ciphertext_ciphertext_equality(bencher: &mut Bencher) {
+    let source_keypair = ElGamalKeypair::new_rand();
+    let destination_keypair = ElGamalKeypair::new_rand();
+    let amount = 55_u64;
+    let source_ciphertext = source_keypair.public.encrypt(amount);
+    let destination_opening = PedersenOpening::new_rand();
+    let destination_ciphertext = destination_keypair
+        .public
+        .encrypt_with(amount, &destination_opening);
+    let proof_data = CiphertextCiphertextEqualityProofData::new(
+        &source_keypair,
+        &destination_keypair.public,
+        &source_ciphertext,
+        &destination_ciphertext,
+        &destination_opening,
+        amount,
+    )
+    .unwrap();
+    bench_verify_proof(
+        bencher,
+        ProofInstruction::VerifyCiphertextCiphertextEquality,
+        &proof_data,
+    );
+}
+
+#[bench]
+fn bench_verify_batched_range_proof_u64(bencher: &mut Bencher) {
+    let (instruction_data, _) =
+        batched_range_proof_data(vec![255, 77, 99, 99, 11, 33, 99, 99], vec![16; 8]);
+    bench_instruction(bencher, &instruction_data, Vec::new(), Vec::new());
+}
+
+#[bench]
+fn bench_verify_batched_range_proof_u128(bencher: &mut Bencher) {
+    let (_, instruction_data) =
+        batched_range_proof_data(vec![255, 77, 99, 99, 11, 33, 99, 99], vec![16; 8]);
+    bench_instruction(bencher, &instruction_data, Vec::new(), Vec::new());
+}
+
+#[bench]
+fn bench_close_context_state(bencher: &mut Bencher) {
+    let context_state_account = Pubkey::new_unique();
+    let destination_account = Pubkey::new_unique();
+    let context_state_authority = Pubkey::new_unique();
+
+    let keypair = ElGamalKeypair::new_rand();
+    let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+    let data = ProofContextState::encode(
+        &context_state_authority,
+        ProofType::PubkeyValidity,
+        proof_data.context_data(),
+    );
+    assert_eq!(
+        data.len(),
+        size_of::<ProofContextState<PubkeyValidityProofContext>>()
+    );
+    let mut proof_context_account = AccountSharedData::new(1_000_000, data.len(), &id());
+    proof_context_account.set_data(data);
+
+    let instruction = close_context_state(
+        ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        },
+        &destination_account,
+    );
+    bench_instruction(
+        bencher,
+        &instruction.data,
+        vec![
+            (context_state_account, proof_context_account),
+            (
+                destination_account,
+                AccountSharedData::new(0, 0, &system_program::id()),
+            ),
+            (
+                context_state_authority,
+                AccountSharedData::new(0, 0, &system_program::id()),
+            ),
+        ],
+        instruction.accounts,
+    );
+}
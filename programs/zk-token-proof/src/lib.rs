@@ -3,11 +3,36 @@
 use {
     bytemuck::Pod,
     solana_program_runtime::{ic_msg, invoke_context::InvokeContext},
-    solana_sdk::instruction::{InstructionError, TRANSACTION_LEVEL_STACK_HEIGHT},
-    solana_zk_token_sdk::zk_token_proof_instruction::*,
+    solana_sdk::{
+        feature_set,
+        instruction::{InstructionError, TRANSACTION_LEVEL_STACK_HEIGHT},
+        system_program,
+    },
+    solana_zk_token_sdk::{
+        zk_token_proof_instruction::*,
+        zk_token_proof_program::id,
+        zk_token_proof_state::{ProofContextState, ProofContextStateMeta, ProofType},
+    },
     std::result::Result,
 };
 
+// Charged for every instruction until `zk_token_proof_context_state` is active
+pub const VERIFY_PROOF_COMPUTE_UNITS: u64 = 100_000;
+
+// Measured with `benches/verify_proofs.rs`, scaled so that the ed25519 signature verification
+// benchmark costs the 720 units that the cost model charges per signature. Each value is the
+// highest of several runs, rounded up.
+pub const CLOSE_CONTEXT_STATE_COMPUTE_UNITS: u64 = 100;
+pub const VERIFY_CLOSE_ACCOUNT_COMPUTE_UNITS: u64 = 2_500;
+pub const VERIFY_WITHDRAW_COMPUTE_UNITS: u64 = 43_000;
+pub const VERIFY_WITHDRAW_WITHHELD_TOKENS_COMPUTE_UNITS: u64 = 3_200;
+pub const VERIFY_TRANSFER_COMPUTE_UNITS: u64 = 87_000;
+pub const VERIFY_TRANSFER_WITH_FEE_COMPUTE_UNITS: u64 = 151_000;
+pub const VERIFY_PUBKEY_VALIDITY_COMPUTE_UNITS: u64 = 1_100;
+pub const VERIFY_CIPHERTEXT_CIPHERTEXT_EQUALITY_COMPUTE_UNITS: u64 = 3_000;
+pub const VERIFY_BATCHED_RANGE_PROOF_U64_COMPUTE_UNITS: u64 = 40_000;
+pub const VERIFY_BATCHED_RANGE_PROOF_U128_COMPUTE_UNITS: u64 = 71_000;
+
 fn verify<T: Pod + Verifiable>(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
@@ -25,28 +50,180 @@ fn verify<T: Pod + Verifiable>(invoke_context: &mut InvokeContext) -> Result<(),
     })
 }
 
-pub fn process_instruction(
-    _first_instruction_account: usize,
-    invoke_context: &mut InvokeContext,
-) -> Result<(), InstructionError> {
-    if invoke_context.get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
-        // Not supported as an inner instruction
-        return Err(InstructionError::UnsupportedProgramId);
+/// Verifies a proof and, if a proof context account is provided with the instruction, writes the
+/// context of the verified proof into it
+fn process_verify_proof<T, U>(invoke_context: &mut InvokeContext) -> Result<(), InstructionError>
+where
+    T: Pod + Verifiable + ZkProofData<U>,
+    U: Pod,
+{
+    verify::<T>(invoke_context)?;
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    if instruction_context.get_number_of_instruction_accounts() == 0 {
+        return Ok(());
     }
 
-    // Consume compute units since proof verification is an expensive operation
-    {
-        let compute_meter = invoke_context.get_compute_meter();
-        // TODO: Tune the number of units consumed.  The current value is just a rough estimate
-        compute_meter.borrow_mut().consume(100_000)?;
+    // `verify` already checked that the instruction data decodes as `T`
+    let proof_data = ProofInstruction::decode_data::<T>(instruction_context.get_instruction_data())
+        .ok_or(InstructionError::InvalidInstructionData)?;
+
+    let context_state_authority = *instruction_context
+        .try_borrow_instruction_account(transaction_context, 1)?
+        .get_key();
+
+    let mut proof_context_account =
+        instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+
+    if *proof_context_account.get_owner() != id() {
+        return Err(InstructionError::InvalidAccountOwner);
+    }
+
+    let proof_context_state_meta =
+        ProofContextStateMeta::try_from_bytes(proof_context_account.get_data())?;
+
+    if proof_context_state_meta.proof_type != ProofType::Uninitialized.into() {
+        return Err(InstructionError::AccountAlreadyInitialized);
     }
 
+    let context_state_data = ProofContextState::<U>::encode(
+        &context_state_authority,
+        T::PROOF_TYPE,
+        proof_data.context_data(),
+    );
+
+    if proof_context_account.get_data().len() != context_state_data.len() {
+        return Err(InstructionError::InvalidAccountData);
+    }
+
+    proof_context_account.set_data(&context_state_data)
+}
+
+fn process_close_proof_context(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    let owner_pubkey = {
+        let owner_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+
+        if !owner_account.is_signer() {
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        *owner_account.get_key()
+    };
+
+    let proof_context_account_pubkey = *instruction_context
+        .try_borrow_instruction_account(transaction_context, 0)?
+        .get_key();
+    let destination_account_pubkey = *instruction_context
+        .try_borrow_instruction_account(transaction_context, 1)?
+        .get_key();
+    if proof_context_account_pubkey == destination_account_pubkey {
+        return Err(InstructionError::InvalidInstructionData);
+    }
+
+    let mut proof_context_account =
+        instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+
+    if *proof_context_account.get_owner() != id() {
+        return Err(InstructionError::InvalidAccountOwner);
+    }
+
+    let proof_context_state_meta =
+        ProofContextStateMeta::try_from_bytes(proof_context_account.get_data())?;
+
+    if proof_context_state_meta.proof_type == ProofType::Uninitialized.into() {
+        return Err(InstructionError::UninitializedAccount);
+    }
+
+    if owner_pubkey != proof_context_state_meta.context_state_authority {
+        return Err(InstructionError::InvalidAccountOwner);
+    }
+
+    let mut destination_account =
+        instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+    destination_account.checked_add_lamports(proof_context_account.get_lamports())?;
+
+    proof_context_account.set_lamports(0)?;
+    proof_context_account.set_data_length(0)?;
+    proof_context_account.set_owner(system_program::id().as_ref())?;
+
+    Ok(())
+}
+
+fn compute_units(instruction: ProofInstruction) -> u64 {
+    match instruction {
+        ProofInstruction::VerifyCloseAccount => VERIFY_CLOSE_ACCOUNT_COMPUTE_UNITS,
+        ProofInstruction::VerifyWithdraw => VERIFY_WITHDRAW_COMPUTE_UNITS,
+        ProofInstruction::VerifyWithdrawWithheldTokens => {
+            VERIFY_WITHDRAW_WITHHELD_TOKENS_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyTransfer => VERIFY_TRANSFER_COMPUTE_UNITS,
+        ProofInstruction::VerifyTransferWithFee => VERIFY_TRANSFER_WITH_FEE_COMPUTE_UNITS,
+        ProofInstruction::CloseContextState => CLOSE_CONTEXT_STATE_COMPUTE_UNITS,
+        ProofInstruction::VerifyPubkeyValidity => VERIFY_PUBKEY_VALIDITY_COMPUTE_UNITS,
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            VERIFY_CIPHERTEXT_CIPHERTEXT_EQUALITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedRangeProofU64 => {
+            VERIFY_BATCHED_RANGE_PROOF_U64_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedRangeProofU128 => {
+            VERIFY_BATCHED_RANGE_PROOF_U128_COMPUTE_UNITS
+        }
+    }
+}
+
+pub fn process_instruction(
+    _first_instruction_account: usize,
+    invoke_context: &mut InvokeContext,
+) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
     let instruction_data = instruction_context.get_instruction_data();
     let instruction = ProofInstruction::decode_type(instruction_data);
 
-    match instruction.ok_or(InstructionError::InvalidInstructionData)? {
+    // Consume compute units since proof verification is an expensive operation
+    let instruction = if invoke_context
+        .feature_set
+        .is_active(&feature_set::zk_token_proof_context_state::id())
+    {
+        if invoke_context.get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT
+            && instruction != Some(ProofInstruction::CloseContextState)
+        {
+            // Proof verification is not supported as an inner instruction
+            return Err(InstructionError::UnsupportedProgramId);
+        }
+        let instruction = instruction.ok_or(InstructionError::InvalidInstructionData)?;
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .consume(compute_units(instruction))?;
+        instruction
+    } else {
+        if invoke_context.get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+            // Not supported as an inner instruction
+            return Err(InstructionError::UnsupportedProgramId);
+        }
+        invoke_context
+            .get_compute_meter()
+            .borrow_mut()
+            .consume(VERIFY_PROOF_COMPUTE_UNITS)?;
+        match instruction {
+            Some(
+                instruction @ (ProofInstruction::VerifyCloseAccount
+                | ProofInstruction::VerifyWithdraw
+                | ProofInstruction::VerifyWithdrawWithheldTokens
+                | ProofInstruction::VerifyTransfer
+                | ProofInstruction::VerifyTransferWithFee),
+            ) => instruction,
+            _ => return Err(InstructionError::InvalidInstructionData),
+        }
+    };
+
+    match instruction {
         ProofInstruction::VerifyCloseAccount => {
             ic_msg!(invoke_context, "VerifyCloseAccount");
             verify::<CloseAccountData>(invoke_context)
@@ -67,5 +244,32 @@ pub fn process_instruction(
             ic_msg!(invoke_context, "VerifyTransferWithFee");
             verify::<TransferWithFeeData>(invoke_context)
         }
+        ProofInstruction::CloseContextState => {
+            ic_msg!(invoke_context, "CloseContextState");
+            process_close_proof_context(invoke_context)
+        }
+        ProofInstruction::VerifyPubkeyValidity => {
+            ic_msg!(invoke_context, "VerifyPubkeyValidity");
+            process_verify_proof::<PubkeyValidityData, PubkeyValidityProofContext>(invoke_context)
+        }
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            ic_msg!(invoke_context, "VerifyCiphertextCiphertextEquality");
+            process_verify_proof::<
+                CiphertextCiphertextEqualityProofData,
+                CiphertextCiphertextEqualityProofContext,
+            >(invoke_context)
+        }
+        ProofInstruction::VerifyBatchedRangeProofU64 => {
+            ic_msg!(invoke_context, "VerifyBatchedRangeProofU64");
+            process_verify_proof::<BatchedRangeProofU64Data, BatchedRangeProofContext>(
+                invoke_context,
+            )
+        }
+        ProofInstruction::VerifyBatchedRangeProofU128 => {
+            ic_msg!(invoke_context, "VerifyBatchedRangeProofU128");
+            process_verify_proof::<BatchedRangeProofU128Data, BatchedRangeProofContext>(
+                invoke_context,
+            )
+        }
     }
 }
@@ -3,8 +3,11 @@ use {
     serde::{Deserialize, Serialize},
     serde_json::Result,
     solana_bpf_loader_program::{
-        create_vm, serialization::serialize_parameters, syscalls::register_syscalls, BpfError,
-        ThisInstructionMeter,
+        coverage::{instruction_hits, write_lcov},
+        create_vm,
+        serialization::serialize_parameters,
+        syscalls::register_syscalls,
+        BpfError, ThisInstructionMeter,
     },
     solana_program_runtime::invoke_context::{prepare_mock_invoke_context, InvokeContext},
     solana_rbpf::{
@@ -152,6 +155,18 @@ native machine code before execting it in the virtual machine.",
                 .short('p')
                 .long("profile"),
         )
+        .arg(
+            Arg::new("coverage")
+                .help(
+                    "Output lcov coverage to FILE using tracing instrumentation. PROGRAM has to be \
+                     an unstripped ELF shared-object file, whose DWARF line info maps the executed \
+                     instructions to source lines",
+                )
+                .short('c')
+                .long("coverage")
+                .takes_value(true)
+                .value_name("FILE"),
+        )
         .arg(
             Arg::new("verify")
                 .help("Run the verifier before execution or disassembly")
@@ -170,7 +185,9 @@ native machine code before execting it in the virtual machine.",
         .get_matches();
 
     let config = Config {
-        enable_instruction_tracing: matches.is_present("trace") || matches.is_present("profile"),
+        enable_instruction_tracing: matches.is_present("trace")
+            || matches.is_present("profile")
+            || matches.is_present("coverage"),
         enable_symbol_and_section_labels: true,
         ..Config::default()
     };
@@ -250,7 +267,12 @@ native machine code before execting it in the virtual machine.",
     let mut contents = Vec::new();
     file.read_to_end(&mut contents).unwrap();
     let syscall_registry = register_syscalls(&mut invoke_context, true).unwrap();
-    let mut executable = if magic == [0x7f, 0x45, 0x4c, 0x46] {
+    let is_elf = magic == [0x7f, 0x45, 0x4c, 0x46];
+    if matches.is_present("coverage") && !is_elf {
+        eprintln!("Coverage requires an ELF shared-object file");
+        std::process::exit(1);
+    }
+    let mut executable = if is_elf {
         Executable::<BpfError, ThisInstructionMeter>::from_elf(
             &contents,
             None,
@@ -342,6 +364,12 @@ native machine code before execting it in the virtual machine.",
             .visualize_graphically(&mut file, Some(&dynamic_analysis))
             .unwrap();
     }
+    if let Some(coverage_file) = matches.value_of("coverage") {
+        eprintln!("Coverage is saved in {}", coverage_file);
+        let hits = instruction_hits(executable.get_text_bytes().0, vm.get_tracer());
+        let mut file = File::create(coverage_file).unwrap();
+        write_lcov(&mut file, &contents, &hits).unwrap();
+    }
 }
 
 #[derive(Serialize)]
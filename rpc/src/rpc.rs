@@ -21,7 +21,7 @@ use {
         rpc_deprecated_config::*,
         rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
         rpc_request::{
-            TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
+            TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_GET_ACCOUNT_HISTORY_LIMIT,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
             MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
             MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
@@ -35,6 +35,7 @@ use {
     solana_ledger::{
         blockstore::{Blockstore, SignatureInfosForAddress},
         blockstore_db::BlockstoreError,
+        blockstore_meta::BlockCostInfo,
         get_tmp_ledger_path,
         leader_schedule_cache::LeaderScheduleCache,
     },
@@ -46,7 +47,10 @@ use {
         bank::{Bank, TransactionSimulationResult},
         bank_forks::BankForks,
         commitment::{BlockCommitmentArray, BlockCommitmentCache, CommitmentSlots},
-        inline_spl_token::{SPL_TOKEN_ACCOUNT_MINT_OFFSET, SPL_TOKEN_ACCOUNT_OWNER_OFFSET},
+        inline_spl_token::{
+            SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET, SPL_TOKEN_ACCOUNT_MINT_OFFSET,
+            SPL_TOKEN_ACCOUNT_OWNER_OFFSET,
+        },
         inline_spl_token_2022::{self, ACCOUNTTYPE_ACCOUNT},
         non_circulating_supply::calculate_non_circulating_supply,
         snapshot_config::SnapshotConfig,
@@ -148,6 +152,7 @@ fn is_finalized(
 pub struct JsonRpcConfig {
     pub enable_rpc_transaction_history: bool,
     pub enable_extended_tx_metadata_storage: bool,
+    pub enable_rpc_account_history: bool,
     pub faucet_addr: Option<SocketAddr>,
     pub health_check_slot_distance: u64,
     pub rpc_bigtable_config: Option<RpcBigtableConfig>,
@@ -410,6 +415,122 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, response))
     }
 
+    pub fn get_account_info_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+        config: RpcAccountInfoConfig,
+    ) -> Result<RpcResponse<Option<UiAccount>>> {
+        let encoding = config.encoding.unwrap_or(UiAccountEncoding::Binary);
+        check_slice_and_encoding(&encoding, config.data_slice.is_some())?;
+
+        let response = self
+            .get_historical_account(pubkey, slot)?
+            .map(|account| encode_account(&account, pubkey, encoding, config.data_slice))
+            .transpose()?;
+        Ok(RpcResponse {
+            context: RpcResponseContext::new(slot),
+            value: response,
+        })
+    }
+
+    /// Returns the state of `pubkey` as of `slot`: the latest version recorded at or before
+    /// `slot`, `None` if the account did not exist at `slot`, or an
+    /// `AccountStateNotAvailable` error if its state at `slot` predates the recorded history
+    fn get_historical_account(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> Result<Option<AccountSharedData>> {
+        self.check_account_history_slot(slot)?;
+        let mut history = self
+            .blockstore
+            .get_account_history(*pubkey, Some(slot.saturating_add(1)), None, 1)
+            .map_err(|err| Error::invalid_params(format!("{}", err)))?;
+        if let Some((_, meta)) = history.pop() {
+            return Ok(Some(AccountSharedData::from(meta.account))
+                .filter(|account| account.lamports() > 0));
+        }
+
+        // Nothing was recorded up to `slot`, so the account was last written either after
+        // `slot` or before the recorded history begins
+        let oldest_after = self
+            .blockstore
+            .get_oldest_account_history_after(*pubkey, slot)
+            .map_err(|err| Error::invalid_params(format!("{}", err)))?;
+        match oldest_after {
+            // Created after `slot`
+            Some((_, meta)) if meta.created => Ok(None),
+            // Written before the recorded history begins, and again after `slot`
+            Some(_) => Err(RpcCustomError::AccountStateNotAvailable { slot }.into()),
+            None => {
+                let bank = self.bank(Some(CommitmentConfig::finalized()));
+                match bank.get_account_modified_slot(pubkey) {
+                    Some((account, modified_slot)) if modified_slot <= slot => {
+                        Ok(Some(account).filter(|account| account.lamports() > 0))
+                    }
+                    // Written after `slot`, but not recorded yet
+                    Some(_) => Err(RpcCustomError::AccountStateNotAvailable { slot }.into()),
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
+    fn check_account_history_slot(&self, slot: Slot) -> Result<()> {
+        if !self.config.enable_rpc_account_history {
+            return Err(RpcCustomError::AccountHistoryNotAvailable.into());
+        }
+        match self
+            .blockstore
+            .get_account_history_slot_range()
+            .map_err(|err| Error::invalid_params(format!("{}", err)))?
+        {
+            Some((lowest_slot, highest_slot)) if (lowest_slot..=highest_slot).contains(&slot) => {
+                Ok(())
+            }
+            _ => Err(RpcCustomError::AccountStateNotAvailable { slot }.into()),
+        }
+    }
+
+    pub fn get_account_history(
+        &self,
+        pubkey: &Pubkey,
+        config: Option<RpcAccountHistoryConfig>,
+    ) -> Result<Vec<RpcAccountHistoryEntry>> {
+        if !self.config.enable_rpc_account_history {
+            return Err(RpcCustomError::AccountHistoryNotAvailable.into());
+        }
+        let RpcAccountHistoryConfig {
+            encoding,
+            data_slice,
+            before,
+            until,
+            limit,
+        } = config.unwrap_or_default();
+        let limit = limit.unwrap_or(MAX_GET_ACCOUNT_HISTORY_LIMIT);
+        if limit == 0 || limit > MAX_GET_ACCOUNT_HISTORY_LIMIT {
+            return Err(Error::invalid_params(format!(
+                "Invalid limit; max {}",
+                MAX_GET_ACCOUNT_HISTORY_LIMIT
+            )));
+        }
+        let encoding = encoding.unwrap_or(UiAccountEncoding::Base64);
+        check_slice_and_encoding(&encoding, data_slice.is_some())?;
+
+        self.blockstore
+            .get_account_history(*pubkey, before, until, limit)
+            .map_err(|err| Error::invalid_params(format!("{}", err)))?
+            .into_iter()
+            .map(|(slot, meta)| {
+                Ok(RpcAccountHistoryEntry {
+                    slot,
+                    account: encode_account(&meta.account, pubkey, encoding, data_slice)?,
+                })
+            })
+            .collect()
+    }
+
     pub fn get_multiple_accounts(
         &self,
         pubkeys: Vec<Pubkey>,
@@ -469,6 +590,12 @@ impl JsonRpcRequestProcessor {
                 self.get_filtered_spl_token_accounts_by_owner(&bank, program_id, &owner, filters)?
             } else if let Some(mint) = get_spl_token_mint_filter(program_id, &filters) {
                 self.get_filtered_spl_token_accounts_by_mint(&bank, program_id, &mint, filters)?
+            } else if let Some((offset, key)) =
+                get_program_data_key_filter(program_id, &filters, &self.config.account_indexes)
+            {
+                self.get_filtered_program_accounts_by_data_key(
+                    &bank, program_id, offset, &key, filters,
+                )?
             } else {
                 self.get_filtered_program_accounts(&bank, program_id, filters)?
             }
@@ -957,6 +1084,7 @@ impl JsonRpcRequestProcessor {
                     epoch_credits,
                     epoch_vote_account: epoch_vote_accounts.contains_key(vote_pubkey),
                     last_vote,
+                    vote_latencies: vote_state.vote_latencies.iter().copied().collect(),
                 })
             })
             .partition(|vote_account_info| {
@@ -1327,6 +1455,44 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    pub fn get_block_cost_info(&self, slot: Slot) -> Result<Option<RpcBlockCostInfo>> {
+        let result = self.blockstore.get_block_cost_info(slot);
+        self.check_slot_cleaned_up(&result, slot)?;
+        Ok(result.ok().flatten().map(|block_cost_info| {
+            let BlockCostInfo {
+                block_cost,
+                vote_cost,
+                transaction_count,
+                account_data_size,
+                costliest_accounts,
+                rejected_transaction_counts: rejected,
+            } = block_cost_info;
+            RpcBlockCostInfo {
+                slot,
+                block_cost,
+                vote_cost,
+                transaction_count,
+                account_data_size,
+                costliest_accounts: costliest_accounts
+                    .into_iter()
+                    .map(|(pubkey, cost)| RpcAccountCost {
+                        pubkey: pubkey.to_string(),
+                        cost,
+                    })
+                    .collect(),
+                rejected_transaction_counts: RpcRejectedTransactionCounts {
+                    would_exceed_block_max_limit: rejected.would_exceed_block_max_limit,
+                    would_exceed_vote_max_limit: rejected.would_exceed_vote_max_limit,
+                    would_exceed_account_max_limit: rejected.would_exceed_account_max_limit,
+                    would_exceed_account_data_block_limit: rejected
+                        .would_exceed_account_data_block_limit,
+                    would_exceed_account_data_total_limit: rejected
+                        .would_exceed_account_data_total_limit,
+                },
+            }
+        }))
+    }
+
     pub fn get_signature_confirmation_status(
         &self,
         signature: Signature,
@@ -1943,7 +2109,7 @@ impl JsonRpcRequestProcessor {
             }),
             // Filter on Delegate address
             RpcFilterType::Memcmp(Memcmp {
-                offset: 76,
+                offset: SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET,
                 bytes: MemcmpEncodedBytes::Bytes(delegate.to_bytes().into()),
                 encoding: None,
             }),
@@ -1954,7 +2120,30 @@ impl JsonRpcRequestProcessor {
         } else {
             // Filter on Token Account state
             filters.push(RpcFilterType::TokenAccountState);
-            self.get_filtered_program_accounts(&bank, &token_program_id, filters)?
+            if self
+                .config
+                .account_indexes
+                .contains(&AccountIndex::ProgramDataKey {
+                    program_id: token_program_id,
+                    offset: SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET,
+                })
+            {
+                if !self.config.account_indexes.include_key(delegate) {
+                    return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
+                        index_key: delegate.to_string(),
+                    }
+                    .into());
+                }
+                self.get_filtered_program_accounts_by_data_key(
+                    &bank,
+                    &token_program_id,
+                    SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET,
+                    delegate,
+                    filters,
+                )?
+            } else {
+                self.get_filtered_program_accounts(&bank, &token_program_id, filters)?
+            }
         };
         let accounts = if encoding == UiAccountEncoding::JsonParsed {
             get_parsed_token_accounts(bank.clone(), keyed_accounts.into_iter()).collect()
@@ -2125,6 +2314,39 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    /// Get an iterator of program accounts by the key at `offset` of their data, from the
+    /// matching program data key index
+    fn get_filtered_program_accounts_by_data_key(
+        &self,
+        bank: &Arc<Bank>,
+        program_id: &Pubkey,
+        offset: usize,
+        key: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
+        // The program data key index checks the account owner and key on inclusion. However, an
+        // account may remain in the index after it changed owner or key, or was wiped. The memcmp
+        // filter matching the index is among `filters`, so filtering again excludes these accounts.
+        bank.get_filtered_indexed_accounts(
+            &IndexKey::ProgramDataKey {
+                program_id: *program_id,
+                offset,
+                key: *key,
+            },
+            |account| {
+                account.owner() == program_id
+                    && filters
+                        .iter()
+                        .all(|filter_type| filter_type.allows(account))
+            },
+            &ScanConfig::default(),
+            bank.byte_limit_for_scans(),
+        )
+        .map_err(|e| RpcCustomError::ScanError {
+            message: e.to_string(),
+        })
+    }
+
     fn get_latest_blockhash(&self, config: RpcContextConfig) -> Result<RpcResponse<RpcBlockhash>> {
         let bank = self.get_bank_with_config(config)?;
         let blockhash = bank.last_blockhash();
@@ -2443,6 +2665,35 @@ fn get_spl_token_mint_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
     }
 }
 
+/// Find a memcmp filter that selects accounts by a key which a program data key index of
+/// `program_id` is keyed by. Return the offset and key of that filter.
+/// NOTE: `optimize_filters()` should almost always be called before using this method because of
+/// the strict match on `MemcmpEncodedBytes::Bytes`.
+fn get_program_data_key_filter(
+    program_id: &Pubkey,
+    filters: &[RpcFilterType],
+    account_indexes: &AccountSecondaryIndexes,
+) -> Option<(usize, Pubkey)> {
+    filters.iter().find_map(|filter| match filter {
+        RpcFilterType::Memcmp(Memcmp {
+            offset,
+            bytes: MemcmpEncodedBytes::Bytes(bytes),
+            ..
+        }) if bytes.len() == PUBKEY_BYTES => {
+            let key = Pubkey::new(bytes);
+            // all-zero keys are not indexed
+            (key != Pubkey::default()
+                && account_indexes.include_key(&key)
+                && account_indexes.contains(&AccountIndex::ProgramDataKey {
+                    program_id: *program_id,
+                    offset: *offset,
+                }))
+            .then(|| (*offset, key))
+        }
+        _ => None,
+    })
+}
+
 /// Analyze a passed Pubkey that may be a Token program id or Mint address to determine the program
 /// id and optional Mint
 fn get_token_program_id_and_mint(
@@ -2965,9 +3216,17 @@ pub mod rpc_accounts {
             &self,
             meta: Self::Metadata,
             pubkey_str: String,
-            config: Option<RpcAccountInfoConfig>,
+            config: Option<RpcGetAccountInfoConfig>,
         ) -> Result<RpcResponse<Option<UiAccount>>>;
 
+        #[rpc(meta, name = "getAccountHistory")]
+        fn get_account_history(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            config: Option<RpcAccountHistoryConfig>,
+        ) -> Result<Vec<RpcAccountHistoryEntry>>;
+
         #[rpc(meta, name = "getMultipleAccounts")]
         fn get_multiple_accounts(
             &self,
@@ -3068,11 +3327,29 @@ pub mod rpc_accounts {
             &self,
             meta: Self::Metadata,
             pubkey_str: String,
-            config: Option<RpcAccountInfoConfig>,
+            config: Option<RpcGetAccountInfoConfig>,
         ) -> Result<RpcResponse<Option<UiAccount>>> {
             debug!("get_account_info rpc request received: {:?}", pubkey_str);
             let pubkey = verify_pubkey(&pubkey_str)?;
-            meta.get_account_info(&pubkey, config)
+            let RpcGetAccountInfoConfig {
+                account_config,
+                slot,
+            } = config.unwrap_or_default();
+            match slot {
+                Some(slot) => meta.get_account_info_at_slot(&pubkey, slot, account_config),
+                None => meta.get_account_info(&pubkey, Some(account_config)),
+            }
+        }
+
+        fn get_account_history(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            config: Option<RpcAccountHistoryConfig>,
+        ) -> Result<Vec<RpcAccountHistoryEntry>> {
+            debug!("get_account_history rpc request received: {:?}", pubkey_str);
+            let pubkey = verify_pubkey(&pubkey_str)?;
+            meta.get_account_history(&pubkey, config)
         }
 
         fn get_multiple_accounts(
@@ -3332,6 +3609,13 @@ pub mod rpc_full {
             slot: Slot,
         ) -> BoxFuture<Result<Option<UnixTimestamp>>>;
 
+        #[rpc(meta, name = "getBlockCostInfo")]
+        fn get_block_cost_info(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcBlockCostInfo>>;
+
         #[rpc(meta, name = "getBlocks")]
         fn get_blocks(
             &self,
@@ -3652,7 +3936,10 @@ pub mod rpc_full {
                     post_simulation_accounts: _,
                     units_consumed,
                     return_data,
-                } = preflight_bank.simulate_transaction(transaction)
+                    inner_instructions: _,
+                    accounts_data_len_delta: _,
+                    instruction_compute_units: _,
+                } = preflight_bank.simulate_transaction(transaction, false)
                 {
                     match err {
                         TransactionError::BlockhashNotFound => {
@@ -3738,7 +4025,10 @@ pub mod rpc_full {
                 post_simulation_accounts,
                 units_consumed,
                 return_data,
-            } = bank.simulate_transaction(transaction);
+                inner_instructions: _,
+                accounts_data_len_delta: _,
+                instruction_compute_units: _,
+            } = bank.simulate_transaction(transaction, false);
 
             let accounts = if let Some(config_accounts) = config_accounts {
                 let accounts_encoding = config_accounts
@@ -3853,6 +4143,15 @@ pub mod rpc_full {
             Box::pin(async move { meta.get_block_time(slot).await })
         }
 
+        fn get_block_cost_info(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcBlockCostInfo>> {
+            debug!("get_block_cost_info rpc request received: {:?}", slot);
+            meta.get_block_cost_info(slot)
+        }
+
         fn get_transaction(
             &self,
             meta: Self::Metadata,
@@ -4546,6 +4845,7 @@ pub mod tests {
         solana_address_lookup_table_program::state::{AddressLookupTable, LookupTableMeta},
         solana_client::{
             rpc_custom_error::{
+                JSON_RPC_SERVER_ERROR_ACCOUNT_STATE_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
@@ -4555,12 +4855,13 @@ pub mod tests {
         solana_entry::entry::next_versioned_entry,
         solana_gossip::{contact_info::ContactInfo, socketaddr},
         solana_ledger::{
-            blockstore_meta::PerfSample,
+            blockstore_meta::{AccountHistoryMeta, PerfSample, RejectedTransactionCounts},
             blockstore_processor::fill_blockstore_slot_with_ticks,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
         },
         solana_runtime::{
-            accounts_background_service::AbsRequestSender, commitment::BlockCommitment,
+            accounts_background_service::AbsRequestSender, accounts_db::AccountShrinkThreshold,
+            accounts_index::AccountSecondaryIndexesIncludeExclude, commitment::BlockCommitment,
             inline_spl_token, non_circulating_supply::non_circulating_accounts,
         },
         solana_sdk::{
@@ -4682,6 +4983,7 @@ pub mod tests {
             let meta = JsonRpcRequestProcessor::new(
                 JsonRpcConfig {
                     enable_rpc_transaction_history: true,
+                    enable_rpc_account_history: true,
                     ..JsonRpcConfig::default()
                 },
                 None,
@@ -6962,6 +7264,151 @@ pub mod tests {
         assert_eq!(response, expected);
     }
 
+    #[test]
+    fn test_get_block_cost_info() {
+        let rpc = RpcHandler::start();
+        rpc.add_roots_to_blockstore(vec![1, 2, 3]);
+
+        let costly_account = Pubkey::new_unique();
+        rpc.blockstore
+            .write_block_cost_info(
+                2,
+                &BlockCostInfo {
+                    block_cost: 1_000,
+                    vote_cost: 200,
+                    transaction_count: 4,
+                    account_data_size: 128,
+                    costliest_accounts: vec![(costly_account, 800)],
+                    rejected_transaction_counts: RejectedTransactionCounts {
+                        would_exceed_account_max_limit: 3,
+                        ..RejectedTransactionCounts::default()
+                    },
+                },
+            )
+            .unwrap();
+
+        let request = create_test_request("getBlockCostInfo", Some(json!([2u64])));
+        let result: Option<RpcBlockCostInfo> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let expected = Some(RpcBlockCostInfo {
+            slot: 2,
+            block_cost: 1_000,
+            vote_cost: 200,
+            transaction_count: 4,
+            account_data_size: 128,
+            costliest_accounts: vec![RpcAccountCost {
+                pubkey: costly_account.to_string(),
+                cost: 800,
+            }],
+            rejected_transaction_counts: RpcRejectedTransactionCounts {
+                would_exceed_account_max_limit: 3,
+                ..RpcRejectedTransactionCounts::default()
+            },
+        });
+        assert_eq!(result, expected);
+
+        let request = create_test_request("getBlockCostInfo", Some(json!([3u64])));
+        let result: Option<RpcBlockCostInfo> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_get_account_history() {
+        let rpc = RpcHandler::start();
+        let pubkey = Pubkey::new_unique();
+        let created_pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let meta = |lamports, created| AccountHistoryMeta {
+            account: Account::new(lamports, 0, &owner),
+            created,
+        };
+        for (slot, lamports) in [(1, 10), (3, 30)] {
+            rpc.blockstore
+                .write_account_history(slot, vec![(pubkey, meta(lamports, false))])
+                .unwrap();
+        }
+        rpc.blockstore
+            .write_account_history(5, vec![(created_pubkey, meta(50, true))])
+            .unwrap();
+
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([pubkey.to_string(), {"encoding": "base64", "slot": 2}])),
+        );
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.context.slot, 2);
+        assert_eq!(result.value.unwrap().lamports, 10);
+
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([pubkey.to_string(), {"encoding": "base64", "slot": 3}])),
+        );
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.unwrap().lamports, 30);
+
+        // Never written, and not in the bank either
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([Pubkey::new_unique().to_string(), {"slot": 2}])),
+        );
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value, None);
+
+        // Created after the slot
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([created_pubkey.to_string(), {"slot": 4}])),
+        );
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value, None);
+
+        // Written before the recorded history begins, and again after the slot
+        rpc.blockstore
+            .write_account_history(0, vec![(Pubkey::new_unique(), meta(1, true))])
+            .unwrap();
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([pubkey.to_string(), {"slot": 0}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_ACCOUNT_STATE_NOT_AVAILABLE);
+
+        // Outside of the recorded history
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([pubkey.to_string(), {"slot": 6}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_ACCOUNT_STATE_NOT_AVAILABLE);
+
+        let request = create_test_request("getAccountHistory", Some(json!([pubkey.to_string()])));
+        let result: Vec<RpcAccountHistoryEntry> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result
+                .iter()
+                .map(|entry| (entry.slot, entry.account.lamports))
+                .collect::<Vec<_>>(),
+            vec![(3, 30), (1, 10)]
+        );
+
+        let request = create_test_request(
+            "getAccountHistory",
+            Some(json!([pubkey.to_string(), {"before": 3}])),
+        );
+        let result: Vec<RpcAccountHistoryEntry> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result.iter().map(|entry| entry.slot).collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
     #[test]
     fn test_get_vote_accounts() {
         let rpc = RpcHandler::start();
@@ -8161,6 +8608,139 @@ pub mod tests {
         .is_none());
     }
 
+    #[test]
+    fn test_get_token_accounts_by_delegate_with_index() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(TEST_MINT_LAMPORTS);
+        let account_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: AccountIndex::spl_token_delegate().into_iter().collect(),
+        };
+        let bank = Arc::new(Bank::new_with_paths_for_tests(
+            &genesis_config,
+            Vec::new(),
+            None,
+            None,
+            account_indexes.clone(),
+            false,
+            AccountShrinkThreshold::default(),
+            false,
+        ));
+        let mut meta = JsonRpcRequestProcessor::new_from_bank(&bank, SocketAddrSpace::Unspecified);
+        meta.config.account_indexes = account_indexes;
+
+        let delegate = Pubkey::new_unique();
+        let token_account_pubkey = Pubkey::new_unique();
+        let mut account_data = vec![0; TokenAccount::get_packed_len()];
+        let token_account = TokenAccount {
+            mint: SplTokenPubkey::new(&[2; 32]),
+            owner: SplTokenPubkey::new(&[3; 32]),
+            delegate: COption::Some(SplTokenPubkey::new(delegate.as_ref())),
+            amount: 420,
+            state: TokenAccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 30,
+            close_authority: COption::None,
+        };
+        TokenAccount::pack(token_account, &mut account_data).unwrap();
+        let token_account = AccountSharedData::from(Account {
+            lamports: 111,
+            data: account_data,
+            owner: inline_spl_token::id(),
+            ..Account::default()
+        });
+        bank.store_account(&token_account_pubkey, &token_account);
+        // An account of another delegate is not returned
+        let mut other_token_account = token_account;
+        other_token_account.data_as_mut_slice()
+            [SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET..SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET + PUBKEY_BYTES]
+            .copy_from_slice(Pubkey::new_unique().as_ref());
+        bank.store_account(&Pubkey::new_unique(), &other_token_account);
+
+        let accounts = meta
+            .get_token_accounts_by_delegate(
+                &delegate,
+                TokenAccountsFilter::ProgramId(inline_spl_token::id()),
+                None,
+            )
+            .unwrap()
+            .value;
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].pubkey, token_account_pubkey.to_string());
+
+        // The delegate index is used, so a delegate excluded from it is an error
+        meta.config.account_indexes.keys = Some(AccountSecondaryIndexesIncludeExclude {
+            exclude: true,
+            keys: HashSet::from([delegate]),
+        });
+        let error = meta
+            .get_token_accounts_by_delegate(
+                &delegate,
+                TokenAccountsFilter::ProgramId(inline_spl_token::id()),
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            RpcCustomError::KeyExcludedFromSecondaryIndex {
+                index_key: delegate.to_string(),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_get_program_data_key_filter() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let offset = 8;
+        let account_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::ProgramDataKey { program_id, offset }]),
+        };
+        let memcmp = |offset, bytes: &[u8]| {
+            RpcFilterType::Memcmp(Memcmp {
+                offset,
+                bytes: MemcmpEncodedBytes::Bytes(bytes.to_vec()),
+                encoding: None,
+            })
+        };
+
+        assert_eq!(
+            get_program_data_key_filter(
+                &program_id,
+                &[RpcFilterType::DataSize(40), memcmp(offset, key.as_ref())],
+                &account_indexes,
+            ),
+            Some((offset, key))
+        );
+
+        // Not indexed: other program, other offset, partial key, all-zero key
+        assert!(get_program_data_key_filter(
+            &Pubkey::new_unique(),
+            &[memcmp(offset, key.as_ref())],
+            &account_indexes,
+        )
+        .is_none());
+        assert!(get_program_data_key_filter(
+            &program_id,
+            &[memcmp(offset + 1, key.as_ref())],
+            &account_indexes,
+        )
+        .is_none());
+        assert!(get_program_data_key_filter(
+            &program_id,
+            &[memcmp(offset, &key.as_ref()[1..])],
+            &account_indexes,
+        )
+        .is_none());
+        assert!(get_program_data_key_filter(
+            &program_id,
+            &[memcmp(offset, Pubkey::default().as_ref())],
+            &account_indexes,
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_rpc_single_gossip() {
         let exit = Arc::new(AtomicBool::new(false));
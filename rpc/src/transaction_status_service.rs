@@ -356,6 +356,7 @@ pub(crate) mod tests {
             return_data: None,
             executed_units: 0,
             accounts_data_len_delta: 0,
+            instruction_compute_units: None,
         });
 
         let balances = TransactionBalancesSet {
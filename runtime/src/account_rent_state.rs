@@ -10,7 +10,7 @@ use {
 };
 
 #[derive(Debug, PartialEq, Eq)]
-pub(crate) enum RentState {
+pub enum RentState {
     /// account.lamports == 0
     Uninitialized,
     /// 0 < account.lamports < rent-exempt-minimum
@@ -21,7 +21,7 @@ pub(crate) enum RentState {
 }
 
 impl RentState {
-    pub(crate) fn from_account(account: &AccountSharedData, rent: &Rent) -> Self {
+    pub fn from_account(account: &AccountSharedData, rent: &Rent) -> Self {
         if account.lamports() == 0 {
             Self::Uninitialized
         } else if !rent.is_exempt(account.lamports(), account.data().len()) {
@@ -31,7 +31,7 @@ impl RentState {
         }
     }
 
-    pub(crate) fn transition_allowed_from(
+    pub fn transition_allowed_from(
         &self,
         pre_rent_state: &RentState,
         do_support_realloc: bool,
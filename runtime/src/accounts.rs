@@ -26,12 +26,17 @@ use {
     log::*,
     rand::{thread_rng, Rng},
     solana_address_lookup_table_program::{error::AddressLookupError, state::AddressLookupTable},
+    solana_program_runtime::compute_budget::ComputeBudget,
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
         account_utils::StateMut,
         bpf_loader_upgradeable::{self, UpgradeableLoaderState},
         clock::{BankId, Slot, INITIAL_RENT_EPOCH},
-        feature_set::{self, add_set_compute_unit_price_ix, tx_wide_compute_cap, FeatureSet},
+        feature_set::{
+            self, add_set_compute_unit_price_ix, cap_transaction_accounts_data_size,
+            default_units_per_instruction, enable_secp256r1_precompile, requestable_heap_size,
+            tx_wide_compute_cap, FeatureSet,
+        },
         fee::FeeStructure,
         genesis_config::ClusterType,
         hash::Hash,
@@ -237,6 +242,7 @@ impl Accounts {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn load_transaction(
         &self,
         ancestors: &Ancestors,
@@ -246,6 +252,7 @@ impl Accounts {
         rent_collector: &RentCollector,
         feature_set: &FeatureSet,
         account_overrides: Option<&AccountOverrides>,
+        requested_loaded_accounts_data_size_limit: Option<usize>,
     ) -> Result<LoadedTransaction> {
         // Copy all the accounts
         let message = tx.message();
@@ -261,6 +268,7 @@ impl Accounts {
             let mut accounts = Vec::with_capacity(account_keys.len());
             let mut account_deps = Vec::with_capacity(account_keys.len());
             let mut rent_debits = RentDebits::default();
+            let mut accumulated_accounts_data_size: usize = 0;
             for (i, key) in account_keys.iter().enumerate() {
                 let account = if !message.is_non_loader_key(i) {
                     // Fill in an empty account for the program slots.
@@ -332,6 +340,12 @@ impl Accounts {
                                         .accounts_db
                                         .load_with_fixed_root(ancestors, &programdata_address)
                                     {
+                                        Self::accumulate_and_check_loaded_account_data_size(
+                                            &mut accumulated_accounts_data_size,
+                                            programdata_account.data().len(),
+                                            requested_loaded_accounts_data_size_limit,
+                                            error_counters,
+                                        )?;
                                         account_deps
                                             .push((programdata_address, programdata_account));
                                     } else {
@@ -354,6 +368,12 @@ impl Accounts {
                         account
                     }
                 };
+                Self::accumulate_and_check_loaded_account_data_size(
+                    &mut accumulated_accounts_data_size,
+                    account.data().len(),
+                    requested_loaded_accounts_data_size_limit,
+                    error_counters,
+                )?;
                 accounts.push((*key, account));
             }
             debug_assert_eq!(accounts.len(), account_keys.len());
@@ -374,6 +394,8 @@ impl Accounts {
                             &mut accounts,
                             instruction.program_id_index as usize,
                             error_counters,
+                            &mut accumulated_accounts_data_size,
+                            requested_loaded_accounts_data_size_limit,
                         )
                     })
                     .collect::<Result<Vec<Vec<usize>>>>()?;
@@ -451,6 +473,8 @@ impl Accounts {
         accounts: &mut Vec<TransactionAccount>,
         mut program_account_index: usize,
         error_counters: &mut TransactionErrorMetrics,
+        accumulated_accounts_data_size: &mut usize,
+        requested_loaded_accounts_data_size_limit: Option<usize>,
     ) -> Result<Vec<usize>> {
         let mut account_indices = Vec::new();
         let mut program_id = match accounts.get(program_account_index) {
@@ -473,6 +497,12 @@ impl Accounts {
                 .load_with_fixed_root(ancestors, &program_id)
             {
                 Some((program_account, _)) => {
+                    Self::accumulate_and_check_loaded_account_data_size(
+                        accumulated_accounts_data_size,
+                        program_account.data().len(),
+                        requested_loaded_accounts_data_size_limit,
+                        error_counters,
+                    )?;
                     let account_index = accounts.len();
                     accounts.push((program_id, program_account));
                     account_index
@@ -502,6 +532,12 @@ impl Accounts {
                         .load_with_fixed_root(ancestors, &programdata_address)
                     {
                         Some((programdata_account, _)) => {
+                            Self::accumulate_and_check_loaded_account_data_size(
+                                accumulated_accounts_data_size,
+                                programdata_account.data().len(),
+                                requested_loaded_accounts_data_size_limit,
+                                error_counters,
+                            )?;
                             let account_index = accounts.len();
                             accounts.push((programdata_address, programdata_account));
                             account_index
@@ -523,6 +559,51 @@ impl Accounts {
         Ok(account_indices)
     }
 
+    /// Returns the loaded accounts data size limit requested by the transaction's compute budget
+    /// instructions, or `None` if the limit is not enforced yet
+    fn get_requested_loaded_accounts_data_size_limit(
+        tx: &SanitizedTransaction,
+        feature_set: &FeatureSet,
+    ) -> Result<Option<usize>> {
+        if !feature_set.is_active(&cap_transaction_accounts_data_size::id()) {
+            return Ok(None);
+        }
+        let mut compute_budget = ComputeBudget::default();
+        if !feature_set.is_active(&tx_wide_compute_cap::id()) {
+            // compute budget instructions are ignored until the compute cap is transaction wide
+            return Ok(Some(compute_budget.loaded_accounts_data_size_limit));
+        }
+        compute_budget.process_instructions(
+            tx.message().program_instructions_iter(),
+            feature_set.is_active(&requestable_heap_size::id()),
+            feature_set.is_active(&default_units_per_instruction::id()),
+            feature_set.is_active(&add_set_compute_unit_price_ix::id()),
+            true, // the limit is only enforced while the instruction is supported
+        )?;
+        Ok(Some(compute_budget.loaded_accounts_data_size_limit))
+    }
+
+    /// Adds `account_data_size` to `accumulated_accounts_data_size`, failing with
+    /// `MaxLoadedAccountsDataSizeExceeded` once the total goes over the requested limit
+    fn accumulate_and_check_loaded_account_data_size(
+        accumulated_accounts_data_size: &mut usize,
+        account_data_size: usize,
+        requested_loaded_accounts_data_size_limit: Option<usize>,
+        error_counters: &mut TransactionErrorMetrics,
+    ) -> Result<()> {
+        if let Some(requested_loaded_accounts_data_size_limit) =
+            requested_loaded_accounts_data_size_limit
+        {
+            *accumulated_accounts_data_size =
+                accumulated_accounts_data_size.saturating_add(account_data_size);
+            if *accumulated_accounts_data_size > requested_loaded_accounts_data_size_limit {
+                error_counters.max_loaded_accounts_data_size_exceeded += 1;
+                return Err(TransactionError::MaxLoadedAccountsDataSizeExceeded);
+            }
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn load_accounts(
         &self,
@@ -553,11 +634,19 @@ impl Accounts {
                             fee_structure,
                             feature_set.is_active(&tx_wide_compute_cap::id()),
                             feature_set.is_active(&add_set_compute_unit_price_ix::id()),
+                            feature_set.is_active(&cap_transaction_accounts_data_size::id()),
+                            feature_set.is_active(&enable_secp256r1_precompile::id()),
                         )
                     } else {
                         return (Err(TransactionError::BlockhashNotFound), None);
                     };
 
+                    let requested_loaded_accounts_data_size_limit =
+                        match Self::get_requested_loaded_accounts_data_size_limit(tx, feature_set) {
+                            Ok(limit) => limit,
+                            Err(e) => return (Err(e), None),
+                        };
+
                     let loaded_transaction = match self.load_transaction(
                         ancestors,
                         tx,
@@ -566,6 +655,7 @@ impl Accounts {
                         rent_collector,
                         feature_set,
                         account_overrides,
+                        requested_loaded_accounts_data_size_limit,
                     ) {
                         Ok(loaded_transaction) => loaded_transaction,
                         Err(e) => return (Err(e), None),
@@ -1406,10 +1496,11 @@ mod tests {
         solana_program_runtime::invoke_context::Executors,
         solana_sdk::{
             account::{AccountSharedData, WritableAccount},
+            compute_budget::{self, ComputeBudgetInstruction},
             epoch_schedule::EpochSchedule,
             genesis_config::ClusterType,
             hash::Hash,
-            instruction::{CompiledInstruction, InstructionError},
+            instruction::{AccountMeta, CompiledInstruction, Instruction, InstructionError},
             message::{Message, MessageHeader},
             nonce, nonce_account,
             rent::Rent,
@@ -1452,6 +1543,7 @@ mod tests {
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                instruction_compute_units: None,
             },
             executors: Rc::new(RefCell::new(Executors::default())),
         }
@@ -1662,6 +1754,8 @@ mod tests {
             &FeeStructure::default(),
             false,
             true,
+            true,
+            true,
         );
         assert_eq!(fee, 10);
 
@@ -1826,6 +1920,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_accounts_loaded_accounts_data_size_limit() {
+        let mut error_counters = TransactionErrorMetrics::default();
+
+        let keypair = Keypair::new();
+        let key1 = Pubkey::new_unique();
+
+        let mut payer_account = AccountSharedData::new(1, 0, &Pubkey::default());
+        payer_account.set_rent_epoch(1);
+        let mut data_account = AccountSharedData::new(1, 1024, &Pubkey::default());
+        data_account.set_rent_epoch(1);
+        let mut compute_budget_program = AccountSharedData::new(1, 0, &native_loader::id());
+        compute_budget_program.set_executable(true);
+        let accounts: Vec<TransactionAccount> = vec![
+            (keypair.pubkey(), payer_account),
+            (key1, data_account),
+            (compute_budget::id(), compute_budget_program),
+        ];
+
+        let new_tx = |limit| {
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(limit),
+                    Instruction::new_with_bincode(
+                        native_loader::id(),
+                        &(),
+                        vec![AccountMeta::new_readonly(key1, false)],
+                    ),
+                ],
+                Some(&keypair.pubkey()),
+                &[&keypair],
+                Hash::default(),
+            )
+        };
+
+        let loaded_accounts = load_accounts(new_tx(1024), &accounts, &mut error_counters);
+        assert!(loaded_accounts[0].0.is_ok());
+
+        let loaded_accounts = load_accounts(new_tx(1023), &accounts, &mut error_counters);
+        assert_eq!(
+            loaded_accounts[0].0.as_ref().unwrap_err(),
+            &TransactionError::MaxLoadedAccountsDataSizeExceeded
+        );
+        assert_eq!(error_counters.max_loaded_accounts_data_size_exceeded, 1);
+
+        // The limit is not enforced until the feature is activated
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&cap_transaction_accounts_data_size::id());
+        let loaded_accounts = load_accounts_with_fee_and_rent(
+            new_tx(1023),
+            &accounts,
+            0,
+            &RentCollector::default(),
+            &mut error_counters,
+            &feature_set,
+            &FeeStructure::default(),
+        );
+        assert!(loaded_accounts[0].0.is_ok());
+    }
+
     #[test]
     fn test_load_accounts_max_call_depth() {
         let mut accounts: Vec<TransactionAccount> = Vec::new();
@@ -2454,6 +2608,8 @@ mod tests {
                 &mut vec![(keypair.pubkey(), account)],
                 0,
                 &mut error_counters,
+                &mut 0,
+                None,
             ),
             Err(TransactionError::ProgramAccountNotFound)
         );
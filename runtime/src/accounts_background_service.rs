@@ -235,6 +235,7 @@ impl SnapshotRequestHandler {
                             use_write_cache: false,
                             epoch_schedule: snapshot_root_bank.epoch_schedule(),
                             rent_collector: snapshot_root_bank.rent_collector(),
+                            retain_hash_cache: false,
                         },
                     ).unwrap();
                     assert_eq!(previous_hash, this_hash);
@@ -45,7 +45,8 @@ use {
             AppendVec, AppendVecAccountsIter, StoredAccountMeta, StoredMeta, StoredMetaWriteVersion,
         },
         bank::Rewrites,
-        cache_hash_data::CacheHashData,
+        cache_hash_data::{CacheHashData, CACHE_HASH_DATA_FILE_VERSION},
+        cold_storage::{ColdAccount, ColdPageCache, ColdStorageFile, ColdStorageStats},
         contains::Contains,
         expected_rent_collection::{ExpectedRentCollection, SlotInfoInEpoch},
         pubkey_bins::PubkeyBinCalculator24,
@@ -61,6 +62,7 @@ use {
         DashMap, DashSet,
     },
     log::*,
+    once_cell::sync::OnceCell,
     rand::{thread_rng, Rng},
     rayon::{prelude::*, ThreadPool},
     serde::{Deserialize, Serialize},
@@ -81,7 +83,6 @@ use {
         boxed::Box,
         collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
         convert::TryFrom,
-        hash::{Hash as StdHash, Hasher as StdHasher},
         io::{Error as IoError, Result as IoResult},
         ops::{Range, RangeBounds},
         path::{Path, PathBuf},
@@ -147,6 +148,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     write_cache_limit_bytes: None,
     skip_rewrites: false,
     ancient_append_vecs: false,
+    cold_storage_epochs: None,
     skip_initial_hash_calc: false,
 };
 pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig {
@@ -157,6 +159,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     write_cache_limit_bytes: None,
     skip_rewrites: false,
     ancient_append_vecs: false,
+    cold_storage_epochs: None,
     skip_initial_hash_calc: false,
 };
 
@@ -197,6 +200,8 @@ pub struct AccountsDbConfig {
     pub write_cache_limit_bytes: Option<u64>,
     pub skip_rewrites: bool,
     pub ancient_append_vecs: bool,
+    /// storages of roots that have not been written to for this many epochs are compressed into cold storage
+    pub cold_storage_epochs: Option<u64>,
     pub skip_initial_hash_calc: bool,
 }
 
@@ -479,11 +484,7 @@ impl<'a> LoadedAccountAccessor<'a> {
                 // get account meta from the storage entry here
                 maybe_storage_entry
                     .as_ref()
-                    .and_then(|(storage_entry, offset)| {
-                        storage_entry
-                            .get_stored_account_meta(*offset)
-                            .map(LoadedAccount::Stored)
-                    })
+                    .and_then(|(storage_entry, offset)| storage_entry.get_loaded_account(*offset))
             }
         }
     }
@@ -492,6 +493,7 @@ impl<'a> LoadedAccountAccessor<'a> {
 pub enum LoadedAccount<'a> {
     Stored(StoredAccountMeta<'a>),
     Cached(Cow<'a, CachedAccount>),
+    Cold(ColdAccount),
 }
 
 impl<'a> LoadedAccount<'a> {
@@ -499,6 +501,7 @@ impl<'a> LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => *stored_account_meta.hash,
             LoadedAccount::Cached(cached_account) => cached_account.hash(),
+            LoadedAccount::Cold(cold_account) => cold_account.hash,
         }
     }
 
@@ -506,6 +509,7 @@ impl<'a> LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => &stored_account_meta.meta.pubkey,
             LoadedAccount::Cached(cached_account) => cached_account.pubkey(),
+            LoadedAccount::Cold(cold_account) => &cold_account.pubkey,
         }
     }
 
@@ -513,6 +517,7 @@ impl<'a> LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => stored_account_meta.meta.write_version,
             LoadedAccount::Cached(_) => CACHE_VIRTUAL_WRITE_VERSION,
+            LoadedAccount::Cold(cold_account) => cold_account.write_version,
         }
     }
 
@@ -526,6 +531,9 @@ impl<'a> LoadedAccount<'a> {
             LoadedAccount::Cached(cached_account) => {
                 AccountsDb::hash_account(slot, &cached_account.account, pubkey)
             }
+            LoadedAccount::Cold(cold_account) => {
+                AccountsDb::hash_account(slot, &cold_account.account, &cold_account.pubkey)
+            }
         }
     }
 
@@ -533,6 +541,7 @@ impl<'a> LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => stored_account_meta.stored_size,
             LoadedAccount::Cached(_) => CACHE_VIRTUAL_STORED_SIZE as usize,
+            LoadedAccount::Cold(cold_account) => cold_account.stored_size,
         }
     }
 
@@ -543,12 +552,13 @@ impl<'a> LoadedAccount<'a> {
                 Cow::Owned(cached_account) => cached_account.account.clone(),
                 Cow::Borrowed(cached_account) => cached_account.account.clone(),
             },
+            LoadedAccount::Cold(cold_account) => cold_account.account,
         }
     }
 
     pub fn is_cached(&self) -> bool {
         match self {
-            LoadedAccount::Stored(_) => false,
+            LoadedAccount::Stored(_) | LoadedAccount::Cold(_) => false,
             LoadedAccount::Cached(_) => true,
         }
     }
@@ -559,6 +569,7 @@ impl<'a> ReadableAccount for LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => stored_account_meta.account_meta.lamports,
             LoadedAccount::Cached(cached_account) => cached_account.account.lamports(),
+            LoadedAccount::Cold(cold_account) => cold_account.lamports(),
         }
     }
 
@@ -566,12 +577,14 @@ impl<'a> ReadableAccount for LoadedAccount<'a> {
         match self {
             LoadedAccount::Stored(stored_account_meta) => stored_account_meta.data,
             LoadedAccount::Cached(cached_account) => cached_account.account.data(),
+            LoadedAccount::Cold(cold_account) => cold_account.data(),
         }
     }
     fn owner(&self) -> &Pubkey {
         match self {
             LoadedAccount::Stored(stored_account_meta) => &stored_account_meta.account_meta.owner,
             LoadedAccount::Cached(cached_account) => cached_account.account.owner(),
+            LoadedAccount::Cold(cold_account) => cold_account.owner(),
         }
     }
     fn executable(&self) -> bool {
@@ -580,6 +593,7 @@ impl<'a> ReadableAccount for LoadedAccount<'a> {
                 stored_account_meta.account_meta.executable
             }
             LoadedAccount::Cached(cached_account) => cached_account.account.executable(),
+            LoadedAccount::Cold(cold_account) => cold_account.executable(),
         }
     }
     fn rent_epoch(&self) -> Epoch {
@@ -588,6 +602,7 @@ impl<'a> ReadableAccount for LoadedAccount<'a> {
                 stored_account_meta.account_meta.rent_epoch
             }
             LoadedAccount::Cached(cached_account) => cached_account.account.rent_epoch(),
+            LoadedAccount::Cold(cold_account) => cold_account.rent_epoch(),
         }
     }
     fn to_account_shared_data(&self) -> AccountSharedData {
@@ -601,6 +616,7 @@ impl<'a> ReadableAccount for LoadedAccount<'a> {
             ),
             // clone here to prevent data copy
             LoadedAccount::Cached(cached_account) => cached_account.account.clone(),
+            LoadedAccount::Cold(cold_account) => cold_account.account.clone(),
         }
     }
 }
@@ -678,7 +694,10 @@ pub struct AccountStorageEntry {
     pub(crate) slot: AtomicU64,
 
     /// storage holding the accounts
-    pub(crate) accounts: AppendVec,
+    ///
+    /// A cold storage has no append vec until one is thawed from its cold storage file, see
+    /// `accounts()`.
+    accounts: OnceCell<AppendVec>,
 
     /// Keeps track of the number of accounts stored in a specific AppendVec.
     ///  This is periodically checked to reuse the stores that do not have
@@ -696,6 +715,9 @@ pub struct AccountStorageEntry {
     approx_store_count: AtomicUsize,
 
     alive_bytes: AtomicUsize,
+
+    /// compressed contents of this storage, which loads are served from, once this storage is cold
+    cold_storage: Option<Arc<ColdStorageFile>>,
 }
 
 impl AccountStorageEntry {
@@ -707,10 +729,11 @@ impl AccountStorageEntry {
         Self {
             id: AtomicAppendVecId::new(id),
             slot: AtomicU64::new(slot),
-            accounts,
+            accounts: OnceCell::with_value(accounts),
             count_and_status: RwLock::new((0, AccountStorageStatus::Available)),
             approx_store_count: AtomicUsize::new(0),
             alive_bytes: AtomicUsize::new(0),
+            cold_storage: None,
         }
     }
 
@@ -723,13 +746,55 @@ impl AccountStorageEntry {
         Self {
             id: AtomicAppendVecId::new(id),
             slot: AtomicU64::new(slot),
-            accounts,
+            accounts: OnceCell::with_value(accounts),
             count_and_status: RwLock::new((0, AccountStorageStatus::Available)),
             approx_store_count: AtomicUsize::new(num_accounts),
             alive_bytes: AtomicUsize::new(0),
+            cold_storage: None,
         }
     }
 
+    /// A storage with the same slot, id and counts as 'storage', whose accounts are only in
+    /// 'cold_storage'. The append vec of 'storage' is dropped with the last reference to 'storage'.
+    fn new_cold(storage: &AccountStorageEntry, cold_storage: Arc<ColdStorageFile>) -> Self {
+        // nothing is appended to a cold storage
+        let (count, _status) = *storage.count_and_status.read().unwrap();
+        Self {
+            id: AtomicAppendVecId::new(storage.append_vec_id()),
+            slot: AtomicU64::new(storage.slot()),
+            accounts: OnceCell::new(),
+            count_and_status: RwLock::new((count, AccountStorageStatus::Full)),
+            approx_store_count: AtomicUsize::new(storage.approx_stored_count()),
+            alive_bytes: AtomicUsize::new(storage.alive_bytes()),
+            cold_storage: Some(cold_storage),
+        }
+    }
+
+    /// The append vec holding the accounts of this storage.
+    /// A cold storage is thawed into a temporary append vec on first use, which lives as long as this
+    /// storage.
+    pub(crate) fn accounts(&self) -> &AppendVec {
+        self.accounts.get_or_init(|| {
+            let cold_storage = self
+                .cold_storage
+                .as_ref()
+                .expect("a storage without an append vec is cold");
+            cold_storage.thaw().unwrap_or_else(|err| {
+                panic!(
+                    "Unable to thaw cold storage of slot {} id {}: {}",
+                    self.slot(),
+                    self.append_vec_id(),
+                    err
+                )
+            })
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_accounts(&mut self, accounts: AppendVec) {
+        self.accounts = OnceCell::with_value(accounts);
+    }
+
     pub fn set_status(&self, mut status: AccountStorageStatus) {
         let mut count_and_status = self.count_and_status.write().unwrap();
 
@@ -744,7 +809,7 @@ impl AccountStorageEntry {
             //          **and**
             //  the append_vec has previously been completely full
             //
-            self.accounts.reset();
+            self.accounts().reset();
             status = AccountStorageStatus::Available;
         }
 
@@ -752,8 +817,10 @@ impl AccountStorageEntry {
     }
 
     pub fn recycle(&self, slot: Slot, id: AppendVecId) {
+        // cold storages are never recycled, see 'recycle_slot_stores'
+        debug_assert!(!self.is_cold());
         let mut count_and_status = self.count_and_status.write().unwrap();
-        self.accounts.reset();
+        self.accounts().reset();
         *count_and_status = (0, AccountStorageStatus::Available);
         self.slot.store(slot, Ordering::Release);
         self.id.store(id, Ordering::Release);
@@ -777,12 +844,36 @@ impl AccountStorageEntry {
         self.alive_bytes.load(Ordering::SeqCst)
     }
 
+    pub fn cold_storage(&self) -> Option<&Arc<ColdStorageFile>> {
+        self.cold_storage.as_ref()
+    }
+
+    pub fn is_cold(&self) -> bool {
+        self.cold_storage.is_some()
+    }
+
+    /// true if this storage is cold and an append vec has been thawed from its cold storage file
+    pub fn is_thawed(&self) -> bool {
+        self.is_cold() && self.accounts.get().is_some()
+    }
+
+    /// true iff this storage is an ancient append vec, see 'is_ancient'
+    fn is_ancient(&self) -> bool {
+        self.total_bytes() >= get_ancient_append_vec_capacity()
+    }
+
     pub fn written_bytes(&self) -> u64 {
-        self.accounts.len() as u64
+        match &self.cold_storage {
+            Some(cold_storage) => cold_storage.len() as u64,
+            None => self.accounts().len() as u64,
+        }
     }
 
     pub fn total_bytes(&self) -> u64 {
-        self.accounts.capacity()
+        match &self.cold_storage {
+            Some(cold_storage) => cold_storage.capacity(),
+            None => self.accounts().capacity(),
+        }
     }
 
     pub fn has_accounts(&self) -> bool {
@@ -798,11 +889,40 @@ impl AccountStorageEntry {
     }
 
     pub fn flush(&self) -> Result<(), IoError> {
-        self.accounts.flush()
+        match self.accounts.get() {
+            Some(accounts) => accounts.flush(),
+            // the cold storage file is complete once it is created
+            None => Ok(()),
+        }
     }
 
     fn get_stored_account_meta(&self, offset: usize) -> Option<StoredAccountMeta> {
-        Some(self.accounts.get_account(offset)?.0)
+        Some(self.accounts().get_account(offset)?.0)
+    }
+
+    /// The account at 'offset'. The accounts of a cold storage are loaded from its cold storage
+    /// file, without thawing it.
+    fn get_loaded_account(&self, offset: usize) -> Option<LoadedAccount> {
+        match &self.cold_storage {
+            Some(cold_storage) if !self.is_thawed() => {
+                cold_storage.load_account(offset).map(LoadedAccount::Cold)
+            }
+            _ => self
+                .get_stored_account_meta(offset)
+                .map(LoadedAccount::Stored),
+        }
+    }
+
+    /// Call 'scan_func' with each account of this storage, in the order they were written.
+    /// A cold storage is read from its cold storage file, without thawing it.
+    fn scan_accounts(&self, mut scan_func: impl FnMut(LoadedAccount)) {
+        match &self.cold_storage {
+            Some(cold_storage) if !self.is_thawed() => cold_storage
+                .accounts()
+                .for_each(|account| scan_func(LoadedAccount::Cold(account))),
+            _ => AppendVecAccountsIter::new(self.accounts())
+                .for_each(|account| scan_func(LoadedAccount::Stored(account))),
+        }
     }
 
     fn add_account(&self, num_bytes: usize) {
@@ -825,14 +945,14 @@ impl AccountStorageEntry {
     }
 
     pub fn all_accounts(&self) -> Vec<StoredAccountMeta> {
-        self.accounts.accounts(0)
+        self.accounts().accounts(0)
     }
 
     fn remove_account(&self, num_bytes: usize, reset_accounts: bool) -> usize {
         let mut count_and_status = self.count_and_status.write().unwrap();
         let (mut count, mut status) = *count_and_status;
 
-        if count == 1 && status == AccountStorageStatus::Full && reset_accounts {
+        if count == 1 && status == AccountStorageStatus::Full && reset_accounts && !self.is_cold() {
             // this case arises when we remove the last account from the
             //  storage, but we've learned from previous write attempts that
             //  the storage is full
@@ -844,7 +964,7 @@ impl AccountStorageEntry {
             //
             // otherwise, the storage may be in flight with a store()
             //   call
-            self.accounts.reset();
+            self.accounts().reset();
             status = AccountStorageStatus::Available;
         }
 
@@ -864,7 +984,7 @@ impl AccountStorageEntry {
     }
 
     pub fn get_path(&self) -> PathBuf {
-        self.accounts.get_path()
+        self.accounts().get_path()
     }
 }
 
@@ -1022,6 +1142,17 @@ pub struct AccountsDb {
     /// true iff we want to squash old append vecs together into 'ancient append vecs'
     pub ancient_append_vecs: bool,
 
+    /// storages of roots that have not been written to for this many epochs are compressed into cold storage
+    pub cold_storage_epochs: Option<u64>,
+
+    /// roots older than this slot have not been written to for 'cold_storage_epochs'
+    cold_storage_max_slot: RwLock<Slot>,
+
+    /// decompressed pages of cold storage files
+    cold_page_cache: Arc<ColdPageCache>,
+
+    cold_storage_stats: Arc<ColdStorageStats>,
+
     /// true iff we want to skip the initial hash calculation on startup
     pub skip_initial_hash_calc: bool,
 
@@ -1870,6 +2001,7 @@ impl AccountsDb {
     ) -> Self {
         let num_threads = get_thread_count();
         const MAX_READ_ONLY_CACHE_DATA_SIZE: usize = 200_000_000;
+        const MAX_COLD_PAGE_CACHE_DATA_SIZE: usize = 200_000_000;
 
         let mut temp_accounts_hash_cache_path = None;
         let accounts_hash_cache_path = accounts_hash_cache_path.unwrap_or_else(|| {
@@ -1899,6 +2031,10 @@ impl AccountsDb {
             skip_rewrites: false,
             skip_initial_hash_calc: false,
             ancient_append_vecs: false,
+            cold_storage_epochs: None,
+            cold_storage_max_slot: RwLock::default(),
+            cold_page_cache: Arc::new(ColdPageCache::new(MAX_COLD_PAGE_CACHE_DATA_SIZE)),
+            cold_storage_stats: Arc::default(),
             accounts_index,
             storage: AccountStorage::default(),
             accounts_cache: AccountsCache::default(),
@@ -2007,6 +2143,10 @@ impl AccountsDb {
             .map(|config| config.ancient_append_vecs)
             .unwrap_or_default();
 
+        let cold_storage_epochs = accounts_db_config
+            .as_ref()
+            .and_then(|config| config.cold_storage_epochs);
+
         let filler_account_suffix = if filler_accounts_config.count > 0 {
             Some(solana_sdk::pubkey::new_rand())
         } else {
@@ -2018,6 +2158,7 @@ impl AccountsDb {
             skip_rewrites,
             skip_initial_hash_calc,
             ancient_append_vecs,
+            cold_storage_epochs,
             cluster_type: Some(*cluster_type),
             account_indexes,
             caching_enabled,
@@ -2332,6 +2473,21 @@ impl AccountsDb {
 
         // now that calculate_accounts_hash_without_index is complete, we can remove old historical roots
         self.remove_old_historical_roots(accounts_hash_complete_one_epoch_old);
+
+        self.update_cold_storage_max_slot(completed_slot, epoch_schedule);
+    }
+
+    /// Roots older than 'cold_storage_epochs' before 'slot' become eligible for cold storage.
+    /// Cold storage files are not part of snapshots and the accounts paths are cleared at startup,
+    /// so this is also called for the snapshot slot at startup. The accounts background service
+    /// then compresses the storages unpacked from the snapshot again.
+    pub fn update_cold_storage_max_slot(&self, slot: Slot, epoch_schedule: &EpochSchedule) {
+        if let Some(cold_storage_epochs) = self.cold_storage_epochs {
+            let cold_slot = slot
+                .saturating_sub(cold_storage_epochs.saturating_mul(epoch_schedule.slots_per_epoch));
+            let mut cold_storage_max_slot = self.cold_storage_max_slot.write().unwrap();
+            *cold_storage_max_slot = std::cmp::max(*cold_storage_max_slot, cold_slot);
+        }
     }
 
     /// get the slot that is one epoch older than the highest slot that has been used for hash calculation
@@ -2403,9 +2559,9 @@ impl AccountsDb {
         let dirty_stores_len = dirty_stores.len();
         let pubkeys = DashSet::new();
         for (_slot, store) in dirty_stores {
-            for account in store.accounts.accounts(0) {
-                pubkeys.insert(account.meta.pubkey);
-            }
+            store.scan_accounts(|account| {
+                pubkeys.insert(*account.pubkey());
+            });
         }
         trace!(
             "dirty_stores.len: {} pubkeys.len: {}",
@@ -3045,7 +3201,7 @@ impl AccountsDb {
             let mut start = 0;
             original_bytes += store.total_bytes();
             let store_id = store.append_vec_id();
-            while let Some((account, next)) = store.accounts.get_account(start) {
+            while let Some((account, next)) = store.accounts().get_account(start) {
                 let new_entry = FoundStoredAccount {
                     account,
                     store_id,
@@ -3572,7 +3728,12 @@ impl AccountsDb {
             return false;
         }
         let storage = all_storages.first().unwrap();
-        let accounts = &storage.accounts;
+        if storage.is_cold() && !self.is_candidate_for_shrink(storage, true) {
+            // a cold storage is only moved once enough of its accounts are dead, which thaws it
+            *current_ancient = None;
+            return false;
+        }
+        let accounts = storage.accounts();
         if is_full_ancient(accounts) {
             if self.is_candidate_for_shrink(storage, true) {
                 // we are full, but we are a candidate for shrink, so either append us to the previous append vec
@@ -3689,7 +3850,7 @@ impl AccountsDb {
             create_and_insert_store_elapsed += time.as_micros() as u64;
             let (ancient_slot, ancient_store) =
                 current_ancient.as_ref().map(|(a, b)| (*a, b)).unwrap();
-            let available_bytes = ancient_store.accounts.remaining_bytes();
+            let available_bytes = ancient_store.accounts().remaining_bytes();
             let mut start = Measure::start("find_alive_elapsed");
             let to_store = AccountsToStore::new(available_bytes, &alive_accounts, slot);
             start.stop();
@@ -3716,7 +3877,7 @@ impl AccountsDb {
                 current_ancient = result.0;
                 let (ancient_slot, ancient_store) =
                     current_ancient.as_ref().map(|(a, b)| (*a, b)).unwrap();
-                let available_bytes = ancient_store.accounts.remaining_bytes();
+                let available_bytes = ancient_store.accounts().remaining_bytes();
 
                 // we could sort alive_accounts
                 let to_store = AccountsToStore::new(available_bytes, &alive_accounts, slot);
@@ -3828,11 +3989,76 @@ impl AccountsDb {
         self.shrink_ancient_stats.report();
     }
 
+    /// Compress the storages of roots that have not been written to for 'cold_storage_epochs' into
+    /// cold storage files, and replace them with cold storages. Cold storages that have been thawed
+    /// since are replaced with cold storages of the same cold storage file.
+    /// A replaced append vec and its file are dropped with the last reference to its storage.
+    ///
+    /// The storages of roots are only written to or replaced by clean, shrink and this function,
+    /// which all run in the accounts background service, so their counts can't change while
+    /// they are replaced.
+    pub(crate) fn move_old_slots_to_cold_storage(&self) {
+        if self.cold_storage_epochs.is_none() {
+            return;
+        }
+
+        let cold_storage_max_slot = *self.cold_storage_max_slot.read().unwrap();
+        for slot in self.get_roots_less_than(cold_storage_max_slot) {
+            let slot_stores = match self.storage.get_slot_stores(slot) {
+                Some(slot_stores) => slot_stores,
+                None => continue,
+            };
+            let storage = {
+                let slot_stores = slot_stores.read().unwrap();
+                match slot_stores.values().next() {
+                    Some(storage) if slot_stores.len() == 1 => Arc::clone(storage),
+                    _ => continue,
+                }
+            };
+
+            let cold_storage = match storage.cold_storage() {
+                Some(cold_storage) if storage.is_thawed() => Arc::clone(cold_storage),
+                Some(_) => continue,
+                None => {
+                    let accounts = storage.accounts();
+                    if accounts.is_empty() || (is_ancient(accounts) && !is_full_ancient(accounts)) {
+                        // more accounts can still be squashed into an ancient append vec
+                        continue;
+                    }
+                    let path = ColdStorageFile::path_for(&accounts.get_path());
+                    match ColdStorageFile::new(
+                        &path,
+                        accounts,
+                        Arc::clone(&self.cold_page_cache),
+                        Arc::clone(&self.cold_storage_stats),
+                    ) {
+                        Ok(cold_storage) => Arc::new(cold_storage),
+                        Err(err) => {
+                            error!("failed to create cold storage file {:?}: {}", path, err);
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let cold_storage = Arc::new(AccountStorageEntry::new_cold(&storage, cold_storage));
+            let mut slot_stores = slot_stores.write().unwrap();
+            if let Some(current) = slot_stores.get_mut(&storage.append_vec_id()) {
+                if Arc::ptr_eq(current, &storage) {
+                    *current = cold_storage;
+                }
+            }
+        }
+
+        self.cold_storage_stats.report(&self.cold_page_cache);
+    }
+
     pub fn shrink_candidate_slots(&self) -> usize {
         let shrink_candidates_slots =
             std::mem::take(&mut *self.shrink_candidate_slots.lock().unwrap());
         if !shrink_candidates_slots.is_empty() {
             self.shrink_ancient_slots();
+            self.move_old_slots_to_cold_storage();
         }
 
         let (shrink_slots, shrink_slots_next_batch) = {
@@ -4037,6 +4263,7 @@ impl AccountsDb {
             IndexKey::ProgramId(key) => key,
             IndexKey::SplTokenMint(key) => key,
             IndexKey::SplTokenOwner(key) => key,
+            IndexKey::ProgramDataKey { key, .. } => key,
         };
         if !self.account_indexes.include_key(key) {
             // the requested key was not indexed in the secondary index, so do a normal scan
@@ -4111,9 +4338,7 @@ impl AccountsDb {
                 .unwrap_or_default();
             self.thread_pool.install(|| {
                 storage_maps.par_iter().for_each(|storage| {
-                    AppendVecAccountsIter::new(&storage.accounts).for_each(|account| {
-                        storage_scan_func(&retval, LoadedAccount::Stored(account))
-                    })
+                    storage.scan_accounts(|account| storage_scan_func(&retval, account))
                 });
             });
 
@@ -4625,11 +4850,11 @@ impl AccountsDb {
         let mut recycle_stores = self.recycle_stores.write().unwrap();
         for (i, (_recycled_time, store)) in recycle_stores.iter().enumerate() {
             if Arc::strong_count(store) == 1 {
-                max = std::cmp::max(store.accounts.capacity(), max);
-                min = std::cmp::min(store.accounts.capacity(), min);
+                max = std::cmp::max(store.total_bytes(), max);
+                min = std::cmp::min(store.total_bytes(), min);
                 avail += 1;
 
-                if store.accounts.capacity() >= min_size && store.accounts.capacity() < max_size {
+                if store.total_bytes() >= min_size && store.total_bytes() < max_size {
                     let ret = recycle_stores.remove_entry(i);
                     drop(recycle_stores);
                     let old_id = ret.append_vec_id();
@@ -4750,7 +4975,7 @@ impl AccountsDb {
         let slot_storage_r = slot_storage.read().unwrap();
         for (_id, store) in slot_storage_r.iter() {
             if store.status() == AccountStorageStatus::Available
-                && (store.accounts.capacity() - store.accounts.len() as u64) > size
+                && (store.total_bytes() - store.written_bytes()) > size
             {
                 return true;
             }
@@ -4776,10 +5001,10 @@ impl AccountsDb {
             "creating store: {} slot: {} len: {} size: {} from: {} path: {:?}",
             store.append_vec_id(),
             slot,
-            store.accounts.len(),
-            store.accounts.capacity(),
+            store.written_bytes(),
+            store.total_bytes(),
             from,
-            store.accounts.get_path()
+            store.get_path()
         );
 
         store
@@ -4881,6 +5106,10 @@ impl AccountsDb {
         for slot_entries in slot_stores {
             let entry = slot_entries.read().unwrap();
             for (_store_id, stores) in entry.iter() {
+                if stores.is_cold() {
+                    // a cold storage has no append vec of its own to reuse
+                    continue;
+                }
                 if recycle_stores.entry_count() > MAX_RECYCLE_STORES {
                     let dropped_count = total_removed_storage_entries - recycled_count;
                     self.stats
@@ -4987,7 +5216,7 @@ impl AccountsDb {
                     total_removed_storage_entries += r_slot_removed_storages.len();
                     total_removed_stored_bytes += r_slot_removed_storages
                         .values()
-                        .map(|i| i.accounts.capacity())
+                        .map(|i| i.total_bytes())
                         .sum::<u64>();
                 }
                 all_removed_slot_storages.push(slot_storages_to_be_removed.clone());
@@ -5320,7 +5549,7 @@ impl AccountsDb {
             storage_find.stop();
             total_storage_find_us += storage_find.as_us();
             let mut append_accounts = Measure::start("append_accounts");
-            let rvs = storage.accounts.append_accounts(
+            let rvs = storage.accounts().append_accounts(
                 &accounts_and_meta_to_store[infos.len()..],
                 &hashes[infos.len()..],
             );
@@ -6189,9 +6418,9 @@ impl AccountsDb {
         let mut len = storages.len();
         if len == 1 {
             // only 1 storage, so no need to interleave between multiple storages based on write_version
-            AppendVecAccountsIter::new(&storages[0].accounts).for_each(|account| {
-                if scanner.filter(&account.meta.pubkey) {
-                    scanner.found_account(&LoadedAccount::Stored(account))
+            storages[0].scan_accounts(|account| {
+                if scanner.filter(account.pubkey()) {
+                    scanner.found_account(&account)
                 }
             });
         } else {
@@ -6200,7 +6429,7 @@ impl AccountsDb {
             let mut current =
                 Vec::<(StoredMetaWriteVersion, Option<StoredAccountMeta<'_>>)>::with_capacity(len);
             for storage in storages {
-                let mut iterator = AppendVecAccountsIter::new(&storage.accounts);
+                let mut iterator = AppendVecAccountsIter::new(storage.accounts());
                 if let Some(item) = iterator
                     .next()
                     .map(|stored_account| (stored_account.meta.write_version, Some(stored_account)))
@@ -6259,7 +6488,7 @@ impl AccountsDb {
             let num_accounts = sub_storages
                 .iter()
                 .map(|storage| {
-                    if is_ancient(&storage.accounts) {
+                    if storage.is_ancient() {
                         ancients += 1;
                     }
                     storage.count()
@@ -6281,6 +6510,20 @@ impl AccountsDb {
         }
     }
 
+    /// Feed an identity of `storage`'s contents into a cache key.
+    /// Append vecs are only ever appended to, so the written length, the number of
+    /// accounts and the first stored account identify the contents. Unlike file
+    /// metadata, these survive a restart from a snapshot.
+    fn hash_storage_for_cache(storage: &AccountStorageEntry, hasher: &mut blake3::Hasher) {
+        hasher.update(&storage.written_bytes().to_le_bytes());
+        hasher.update(&(storage.approx_stored_count() as u64).to_le_bytes());
+        if let Some(first_account) = storage.get_loaded_account(0) {
+            hasher.update(first_account.pubkey().as_ref());
+            hasher.update(&first_account.write_version().to_le_bytes());
+            hasher.update(first_account.loaded_hash().as_ref());
+        }
+    }
+
     /// Scan through all the account storage in parallel
     fn scan_account_storage_no_bank<S>(
         &self,
@@ -6326,7 +6569,10 @@ impl AccountsDb {
                     return scanner.scanning_complete();
                 }
 
-                let should_cache_hash_data = CalcAccountsHashConfig::get_should_cache_hash_data();
+                // filler accounts are never rewritten, so they are rehashed like skipped rewrites
+                let should_cache_hash_data =
+                    CalcAccountsHashConfig::get_should_cache_hash_data(self.skip_rewrites)
+                        && self.filler_accounts_config.count == 0;
 
                 let eligible_for_caching =
                     !config.use_write_cache && end.saturating_sub(start) == MAX_ITEMS_PER_CHUNK;
@@ -6349,45 +6595,36 @@ impl AccountsDb {
                 // if we're using the write cache, we can't cache the hash calc results because not all accounts are in append vecs.
                 if should_cache_hash_data && eligible_for_caching {
                     let mut load_from_cache = true;
-                    let mut hasher = std::collections::hash_map::DefaultHasher::new(); // wrong one?
+                    let mut hasher = blake3::Hasher::new();
+                    hasher.update(&CACHE_HASH_DATA_FILE_VERSION.to_le_bytes());
+                    hasher.update(&(bin_range.start as u64).to_le_bytes());
+                    hasher.update(&(bin_range.end as u64).to_le_bytes());
 
                     for (slot, sub_storages) in snapshot_storages.iter_range(start..end) {
                         if bin_range.start == 0 && slot < one_epoch_old {
                             self.update_old_slot_stats(stats, sub_storages);
                         }
-                        bin_range.start.hash(&mut hasher);
-                        bin_range.end.hash(&mut hasher);
                         if let Some(sub_storages) = sub_storages {
                             if sub_storages.len() > 1 {
                                 load_from_cache = false;
                                 break;
                             }
-                            let storage_file = sub_storages.first().unwrap().accounts.get_path();
-                            slot.hash(&mut hasher);
-                            storage_file.hash(&mut hasher);
-                            // check alive_bytes, etc. here?
-                            let amod = std::fs::metadata(storage_file);
-                            if amod.is_err() {
-                                load_from_cache = false;
-                                break;
-                            }
-                            let amod = amod.unwrap().modified();
-                            if amod.is_err() {
-                                load_from_cache = false;
-                                break;
-                            }
-                            let amod = amod
-                                .unwrap()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
-                            amod.hash(&mut hasher);
+                            hasher.update(&slot.to_le_bytes());
+                            Self::hash_storage_for_cache(
+                                sub_storages.first().unwrap(),
+                                &mut hasher,
+                            );
                         }
                     }
                     if load_from_cache {
                         // we have a hash value for all the storages in this slot
                         // so, build a file name:
-                        let hash = hasher.finish();
+                        let hash = Hash::new_from_array(
+                            <[u8; solana_sdk::hash::HASH_BYTES]>::try_from(
+                                hasher.finalize().as_slice(),
+                            )
+                            .unwrap(),
+                        );
                         file_name = format!(
                             "{}.{}.{}.{}.{}",
                             start, end, bin_range.start, bin_range.end, hash
@@ -6568,6 +6805,7 @@ impl AccountsDb {
                     use_write_cache: can_cached_slot_be_unflushed,
                     epoch_schedule,
                     rent_collector,
+                    retain_hash_cache: false,
                 },
                 expected_capitalization,
             )
@@ -6695,7 +6933,10 @@ impl AccountsDb {
             let mut previous_pass = PreviousPass::default();
             let mut final_result = (Hash::default(), 0);
 
-            let cache_hash_data = CacheHashData::new(&self.accounts_hash_cache_path);
+            let mut cache_hash_data = CacheHashData::new(&self.accounts_hash_cache_path);
+            if config.retain_hash_cache {
+                cache_hash_data.retain_used_cache_files();
+            }
 
             for pass in 0..num_hash_scan_passes {
                 let bounds = Range {
@@ -6829,6 +7070,7 @@ impl AccountsDb {
                     use_write_cache: can_cached_slot_be_unflushed,
                     epoch_schedule,
                     rent_collector,
+                    retain_hash_cache: false,
                 },
                 None,
             )?;
@@ -7105,7 +7347,7 @@ impl AccountsDb {
         store: &Arc<AccountStorageEntry>,
         allow_shrink_ancient: bool,
     ) -> bool {
-        let total_bytes = if is_ancient(&store.accounts) {
+        let total_bytes = if store.is_ancient() {
             if !allow_shrink_ancient {
                 return false;
             }
@@ -7781,7 +8023,7 @@ impl AccountsDb {
             .sum();
         let mut accounts_map = GenerateIndexAccountsMap::with_capacity(num_accounts);
         storage_maps.iter().for_each(|storage| {
-            AppendVecAccountsIter::new(&storage.accounts).for_each(|stored_account| {
+            AppendVecAccountsIter::new(storage.accounts()).for_each(|stored_account| {
                 let this_version = stored_account.meta.write_version;
                 let pubkey = stored_account.meta.pubkey;
                 assert!(!self.is_filler_account(&pubkey));
@@ -8391,8 +8633,8 @@ impl AccountsDb {
                 entry.append_vec_id(),
                 *entry.count_and_status.read().unwrap(),
                 entry.approx_store_count.load(Ordering::Relaxed),
-                entry.accounts.len(),
-                entry.accounts.capacity(),
+                entry.written_bytes(),
+                entry.total_bytes(),
                 recycled_time,
             );
         }
@@ -8435,8 +8677,8 @@ impl AccountsDb {
                     id,
                     *entry.count_and_status.read().unwrap(),
                     entry.approx_store_count.load(Ordering::Relaxed),
-                    entry.accounts.len(),
-                    entry.accounts.capacity(),
+                    entry.written_bytes(),
+                    entry.total_bytes(),
                 );
             }
         }
@@ -8710,6 +8952,7 @@ pub mod tests {
                     use_write_cache: false,
                     epoch_schedule: &EpochSchedule::default(),
                     rent_collector: &RentCollector::default(),
+                    retain_hash_cache: false,
                 },
                 None,
             )
@@ -8920,6 +9163,52 @@ pub mod tests {
         assert_eq!(result, vec![expected]);
     }
 
+    #[test]
+    fn test_accountsdb_scan_snapshot_stores_reuses_cache_files() {
+        // a full chunk of slots is cached
+        let bins = 1;
+        let (storages, raw_expected) = sample_storages_and_account_in_slot(0);
+        let storage_data = vec![(&storages[0], 0)];
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let temp_dir = TempDir::new().unwrap();
+
+        let scan = |len: usize| {
+            let cache_hash_data = CacheHashData::new(&temp_dir.path());
+            let result = accounts_db
+                .scan_snapshot_stores_with_cache(
+                    &cache_hash_data,
+                    &SortedStorages::new_debug(&storage_data[..], 0, len),
+                    &mut HashStats::default(),
+                    bins,
+                    &Range {
+                        start: 0,
+                        end: bins,
+                    },
+                    &CalcAccountsHashConfig {
+                        use_bg_thread_pool: false,
+                        check_hash: false,
+                        ancestors: None,
+                        use_write_cache: false,
+                        epoch_schedule: &EpochSchedule::default(),
+                        rent_collector: &RentCollector::default(),
+                        retain_hash_cache: false,
+                    },
+                    None,
+                )
+                .unwrap();
+            assert_eq!(result[0], vec![raw_expected.clone()]);
+            let stats = cache_hash_data.stats.lock().unwrap();
+            (stats.saved_to_cache, stats.loaded_from_cache)
+        };
+
+        assert_eq!(scan(MAX_ITEMS_PER_CHUNK as usize + 1), (1, 0));
+        // the cache files of a chunk do not depend on the max slot of the calculation
+        assert_eq!(scan(MAX_ITEMS_PER_CHUNK as usize + 2), (0, 1));
+        // until a later chunk is full, too
+        assert_eq!(scan(MAX_ITEMS_PER_CHUNK as usize * 2), (1, 1));
+        assert_eq!(scan(MAX_ITEMS_PER_CHUNK as usize * 2 + 1), (0, 2));
+    }
+
     #[test]
     fn test_accountsdb_scan_snapshot_stores_2nd_chunk() {
         // enough stores to get to 2nd chunk
@@ -9099,6 +9388,7 @@ pub mod tests {
                     use_write_cache: false,
                     epoch_schedule: &EpochSchedule::default(),
                     rent_collector: &RentCollector::default(),
+                    retain_hash_cache: false,
                 },
                 &get_storage_refs(&storages),
                 HashStats::default(),
@@ -9128,6 +9418,7 @@ pub mod tests {
                     use_write_cache: false,
                     epoch_schedule: &EpochSchedule::default(),
                     rent_collector: &RentCollector::default(),
+                    retain_hash_cache: false,
                 },
                 &get_storage_refs(&storages),
                 HashStats::default(),
@@ -9202,7 +9493,7 @@ pub mod tests {
         let size: usize = 123;
         let mut data = AccountStorageEntry::new(&paths[0], slot_expected, 0, size as u64);
         let av = AppendVec::new(&tf.path, true, 1024 * 1024);
-        data.accounts = av;
+        data.set_accounts(av);
 
         let arc = Arc::new(data);
         let storages = vec![vec![arc]];
@@ -9214,7 +9505,7 @@ pub mod tests {
             write_version: 1,
         };
         storages[0][0]
-            .accounts
+            .accounts()
             .append_accounts(&[(sm, Some(&acc))], &[&Hash::default()]);
 
         let calls = Arc::new(AtomicU64::new(0));
@@ -9240,6 +9531,7 @@ pub mod tests {
                 use_write_cache: false,
                 epoch_schedule: &EpochSchedule::default(),
                 rent_collector: &RentCollector::default(),
+                retain_hash_cache: false,
             },
             &get_storage_refs(&storages),
             test_scan,
@@ -9271,7 +9563,7 @@ pub mod tests {
         let size: usize = 123;
         let mut data = AccountStorageEntry::new(&paths[0], slot_expected, 0, size as u64);
         let av = AppendVec::new(&tf.path, true, 1024 * 1024);
-        data.accounts = av;
+        data.set_accounts(av);
 
         let arc = Arc::new(data);
         let storages = vec![vec![arc]];
@@ -9283,7 +9575,7 @@ pub mod tests {
             write_version: 1,
         };
         storages[0][0]
-            .accounts
+            .accounts()
             .append_accounts(&[(sm, Some(&acc))], &[&Hash::default()]);
 
         let calls = Arc::new(AtomicU64::new(0));
@@ -9320,7 +9612,7 @@ pub mod tests {
         let size: usize = 123;
         let mut data = AccountStorageEntry::new(&paths[0], slot, 0, size as u64);
         let av = AppendVec::new(&tf.path, true, 1024 * 1024);
-        data.accounts = av;
+        data.set_accounts(av);
 
         let arc = Arc::new(data);
         let storages = vec![vec![arc]];
@@ -9331,7 +9623,7 @@ pub mod tests {
             write_version,
         };
         storages[0][0]
-            .accounts
+            .accounts()
             .append_accounts(&[(sm, Some(&acc))], &[&Hash::default()]);
         storages
     }
@@ -11282,6 +11574,7 @@ pub mod tests {
                         use_write_cache: false,
                         epoch_schedule: &EpochSchedule::default(),
                         rent_collector: &RentCollector::default(),
+                        retain_hash_cache: false,
                     },
                 )
                 .is_err());
@@ -11314,6 +11607,7 @@ pub mod tests {
                     use_write_cache: false,
                     epoch_schedule: &EpochSchedule::default(),
                     rent_collector: &RentCollector::default(),
+                    retain_hash_cache: false,
                 },
             )
             .unwrap(),
@@ -11327,6 +11621,7 @@ pub mod tests {
                     use_write_cache: false,
                     epoch_schedule: &EpochSchedule::default(),
                     rent_collector: &RentCollector::default(),
+                    retain_hash_cache: false,
                 },
             )
             .unwrap(),
@@ -12657,7 +12952,7 @@ pub mod tests {
         let slot_stores = accounts.storage.get_slot_stores(0).unwrap();
         let mut total_len = 0;
         for (_id, store) in slot_stores.read().unwrap().iter() {
-            total_len += store.accounts.len();
+            total_len += store.accounts().len();
         }
         info!("total: {}", total_len);
         assert!(total_len < STORE_META_OVERHEAD);
@@ -13087,6 +13382,88 @@ pub mod tests {
         assert_eq!(db.read_only_accounts_cache.cache_len(), 1);
     }
 
+    #[test]
+    fn test_load_from_cold_storage() {
+        let caching_enabled = true;
+        let mut db = AccountsDb::new_with_config_for_tests(
+            Vec::new(),
+            &ClusterType::Development,
+            AccountSecondaryIndexes::default(),
+            caching_enabled,
+            AccountShrinkThreshold::default(),
+        );
+        db.cold_storage_epochs = Some(1);
+
+        let account_key = Pubkey::new_unique();
+        let account = AccountSharedData::new(1, 10, AccountSharedData::default().owner());
+        db.store_cached(0, &[(&account_key, &account)]);
+        db.add_root(0);
+        db.flush_accounts_cache(true, None);
+
+        // slot 0 has not been untouched for an epoch yet
+        db.move_old_slots_to_cold_storage();
+        let storage = db.storage.get_slot_storage_entries(0).unwrap().remove(0);
+        assert!(!storage.is_cold());
+        let append_vec_path = storage.get_path();
+
+        let epoch_schedule = EpochSchedule::default();
+        db.notify_accounts_hash_calculated_complete(
+            epoch_schedule.slots_per_epoch + 1,
+            &epoch_schedule,
+        );
+        db.move_old_slots_to_cold_storage();
+        let cold = db.storage.get_slot_storage_entries(0).unwrap().remove(0);
+        assert!(cold.is_cold());
+        assert!(!cold.is_thawed());
+        assert_eq!(cold.append_vec_id(), storage.append_vec_id());
+        assert_eq!(cold.written_bytes(), storage.written_bytes());
+        assert_eq!(cold.total_bytes(), storage.total_bytes());
+        assert_eq!(cold.count(), storage.count());
+        assert_eq!(cold.status(), AccountStorageStatus::Full);
+        // the append vec is removed once nothing refers to the hot storage anymore
+        drop(storage);
+        assert!(!append_vec_path.exists());
+
+        // loads are served from the cold storage file without thawing it
+        let loaded_account = db
+            .load_with_fixed_root(&Ancestors::default(), &account_key)
+            .map(|(account, _)| account)
+            .unwrap();
+        assert_eq!(loaded_account, account);
+        // the metadata and the data of the account are in the same page
+        assert_eq!(db.cold_page_cache.get_and_reset_stats(), (1, 1));
+        assert!(!cold.is_thawed());
+
+        // operations that need the append vec thaw it into a new file
+        let all_accounts = cold.all_accounts();
+        assert_eq!(all_accounts.len(), 1);
+        assert_eq!(all_accounts[0].meta.pubkey, account_key);
+        assert!(cold.is_thawed());
+        let thawed_path = cold.get_path();
+        assert!(thawed_path.exists());
+        drop(all_accounts);
+
+        // the next pass drops the thawed append vec again, reusing the cold storage file
+        db.move_old_slots_to_cold_storage();
+        let refrozen = db.storage.get_slot_storage_entries(0).unwrap().remove(0);
+        assert!(refrozen.is_cold());
+        assert!(!refrozen.is_thawed());
+        assert!(Arc::ptr_eq(
+            refrozen.cold_storage().unwrap(),
+            cold.cold_storage().unwrap()
+        ));
+        let cold_path = ColdStorageFile::path_for(&append_vec_path);
+        drop(cold);
+        assert!(!thawed_path.exists());
+        assert!(cold_path.exists());
+
+        let loaded_account = db
+            .load_with_fixed_root(&Ancestors::default(), &account_key)
+            .map(|(account, _)| account)
+            .unwrap();
+        assert_eq!(loaded_account, account);
+    }
+
     #[test]
     fn test_flush_cache_clean() {
         let caching_enabled = true;
@@ -43,13 +43,17 @@ pub struct CalcAccountsHashConfig<'a> {
     pub use_write_cache: bool,
     pub epoch_schedule: &'a EpochSchedule,
     pub rent_collector: &'a RentCollector,
+    /// keep the cached data used by this calculation across later calculations, so that a
+    /// validator restarting from a snapshot at this slot can reuse it
+    pub retain_hash_cache: bool,
 }
 
 impl<'a> CalcAccountsHashConfig<'a> {
     /// return true if we should cache accounts hash intermediate data between calls
-    pub fn get_should_cache_hash_data() -> bool {
-        // when we are skipping rewrites, we cannot rely on the cached data from old append vecs, so we have to disable caching for now
-        false
+    pub fn get_should_cache_hash_data(skip_rewrites: bool) -> bool {
+        // skipped rewrites of old append vecs are rehashed relative to the max slot of each
+        // calculation, so their cached data could not be reused across calculations
+        !skip_rewrites
     }
 }
 
@@ -95,7 +99,7 @@ impl HashStats {
                 let result = storages
                     .iter()
                     .map(|storage| {
-                        let cap = storage.accounts.capacity() as usize;
+                        let cap = storage.total_bytes() as usize;
                         sum += cap;
                         cap
                     })
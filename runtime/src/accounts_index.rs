@@ -22,7 +22,7 @@ use {
     solana_sdk::{
         account::ReadableAccount,
         clock::{BankId, Slot},
-        pubkey::Pubkey,
+        pubkey::{Pubkey, PUBKEY_BYTES},
     },
     std::{
         collections::{btree_map::BTreeMap, HashSet},
@@ -142,6 +142,11 @@ pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    ProgramDataKey {
+        program_id: Pubkey,
+        offset: usize,
+        key: Pubkey,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -149,6 +154,24 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    /// accounts owned by 'program_id', indexed by the 32-byte key at 'offset' of their data.
+    /// All-zero keys are not indexed.
+    ProgramDataKey {
+        program_id: Pubkey,
+        offset: usize,
+    },
+}
+
+impl AccountIndex {
+    /// indexes of spl-token and spl-token-2022 accounts by delegate
+    pub fn spl_token_delegate() -> [Self; 2] {
+        [inline_spl_token::id(), inline_spl_token_2022::id()].map(|program_id| {
+            Self::ProgramDataKey {
+                program_id,
+                offset: inline_spl_token::SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET,
+            }
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -646,6 +669,7 @@ pub struct AccountsIndex<T: IndexValue> {
     program_id_index: SecondaryIndex<DashMapSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<DashMapSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    program_data_key_indexes: ProgramDataKeyIndexes,
     pub(crate) roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -688,6 +712,7 @@ impl<T: IndexValue> AccountsIndex<T> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            program_data_key_indexes: ProgramDataKeyIndexes::default(),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -921,6 +946,22 @@ impl<T: IndexValue> AccountsIndex<T> {
                     config,
                 );
             }
+            ScanTypes::Indexed(IndexKey::ProgramDataKey {
+                program_id,
+                offset,
+                key,
+            }) => {
+                if let Some(index) = self.program_data_key_indexes.get(&program_id, offset) {
+                    self.do_scan_secondary_index(
+                        ancestors,
+                        func,
+                        &index,
+                        &key,
+                        Some(max_root),
+                        config,
+                    );
+                }
+            }
         }
 
         {
@@ -1410,6 +1451,43 @@ impl<T: IndexValue> AccountsIndex<T> {
         }
     }
 
+    fn update_program_data_key_indexes(
+        &self,
+        pubkey: &Pubkey,
+        account_owner: &Pubkey,
+        account_data: &[u8],
+        account_indexes: &AccountSecondaryIndexes,
+    ) {
+        for index in &account_indexes.indexes {
+            if let AccountIndex::ProgramDataKey { program_id, offset } = index {
+                if program_id != account_owner {
+                    continue;
+                }
+                // token programs also own mints and multisigs, so only index the delegate of
+                // token accounts which have one
+                let key = if AccountIndex::spl_token_delegate().contains(index) {
+                    if *program_id == inline_spl_token::id() {
+                        inline_spl_token::Account::unpack_account_delegate(account_data).copied()
+                    } else {
+                        inline_spl_token_2022::Account::unpack_account_delegate(account_data)
+                            .copied()
+                    }
+                } else {
+                    offset
+                        .checked_add(PUBKEY_BYTES)
+                        .and_then(|end| account_data.get(*offset..end))
+                        .map(Pubkey::new)
+                };
+                if let Some(key) = key {
+                    if key != Pubkey::default() && account_indexes.include_key(&key) {
+                        self.program_data_key_indexes
+                            .insert(program_id, *offset, &key, pubkey);
+                    }
+                }
+            }
+        }
+    }
+
     /// log any secondary index counts, if non-zero
     pub(crate) fn log_secondary_indexes(&self) {
         if !self.program_id_index.index.is_empty() {
@@ -1424,6 +1502,9 @@ impl<T: IndexValue> AccountsIndex<T> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        if !self.program_data_key_indexes.is_empty() {
+            self.program_data_key_indexes.log_contents();
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1444,6 +1525,7 @@ impl<T: IndexValue> AccountsIndex<T> {
         {
             self.program_id_index.insert(account_owner, pubkey);
         }
+        self.update_program_data_key_indexes(pubkey, account_owner, account_data, account_indexes);
         // Note because of the below check below on the account data length, when an
         // account hits zero lamports and is reset to AccountSharedData::Default, then we skip
         // the below updates to the secondary indexes.
@@ -1640,6 +1722,8 @@ impl<T: IndexValue> AccountsIndex<T> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        self.program_data_key_indexes.remove_by_inner_key(inner_key);
     }
 
     fn purge_older_root_entries(
@@ -3584,6 +3668,154 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_program_data_key_secondary_index() {
+        let index = AccountsIndex::<bool>::default_for_tests();
+        let program_id = Pubkey::new_unique();
+        let offset = 8;
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::ProgramDataKey { program_id, offset }]),
+        };
+        let account_key = Pubkey::new_unique();
+        let index_key = Pubkey::new_unique();
+        let mut account_data = vec![0; offset + PUBKEY_BYTES];
+
+        // All-zero key
+        index.update_secondary_indexes(
+            &account_key,
+            &AccountSharedData::create(0, account_data.clone(), program_id, false, 0),
+            &secondary_indexes,
+        );
+        assert!(index
+            .program_data_key_indexes
+            .get(&program_id, offset)
+            .is_none());
+
+        account_data[offset..].copy_from_slice(index_key.as_ref());
+        // Wrong program id
+        index.update_secondary_indexes(
+            &account_key,
+            &AccountSharedData::create(0, account_data.clone(), Pubkey::default(), false, 0),
+            &secondary_indexes,
+        );
+        // Account data too short
+        index.update_secondary_indexes(
+            &account_key,
+            &AccountSharedData::create(0, account_data[1..].to_vec(), program_id, false, 0),
+            &secondary_indexes,
+        );
+        assert!(index
+            .program_data_key_indexes
+            .get(&program_id, offset)
+            .is_none());
+
+        // Just right
+        index.upsert(
+            0,
+            0,
+            &account_key,
+            &AccountSharedData::create(0, account_data, program_id, false, 0),
+            &secondary_indexes,
+            true,
+            &mut vec![],
+            UPSERT_PREVIOUS_SLOT_ENTRY_WAS_CACHED_FALSE,
+        );
+        let secondary_index = index
+            .program_data_key_indexes
+            .get(&program_id, offset)
+            .unwrap();
+        check_secondary_index_mapping_correct(&secondary_index, &[index_key], &account_key);
+        assert!(index
+            .program_data_key_indexes
+            .get(&program_id, offset + 1)
+            .is_none());
+
+        index.slot_list_mut(&account_key, |slot_list| slot_list.clear());
+
+        // Everything should be deleted
+        index.handle_dead_keys(&[&account_key], &secondary_indexes);
+        assert!(secondary_index.index.is_empty());
+        assert!(secondary_index.reverse_index.is_empty());
+        assert!(index.program_data_key_indexes.is_empty());
+    }
+
+    #[test]
+    fn test_spl_token_delegate_secondary_index() {
+        let index = AccountsIndex::<bool>::default_for_tests();
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: AccountIndex::spl_token_delegate().into_iter().collect(),
+        };
+        let delegate_start = inline_spl_token::SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET;
+        for token_id in [inline_spl_token::id(), inline_spl_token_2022::id()] {
+            let account_key = Pubkey::new_unique();
+            let delegate = Pubkey::new_unique();
+            let mut account_data = vec![0; inline_spl_token::Account::get_packed_len()];
+            account_data[delegate_start..delegate_start + PUBKEY_BYTES]
+                .copy_from_slice(delegate.as_ref());
+
+            // A delegate key left behind by a revoked `COption` is not indexed
+            index.update_secondary_indexes(
+                &account_key,
+                &AccountSharedData::create(0, account_data.clone(), token_id, false, 0),
+                &secondary_indexes,
+            );
+            assert!(index
+                .program_data_key_indexes
+                .get(&token_id, delegate_start)
+                .is_none());
+
+            account_data[delegate_start - 4] = 1;
+            index.update_secondary_indexes(
+                &account_key,
+                &AccountSharedData::create(0, account_data, token_id, false, 0),
+                &secondary_indexes,
+            );
+            let secondary_index = index
+                .program_data_key_indexes
+                .get(&token_id, delegate_start)
+                .unwrap();
+            check_secondary_index_mapping_correct(&secondary_index, &[delegate], &account_key);
+        }
+    }
+
+    #[test]
+    fn test_spl_token_delegate_secondary_index_skips_non_token_accounts() {
+        let index = AccountsIndex::<bool>::default_for_tests();
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: AccountIndex::spl_token_delegate().into_iter().collect(),
+        };
+        let delegate_start = inline_spl_token::SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET;
+        let junk_data = |len| {
+            let mut account_data = vec![0; len];
+            account_data[delegate_start - 4] = 1;
+            account_data[delegate_start..delegate_start + PUBKEY_BYTES]
+                .copy_from_slice(Pubkey::new_unique().as_ref());
+            account_data
+        };
+
+        // spl-token multisig
+        let multisig_len = 355;
+        // spl-token-2022 mint with extensions, whose account type follows the base account length
+        let mut mint_2022_data = junk_data(inline_spl_token::Account::get_packed_len() + 2);
+        mint_2022_data[inline_spl_token::Account::get_packed_len()] =
+            inline_spl_token_2022::ACCOUNTTYPE_ACCOUNT - 1;
+        for (token_id, account_data) in [
+            (inline_spl_token::id(), junk_data(multisig_len)),
+            (inline_spl_token_2022::id(), junk_data(multisig_len)),
+            (inline_spl_token_2022::id(), mint_2022_data),
+        ] {
+            index.update_secondary_indexes(
+                &Pubkey::new_unique(),
+                &AccountSharedData::create(0, account_data, token_id, false, 0),
+                &secondary_indexes,
+            );
+        }
+        assert!(index.program_data_key_indexes.is_empty());
+    }
+
     fn run_test_secondary_indexes_same_slot_and_forks<
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
     >(
@@ -501,6 +501,11 @@ impl AppendVec {
         self.path.clone()
     }
 
+    /// Return the bytes of all accounts written so far.
+    pub(crate) fn written_data(&self) -> &[u8] {
+        &self.map[..self.len()]
+    }
+
     /// Return account metadata for each account, starting from `offset`.
     pub fn accounts(&self, mut offset: usize) -> Vec<StoredAccountMeta> {
         let mut accounts = vec![];
@@ -108,9 +108,9 @@ use {
         epoch_schedule::EpochSchedule,
         feature,
         feature_set::{
-            self, add_set_compute_unit_price_ix, default_units_per_instruction,
-            disable_fee_calculator, nonce_must_be_writable, requestable_heap_size,
-            tx_wide_compute_cap, FeatureSet,
+            self, add_set_compute_unit_price_ix, cap_transaction_accounts_data_size,
+            default_units_per_instruction, disable_fee_calculator, enable_secp256r1_precompile,
+            nonce_must_be_writable, requestable_heap_size, tx_wide_compute_cap, FeatureSet,
         },
         fee::FeeStructure,
         fee_calculator::{FeeCalculator, FeeRateGovernor},
@@ -129,7 +129,7 @@ use {
         packet::PACKET_DATA_SIZE,
         precompiles::get_precompiles,
         pubkey::Pubkey,
-        saturating_add_assign, secp256k1_program,
+        saturating_add_assign, secp256k1_program, secp256r1_program,
         signature::{Keypair, Signature},
         slot_hashes::SlotHashes,
         slot_history::{Check, SlotHistory},
@@ -638,6 +638,8 @@ pub struct TransactionExecutionDetails {
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
+    /// Recorded along with `inner_instructions`
+    pub instruction_compute_units: Option<Vec<InstructionComputeUnits>>,
 }
 
 /// Type safe representation of a transaction execution attempt which
@@ -734,6 +736,10 @@ pub struct TransactionSimulationResult {
     pub post_simulation_accounts: Vec<TransactionAccount>,
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
+    pub inner_instructions: Option<InnerInstructionsList>,
+    /// The change in accounts data len charged to the accounts data meter
+    pub accounts_data_len_delta: i64,
+    pub instruction_compute_units: Option<Vec<InstructionComputeUnits>>,
 }
 pub struct TransactionBalancesSet {
     pub pre_balances: TransactionBalances,
@@ -788,6 +794,35 @@ pub fn inner_instructions_list_from_instruction_trace(
         .collect()
 }
 
+/// Compute units consumed by an instruction or CPI, including the instructions it invoked
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionComputeUnits {
+    pub program_id: Pubkey,
+    /// 1 for a top-level instruction, and one more for each level of CPI
+    pub stack_height: usize,
+    pub units_consumed: u64,
+}
+
+/// Collect the compute units consumed by every instruction in an InstructionTrace, in the order
+/// the instructions were invoked
+pub fn instruction_compute_units_from_instruction_trace(
+    instruction_trace: &InstructionTrace,
+    transaction_accounts: &[TransactionAccount],
+) -> Vec<InstructionComputeUnits> {
+    instruction_trace
+        .iter()
+        .flatten()
+        .map(|instruction_context| InstructionComputeUnits {
+            program_id: transaction_accounts
+                .get(instruction_context.get_program_id_index())
+                .map(|(pubkey, _)| *pubkey)
+                .unwrap_or_default(),
+            stack_height: instruction_context.get_stack_height(),
+            units_consumed: instruction_context.get_compute_units_consumed(),
+        })
+        .collect()
+}
+
 /// A list of log messages emitted during a transaction
 pub type TransactionLogMessages = Vec<String>;
 
@@ -3697,6 +3732,10 @@ impl Bank {
             self.feature_set.is_active(&tx_wide_compute_cap::id()),
             self.feature_set
                 .is_active(&add_set_compute_unit_price_ix::id()),
+            self.feature_set
+                .is_active(&cap_transaction_accounts_data_size::id()),
+            self.feature_set
+                .is_active(&enable_secp256r1_precompile::id()),
         ))
     }
 
@@ -3712,6 +3751,10 @@ impl Bank {
             self.feature_set.is_active(&tx_wide_compute_cap::id()),
             self.feature_set
                 .is_active(&add_set_compute_unit_price_ix::id()),
+            self.feature_set
+                .is_active(&cap_transaction_accounts_data_size::id()),
+            self.feature_set
+                .is_active(&enable_secp256r1_precompile::id()),
         )
     }
 
@@ -3928,10 +3971,11 @@ impl Bank {
     pub fn simulate_transaction(
         &self,
         transaction: SanitizedTransaction,
+        enable_cpi_recording: bool,
     ) -> TransactionSimulationResult {
         assert!(self.is_frozen(), "simulation bank must be frozen");
 
-        self.simulate_transaction_unchecked(transaction)
+        self.simulate_transaction_unchecked(transaction, enable_cpi_recording)
     }
 
     /// Run transactions against a bank without committing the results; does not check if the bank
@@ -3939,6 +3983,7 @@ impl Bank {
     pub fn simulate_transaction_unchecked(
         &self,
         transaction: SanitizedTransaction,
+        enable_cpi_recording: bool,
     ) -> TransactionSimulationResult {
         let account_keys = transaction.message().account_keys();
         let number_of_accounts = account_keys.len();
@@ -3956,7 +4001,7 @@ impl Bank {
             // for processing. During forwarding, the transaction could expire if the
             // delay is not accounted for.
             MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
-            false,
+            enable_cpi_recording,
             true,
             true,
             &mut timings,
@@ -3990,11 +4035,21 @@ impl Bank {
 
         let execution_result = execution_results.pop().unwrap();
         let flattened_result = execution_result.flattened_result();
-        let (logs, return_data) = match execution_result {
-            TransactionExecutionResult::Executed { details, .. } => {
-                (details.log_messages, details.return_data)
-            }
-            TransactionExecutionResult::NotExecuted(_) => (None, None),
+        let (
+            logs,
+            return_data,
+            inner_instructions,
+            accounts_data_len_delta,
+            instruction_compute_units,
+        ) = match execution_result {
+            TransactionExecutionResult::Executed { details, .. } => (
+                details.log_messages,
+                details.return_data,
+                details.inner_instructions,
+                details.accounts_data_len_delta,
+                details.instruction_compute_units,
+            ),
+            TransactionExecutionResult::NotExecuted(_) => (None, None, None, 0, None),
         };
         let logs = logs.unwrap_or_default();
 
@@ -4004,6 +4059,9 @@ impl Bank {
             post_simulation_accounts,
             units_consumed,
             return_data,
+            inner_instructions,
+            accounts_data_len_delta,
+            instruction_compute_units,
         }
     }
 
@@ -4242,6 +4300,18 @@ impl Bank {
         self.cached_executors.write().unwrap().clear();
     }
 
+    /// Overwrite `accounts` in this bank, adjusting the capitalization to match
+    ///
+    /// This mints and burns lamports at will, so it's only meant for the test validator's
+    /// admin calls that edit the state of a running local cluster.
+    pub fn store_accounts_for_test_validator(&self, accounts: &[(Pubkey, AccountSharedData)]) {
+        for (pubkey, account) in accounts {
+            self.store_account_and_update_capitalization(pubkey, account);
+        }
+        // Programs may have been replaced
+        self.clear_executors();
+    }
+
     /// Execute a transaction using the provided loaded accounts and update
     /// the executors cache if the transaction was successful.
     #[allow(clippy::too_many_arguments)]
@@ -4361,12 +4431,18 @@ impl Bank {
         } = transaction_context.into();
         loaded_transaction.accounts = accounts;
 
-        let inner_instructions = if enable_cpi_recording {
-            Some(inner_instructions_list_from_instruction_trace(
-                &instruction_trace,
-            ))
+        let (inner_instructions, instruction_compute_units) = if enable_cpi_recording {
+            (
+                Some(inner_instructions_list_from_instruction_trace(
+                    &instruction_trace,
+                )),
+                Some(instruction_compute_units_from_instruction_trace(
+                    &instruction_trace,
+                    &loaded_transaction.accounts,
+                )),
+            )
         } else {
-            None
+            (None, None)
         };
 
         let return_data = if enable_return_data_recording {
@@ -4391,6 +4467,7 @@ impl Bank {
                 return_data,
                 executed_units,
                 accounts_data_len_delta,
+                instruction_compute_units,
             },
             executors,
         }
@@ -4488,6 +4565,7 @@ impl Bank {
                                 feature_set.is_active(&requestable_heap_size::id()),
                                 feature_set.is_active(&default_units_per_instruction::id()),
                                 feature_set.is_active(&add_set_compute_unit_price_ix::id()),
+                                feature_set.is_active(&cap_transaction_accounts_data_size::id()),
                             );
                             compute_budget_process_transaction_time.stop();
                             saturating_add_assign!(
@@ -4720,12 +4798,18 @@ impl Bank {
         self.accounts_data_size_initial = amount;
     }
 
-    fn get_num_signatures_in_message(message: &SanitizedMessage) -> u64 {
+    fn get_num_signatures_in_message(
+        message: &SanitizedMessage,
+        enable_secp256r1_precompile: bool,
+    ) -> u64 {
         let mut num_signatures = u64::from(message.header().num_required_signatures);
         // This next part is really calculating the number of pre-processor
         // operations being done and treating them like a signature
         for (program_id, instruction) in message.program_instructions_iter() {
-            if secp256k1_program::check_id(program_id) || ed25519_program::check_id(program_id) {
+            if secp256k1_program::check_id(program_id)
+                || ed25519_program::check_id(program_id)
+                || (enable_secp256r1_precompile && secp256r1_program::check_id(program_id))
+            {
                 if let Some(num_verifies) = instruction.data.get(0) {
                     num_signatures = num_signatures.saturating_add(u64::from(*num_verifies));
                 }
@@ -4748,6 +4832,8 @@ impl Bank {
         fee_structure: &FeeStructure,
         tx_wide_compute_cap: bool,
         support_set_compute_unit_price_ix: bool,
+        support_set_loaded_accounts_data_size_limit_ix: bool,
+        enable_secp256r1_precompile: bool,
     ) -> u64 {
         if tx_wide_compute_cap {
             // Fee based on compute units and signatures
@@ -4766,11 +4852,13 @@ impl Bank {
                     false,
                     false,
                     support_set_compute_unit_price_ix,
+                    support_set_loaded_accounts_data_size_limit_ix,
                 )
                 .unwrap_or_default();
             let prioritization_fee = prioritization_fee_details.get_fee();
-            let signature_fee = Self::get_num_signatures_in_message(message)
-                .saturating_mul(fee_structure.lamports_per_signature);
+            let signature_fee =
+                Self::get_num_signatures_in_message(message, enable_secp256r1_precompile)
+                    .saturating_mul(fee_structure.lamports_per_signature);
             let write_lock_fee = Self::get_num_write_locks_in_message(message)
                 .saturating_mul(fee_structure.lamports_per_write_lock);
             let compute_fee = fee_structure
@@ -4794,7 +4882,10 @@ impl Bank {
                 .round() as u64
         } else {
             // Fee based only on signatures
-            lamports_per_signature.saturating_mul(Self::get_num_signatures_in_message(message))
+            lamports_per_signature.saturating_mul(Self::get_num_signatures_in_message(
+                message,
+                enable_secp256r1_precompile,
+            ))
         }
     }
 
@@ -4836,6 +4927,10 @@ impl Bank {
                     self.feature_set.is_active(&tx_wide_compute_cap::id()),
                     self.feature_set
                         .is_active(&add_set_compute_unit_price_ix::id()),
+                    self.feature_set
+                        .is_active(&cap_transaction_accounts_data_size::id()),
+                    self.feature_set
+                        .is_active(&enable_secp256r1_precompile::id()),
                 );
 
                 // In case of instruction error, even though no accounts
@@ -6824,6 +6919,12 @@ impl Bank {
         verify = verify && self.verify_hash();
         verify2_time.stop();
 
+        // rebuild the cold storage files, which are not part of the snapshot
+        self.rc
+            .accounts
+            .accounts_db
+            .update_cold_storage_max_slot(self.slot(), self.epoch_schedule());
+
         datapoint_info!(
             "verify_snapshot_bank",
             ("clean_us", clean_time.as_us(), i64),
@@ -7592,6 +7693,15 @@ pub(crate) mod tests {
                 MAX_LOCKOUT_HISTORY,
             },
         },
+        solana_zk_token_sdk::{
+            encryption::elgamal::ElGamalKeypair,
+            zk_token_proof_instruction::{
+                close_context_state, verify_pubkey_validity, ContextStateInfo, PubkeyValidityData,
+                PubkeyValidityProofContext, ZkProofData,
+            },
+            zk_token_proof_program,
+            zk_token_proof_state::{ProofContextState, ProofType},
+        },
         std::{result, sync::atomic::Ordering::Release, thread::Builder, time::Duration},
         test_utils::goto_end_of_slot,
     };
@@ -7616,6 +7726,7 @@ pub(crate) mod tests {
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                instruction_compute_units: None,
             },
             executors: Rc::new(RefCell::new(Executors::default())),
         }
@@ -8241,6 +8352,27 @@ pub(crate) mod tests {
         assert_eq!(account, bank.get_account(&pubkey).unwrap());
     }
 
+    #[test]
+    fn test_store_accounts_for_test_validator() {
+        let (genesis_config, mint_keypair) = create_genesis_config(400);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let new_pubkey = solana_sdk::pubkey::new_rand();
+        let new_account = AccountSharedData::new(300, 0, &system_program::id());
+
+        assert_capitalization_diff(
+            &bank,
+            || {
+                bank.store_accounts_for_test_validator(&[
+                    (mint_keypair.pubkey(), AccountSharedData::default()),
+                    (new_pubkey, new_account.clone()),
+                ])
+            },
+            |old, new| assert_eq!(old - 100, new),
+        );
+        assert_eq!(bank.get_account(&mint_keypair.pubkey()), None);
+        assert_eq!(bank.get_account(&new_pubkey), Some(new_account));
+    }
+
     #[test]
     fn test_rent_distribution() {
         solana_logger::setup();
@@ -10318,6 +10450,8 @@ pub(crate) mod tests {
             &FeeStructure::default(),
             true,
             true,
+            true,
+            true,
         );
 
         let (expected_fee_collected, expected_fee_burned) =
@@ -10501,6 +10635,8 @@ pub(crate) mod tests {
             &FeeStructure::default(),
             true,
             true,
+            true,
+            true,
         );
         assert_eq!(
             bank.get_balance(&mint_keypair.pubkey()),
@@ -10519,6 +10655,8 @@ pub(crate) mod tests {
             &FeeStructure::default(),
             true,
             true,
+            true,
+            true,
         );
         assert_eq!(
             bank.get_balance(&mint_keypair.pubkey()),
@@ -10636,6 +10774,8 @@ pub(crate) mod tests {
                             &FeeStructure::default(),
                             true,
                             true,
+                            true,
+                            true,
                         ) * 2
                     )
                     .0
@@ -17217,13 +17357,29 @@ pub(crate) mod tests {
         let message =
             SanitizedMessage::try_from(Message::new(&[], Some(&Pubkey::new_unique()))).unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 0, &FeeStructure::default(), false, true),
+            Bank::calculate_fee(
+                &message,
+                0,
+                &FeeStructure::default(),
+                false,
+                true,
+                true,
+                true
+            ),
             0
         );
 
         // One signature, a fee.
         assert_eq!(
-            Bank::calculate_fee(&message, 1, &FeeStructure::default(), false, true),
+            Bank::calculate_fee(
+                &message,
+                1,
+                &FeeStructure::default(),
+                false,
+                true,
+                true,
+                true
+            ),
             1
         );
 
@@ -17234,7 +17390,15 @@ pub(crate) mod tests {
         let ix1 = system_instruction::transfer(&key1, &key0, 1);
         let message = SanitizedMessage::try_from(Message::new(&[ix0, ix1], Some(&key0))).unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 2, &FeeStructure::default(), false, true),
+            Bank::calculate_fee(
+                &message,
+                2,
+                &FeeStructure::default(),
+                false,
+                true,
+                true,
+                true
+            ),
             4
         );
     }
@@ -17250,7 +17414,7 @@ pub(crate) mod tests {
         let message =
             SanitizedMessage::try_from(Message::new(&[], Some(&Pubkey::new_unique()))).unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 1, &fee_structure, true, true),
+            Bank::calculate_fee(&message, 1, &fee_structure, true, true, true, true),
             max_fee + lamports_per_signature
         );
 
@@ -17262,7 +17426,7 @@ pub(crate) mod tests {
             SanitizedMessage::try_from(Message::new(&[ix0, ix1], Some(&Pubkey::new_unique())))
                 .unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 1, &fee_structure, true, true),
+            Bank::calculate_fee(&message, 1, &fee_structure, true, true, true, true),
             max_fee + 3 * lamports_per_signature
         );
 
@@ -17295,7 +17459,7 @@ pub(crate) mod tests {
                 Some(&Pubkey::new_unique()),
             ))
             .unwrap();
-            let fee = Bank::calculate_fee(&message, 1, &fee_structure, true, true);
+            let fee = Bank::calculate_fee(&message, 1, &fee_structure, true, true, true, true);
             assert_eq!(
                 fee,
                 lamports_per_signature + prioritization_fee_details.get_fee()
@@ -17330,7 +17494,15 @@ pub(crate) mod tests {
         ))
         .unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 1, &FeeStructure::default(), false, true),
+            Bank::calculate_fee(
+                &message,
+                1,
+                &FeeStructure::default(),
+                false,
+                true,
+                true,
+                true
+            ),
             2
         );
 
@@ -17342,11 +17514,86 @@ pub(crate) mod tests {
         ))
         .unwrap();
         assert_eq!(
-            Bank::calculate_fee(&message, 1, &FeeStructure::default(), false, true),
+            Bank::calculate_fee(
+                &message,
+                1,
+                &FeeStructure::default(),
+                false,
+                true,
+                true,
+                true
+            ),
             11
         );
     }
 
+    #[test]
+    fn test_calculate_fee_secp256r1() {
+        let key0 = Pubkey::new_unique();
+        let key1 = Pubkey::new_unique();
+        let ix0 = system_instruction::transfer(&key0, &key1, 1);
+        let secp256r1_instruction = Instruction {
+            program_id: secp256r1_program::id(),
+            accounts: vec![],
+            data: vec![10],
+        };
+        let message =
+            SanitizedMessage::try_from(Message::new(&[ix0, secp256r1_instruction], Some(&key0)))
+                .unwrap();
+
+        // secp256r1 signatures are only charged for once the precompile is enabled
+        for tx_wide_compute_cap in [false, true] {
+            let fee_without_precompile = Bank::calculate_fee(
+                &message,
+                1,
+                &FeeStructure::default(),
+                tx_wide_compute_cap,
+                true,
+                true,
+                false,
+            );
+            let fee_with_precompile = Bank::calculate_fee(
+                &message,
+                1,
+                &FeeStructure::default(),
+                tx_wide_compute_cap,
+                true,
+                true,
+                true,
+            );
+            let lamports_per_signature = if tx_wide_compute_cap {
+                FeeStructure::default().lamports_per_signature
+            } else {
+                1
+            };
+            assert_eq!(
+                fee_with_precompile - fee_without_precompile,
+                10 * lamports_per_signature
+            );
+        }
+
+        // The fee charged by a bank is unchanged until the feature is activated
+        let (genesis_config, _mint_keypair) = create_genesis_config(1);
+        let mut bank = Bank::new_for_tests(&genesis_config);
+        bank.deactivate_feature(&feature_set::enable_secp256r1_precompile::id());
+        let fee_before_activation =
+            bank.get_fee_for_message_with_lamports_per_signature(&message, 1);
+        let message_without_secp256r1 = SanitizedMessage::try_from(Message::new(
+            &[system_instruction::transfer(&key0, &key1, 1)],
+            Some(&key0),
+        ))
+        .unwrap();
+        assert_eq!(
+            fee_before_activation,
+            bank.get_fee_for_message_with_lamports_per_signature(&message_without_secp256r1, 1)
+        );
+        bank.activate_feature(&feature_set::enable_secp256r1_precompile::id());
+        assert!(
+            bank.get_fee_for_message_with_lamports_per_signature(&message, 1)
+                > fee_before_activation
+        );
+    }
+
     #[test]
     fn test_an_empty_instruction_without_program() {
         let (genesis_config, mint_keypair) = create_genesis_config(1);
@@ -18319,6 +18566,184 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn test_simulate_transaction_instruction_compute_units() {
+        let (genesis_config, mint_keypair) = create_genesis_config(500);
+        let mut bank = Bank::new_for_tests(&genesis_config);
+
+        // Consumes the units in the first byte of its data, then invokes itself to consume the
+        // units in the second byte
+        fn mock_process_instruction(
+            _first_instruction_account: usize,
+            invoke_context: &mut InvokeContext,
+        ) -> result::Result<(), InstructionError> {
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context.get_current_instruction_context()?;
+            let program_id = instruction_context.get_program_id(transaction_context);
+            let instruction_data = instruction_context.get_instruction_data().to_vec();
+            invoke_context
+                .get_compute_meter()
+                .borrow_mut()
+                .consume(u64::from(instruction_data[0]))?;
+            if instruction_data[1] > 0 {
+                let instruction =
+                    Instruction::new_with_bytes(program_id, &[instruction_data[1], 0], vec![]);
+                invoke_context.native_invoke(instruction, &[])?;
+            }
+            Ok(())
+        }
+
+        let mock_program_id = Pubkey::new_unique();
+        bank.add_builtin("mock_program", &mock_program_id, mock_process_instruction);
+
+        let tx =
+            SanitizedTransaction::from_transaction_for_tests(Transaction::new_signed_with_payer(
+                &[
+                    Instruction::new_with_bytes(
+                        mock_program_id,
+                        &[100, 20],
+                        vec![AccountMeta::new_readonly(mock_program_id, false)],
+                    ),
+                    Instruction::new_with_bytes(mock_program_id, &[30, 0], vec![]),
+                ],
+                Some(&mint_keypair.pubkey()),
+                &[&mint_keypair],
+                bank.last_blockhash(),
+            ));
+
+        let result = bank.simulate_transaction_unchecked(tx.clone(), true);
+        assert_eq!(result.result, Ok(()));
+        assert_eq!(
+            result.instruction_compute_units,
+            Some(vec![
+                InstructionComputeUnits {
+                    program_id: mock_program_id,
+                    stack_height: 1,
+                    units_consumed: 120,
+                },
+                InstructionComputeUnits {
+                    program_id: mock_program_id,
+                    stack_height: 2,
+                    units_consumed: 20,
+                },
+                InstructionComputeUnits {
+                    program_id: mock_program_id,
+                    stack_height: 1,
+                    units_consumed: 30,
+                },
+            ])
+        );
+
+        let result = bank.simulate_transaction_unchecked(tx, false);
+        assert_eq!(result.instruction_compute_units, None);
+    }
+
+    #[test]
+    fn test_zk_token_proof_inner_instructions() {
+        let (mut genesis_config, mint_keypair) = create_genesis_config(1_000_000);
+        activate_all_features(&mut genesis_config);
+        let mut bank = Bank::new_for_tests(&genesis_config);
+
+        // Invokes the ZkToken Proof program with its own instruction data and accounts, except for
+        // the last account which is the ZkToken Proof program itself
+        fn mock_process_instruction(
+            _first_instruction_account: usize,
+            invoke_context: &mut InvokeContext,
+        ) -> result::Result<(), InstructionError> {
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context.get_current_instruction_context()?;
+            let number_of_accounts = instruction_context
+                .get_number_of_instruction_accounts()
+                .saturating_sub(1);
+            let accounts = (0..number_of_accounts)
+                .map(|index_in_instruction| {
+                    let account = instruction_context.try_borrow_instruction_account(
+                        transaction_context,
+                        index_in_instruction,
+                    )?;
+                    Ok(AccountMeta {
+                        pubkey: *account.get_key(),
+                        is_signer: account.is_signer(),
+                        is_writable: account.is_writable(),
+                    })
+                })
+                .collect::<result::Result<Vec<_>, InstructionError>>()?;
+            let instruction = Instruction {
+                program_id: zk_token_proof_program::id(),
+                accounts,
+                data: instruction_context.get_instruction_data().to_vec(),
+            };
+            invoke_context.native_invoke(instruction, &[])
+        }
+
+        let mock_program_id = Pubkey::new_unique();
+        bank.add_builtin("mock_program", &mock_program_id, mock_process_instruction);
+        let mock_instruction = |instruction: Instruction, signers: &[&Keypair]| {
+            let mut accounts = instruction.accounts;
+            accounts.push(AccountMeta::new_readonly(
+                zk_token_proof_program::id(),
+                false,
+            ));
+            let mut all_signers = vec![&mint_keypair];
+            all_signers.extend_from_slice(signers);
+            Transaction::new_signed_with_payer(
+                &[Instruction::new_with_bytes(
+                    mock_program_id,
+                    &instruction.data,
+                    accounts,
+                )],
+                Some(&mint_keypair.pubkey()),
+                &all_signers,
+                bank.last_blockhash(),
+            )
+        };
+
+        // Proofs cannot be verified by other programs
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data = PubkeyValidityData::new(&keypair).unwrap();
+        assert_eq!(
+            bank.process_transaction(&mock_instruction(
+                verify_pubkey_validity(None, &proof_data),
+                &[]
+            )),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::UnsupportedProgramId
+            ))
+        );
+
+        // ... but proof context accounts can be closed by other programs on behalf of the
+        // authority
+        let context_state_account = Pubkey::new_unique();
+        let context_state_authority = Keypair::new();
+        let destination_account = Pubkey::new_unique();
+        let space = mem::size_of::<ProofContextState<PubkeyValidityProofContext>>();
+        let lamports = bank.get_minimum_balance_for_rent_exemption(space);
+        let mut account = AccountSharedData::new(lamports, space, &zk_token_proof_program::id());
+        account.set_data(ProofContextState::encode(
+            &context_state_authority.pubkey(),
+            ProofType::PubkeyValidity,
+            proof_data.context_data(),
+        ));
+        bank.store_account(&context_state_account, &account);
+
+        assert_eq!(
+            bank.process_transaction(&mock_instruction(
+                close_context_state(
+                    ContextStateInfo {
+                        context_state_account: &context_state_account,
+                        context_state_authority: &context_state_authority.pubkey(),
+                    },
+                    &destination_account,
+                ),
+                &[&context_state_authority]
+            )),
+            Ok(())
+        );
+        assert_eq!(bank.get_account(&context_state_account), None);
+        assert_eq!(bank.get_balance(&destination_account), lamports);
+    }
+
     #[derive(Serialize, Deserialize)]
     enum MockReallocInstruction {
         Realloc(usize, u64, Pubkey),
@@ -3,7 +3,8 @@
 use {
     lazy_static::lazy_static,
     solana_sdk::{
-        feature, incinerator, native_loader, pubkey::Pubkey, secp256k1_program, system_program,
+        feature, incinerator, native_loader, pubkey::Pubkey, secp256k1_program, secp256r1_program,
+        system_program,
     },
     std::collections::HashMap,
 };
@@ -27,6 +28,11 @@ pub const SIGNATURE_COST: u64 = COMPUTE_UNIT_TO_US_RATIO * 24;
 pub const WRITE_LOCK_UNITS: u64 = COMPUTE_UNIT_TO_US_RATIO * 10;
 /// Number of data bytes per compute units
 pub const DATA_BYTES_UNITS: u64 = 550 /*bytes per us*/ / COMPUTE_UNIT_TO_US_RATIO;
+/// Number of bytes of the requested loaded accounts data size limit per charged page
+pub const LOADED_ACCOUNTS_DATA_SIZE_PAGE_BYTES: u64 = 32 * 1024;
+/// Number of compute units for each page of the requested loaded accounts data size limit,
+/// the same as each additional 32k of requested heap
+pub const LOADED_ACCOUNTS_DATA_SIZE_PAGE_UNITS: u64 = 8;
 // Number of compute units for each built-in programs
 lazy_static! {
     /// Number of compute units for each built-in programs
@@ -40,6 +46,9 @@ lazy_static! {
         (solana_vote_program::id(), COMPUTE_UNIT_TO_US_RATIO * 70),
         // secp256k1 is executed in banking stage, it should cost similar to sigverify
         (secp256k1_program::id(), COMPUTE_UNIT_TO_US_RATIO * 24),
+        // secp256r1 is executed in banking stage, priced for the maximum number of signatures
+        // an instruction can verify
+        (secp256r1_program::id(), COMPUTE_UNIT_TO_US_RATIO * 160),
         (system_program::id(), COMPUTE_UNIT_TO_US_RATIO * 5),
     ]
     .iter()
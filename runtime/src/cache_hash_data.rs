@@ -20,9 +20,16 @@ pub type EntryType = CalculateHashIntermediate;
 pub type SavedType = Vec<Vec<EntryType>>;
 pub type SavedTypeSlice = [Vec<EntryType>];
 
+/// Bump whenever the layout of `Header` or `EntryType` changes, so files written by
+/// another version of the validator are discarded instead of being misread.
+pub const CACHE_HASH_DATA_FILE_VERSION: u64 = 1;
+
 #[repr(C)]
 pub struct Header {
+    version: u64,
     count: usize,
+    /// blake3 hash of the entries following the header
+    checksum: [u8; 32],
 }
 
 struct CacheHashDataFile {
@@ -50,6 +57,13 @@ impl CacheHashDataFile {
         }
     }
 
+    /// the raw bytes of all entries following the header
+    fn entries_bytes(&self, count: usize) -> &[u8] {
+        let start = std::mem::size_of::<Header>();
+        let end = start + count * self.cell_size as usize;
+        &self.mmap[start..end]
+    }
+
     fn get_header_mut(&mut self) -> &mut Header {
         let start = 0_usize;
         let end = start + std::mem::size_of::<Header>();
@@ -88,10 +102,19 @@ impl CacheHashDataFile {
     }
 }
 
+/// Name of the file listing the retained cache files, one per line
+const RETAINED_FILES_FILE_NAME: &str = "retained_files";
+
 pub type PreExistingCacheFiles = HashSet<String>;
 pub struct CacheHashData {
     cache_folder: PathBuf,
     pre_existing_cache_files: Arc<Mutex<PreExistingCacheFiles>>,
+    /// cache files loaded or saved by this calculation
+    used_cache_files: Mutex<HashSet<String>>,
+    /// cache files of the last retained calculation, which are kept even when unused
+    retained_cache_files: HashSet<String>,
+    /// true if the cache files used by this calculation replace the retained cache files
+    retain_used_cache_files: bool,
     pub stats: Arc<Mutex<CacheHashDataStats>>,
 }
 
@@ -109,24 +132,73 @@ impl CacheHashData {
         std::fs::create_dir_all(cache_folder.clone())
             .unwrap_or_else(|_| panic!("error creating cache dir: {:?}", cache_folder));
 
+        let retained_cache_files = fs::read_to_string(cache_folder.join(RETAINED_FILES_FILE_NAME))
+            .map(|names| names.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+
         let result = CacheHashData {
             cache_folder,
             pre_existing_cache_files: Arc::new(Mutex::new(PreExistingCacheFiles::default())),
+            used_cache_files: Mutex::default(),
+            retained_cache_files,
+            retain_used_cache_files: false,
             stats: Arc::new(Mutex::new(CacheHashDataStats::default())),
         };
 
         result.get_cache_files();
         result
     }
+
+    /// Keep the cache files used by this calculation until another calculation is retained,
+    /// even if later calculations do not use them.
+    /// A validator restarting from the snapshot this calculation is for can then reuse them.
+    pub fn retain_used_cache_files(&mut self) {
+        self.retain_used_cache_files = true;
+    }
+
+    /// mark 'file_name' as used by this calculation
+    fn use_cache_file(&self, file_name: &str) {
+        self.pre_existing_cache_files
+            .lock()
+            .unwrap()
+            .remove(file_name);
+        self.used_cache_files
+            .lock()
+            .unwrap()
+            .insert(file_name.to_string());
+    }
+
     fn delete_old_cache_files(&self) {
+        let retained_cache_files = if self.retain_used_cache_files {
+            let used_cache_files = self.used_cache_files.lock().unwrap();
+            let path = self.cache_folder.join(RETAINED_FILES_FILE_NAME);
+            let names = used_cache_files
+                .iter()
+                .map(|name| format!("{}\n", name))
+                .collect::<String>();
+            if let Err(err) = fs::write(&path, names) {
+                info!("failed to write {:?}: {}", path, err);
+            }
+            // the previously retained cache files are no longer needed
+            None
+        } else {
+            Some(&self.retained_cache_files)
+        };
+
         let pre_existing_cache_files = self.pre_existing_cache_files.lock().unwrap();
-        if !pre_existing_cache_files.is_empty() {
-            self.stats.lock().unwrap().unused_cache_files += pre_existing_cache_files.len();
-            for file_name in pre_existing_cache_files.iter() {
-                let result = self.cache_folder.join(file_name);
-                let _ = fs::remove_file(result);
+        let mut unused_cache_files = 0;
+        for file_name in pre_existing_cache_files.iter() {
+            if retained_cache_files
+                .map(|retained_cache_files| retained_cache_files.contains(file_name))
+                .unwrap_or_default()
+            {
+                continue;
             }
+            unused_cache_files += 1;
+            let result = self.cache_folder.join(file_name);
+            let _ = fs::remove_file(result);
         }
+        self.stats.lock().unwrap().unused_cache_files += unused_cache_files;
     }
     fn get_cache_files(&self) {
         if self.cache_folder.is_dir() {
@@ -135,7 +207,10 @@ impl CacheHashData {
                 let mut pre_existing = self.pre_existing_cache_files.lock().unwrap();
                 for entry in dir.flatten() {
                     if let Some(name) = entry.path().file_name() {
-                        pre_existing.insert(name.to_str().unwrap().to_string());
+                        let name = name.to_str().unwrap();
+                        if name != RETAINED_FILES_FILE_NAME {
+                            pre_existing.insert(name.to_string());
+                        }
                     }
                 }
                 self.stats.lock().unwrap().cache_file_count += pre_existing.len();
@@ -194,35 +269,44 @@ impl CacheHashData {
             capacity: 0,
         };
         let header = cache_file.get_header_mut();
+        let version = header.version;
         let entries = header.count;
-
-        let capacity = cell_size * (entries as u64) + header_size;
-        if file_len < capacity {
-            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        let checksum = header.checksum;
+
+        // Cache files outlive the process that wrote them. Anything that was written by
+        // another version or was not completely written is removed and recalculated.
+        let capacity = cell_size
+            .checked_mul(entries as u64)
+            .and_then(|size| size.checked_add(header_size));
+        if version != CACHE_HASH_DATA_FILE_VERSION || capacity != Some(file_len) {
+            info!(
+                "discarding cache file {:?}: version: {}, entries: {}, len on disk: {}",
+                path, version, entries, file_len
+            );
+            stats.invalid_cache_files += 1;
+            drop(cache_file);
+            let _ = remove_file(&path);
+            return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
         }
+        let capacity = file_len;
         cache_file.capacity = capacity;
-        assert_eq!(
-            capacity, file_len,
-            "expected: {}, len on disk: {} {:?}, entries: {}, cell_size: {}",
-            capacity, file_len, path, entries, cell_size
-        );
+
+        let mut m_verify = Measure::start("verify");
+        let computed_checksum = blake3::hash(cache_file.entries_bytes(entries));
+        m_verify.stop();
+        stats.verify_us += m_verify.as_us();
+        if computed_checksum.as_bytes() != &checksum {
+            info!("discarding cache file {:?}: checksum mismatch", path);
+            stats.invalid_cache_files += 1;
+            drop(cache_file);
+            let _ = remove_file(&path);
+            return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
+        }
 
         stats.total_entries = entries;
         stats.cache_file_size += capacity as usize;
 
-        let file_name_lookup = file_name.as_ref().to_str().unwrap().to_string();
-        let found = self
-            .pre_existing_cache_files
-            .lock()
-            .unwrap()
-            .remove(&file_name_lookup);
-        if !found {
-            info!(
-                "tried to mark {:?} as used, but it wasn't in the set, one example: {:?}",
-                file_name_lookup,
-                self.pre_existing_cache_files.lock().unwrap().iter().next()
-            );
-        }
+        self.use_cache_file(file_name.as_ref().to_str().unwrap());
 
         stats.loaded_from_cache += 1;
         stats.entries_loaded_from_cache += entries;
@@ -286,6 +370,7 @@ impl CacheHashData {
         };
 
         let mut header = cache_file.get_header_mut();
+        header.version = CACHE_HASH_DATA_FILE_VERSION;
         header.count = entries;
 
         stats.cache_file_size = capacity as usize;
@@ -301,11 +386,16 @@ impl CacheHashData {
             })
         });
         assert_eq!(i, entries);
+        // the checksum is written last so a partially written file never validates
+        let checksum = *blake3::hash(cache_file.entries_bytes(entries)).as_bytes();
+        cache_file.get_header_mut().checksum = checksum;
+        cache_file.mmap.flush()?;
         m2.stop();
         stats.write_to_mmap_us += m2.as_us();
         m.stop();
         stats.save_us += m.as_us();
         stats.saved_to_cache += 1;
+        self.use_cache_file(file_name.to_str().unwrap());
         Ok(())
     }
 }
@@ -386,6 +476,103 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_load_rejects_invalid_files() {
+        let tmpdir = tempfile::TempDir::new().unwrap();
+        let bin_calculator = PubkeyBinCalculator24::new(1);
+        let (data, _total_points) = generate_test_data(5, 1, &bin_calculator);
+        let cache = CacheHashData::new(&tmpdir);
+        let cache_folder = CacheHashData::get_cache_root_path(&tmpdir);
+        let file = Path::new("test");
+
+        let corrupt = |offset_from_end: usize, file_version: Option<u64>| {
+            cache.save(file, &data).unwrap();
+            let path = cache_folder.join(file);
+            let mut contents = fs::read(&path).unwrap();
+            if let Some(file_version) = file_version {
+                contents[..std::mem::size_of::<u64>()].copy_from_slice(&file_version.to_ne_bytes());
+            } else {
+                let len = contents.len();
+                contents[len - offset_from_end] ^= 0xff;
+            }
+            fs::write(&path, contents).unwrap();
+        };
+
+        // entries that do not match the checksum
+        corrupt(1, None);
+        let mut accum = vec![vec![]];
+        assert!(cache.load(&file, &mut accum, 0, &bin_calculator).is_err());
+        assert_eq!(accum, vec![vec![]]);
+        assert!(!cache_folder.join(file).exists());
+
+        // written by another version
+        corrupt(1, Some(CACHE_HASH_DATA_FILE_VERSION + 1));
+        assert!(cache.load(&file, &mut accum, 0, &bin_calculator).is_err());
+        assert!(!cache_folder.join(file).exists());
+
+        // truncated
+        cache.save(file, &data).unwrap();
+        let path = cache_folder.join(file);
+        let contents = fs::read(&path).unwrap();
+        fs::write(&path, &contents[..contents.len() - 1]).unwrap();
+        assert!(cache.load(&file, &mut accum, 0, &bin_calculator).is_err());
+        assert_eq!(cache.stats.lock().unwrap().invalid_cache_files, 3);
+
+        // and a valid file still loads
+        cache.save(file, &data).unwrap();
+        cache.load(&file, &mut accum, 0, &bin_calculator).unwrap();
+        assert_eq!(accum, data);
+    }
+
+    #[test]
+    fn test_retain_used_cache_files() {
+        let tmpdir = tempfile::TempDir::new().unwrap();
+        let bin_calculator = PubkeyBinCalculator24::new(1);
+        let (data, _total_points) = generate_test_data(5, 1, &bin_calculator);
+        let cache_folder = CacheHashData::get_cache_root_path(&tmpdir);
+        let retained_file = Path::new("retained");
+        let other_file = Path::new("other");
+
+        {
+            let mut cache = CacheHashData::new(&tmpdir);
+            cache.retain_used_cache_files();
+            cache.save(retained_file, &data).unwrap();
+        }
+        {
+            // unused retained files survive a calculation that is not retained
+            let cache = CacheHashData::new(&tmpdir);
+            assert!(cache
+                .retained_cache_files
+                .contains(retained_file.to_str().unwrap()));
+            cache.save(other_file, &data).unwrap();
+        }
+        assert!(cache_folder.join(retained_file).exists());
+        assert!(cache_folder.join(other_file).exists());
+        {
+            // files used by the next retained calculation replace them
+            let mut cache = CacheHashData::new(&tmpdir);
+            cache.retain_used_cache_files();
+            let mut accum = vec![vec![]];
+            cache
+                .load(&other_file, &mut accum, 0, &bin_calculator)
+                .unwrap();
+        }
+        assert!(!cache_folder.join(retained_file).exists());
+        assert!(cache_folder.join(other_file).exists());
+        {
+            // a file that is saved again after it failed to load is used
+            fs::write(cache_folder.join(retained_file), [0u8; 4]).unwrap();
+            let cache = CacheHashData::new(&tmpdir);
+            let mut accum = vec![vec![]];
+            assert!(cache
+                .load(&retained_file, &mut accum, 0, &bin_calculator)
+                .is_err());
+            cache.save(retained_file, &data).unwrap();
+        }
+        assert!(cache_folder.join(retained_file).exists());
+        assert!(cache_folder.join(RETAINED_FILES_FILE_NAME).exists());
+    }
+
     fn bin_data(
         data: &mut SavedType,
         bin_calculator: &PubkeyBinCalculator24,
@@ -15,6 +15,8 @@ pub struct CacheHashDataStats {
     pub decode_us: u64,
     pub merge_us: u64,
     pub unused_cache_files: usize,
+    pub invalid_cache_files: usize,
+    pub verify_us: u64,
 }
 
 impl CacheHashDataStats {
@@ -32,6 +34,8 @@ impl CacheHashDataStats {
         self.cache_file_count += other.cache_file_count;
         self.write_to_mmap_us += other.write_to_mmap_us;
         self.unused_cache_files += other.unused_cache_files;
+        self.invalid_cache_files += other.invalid_cache_files;
+        self.verify_us += other.verify_us;
     }
 
     pub fn report(&self) {
@@ -54,6 +58,8 @@ impl CacheHashDataStats {
             ("read_us", self.read_us, i64),
             ("decode_us", self.decode_us, i64),
             ("unused_cache_files", self.unused_cache_files, i64),
+            ("invalid_cache_files", self.invalid_cache_files, i64),
+            ("verify_us", self.verify_us, i64),
         );
     }
 }
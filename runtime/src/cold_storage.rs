@@ -0,0 +1,704 @@
+//! Cold storage holds the accounts of storages that have not been written to for a number of epochs.
+//! The written contents of such an append vec are compressed page by page into a file which is read
+//! on demand, and the append vec and its file are dropped.
+//! Decompressed pages are kept in a `ColdPageCache`, a size-bounded LRU shared by all cold storage files.
+//! Operations that need the append vec itself 'thaw' a cold storage file back into a temporary append vec.
+//! Snapshot archives stream the contents of cold storage files instead, see `ColdStorageFile::reader`.
+use {
+    crate::append_vec::{
+        AccountMeta, AppendVec, StoredAccountMeta, StoredMeta, ALIGN_BOUNDARY_OFFSET,
+    },
+    index_list::{Index, IndexList},
+    log::*,
+    solana_measure::measure::Measure,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Epoch,
+        hash::Hash,
+        pubkey::Pubkey,
+        timing::AtomicInterval,
+    },
+    std::{
+        collections::HashMap,
+        fs::{remove_file, File, OpenOptions},
+        io::{self, Read, Seek, SeekFrom, Write},
+        mem,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    },
+};
+
+/// number of uncompressed bytes of an append vec in each compressed page
+pub const COLD_STORAGE_PAGE_SIZE: usize = 64 * 1024;
+
+/// zstd compression level of cold storage pages. 0 selects zstd's default.
+const COLD_STORAGE_COMPRESSION_LEVEL: i32 = 0;
+
+/// Pages are identified by the cold storage file they belong to and their index in that file.
+/// File ids are never reused, so pages of dropped files age out of the cache without being read again.
+type ColdPageKey = (u64, usize);
+
+static NEXT_COLD_STORAGE_FILE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Thawed append vecs get unique paths, so a thawed append vec that is still referenced never shares
+/// its file with one thawed later
+static NEXT_THAWED_APPEND_VEC_ID: AtomicU64 = AtomicU64::new(0);
+
+fn u64_align(addr: usize) -> usize {
+    (addr + (ALIGN_BOUNDARY_OFFSET - 1)) & !(ALIGN_BOUNDARY_OFFSET - 1)
+}
+
+/// An account loaded from a cold storage file, owning its data
+#[derive(Debug, Clone)]
+pub struct ColdAccount {
+    pub pubkey: Pubkey,
+    pub write_version: u64,
+    pub hash: Hash,
+    pub stored_size: usize,
+    pub account: AccountSharedData,
+}
+
+impl ReadableAccount for ColdAccount {
+    fn lamports(&self) -> u64 {
+        self.account.lamports()
+    }
+    fn data(&self) -> &[u8] {
+        self.account.data()
+    }
+    fn owner(&self) -> &Pubkey {
+        self.account.owner()
+    }
+    fn executable(&self) -> bool {
+        self.account.executable()
+    }
+    fn rent_epoch(&self) -> Epoch {
+        self.account.rent_epoch()
+    }
+}
+
+/// The compressed contents of an append vec.
+/// The file is removed when this is dropped.
+#[derive(Debug)]
+pub struct ColdStorageFile {
+    id: u64,
+    path: PathBuf,
+    /// path of the append vec this was created from, which thawed append vecs are named after
+    append_vec_path: PathBuf,
+    file: Mutex<File>,
+    /// number of bytes of the append vec this was created from
+    len: usize,
+    /// file size of the append vec this was created from
+    capacity: u64,
+    /// page 'i' is stored at 'page_offsets[i]..page_offsets[i + 1]' in the file
+    page_offsets: Vec<u64>,
+    cache: Arc<ColdPageCache>,
+    stats: Arc<ColdStorageStats>,
+}
+
+impl Drop for ColdStorageFile {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.path);
+    }
+}
+
+impl ColdStorageFile {
+    /// the path of the cold storage file for the append vec at 'append_vec_path'
+    pub fn path_for(append_vec_path: &Path) -> PathBuf {
+        let mut path = append_vec_path.as_os_str().to_os_string();
+        path.push(".cold");
+        PathBuf::from(path)
+    }
+
+    /// Compress everything written to 'append_vec' so far into a new file at 'path'.
+    /// Accounts appended afterwards are not part of this file.
+    pub fn new(
+        path: &Path,
+        append_vec: &AppendVec,
+        cache: Arc<ColdPageCache>,
+        stats: Arc<ColdStorageStats>,
+    ) -> io::Result<Self> {
+        let mut m = Measure::start("create_cold_storage");
+        let data = append_vec.written_data();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        let mut page_offsets = Vec::with_capacity(data.len() / COLD_STORAGE_PAGE_SIZE + 2);
+        let mut file_offset = 0;
+        page_offsets.push(file_offset);
+        for page in data.chunks(COLD_STORAGE_PAGE_SIZE) {
+            let compressed = zstd::bulk::compress(page, COLD_STORAGE_COMPRESSION_LEVEL)?;
+            file.write_all(&compressed)?;
+            file_offset += compressed.len() as u64;
+            page_offsets.push(file_offset);
+        }
+        file.flush()?;
+        m.stop();
+
+        stats.files_created.fetch_add(1, Ordering::Relaxed);
+        stats.create_us.fetch_add(m.as_us(), Ordering::Relaxed);
+        stats
+            .uncompressed_bytes
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        stats
+            .compressed_bytes
+            .fetch_add(file_offset, Ordering::Relaxed);
+
+        Ok(Self {
+            id: NEXT_COLD_STORAGE_FILE_ID.fetch_add(1, Ordering::Relaxed),
+            path: path.to_path_buf(),
+            append_vec_path: append_vec.get_path(),
+            file: Mutex::new(file),
+            len: data.len(),
+            capacity: append_vec.capacity(),
+            page_offsets,
+            cache,
+            stats,
+        })
+    }
+
+    /// Decompress this file into a new append vec with the accounts and the capacity of the append vec
+    /// this file was created from. The append vec's file is removed when it is dropped.
+    pub fn thaw(&self) -> io::Result<AppendVec> {
+        let mut m = Measure::start("thaw_cold_storage");
+        let mut path = self.append_vec_path.as_os_str().to_os_string();
+        path.push(format!(
+            ".thawed.{}",
+            NEXT_THAWED_APPEND_VEC_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let path = PathBuf::from(path);
+
+        let result = (|| {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)?;
+            for index in 0..self.num_pages() {
+                file.write_all(&self.read_page(index)?)?;
+            }
+            file.set_len(self.capacity)?;
+            file.flush()?;
+            drop(file);
+            AppendVec::new_from_file(&path, self.len).map(|(append_vec, _num_accounts)| append_vec)
+        })();
+        if result.is_err() {
+            let _ = remove_file(&path);
+        }
+        m.stop();
+        self.stats.files_thawed.fetch_add(1, Ordering::Relaxed);
+        self.stats.thaw_us.fetch_add(m.as_us(), Ordering::Relaxed);
+        result
+    }
+
+    /// number of uncompressed bytes in this file
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// file size of the append vec this file was created from
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn num_pages(&self) -> usize {
+        self.page_offsets.len() - 1
+    }
+
+    /// read and decompress page 'index' from the file
+    fn read_page(&self, index: usize) -> io::Result<Vec<u8>> {
+        let start = self.page_offsets[index];
+        let end = self.page_offsets[index + 1];
+        let mut compressed = vec![0; (end - start) as usize];
+
+        let mut m = Measure::start("read_page");
+        {
+            let mut file = self.file.lock().unwrap();
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut compressed)?;
+        }
+        m.stop();
+        self.stats.read_us.fetch_add(m.as_us(), Ordering::Relaxed);
+
+        let mut m = Measure::start("decompress_page");
+        let page = zstd::bulk::decompress(&compressed, COLD_STORAGE_PAGE_SIZE)?;
+        m.stop();
+        self.stats
+            .decompress_us
+            .fetch_add(m.as_us(), Ordering::Relaxed);
+        self.stats.pages_read.fetch_add(1, Ordering::Relaxed);
+        Ok(page)
+    }
+
+    fn get_page(&self, index: usize) -> Option<Arc<Vec<u8>>> {
+        let key = (self.id, index);
+        if let Some(page) = self.cache.load(key) {
+            return Some(page);
+        }
+        let page = match self.read_page(index) {
+            Ok(page) => Arc::new(page),
+            Err(err) => {
+                error!(
+                    "failed to read page {} of cold storage file {:?}: {}",
+                    index, self.path, err
+                );
+                return None;
+            }
+        };
+        self.cache.store(key, Arc::clone(&page));
+        Some(page)
+    }
+
+    /// Copy 'dst.len()' bytes starting at 'offset' of the uncompressed contents into 'dst'.
+    /// Return None if the range is not within this file.
+    fn read_bytes(&self, offset: usize, dst: &mut [u8]) -> Option<()> {
+        let end = offset.checked_add(dst.len())?;
+        if end > self.len {
+            return None;
+        }
+        let mut position = offset;
+        while position < end {
+            let index = position / COLD_STORAGE_PAGE_SIZE;
+            if index >= self.num_pages() {
+                return None;
+            }
+            let page = self.get_page(index)?;
+            let page_start = index * COLD_STORAGE_PAGE_SIZE;
+            let start_in_page = position - page_start;
+            let end_in_page = std::cmp::min(end - page_start, page.len());
+            if start_in_page >= end_in_page {
+                return None;
+            }
+            let copied = end_in_page - start_in_page;
+            let dst_start = position - offset;
+            dst[dst_start..dst_start + copied].copy_from_slice(&page[start_in_page..end_in_page]);
+            position += copied;
+        }
+        Some(())
+    }
+
+    /// Read the contents of the append vec this file was created from, as its file would read,
+    /// without thawing it. Pages are decompressed one at a time and bypass the page cache.
+    pub fn reader(&self) -> ColdStorageReader<'_> {
+        ColdStorageReader {
+            cold_storage: self,
+            next_page: 0,
+            page: io::Cursor::default(),
+            // the rest of the append vec's file is zeros
+            padding: io::repeat(0).take(self.capacity.saturating_sub(self.len as u64)),
+        }
+    }
+
+    /// Iterate over the accounts of this file, in the order they were written
+    pub fn accounts(&self) -> impl Iterator<Item = ColdAccount> + '_ {
+        let mut offset = 0;
+        std::iter::from_fn(move || {
+            let account = self.load_account(offset)?;
+            offset += account.stored_size;
+            Some(account)
+        })
+    }
+
+    /// Return the account stored at 'offset' of the append vec this file was created from.
+    /// Return None if no account was written at 'offset' before this file was created.
+    pub fn load_account(&self, offset: usize) -> Option<ColdAccount> {
+        // accounts are written at aligned offsets in the same layout as 'AppendVec::get_account'
+        if offset % ALIGN_BOUNDARY_OFFSET != 0 {
+            return None;
+        }
+        let account_meta_offset = u64_align(offset.checked_add(mem::size_of::<StoredMeta>())?);
+        let hash_offset = u64_align(account_meta_offset + mem::size_of::<AccountMeta>());
+        let data_offset = u64_align(hash_offset + mem::size_of::<Hash>());
+
+        // read the fixed size part of the account first, which contains the data length.
+        // A u64 buffer keeps the metadata as aligned as it is in the append vec.
+        let mut header = vec![0_u64; (data_offset - offset) / mem::size_of::<u64>()];
+        self.read_bytes(offset, bytemuck::cast_slice_mut(&mut header))?;
+        let header: &[u8] = bytemuck::cast_slice(&header);
+        //UNSAFE: The buffer is aligned and large enough for each type. The bytes were written
+        //by the append vec this file was created from, whose accounts have been sanitized.
+        let (meta, account_meta, hash) = unsafe {
+            (
+                &*(header.as_ptr() as *const StoredMeta),
+                &*(header[account_meta_offset - offset..].as_ptr() as *const AccountMeta),
+                &*(header[hash_offset - offset..].as_ptr() as *const Hash),
+            )
+        };
+
+        let data_len = usize::try_from(meta.data_len).ok()?;
+        let mut data = vec![0; data_len];
+        self.read_bytes(data_offset, &mut data)?;
+        self.stats.accounts_loaded.fetch_add(1, Ordering::Relaxed);
+
+        let stored_size = u64_align(data_offset + data_len) - offset;
+        let account = StoredAccountMeta {
+            meta,
+            account_meta,
+            data: &data,
+            offset,
+            stored_size,
+            hash,
+        };
+        Some(ColdAccount {
+            pubkey: meta.pubkey,
+            write_version: meta.write_version,
+            hash: *hash,
+            stored_size,
+            account: account.clone_account(),
+        })
+    }
+}
+
+/// Reader of the uncompressed contents of a cold storage file, see `ColdStorageFile::reader`
+pub struct ColdStorageReader<'a> {
+    cold_storage: &'a ColdStorageFile,
+    next_page: usize,
+    page: io::Cursor<Vec<u8>>,
+    padding: io::Take<io::Repeat>,
+}
+
+impl Read for ColdStorageReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.page.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            if self.next_page == self.cold_storage.num_pages() {
+                return self.padding.read(buf);
+            }
+            self.page = io::Cursor::new(self.cold_storage.read_page(self.next_page)?);
+            self.next_page += 1;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ColdPageCacheInner {
+    pages: HashMap<ColdPageKey, (Arc<Vec<u8>>, Index)>,
+    // Pages are moved to the end of the queue when they are stored or loaded,
+    // so the least recently used page is at the front and evicted first.
+    queue: IndexList<ColdPageKey>,
+    data_size: usize,
+}
+
+/// LRU of decompressed cold storage pages
+#[derive(Debug)]
+pub struct ColdPageCache {
+    inner: Mutex<ColdPageCacheInner>,
+    max_data_size: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ColdPageCache {
+    pub fn new(max_data_size: usize) -> Self {
+        Self {
+            inner: Mutex::default(),
+            max_data_size,
+            hits: AtomicU64::default(),
+            misses: AtomicU64::default(),
+        }
+    }
+
+    fn load(&self, key: ColdPageKey) -> Option<Arc<Vec<u8>>> {
+        let mut inner = self.inner.lock().unwrap();
+        let ColdPageCacheInner { pages, queue, .. } = &mut *inner;
+        match pages.get_mut(&key) {
+            Some((page, index)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                queue.remove(*index);
+                *index = queue.insert_last(key);
+                Some(Arc::clone(page))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn store(&self, key: ColdPageKey, page: Arc<Vec<u8>>) {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        inner.data_size += page.len();
+        let index = inner.queue.insert_last(key);
+        if let Some((previous, previous_index)) = inner.pages.insert(key, (page, index)) {
+            // another thread read the same page concurrently
+            inner.data_size -= previous.len();
+            inner.queue.remove(previous_index);
+        }
+        while inner.data_size > self.max_data_size {
+            let key = match inner.queue.get_first() {
+                None => break,
+                Some(key) => *key,
+            };
+            if let Some((page, index)) = inner.pages.remove(&key) {
+                inner.queue.remove(index);
+                inner.data_size -= page.len();
+            }
+        }
+    }
+
+    pub fn cache_len(&self) -> usize {
+        self.inner.lock().unwrap().pages.len()
+    }
+
+    pub fn data_size(&self) -> usize {
+        self.inner.lock().unwrap().data_size
+    }
+
+    pub fn get_and_reset_stats(&self) -> (u64, u64) {
+        let hits = self.hits.swap(0, Ordering::Relaxed);
+        let misses = self.misses.swap(0, Ordering::Relaxed);
+        (hits, misses)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ColdStorageStats {
+    last_report: AtomicInterval,
+    files_created: AtomicU64,
+    create_us: AtomicU64,
+    files_thawed: AtomicU64,
+    thaw_us: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
+    accounts_loaded: AtomicU64,
+    pages_read: AtomicU64,
+    read_us: AtomicU64,
+    decompress_us: AtomicU64,
+}
+
+impl ColdStorageStats {
+    pub fn report(&self, cache: &ColdPageCache) {
+        if self.last_report.should_update(1000) {
+            let (page_cache_hits, page_cache_misses) = cache.get_and_reset_stats();
+            datapoint_info!(
+                "accounts_db_cold_storage",
+                (
+                    "files_created",
+                    self.files_created.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                ("create_us", self.create_us.swap(0, Ordering::Relaxed), i64),
+                (
+                    "files_thawed",
+                    self.files_thawed.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                ("thaw_us", self.thaw_us.swap(0, Ordering::Relaxed), i64),
+                (
+                    "uncompressed_bytes",
+                    self.uncompressed_bytes.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "compressed_bytes",
+                    self.compressed_bytes.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "accounts_loaded",
+                    self.accounts_loaded.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "pages_read",
+                    self.pages_read.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                ("read_us", self.read_us.swap(0, Ordering::Relaxed), i64),
+                (
+                    "decompress_us",
+                    self.decompress_us.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                ("page_cache_hits", page_cache_hits, i64),
+                ("page_cache_misses", page_cache_misses, i64),
+                ("page_cache_len", cache.cache_len(), i64),
+                ("page_cache_data_size", cache.data_size(), i64),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::*,
+        crate::append_vec::test_utils::{create_test_account, get_append_vec_path},
+        solana_sdk::account::WritableAccount,
+    };
+
+    fn create_test_append_vec(
+        path: &Path,
+        num_accounts: usize,
+        data_len: usize,
+    ) -> (AppendVec, Vec<(usize, AccountSharedData)>) {
+        let append_vec = AppendVec::new(path, true, 1024 * 1024);
+        let accounts = (0..num_accounts)
+            .map(|sample| {
+                // accounts with zero lamports and data don't pass the append vec sanitization
+                let (mut meta, mut account) = create_test_account(sample + 1);
+                account.set_data(vec![sample as u8; data_len]);
+                meta.data_len = data_len as u64;
+                let offset = append_vec
+                    .append_account(meta, &account, Hash::new_unique())
+                    .unwrap();
+                (offset, account)
+            })
+            .collect();
+        (append_vec, accounts)
+    }
+
+    #[test]
+    fn test_cold_storage_load_account() {
+        let path = get_append_vec_path("test_cold_storage_load_account");
+        // accounts larger than a page and accounts spanning page boundaries
+        for data_len in [0, 100, COLD_STORAGE_PAGE_SIZE + 1] {
+            let (append_vec, accounts) = create_test_append_vec(&path.path, 10, data_len);
+            let cold_path = ColdStorageFile::path_for(&path.path);
+            let stats = Arc::new(ColdStorageStats::default());
+            let cache = Arc::new(ColdPageCache::new(usize::MAX));
+            let cold = ColdStorageFile::new(
+                &cold_path,
+                &append_vec,
+                Arc::clone(&cache),
+                Arc::clone(&stats),
+            )
+            .unwrap();
+            assert_eq!(cold.len(), append_vec.len());
+            assert!(cold_path.exists());
+
+            for (offset, account) in &accounts {
+                assert_eq!(cold.load_account(*offset).unwrap().account, *account);
+            }
+            assert_eq!(stats.accounts_loaded.load(Ordering::Relaxed), 10);
+            assert!(cold.load_account(append_vec.len()).is_none());
+            assert!(cold.load_account(1).is_none());
+
+            // accounts appended after the file was created are not in it
+            let (meta, account) = create_test_account(11);
+            let offset = append_vec
+                .append_account(meta, &account, Hash::default())
+                .unwrap();
+            assert!(cold.load_account(offset).is_none());
+
+            drop(cold);
+            assert!(!cold_path.exists());
+        }
+    }
+
+    #[test]
+    fn test_cold_storage_reader() {
+        let path = get_append_vec_path("test_cold_storage_reader");
+        let (append_vec, _accounts) =
+            create_test_append_vec(&path.path, 10, COLD_STORAGE_PAGE_SIZE / 3);
+        let cache = Arc::new(ColdPageCache::new(usize::MAX));
+        let cold = ColdStorageFile::new(
+            &ColdStorageFile::path_for(&path.path),
+            &append_vec,
+            Arc::clone(&cache),
+            Arc::new(ColdStorageStats::default()),
+        )
+        .unwrap();
+
+        append_vec.flush().unwrap();
+        let mut contents = vec![];
+        cold.reader().read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, std::fs::read(&path.path).unwrap());
+        assert_eq!(contents.len() as u64, append_vec.capacity());
+        assert_eq!(cache.cache_len(), 0);
+    }
+
+    #[test]
+    fn test_cold_storage_thaw() {
+        let path = get_append_vec_path("test_cold_storage_thaw");
+        let (append_vec, accounts) =
+            create_test_append_vec(&path.path, 10, COLD_STORAGE_PAGE_SIZE / 3);
+        let stats = Arc::new(ColdStorageStats::default());
+        let cache = Arc::new(ColdPageCache::new(usize::MAX));
+        let cold = ColdStorageFile::new(
+            &ColdStorageFile::path_for(&path.path),
+            &append_vec,
+            Arc::clone(&cache),
+            Arc::clone(&stats),
+        )
+        .unwrap();
+        assert_eq!(cold.capacity(), append_vec.capacity());
+
+        let thawed = cold.thaw().unwrap();
+        let thawed_path = thawed.get_path();
+        assert_ne!(thawed_path, path.path);
+        assert!(thawed_path.exists());
+        assert_eq!(thawed.len(), append_vec.len());
+        assert_eq!(thawed.capacity(), append_vec.capacity());
+        assert_eq!(thawed.written_data(), append_vec.written_data());
+        for (offset, account) in &accounts {
+            let (stored, _next) = thawed.get_account(*offset).unwrap();
+            assert_eq!(stored.clone_account(), *account);
+            let cold_account = cold.load_account(*offset).unwrap();
+            assert_eq!(cold_account.pubkey, stored.meta.pubkey);
+            assert_eq!(cold_account.hash, *stored.hash);
+            assert_eq!(cold_account.stored_size, stored.stored_size);
+        }
+
+        // each thaw gets its own file, which is removed with the thawed append vec
+        let thawed_again = cold.thaw().unwrap();
+        assert_ne!(thawed_again.get_path(), thawed_path);
+        drop(thawed);
+        assert!(!thawed_path.exists());
+        assert_eq!(stats.files_thawed.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_cold_page_cache() {
+        let path = get_append_vec_path("test_cold_page_cache");
+        let (append_vec, accounts) = create_test_append_vec(&path.path, 4, COLD_STORAGE_PAGE_SIZE);
+        let stats = Arc::new(ColdStorageStats::default());
+        // room for two pages
+        let cache = Arc::new(ColdPageCache::new(2 * COLD_STORAGE_PAGE_SIZE));
+        let cold = ColdStorageFile::new(
+            &ColdStorageFile::path_for(&path.path),
+            &append_vec,
+            Arc::clone(&cache),
+            Arc::clone(&stats),
+        )
+        .unwrap();
+        assert!(cold.num_pages() > 2);
+
+        // the metadata is in page 0, the data spans pages 0 and 1
+        let (offset, account) = &accounts[0];
+        assert_eq!(cold.load_account(*offset).unwrap().account, *account);
+        assert_eq!(cache.get_and_reset_stats(), (1, 2));
+
+        // the same account again is served from the cache
+        assert_eq!(cold.load_account(*offset).unwrap().account, *account);
+        assert_eq!(cache.get_and_reset_stats(), (3, 0));
+        let pages_read = stats.pages_read.load(Ordering::Relaxed);
+
+        // loading all accounts evicts the least recently used pages
+        for (offset, account) in &accounts {
+            assert_eq!(cold.load_account(*offset).unwrap().account, *account);
+        }
+        assert!(cache.data_size() <= 2 * COLD_STORAGE_PAGE_SIZE);
+        assert!(cache.cache_len() <= 2);
+        assert!(stats.pages_read.load(Ordering::Relaxed) > pages_read);
+        let (offset, account) = &accounts[0];
+        assert_eq!(cold.load_account(*offset).unwrap().account, *account);
+        let (_hits, misses) = cache.get_and_reset_stats();
+        assert!(misses > 0);
+    }
+}
@@ -7,9 +7,20 @@
 use {
     crate::{block_cost_limits::*, execute_cost_table::ExecuteCostTable},
     log::*,
+    solana_program_runtime::compute_budget::ComputeBudget,
     solana_sdk::{
-        instruction::CompiledInstruction, program_utils::limited_deserialize, pubkey::Pubkey,
-        system_instruction::SystemInstruction, system_program, transaction::SanitizedTransaction,
+        borsh::try_from_slice_unchecked,
+        compute_budget::{self, ComputeBudgetInstruction},
+        feature_set::{
+            add_set_compute_unit_price_ix, cap_transaction_accounts_data_size,
+            default_units_per_instruction, requestable_heap_size, tx_wide_compute_cap, FeatureSet,
+        },
+        instruction::CompiledInstruction,
+        program_utils::limited_deserialize,
+        pubkey::Pubkey,
+        system_instruction::SystemInstruction,
+        system_program,
+        transaction::SanitizedTransaction,
     },
 };
 
@@ -24,6 +35,7 @@ pub struct TransactionCost {
     pub data_bytes_cost: u64,
     pub builtins_execution_cost: u64,
     pub bpf_execution_cost: u64,
+    pub loaded_accounts_data_size_cost: u64,
     pub account_data_size: u64,
     pub is_simple_vote: bool,
 }
@@ -37,6 +49,7 @@ impl Default for TransactionCost {
             data_bytes_cost: 0u64,
             builtins_execution_cost: 0u64,
             bpf_execution_cost: 0u64,
+            loaded_accounts_data_size_cost: 0u64,
             account_data_size: 0u64,
             is_simple_vote: false,
         }
@@ -58,6 +71,7 @@ impl TransactionCost {
         self.data_bytes_cost = 0;
         self.builtins_execution_cost = 0;
         self.bpf_execution_cost = 0;
+        self.loaded_accounts_data_size_cost = 0;
         self.is_simple_vote = false;
     }
 
@@ -71,6 +85,7 @@ impl TransactionCost {
             .saturating_add(self.write_lock_cost)
             .saturating_add(self.data_bytes_cost)
             .saturating_add(self.builtins_execution_cost)
+            .saturating_add(self.loaded_accounts_data_size_cost)
     }
 }
 
@@ -95,12 +110,18 @@ impl CostModel {
             });
     }
 
-    pub fn calculate_cost(&self, transaction: &SanitizedTransaction) -> TransactionCost {
+    pub fn calculate_cost(
+        &self,
+        transaction: &SanitizedTransaction,
+        feature_set: &FeatureSet,
+    ) -> TransactionCost {
         let mut tx_cost = TransactionCost::new_with_capacity(MAX_WRITABLE_ACCOUNTS);
 
         tx_cost.signature_cost = self.get_signature_cost(transaction);
         self.get_write_lock_cost(&mut tx_cost, transaction);
         self.get_transaction_cost(&mut tx_cost, transaction);
+        tx_cost.loaded_accounts_data_size_cost =
+            Self::get_loaded_accounts_data_size_cost(transaction, feature_set);
         tx_cost.account_data_size = self.calculate_account_data_size(transaction);
         tx_cost.is_simple_vote = transaction.is_simple_vote_transaction();
 
@@ -183,6 +204,59 @@ impl CostModel {
         tx_cost.data_bytes_cost = data_bytes_len_total / DATA_BYTES_UNITS;
     }
 
+    /// Charges the loaded accounts data size limit that the transaction requests with
+    /// `SetLoadedAccountsDataSizeLimit`, so smaller limits schedule more cheaply. Transactions that
+    /// don't request a limit, simple votes among them, are not charged for the default limit.
+    fn get_loaded_accounts_data_size_cost(
+        transaction: &SanitizedTransaction,
+        feature_set: &FeatureSet,
+    ) -> u64 {
+        // Same as `Accounts::get_requested_loaded_accounts_data_size_limit()`
+        if !feature_set.is_active(&cap_transaction_accounts_data_size::id())
+            || !feature_set.is_active(&tx_wide_compute_cap::id())
+        {
+            return 0;
+        }
+        let requests_limit =
+            transaction
+                .message()
+                .program_instructions_iter()
+                .any(|(program_id, instruction)| {
+                    compute_budget::check_id(program_id)
+                        && matches!(
+                            try_from_slice_unchecked(&instruction.data),
+                            Ok(ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(_))
+                        )
+                });
+        if !requests_limit {
+            return 0;
+        }
+        let mut compute_budget = ComputeBudget::default();
+        // Transactions with invalid compute budget instructions fail to load
+        if compute_budget
+            .process_instructions(
+                transaction.message().program_instructions_iter(),
+                feature_set.is_active(&requestable_heap_size::id()),
+                feature_set.is_active(&default_units_per_instruction::id()),
+                feature_set.is_active(&add_set_compute_unit_price_ix::id()),
+                true,
+            )
+            .is_err()
+        {
+            return 0;
+        }
+        Self::calculate_loaded_accounts_data_size_cost(
+            compute_budget.loaded_accounts_data_size_limit as u64,
+        )
+    }
+
+    fn calculate_loaded_accounts_data_size_cost(loaded_accounts_data_size_limit: u64) -> u64 {
+        let pages = loaded_accounts_data_size_limit
+            .saturating_add(LOADED_ACCOUNTS_DATA_SIZE_PAGE_BYTES.saturating_sub(1))
+            .saturating_div(LOADED_ACCOUNTS_DATA_SIZE_PAGE_BYTES);
+        pages.saturating_mul(LOADED_ACCOUNTS_DATA_SIZE_PAGE_UNITS)
+    }
+
     fn calculate_account_data_size_on_deserialized_system_instruction(
         instruction: SystemInstruction,
     ) -> u64 {
@@ -245,16 +319,22 @@ mod tests {
             bank::Bank,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
         },
+        solana_program_runtime::compute_budget::MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES,
         solana_sdk::{
             bpf_loader,
+            compute_budget::ComputeBudgetInstruction,
             hash::Hash,
-            instruction::CompiledInstruction,
+            instruction::{CompiledInstruction, Instruction},
             message::Message,
             signature::{Keypair, Signer},
             system_instruction::{self},
             system_program, system_transaction,
-            transaction::Transaction,
+            transaction::{
+                MessageHash, SanitizedTransaction, SimpleAddressLoader, Transaction,
+                VersionedTransaction,
+            },
         },
+        solana_vote_program::vote_transaction,
         std::{
             str::FromStr,
             sync::{Arc, RwLock},
@@ -460,7 +540,7 @@ mod tests {
         );
 
         let cost_model = CostModel::default();
-        let tx_cost = cost_model.calculate_cost(&tx);
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
         assert_eq!(2 + 2, tx_cost.writable_accounts.len());
         assert_eq!(signer1.pubkey(), tx_cost.writable_accounts[0]);
         assert_eq!(signer2.pubkey(), tx_cost.writable_accounts[1]);
@@ -505,12 +585,101 @@ mod tests {
             .unwrap();
 
         let cost_model = CostModel::default();
-        let tx_cost = cost_model.calculate_cost(&tx);
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
         assert_eq!(expected_account_cost, tx_cost.write_lock_cost);
         assert_eq!(*expected_execution_cost, tx_cost.builtins_execution_cost);
         assert_eq!(2, tx_cost.writable_accounts.len());
     }
 
+    #[test]
+    fn test_cost_model_loaded_accounts_data_size_cost() {
+        let (mint_keypair, start_hash) = test_setup();
+        let new_tx = |instructions: &[Instruction]| {
+            SanitizedTransaction::from_transaction_for_tests(Transaction::new(
+                &[&mint_keypair],
+                Message::new(instructions, Some(&mint_keypair.pubkey())),
+                start_hash,
+            ))
+        };
+        let transfer =
+            system_instruction::transfer(&mint_keypair.pubkey(), &Pubkey::new_unique(), 2);
+        let cost_model = CostModel::default();
+
+        // Without a requested limit, nothing is charged
+        let tx = new_tx(&[transfer.clone()]);
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
+        assert_eq!(0, tx_cost.loaded_accounts_data_size_cost);
+
+        // Requested limits are capped at the default limit
+        let tx = new_tx(&[
+            ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(u32::MAX),
+            transfer.clone(),
+        ]);
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
+        assert_eq!(
+            MAX_LOADED_ACCOUNTS_DATA_SIZE_BYTES as u64 / LOADED_ACCOUNTS_DATA_SIZE_PAGE_BYTES
+                * LOADED_ACCOUNTS_DATA_SIZE_PAGE_UNITS,
+            tx_cost.loaded_accounts_data_size_cost
+        );
+
+        // Partial pages are charged as full pages
+        let tx = new_tx(&[
+            ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(
+                LOADED_ACCOUNTS_DATA_SIZE_PAGE_BYTES as u32 + 1,
+            ),
+            transfer,
+        ]);
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
+        assert_eq!(
+            2 * LOADED_ACCOUNTS_DATA_SIZE_PAGE_UNITS,
+            tx_cost.loaded_accounts_data_size_cost
+        );
+        assert_eq!(
+            tx_cost.signature_cost
+                + tx_cost.write_lock_cost
+                + tx_cost.data_bytes_cost
+                + tx_cost.builtins_execution_cost
+                + tx_cost.loaded_accounts_data_size_cost,
+            tx_cost.sum_without_bpf()
+        );
+
+        // Nothing is charged until the feature is activated, nor while compute budget
+        // instructions are ignored
+        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::default());
+        assert_eq!(0, tx_cost.loaded_accounts_data_size_cost);
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&tx_wide_compute_cap::id());
+        let tx_cost = cost_model.calculate_cost(&tx, &feature_set);
+        assert_eq!(0, tx_cost.loaded_accounts_data_size_cost);
+    }
+
+    #[test]
+    fn test_cost_model_simple_vote_loaded_accounts_data_size_cost() {
+        let (mint_keypair, start_hash) = test_setup();
+        let vote_keypair = Keypair::new();
+        let vote_tx = vote_transaction::new_vote_transaction(
+            vec![42],
+            Hash::new_unique(),
+            start_hash,
+            &mint_keypair,
+            &vote_keypair,
+            &vote_keypair,
+            None,
+        );
+        let vote_tx = SanitizedTransaction::try_create(
+            VersionedTransaction::from(vote_tx),
+            MessageHash::Compute,
+            Some(true),
+            SimpleAddressLoader::Disabled,
+            true, // require_static_program_ids
+        )
+        .unwrap();
+
+        let tx_cost = CostModel::default().calculate_cost(&vote_tx, &FeatureSet::all_enabled());
+        assert!(tx_cost.is_simple_vote);
+        assert_eq!(0, tx_cost.loaded_accounts_data_size_cost);
+    }
+
     #[test]
     fn test_cost_model_update_instruction_cost() {
         let key1 = Pubkey::new_unique();
@@ -573,7 +742,7 @@ mod tests {
                 } else {
                     thread::spawn(move || {
                         let cost_model = cost_model.write().unwrap();
-                        let tx_cost = cost_model.calculate_cost(&tx);
+                        let tx_cost = cost_model.calculate_cost(&tx, &FeatureSet::all_enabled());
                         assert_eq!(3, tx_cost.writable_accounts.len());
                         assert_eq!(expected_account_cost, tx_cost.write_lock_cost);
                     })
@@ -29,6 +29,45 @@ pub enum CostTrackerError {
     WouldExceedAccountDataTotalLimit,
 }
 
+/// Number of rejected attempts to add a transaction to the tracker, for each `CostTrackerError`.
+/// A transaction which is retried after being rejected, as banking stage does, is counted at each
+/// rejected attempt.
+#[derive(AbiExample, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CostTrackerErrorCounts {
+    pub would_exceed_block_max_limit: u64,
+    pub would_exceed_vote_max_limit: u64,
+    pub would_exceed_account_max_limit: u64,
+    pub would_exceed_account_data_block_limit: u64,
+    pub would_exceed_account_data_total_limit: u64,
+}
+
+impl CostTrackerErrorCounts {
+    fn increment(&mut self, error: CostTrackerError) {
+        let count = match error {
+            CostTrackerError::WouldExceedBlockMaxLimit => &mut self.would_exceed_block_max_limit,
+            CostTrackerError::WouldExceedVoteMaxLimit => &mut self.would_exceed_vote_max_limit,
+            CostTrackerError::WouldExceedAccountMaxLimit => {
+                &mut self.would_exceed_account_max_limit
+            }
+            CostTrackerError::WouldExceedAccountDataBlockLimit => {
+                &mut self.would_exceed_account_data_block_limit
+            }
+            CostTrackerError::WouldExceedAccountDataTotalLimit => {
+                &mut self.would_exceed_account_data_total_limit
+            }
+        };
+        saturating_add_assign!(*count, 1);
+    }
+
+    pub fn total(&self) -> u64 {
+        self.would_exceed_block_max_limit
+            .saturating_add(self.would_exceed_vote_max_limit)
+            .saturating_add(self.would_exceed_account_max_limit)
+            .saturating_add(self.would_exceed_account_data_block_limit)
+            .saturating_add(self.would_exceed_account_data_total_limit)
+    }
+}
+
 #[derive(AbiExample, Debug)]
 pub struct CostTracker {
     account_cost_limit: u64,
@@ -39,6 +78,7 @@ pub struct CostTracker {
     vote_cost: u64,
     transaction_count: u64,
     account_data_size: u64,
+    error_counts: CostTrackerErrorCounts,
 
     /// The amount of total account data size remaining.  If `Some`, then do not add transactions
     /// that would cause `account_data_size` to exceed this limit.
@@ -62,6 +102,7 @@ impl Default for CostTracker {
             vote_cost: 0,
             transaction_count: 0,
             account_data_size: 0,
+            error_counts: CostTrackerErrorCounts::default(),
             account_data_size_limit: None,
         }
     }
@@ -90,11 +131,26 @@ impl CostTracker {
     }
 
     pub fn try_add(&mut self, tx_cost: &TransactionCost) -> Result<u64, CostTrackerError> {
-        self.would_fit(tx_cost)?;
+        self.would_fit(tx_cost).map_err(|err| {
+            self.error_counts.increment(err);
+            err
+        })?;
         self.add_transaction_cost(tx_cost);
         Ok(self.block_cost)
     }
 
+    /// Add a transaction that is already part of a block, as when replaying the block, whether or
+    /// not it fits within the limits. Its estimated execution cost is replaced with
+    /// `actual_execution_units`.
+    pub fn add_committed_transaction(
+        &mut self,
+        tx_cost: &TransactionCost,
+        actual_execution_units: u64,
+    ) {
+        self.add_transaction_cost(tx_cost);
+        self.update_execution_cost(tx_cost, actual_execution_units);
+    }
+
     pub fn update_execution_cost(
         &mut self,
         estimated_tx_cost: &TransactionCost,
@@ -130,6 +186,31 @@ impl CostTracker {
         self.transaction_count
     }
 
+    pub fn vote_cost(&self) -> u64 {
+        self.vote_cost
+    }
+
+    pub fn account_data_size(&self) -> u64 {
+        self.account_data_size
+    }
+
+    pub fn error_counts(&self) -> &CostTrackerErrorCounts {
+        &self.error_counts
+    }
+
+    /// Returns up to `limit` writable accounts with non-zero cost, costliest first
+    pub fn costliest_accounts(&self, limit: usize) -> Vec<(Pubkey, u64)> {
+        let mut accounts: Vec<_> = self
+            .cost_by_writable_accounts
+            .iter()
+            .filter(|(_key, cost)| **cost > 0)
+            .map(|(key, cost)| (*key, *cost))
+            .collect();
+        accounts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        accounts.truncate(limit);
+        accounts
+    }
+
     pub fn report_stats(&self, bank_slot: Slot) {
         // skip reporting if block is empty
         if self.transaction_count == 0 {
@@ -148,6 +229,11 @@ impl CostTracker {
             ("costliest_account", costliest_account.to_string(), String),
             ("costliest_account_cost", costliest_account_cost as i64, i64),
             ("account_data_size", self.account_data_size, i64),
+            (
+                "rejected_transaction_count",
+                self.error_counts.total() as i64,
+                i64
+            ),
         );
     }
 
@@ -852,6 +938,30 @@ mod tests {
         assert_eq!(1, cost_tracker.transaction_count);
     }
 
+    #[test]
+    fn test_add_committed_transaction() {
+        let acct1 = Pubkey::new_unique();
+        let acct2 = Pubkey::new_unique();
+        let cost = 100;
+        let tx_cost = TransactionCost {
+            writable_accounts: vec![acct1, acct2],
+            bpf_execution_cost: cost,
+            account_data_size: 10,
+            ..TransactionCost::default()
+        };
+
+        // committed transactions are added beyond the limits, with their actual execution units
+        let mut cost_tracker = CostTracker::default();
+        cost_tracker.set_limits(cost / 2, cost / 2, cost / 2);
+        cost_tracker.add_committed_transaction(&tx_cost, cost * 2);
+        let (_costliest_account, costliest_account_cost) = cost_tracker.find_costliest_account();
+        assert_eq!(cost * 2, cost_tracker.block_cost);
+        assert_eq!(cost * 2, costliest_account_cost);
+        assert_eq!(1, cost_tracker.transaction_count);
+        assert_eq!(10, cost_tracker.account_data_size);
+        assert_eq!(0, cost_tracker.error_counts().total());
+    }
+
     #[test]
     fn test_remove_transaction_cost() {
         let mut cost_tracker = CostTracker::default();
@@ -879,4 +989,80 @@ mod tests {
         assert_eq!(0, cost_tracker.vote_cost);
         assert_eq!(0, cost_tracker.account_data_size);
     }
+
+    #[test]
+    fn test_cost_tracker_error_counts() {
+        let acct1 = Pubkey::new_unique();
+        let cost = 100;
+        let mut testee = CostTracker::new(cost, cost * 2, cost, None);
+
+        let tx_cost = TransactionCost {
+            writable_accounts: vec![acct1],
+            bpf_execution_cost: cost,
+            ..TransactionCost::default()
+        };
+        assert!(testee.try_add(&tx_cost).is_ok());
+        assert_eq!(0, testee.error_counts().total());
+
+        // acct1 is saturated
+        assert_eq!(
+            Err(CostTrackerError::WouldExceedAccountMaxLimit),
+            testee.try_add(&tx_cost)
+        );
+        assert_eq!(
+            Err(CostTrackerError::WouldExceedAccountMaxLimit),
+            testee.try_add(&tx_cost)
+        );
+
+        // vote limit is saturated by a single vote
+        let vote_cost = TransactionCost {
+            writable_accounts: vec![Pubkey::new_unique()],
+            bpf_execution_cost: cost,
+            is_simple_vote: true,
+            ..TransactionCost::default()
+        };
+        assert!(testee.try_add(&vote_cost).is_ok());
+        assert_eq!(
+            Err(CostTrackerError::WouldExceedBlockMaxLimit),
+            testee.try_add(&vote_cost)
+        );
+
+        let error_counts = testee.error_counts();
+        assert_eq!(2, error_counts.would_exceed_account_max_limit);
+        assert_eq!(1, error_counts.would_exceed_block_max_limit);
+        assert_eq!(0, error_counts.would_exceed_vote_max_limit);
+        assert_eq!(3, error_counts.total());
+        // rejections do not change the tracked costs
+        assert_eq!(2, testee.transaction_count());
+        assert_eq!(cost * 2, testee.block_cost());
+        assert_eq!(cost, testee.vote_cost());
+    }
+
+    #[test]
+    fn test_costliest_accounts() {
+        let acct1 = Pubkey::new_unique();
+        let acct2 = Pubkey::new_unique();
+        let acct3 = Pubkey::new_unique();
+        let mut testee = CostTracker::default();
+
+        for (writable_accounts, cost) in [
+            (vec![acct1, acct2], 100),
+            (vec![acct2], 50),
+            (vec![acct3], 10),
+        ] {
+            let tx_cost = TransactionCost {
+                writable_accounts,
+                bpf_execution_cost: cost,
+                ..TransactionCost::default()
+            };
+            assert!(testee.try_add(&tx_cost).is_ok());
+        }
+
+        assert_eq!(
+            vec![(acct2, 150), (acct1, 100)],
+            testee.costliest_accounts(2)
+        );
+        assert_eq!(3, testee.costliest_accounts(10).len());
+        assert!(testee.costliest_accounts(0).is_empty());
+    }
 }
@@ -21,7 +21,11 @@ pub(crate) mod program_v3_4_0 {
 */
 pub const SPL_TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
 pub const SPL_TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+/// offset of the delegate key, after the 4-byte tag of the `COption`
+pub const SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 76;
 const SPL_TOKEN_ACCOUNT_LENGTH: usize = 165;
+const COPTION_TAG_LENGTH: usize = 4;
+const COPTION_SOME_TAG: [u8; COPTION_TAG_LENGTH] = [1, 0, 0, 0];
 
 pub(crate) trait GenericTokenAccount {
     fn valid_account_data(account_data: &[u8]) -> bool;
@@ -56,6 +60,20 @@ pub(crate) trait GenericTokenAccount {
             None
         }
     }
+
+    fn unpack_account_delegate(account_data: &[u8]) -> Option<&Pubkey> {
+        let tag_start = SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET - COPTION_TAG_LENGTH;
+        if Self::valid_account_data(account_data)
+            && account_data[tag_start..SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET] == COPTION_SOME_TAG
+        {
+            Some(Self::unpack_pubkey_unchecked(
+                account_data,
+                SPL_TOKEN_ACCOUNT_DELEGATE_OFFSET,
+            ))
+        } else {
+            None
+        }
+    }
 }
 
 pub struct Account;
@@ -3,11 +3,11 @@ use {
     log::*,
     solana_sdk::{pubkey::Pubkey, timing::AtomicInterval},
     std::{
-        collections::HashSet,
+        collections::{HashMap, HashSet},
         fmt::Debug,
         sync::{
             atomic::{AtomicU64, Ordering},
-            RwLock,
+            Arc, RwLock,
         },
     },
 };
@@ -240,3 +240,70 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .for_each(|(v, k)| info!("owner: {}, accounts: {}", k, v));
     }
 }
+
+pub type ProgramDataKeyIndex = SecondaryIndex<RwLockSecondaryIndexEntry>;
+
+/// Secondary indexes of the accounts owned by a program, keyed by the 32-byte key at an offset of
+/// the account data. There is one index per (program id, offset), created on first insert.
+#[derive(Debug, Default)]
+pub struct ProgramDataKeyIndexes {
+    indexes: RwLock<HashMap<(Pubkey, usize), Arc<ProgramDataKeyIndex>>>,
+}
+
+impl ProgramDataKeyIndexes {
+    pub fn insert(&self, program_id: &Pubkey, offset: usize, key: &Pubkey, inner_key: &Pubkey) {
+        let index = self.get(program_id, offset).unwrap_or_else(|| {
+            Arc::clone(
+                self.indexes
+                    .write()
+                    .unwrap()
+                    .entry((*program_id, offset))
+                    .or_insert_with(|| {
+                        Arc::new(ProgramDataKeyIndex::new("program_data_key_index_stats"))
+                    }),
+            )
+        });
+        index.insert(key, inner_key);
+    }
+
+    pub fn get(&self, program_id: &Pubkey, offset: usize) -> Option<Arc<ProgramDataKeyIndex>> {
+        self.indexes
+            .read()
+            .unwrap()
+            .get(&(*program_id, offset))
+            .cloned()
+    }
+
+    pub fn remove_by_inner_key(&self, inner_key: &Pubkey) {
+        let indexes = self
+            .indexes
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for index in indexes {
+            index.remove_by_inner_key(inner_key);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indexes
+            .read()
+            .unwrap()
+            .values()
+            .all(|index| index.index.is_empty())
+    }
+
+    pub fn log_contents(&self) {
+        for ((program_id, offset), index) in self.indexes.read().unwrap().iter() {
+            if !index.index.is_empty() {
+                info!(
+                    "program data key index, program: {}, offset: {}",
+                    program_id, offset
+                );
+                index.log_contents();
+            }
+        }
+    }
+}
@@ -31,7 +31,7 @@ impl From<&AccountStorageEntry> for SerializableAccountStorageEntry {
     fn from(rhs: &AccountStorageEntry) -> Self {
         Self {
             id: rhs.append_vec_id() as SerializedAppendVecId,
-            accounts_current_len: rhs.accounts.len(),
+            accounts_current_len: rhs.written_bytes() as usize,
         }
     }
 }
@@ -302,13 +302,22 @@ pub fn archive_snapshot_package(
     .map_err(|e| SnapshotError::IoWithSource(e, "create staging symlinks"))?;
 
     // Add the AppendVecs into the compressible list
+    let mut cold_storages = vec![];
     for storage in snapshot_package.snapshot_storages.iter().flatten() {
         storage.flush()?;
+        let file_name =
+            crate::append_vec::AppendVec::file_name(storage.slot(), storage.append_vec_id());
+        match storage.cold_storage() {
+            // Cold storages are streamed into the archive from their compressed pages, so they
+            // are not thawed
+            Some(cold_storage) if !storage.is_thawed() => {
+                cold_storages.push((file_name, Arc::clone(cold_storage)));
+                continue;
+            }
+            _ => {}
+        }
         let storage_path = storage.get_path();
-        let output_path = staging_accounts_dir.join(crate::append_vec::AppendVec::file_name(
-            storage.slot(),
-            storage.append_vec_id(),
-        ));
+        let output_path = staging_accounts_dir.join(file_name);
 
         // `storage_path` - The file path where the AppendVec itself is located
         // `output_path` - The file path where the AppendVec will be placed in the staging directory.
@@ -342,9 +351,19 @@ pub fn archive_snapshot_package(
 
         let do_archive_files = |encoder: &mut dyn Write| -> Result<()> {
             let mut archive = tar::Builder::new(encoder);
-            for dir in ["accounts", "snapshots"] {
-                archive.append_dir_all(dir, staging_dir.as_ref().join(dir))?;
+            archive.append_dir_all("accounts", staging_dir.as_ref().join("accounts"))?;
+            for (file_name, cold_storage) in &cold_storages {
+                let mut header = tar::Header::new_gnu();
+                header.set_entry_type(tar::EntryType::Regular);
+                header.set_size(cold_storage.capacity());
+                header.set_mode(0o644);
+                archive.append_data(
+                    &mut header,
+                    Path::new("accounts").join(file_name),
+                    cold_storage.reader(),
+                )?;
             }
+            archive.append_dir_all("snapshots", staging_dir.as_ref().join("snapshots"))?;
             archive.append_path_with_name(staging_dir.as_ref().join("version"), "version")?;
             archive.into_inner()?;
             Ok(())
@@ -3020,6 +3039,86 @@ mod tests {
         assert_eq!(original_bank, roundtrip_bank);
     }
 
+    /// Test roundtrip of a bank with cold storages to a full snapshot, then back again.  The cold
+    /// storages are archived without being thawed.
+    #[test]
+    fn test_roundtrip_bank_to_and_from_full_snapshot_with_cold_storage() {
+        solana_logger::setup();
+        let genesis_config = GenesisConfig::default();
+        let original_bank = Bank::new_with_paths(
+            &genesis_config,
+            Vec::new(),
+            None,
+            None,
+            AccountSecondaryIndexes::default(),
+            true,
+            AccountShrinkThreshold::default(),
+            false,
+            Some(AccountsDbConfig {
+                cold_storage_epochs: Some(1),
+                ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+            }),
+            None,
+        );
+        while !original_bank.is_complete() {
+            original_bank.register_tick(&Hash::new_unique());
+        }
+        original_bank.squash();
+        original_bank.force_flush_accounts_cache();
+        let accounts_db = &original_bank.rc.accounts.accounts_db;
+        let epoch_schedule = original_bank.epoch_schedule();
+        accounts_db.update_cold_storage_max_slot(
+            original_bank.slot() + 2 * epoch_schedule.slots_per_epoch,
+            epoch_schedule,
+        );
+        accounts_db.move_old_slots_to_cold_storage();
+        let storages = original_bank.get_snapshot_storages(None);
+        assert!(!storages.is_empty());
+        assert!(storages.iter().flatten().all(|storage| storage.is_cold()));
+
+        let accounts_dir = tempfile::TempDir::new().unwrap();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let full_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let incremental_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_info = bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &original_bank,
+            None,
+            full_snapshot_archives_dir.path(),
+            incremental_snapshot_archives_dir.path(),
+            ArchiveFormat::Tar,
+            DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN,
+            DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN,
+        )
+        .unwrap();
+        assert!(storages
+            .iter()
+            .flatten()
+            .all(|storage| !storage.is_thawed()));
+
+        let (roundtrip_bank, _) = bank_from_snapshot_archives(
+            &[PathBuf::from(accounts_dir.path())],
+            bank_snapshots_dir.path(),
+            &snapshot_archive_info,
+            None,
+            &genesis_config,
+            None,
+            None,
+            AccountSecondaryIndexes::default(),
+            false,
+            None,
+            AccountShrinkThreshold::default(),
+            false,
+            false,
+            false,
+            Some(ACCOUNTS_DB_CONFIG_FOR_TESTING),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(original_bank, roundtrip_bank);
+    }
+
     /// Test roundtrip of bank to a full snapshot, then back again.  This test is more involved
     /// than the simple version above; creating multiple banks over multiple slots and doing
     /// multiple transfers.  So this full snapshot should contain more data.
@@ -44,7 +44,7 @@ impl TransactionCostMetricsSender {
     ) {
         let cost_model = self.cost_model.read().unwrap();
         txs.for_each(|tx| {
-            let cost = cost_model.calculate_cost(tx);
+            let cost = cost_model.calculate_cost(tx, &bank.feature_set);
             self.metrics_sender
                 .send(TransactionCostMetrics::TransactionCostDetail {
                     slot: bank.slot(),
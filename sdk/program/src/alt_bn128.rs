@@ -0,0 +1,425 @@
+//! Group operations on the alt_bn128 (BN254) elliptic curve.
+//!
+//! Inputs and outputs follow the encoding of the Ethereum `ECADD`, `ECMUL`
+//! and `ECPAIRING` precompiles, specified in [EIP-196] and [EIP-197]: field
+//! elements are 32 byte big-endian integers, a G1 point is `x || y`, a G2
+//! point is `x_im || x_re || y_im || y_re`, and the point at infinity is
+//! encoded as all zeros.
+//!
+//! [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+//! [EIP-197]: https://eips.ethereum.org/EIPS/eip-197
+
+use thiserror::Error;
+
+/// Size of a field element
+pub const ALT_BN128_FIELD_SIZE: usize = 32;
+/// Size of a G1 point
+pub const ALT_BN128_G1_POINT_SIZE: usize = ALT_BN128_FIELD_SIZE * 2;
+/// Size of a G2 point
+pub const ALT_BN128_G2_POINT_SIZE: usize = ALT_BN128_FIELD_SIZE * 4;
+
+/// Maximum input length of the addition operation, two G1 points
+pub const ALT_BN128_ADDITION_INPUT_LEN: usize = ALT_BN128_G1_POINT_SIZE * 2;
+/// Maximum input length of the multiplication operation, a G1 point and a scalar
+pub const ALT_BN128_MULTIPLICATION_INPUT_LEN: usize =
+    ALT_BN128_G1_POINT_SIZE + ALT_BN128_FIELD_SIZE;
+/// Length of each (G1, G2) pair in the input of the pairing operation
+pub const ALT_BN128_PAIRING_ELEMENT_LEN: usize = ALT_BN128_G1_POINT_SIZE + ALT_BN128_G2_POINT_SIZE;
+
+pub const ALT_BN128_ADDITION_OUTPUT_LEN: usize = ALT_BN128_G1_POINT_SIZE;
+pub const ALT_BN128_MULTIPLICATION_OUTPUT_LEN: usize = ALT_BN128_G1_POINT_SIZE;
+pub const ALT_BN128_PAIRING_OUTPUT_LEN: usize = ALT_BN128_FIELD_SIZE;
+
+/// Group operation ids understood by `sol_alt_bn128_group_op`
+pub const ALT_BN128_ADD: u64 = 0;
+pub const ALT_BN128_MUL: u64 = 1;
+pub const ALT_BN128_PAIRING: u64 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AltBn128Error {
+    #[error("The input data is invalid")]
+    InvalidInputData,
+    #[error("A point is not a valid group element")]
+    GroupError,
+    #[error("Unexpected error")]
+    UnexpectedError,
+}
+
+impl From<u64> for AltBn128Error {
+    fn from(v: u64) -> AltBn128Error {
+        match v {
+            1 => AltBn128Error::InvalidInputData,
+            2 => AltBn128Error::GroupError,
+            _ => AltBn128Error::UnexpectedError,
+        }
+    }
+}
+
+impl From<AltBn128Error> for u64 {
+    fn from(v: AltBn128Error) -> u64 {
+        match v {
+            AltBn128Error::InvalidInputData => 1,
+            AltBn128Error::GroupError => 2,
+            AltBn128Error::UnexpectedError => 3,
+        }
+    }
+}
+
+/// Adds two G1 points
+///
+/// Inputs shorter than `ALT_BN128_ADDITION_INPUT_LEN` are padded with zeros.
+pub fn alt_bn128_addition(input: &[u8]) -> Result<Vec<u8>, AltBn128Error> {
+    if input.len() > ALT_BN128_ADDITION_INPUT_LEN {
+        return Err(AltBn128Error::InvalidInputData);
+    }
+
+    #[cfg(target_os = "solana")]
+    {
+        alt_bn128_group_op(ALT_BN128_ADD, input, ALT_BN128_ADDITION_OUTPUT_LEN)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let input = pad_input(input, ALT_BN128_ADDITION_INPUT_LEN);
+        let p = target_arch::read_g1(&input[..ALT_BN128_G1_POINT_SIZE])?;
+        let q = target_arch::read_g1(&input[ALT_BN128_G1_POINT_SIZE..])?;
+        Ok(target_arch::write_g1(&target_arch::add(&p, &q)).to_vec())
+    }
+}
+
+/// Multiplies a G1 point by a 256-bit scalar
+///
+/// Inputs shorter than `ALT_BN128_MULTIPLICATION_INPUT_LEN` are padded with zeros.
+pub fn alt_bn128_multiplication(input: &[u8]) -> Result<Vec<u8>, AltBn128Error> {
+    if input.len() > ALT_BN128_MULTIPLICATION_INPUT_LEN {
+        return Err(AltBn128Error::InvalidInputData);
+    }
+
+    #[cfg(target_os = "solana")]
+    {
+        alt_bn128_group_op(ALT_BN128_MUL, input, ALT_BN128_MULTIPLICATION_OUTPUT_LEN)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let input = pad_input(input, ALT_BN128_MULTIPLICATION_INPUT_LEN);
+        let p = target_arch::read_g1(&input[..ALT_BN128_G1_POINT_SIZE])?;
+        let scalar = <[u8; ALT_BN128_FIELD_SIZE]>::try_from(&input[ALT_BN128_G1_POINT_SIZE..])
+            .map_err(|_| AltBn128Error::InvalidInputData)?;
+        Ok(target_arch::write_g1(&target_arch::mul(&p, &scalar)).to_vec())
+    }
+}
+
+/// Checks whether the product of the pairings of each (G1, G2) pair is one
+///
+/// Returns a 32 byte big-endian 1 if it is, 0 otherwise. An empty input is a
+/// successful check.
+pub fn alt_bn128_pairing(input: &[u8]) -> Result<Vec<u8>, AltBn128Error> {
+    if input.len() % ALT_BN128_PAIRING_ELEMENT_LEN != 0 {
+        return Err(AltBn128Error::InvalidInputData);
+    }
+
+    #[cfg(target_os = "solana")]
+    {
+        alt_bn128_group_op(ALT_BN128_PAIRING, input, ALT_BN128_PAIRING_OUTPUT_LEN)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let pairs = input
+            .chunks(ALT_BN128_PAIRING_ELEMENT_LEN)
+            .map(|pair| {
+                Ok((
+                    target_arch::read_g1(&pair[..ALT_BN128_G1_POINT_SIZE])?,
+                    target_arch::read_g2(&pair[ALT_BN128_G1_POINT_SIZE..])?,
+                ))
+            })
+            .collect::<Result<Vec<_>, AltBn128Error>>()?;
+
+        let mut output = vec![0u8; ALT_BN128_PAIRING_OUTPUT_LEN];
+        if target_arch::pairing_is_one(&pairs) {
+            output[ALT_BN128_PAIRING_OUTPUT_LEN - 1] = 1;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(target_os = "solana")]
+fn alt_bn128_group_op(
+    group_op: u64,
+    input: &[u8],
+    output_len: usize,
+) -> Result<Vec<u8>, AltBn128Error> {
+    let mut result_buffer = vec![0u8; output_len];
+    let result = unsafe {
+        crate::syscalls::sol_alt_bn128_group_op(
+            group_op,
+            input.as_ptr(),
+            input.len() as u64,
+            result_buffer.as_mut_ptr(),
+        )
+    };
+
+    match result {
+        0 => Ok(result_buffer),
+        error => Err(AltBn128Error::from(error)),
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+fn pad_input(input: &[u8], len: usize) -> Vec<u8> {
+    let mut input = input.to_vec();
+    input.resize(len, 0);
+    input
+}
+
+#[cfg(not(target_os = "solana"))]
+mod target_arch {
+    use {
+        super::*,
+        ark_bn254::{Bn254, Fq, Fq12, Fq2, G1Affine, G2Affine},
+        ark_ec::{pairing::Pairing, AffineRepr, CurveGroup},
+        ark_ff::{BigInteger256, One, PrimeField},
+    };
+
+    fn read_fq(bytes: &[u8]) -> Result<Fq, AltBn128Error> {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+            let chunk = <[u8; 8]>::try_from(chunk).map_err(|_| AltBn128Error::InvalidInputData)?;
+            *limb = u64::from_be_bytes(chunk);
+        }
+        // Rejects encodings of integers that are not smaller than the field modulus
+        Fq::from_bigint(BigInteger256::new(limbs)).ok_or(AltBn128Error::InvalidInputData)
+    }
+
+    fn write_fq(fq: &Fq, output: &mut [u8]) {
+        let limbs = fq.into_bigint().0;
+        for (limb, chunk) in limbs.iter().rev().zip(output.chunks_mut(8)) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+    }
+
+    pub(super) fn read_g1(bytes: &[u8]) -> Result<G1Affine, AltBn128Error> {
+        if bytes.iter().all(|byte| *byte == 0) {
+            return Ok(G1Affine::zero());
+        }
+        let x = read_fq(&bytes[..ALT_BN128_FIELD_SIZE])?;
+        let y = read_fq(&bytes[ALT_BN128_FIELD_SIZE..ALT_BN128_G1_POINT_SIZE])?;
+        // G1 has a cofactor of one, every point on the curve is in the group
+        let point = G1Affine::new_unchecked(x, y);
+        if !point.is_on_curve() {
+            return Err(AltBn128Error::GroupError);
+        }
+        Ok(point)
+    }
+
+    pub(super) fn read_g2(bytes: &[u8]) -> Result<G2Affine, AltBn128Error> {
+        if bytes.iter().all(|byte| *byte == 0) {
+            return Ok(G2Affine::zero());
+        }
+        let field = |index: usize| {
+            read_fq(&bytes[index * ALT_BN128_FIELD_SIZE..(index + 1) * ALT_BN128_FIELD_SIZE])
+        };
+        // Elements of Fq2 are encoded with their imaginary part first
+        let x = Fq2::new(field(1)?, field(0)?);
+        let y = Fq2::new(field(3)?, field(2)?);
+        let point = G2Affine::new_unchecked(x, y);
+        if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(AltBn128Error::GroupError);
+        }
+        Ok(point)
+    }
+
+    pub(super) fn write_g1(point: &G1Affine) -> [u8; ALT_BN128_G1_POINT_SIZE] {
+        let mut output = [0u8; ALT_BN128_G1_POINT_SIZE];
+        if !point.is_zero() {
+            write_fq(&point.x, &mut output[..ALT_BN128_FIELD_SIZE]);
+            write_fq(&point.y, &mut output[ALT_BN128_FIELD_SIZE..]);
+        }
+        output
+    }
+
+    pub(super) fn add(p: &G1Affine, q: &G1Affine) -> G1Affine {
+        (p.into_group() + q).into_affine()
+    }
+
+    pub(super) fn mul(p: &G1Affine, scalar: &[u8; ALT_BN128_FIELD_SIZE]) -> G1Affine {
+        // The scalar is not reduced modulo the group order, as in EIP-196
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(scalar.chunks(8)) {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(chunk);
+            *limb = u64::from_be_bytes(bytes);
+        }
+        p.mul_bigint(limbs).into_affine()
+    }
+
+    pub(super) fn pairing_is_one(pairs: &[(G1Affine, G2Affine)]) -> bool {
+        let (p, q): (Vec<_>, Vec<_>) = pairs.iter().copied().unzip();
+        Bn254::multi_pairing(p, q).0 == Fq12::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Generator of G1, (1, 2)
+    const G1: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                      0000000000000000000000000000000000000000000000000000000000000002";
+    // Negation of the generator of G1, (1, p - 2)
+    const NEG_G1: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                          30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+    // Twice the generator of G1
+    const G1_DOUBLED: &str = "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+                              15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+    // Generator of G2, as given in EIP-197
+    const G2: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                      1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                      090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+                      12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+    // Order of the G1 and G2 groups
+    const GROUP_ORDER: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+    #[test]
+    fn test_alt_bn128_addition() {
+        // chfast1 from the go-ethereum precompile test vectors
+        let input = decode(
+            "18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9\
+             063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266\
+             07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed\
+             06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+        );
+        let expected = decode(
+            "2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703\
+             301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915",
+        );
+        assert_eq!(alt_bn128_addition(&input).unwrap(), expected);
+
+        let input = [decode(G1), decode(G1)].concat();
+        assert_eq!(alt_bn128_addition(&input).unwrap(), decode(G1_DOUBLED));
+
+        // P + (-P) is the point at infinity
+        let input = [decode(G1), decode(NEG_G1)].concat();
+        assert_eq!(
+            alt_bn128_addition(&input).unwrap(),
+            vec![0; ALT_BN128_ADDITION_OUTPUT_LEN]
+        );
+
+        // Missing input is padded with zeros, which encode the point at infinity
+        assert_eq!(alt_bn128_addition(&decode(G1)).unwrap(), decode(G1));
+        assert_eq!(
+            alt_bn128_addition(&[]).unwrap(),
+            vec![0; ALT_BN128_ADDITION_OUTPUT_LEN]
+        );
+
+        assert_eq!(
+            alt_bn128_addition(&[0; ALT_BN128_ADDITION_INPUT_LEN + 1]),
+            Err(AltBn128Error::InvalidInputData)
+        );
+
+        // (1, 3) is not on the curve
+        let mut input = [decode(G1), decode(G1)].concat();
+        input[ALT_BN128_G1_POINT_SIZE - 1] = 3;
+        assert_eq!(alt_bn128_addition(&input), Err(AltBn128Error::GroupError));
+
+        // Coordinates must be smaller than the field modulus
+        let mut input = [decode(G1), decode(G1)].concat();
+        input[..ALT_BN128_FIELD_SIZE].copy_from_slice(&[0xff; ALT_BN128_FIELD_SIZE]);
+        assert_eq!(
+            alt_bn128_addition(&input),
+            Err(AltBn128Error::InvalidInputData)
+        );
+    }
+
+    #[test]
+    fn test_alt_bn128_multiplication() {
+        // chfast1 from the go-ethereum precompile test vectors
+        let input = decode(
+            "2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7\
+             21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204\
+             00000000000000000000000000000000000000000000000011138ce750fa15c2",
+        );
+        let expected = decode(
+            "070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c\
+             031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc",
+        );
+        assert_eq!(alt_bn128_multiplication(&input).unwrap(), expected);
+
+        let two = decode("0000000000000000000000000000000000000000000000000000000000000002");
+        let input = [decode(G1), two].concat();
+        assert_eq!(
+            alt_bn128_multiplication(&input).unwrap(),
+            decode(G1_DOUBLED)
+        );
+
+        // Multiplying by the group order gives the point at infinity
+        let input = [decode(G1), decode(GROUP_ORDER)].concat();
+        assert_eq!(
+            alt_bn128_multiplication(&input).unwrap(),
+            vec![0; ALT_BN128_MULTIPLICATION_OUTPUT_LEN]
+        );
+
+        // A missing scalar is zero
+        assert_eq!(
+            alt_bn128_multiplication(&decode(G1)).unwrap(),
+            vec![0; ALT_BN128_MULTIPLICATION_OUTPUT_LEN]
+        );
+
+        assert_eq!(
+            alt_bn128_multiplication(&[0; ALT_BN128_MULTIPLICATION_INPUT_LEN + 1]),
+            Err(AltBn128Error::InvalidInputData)
+        );
+    }
+
+    #[test]
+    fn test_alt_bn128_pairing() {
+        let one = decode("0000000000000000000000000000000000000000000000000000000000000001");
+        let zero = vec![0; ALT_BN128_PAIRING_OUTPUT_LEN];
+
+        // e(P, Q) * e(-P, Q) == 1
+        let input = [decode(G1), decode(G2), decode(NEG_G1), decode(G2)].concat();
+        assert_eq!(alt_bn128_pairing(&input).unwrap(), one);
+
+        // e(2P, Q) * e(-P, Q) * e(-P, Q) == 1
+        let input = [
+            decode(G1_DOUBLED),
+            decode(G2),
+            decode(NEG_G1),
+            decode(G2),
+            decode(NEG_G1),
+            decode(G2),
+        ]
+        .concat();
+        assert_eq!(alt_bn128_pairing(&input).unwrap(), one);
+
+        // e(P, Q) != 1
+        let input = [decode(G1), decode(G2)].concat();
+        assert_eq!(alt_bn128_pairing(&input).unwrap(), zero);
+
+        // Pairs with the point at infinity contribute one to the product
+        let input = [decode(G1), vec![0; ALT_BN128_G2_POINT_SIZE]].concat();
+        assert_eq!(alt_bn128_pairing(&input).unwrap(), one);
+
+        // The empty product is one, as in EIP-197
+        assert_eq!(alt_bn128_pairing(&[]).unwrap(), one);
+
+        assert_eq!(
+            alt_bn128_pairing(&[0; ALT_BN128_PAIRING_ELEMENT_LEN - 1]),
+            Err(AltBn128Error::InvalidInputData)
+        );
+
+        // Swapping the real and imaginary parts of x moves the point off the curve
+        let mut g2 = decode(G2);
+        g2[..ALT_BN128_FIELD_SIZE * 2].rotate_left(ALT_BN128_FIELD_SIZE);
+        let input = [decode(G1), g2].concat();
+        assert_eq!(alt_bn128_pairing(&input), Err(AltBn128Error::GroupError));
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
     instruction::{AccountMeta, Instruction, InstructionError},
     loader_upgradeable_instruction::UpgradeableLoaderInstruction,
     pubkey::Pubkey,
-    system_instruction, sysvar,
+    system_instruction, system_program, sysvar,
 };
 
 crate::declare_id!("BPFLoaderUpgradeab1e11111111111111111111111");
@@ -300,6 +300,30 @@ pub fn close_any(
     Instruction::new_with_bincode(id(), &UpgradeableLoaderInstruction::Close, metas)
 }
 
+/// Returns the instruction required to extend the size of a program's
+/// executable data account
+pub fn extend_program(
+    program_address: &Pubkey,
+    payer_address: Option<&Pubkey>,
+    additional_bytes: u32,
+) -> Instruction {
+    let (program_data_address, _) =
+        Pubkey::find_program_address(&[program_address.as_ref()], &id());
+    let mut metas = vec![
+        AccountMeta::new(program_data_address, false),
+        AccountMeta::new(*program_address, false),
+    ];
+    if let Some(payer_address) = payer_address {
+        metas.push(AccountMeta::new_readonly(system_program::id(), false));
+        metas.push(AccountMeta::new(*payer_address, true));
+    }
+    Instruction::new_with_bincode(
+        id(),
+        &UpgradeableLoaderInstruction::ExtendProgram { additional_bytes },
+        metas,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, bincode::serialized_size};
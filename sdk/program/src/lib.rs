@@ -448,6 +448,11 @@
 //!   - Instruction: [`solana_sdk::secp256k1_instruction`](https://docs.rs/solana-sdk/latest/solana_sdk/secp256k1_instruction/index.html)
 //!   - Invokable by programs? no
 //!
+//! - __secp256r1 Program__: Verifies secp256r1 (P-256) signatures.
+//!   - ID: [`solana_program::secp256r1_program`]
+//!   - Instruction: [`solana_sdk::secp256r1_instruction`](https://docs.rs/solana-sdk/latest/solana_sdk/secp256r1_instruction/index.html)
+//!   - Invokable by programs? no
+//!
 //! - __BPF Loader__: Deploys, and executes immutable programs on the chain.
 //!   - ID: [`solana_program::bpf_loader`]
 //!   - Instruction: [`solana_program::loader_instruction`]
@@ -559,6 +564,7 @@ extern crate self as solana_program;
 
 pub mod account_info;
 pub mod address_lookup_table_account;
+pub mod alt_bn128;
 pub(crate) mod atomic_u64;
 pub mod blake3;
 pub mod borsh;
@@ -585,6 +591,7 @@ pub mod log;
 pub mod message;
 pub mod native_token;
 pub mod nonce;
+pub mod poseidon;
 pub mod program;
 pub mod program_error;
 pub mod program_memory;
@@ -597,6 +604,7 @@ pub mod rent;
 pub mod sanitize;
 pub mod secp256k1_program;
 pub mod secp256k1_recover;
+pub mod secp256r1_program;
 pub mod serialize_utils;
 pub mod short_vec;
 pub mod slot_hashes;
@@ -0,0 +1,322 @@
+//! Hashing with the [Poseidon] hash function.
+//!
+//! Poseidon is an algebraic hash function designed to be cheap to prove in
+//! zero-knowledge circuits. The only supported parameter set is the one used
+//! by [circom] over the BN254 scalar field, with an x^5 S-box, so hashes
+//! computed here match those computed by circom circuits.
+//!
+//! Each input is a 32 byte encoding of a BN254 scalar field element, in
+//! either big-endian or little-endian byte order, and must be smaller than
+//! the field modulus. Between 1 and [`MAX_INPUTS`] inputs may be hashed at
+//! once.
+//!
+//! [Poseidon]: https://eprint.iacr.org/2019/458
+//! [circom]: https://github.com/iden3/circomlib
+
+use thiserror::Error;
+
+/// Length of a Poseidon hash
+pub const HASH_BYTES: usize = 32;
+/// Maximum number of inputs to a single hash
+pub const MAX_INPUTS: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PoseidonSyscallError {
+    #[error("Invalid parameters")]
+    InvalidParameters,
+    #[error("Invalid endianness")]
+    InvalidEndianness,
+    #[error("Invalid number of inputs, between 1 and 12 are allowed")]
+    InvalidNumberOfInputs,
+    #[error("Invalid length of an input, 32 bytes are required")]
+    InvalidInputLength,
+    #[error("An input is larger than the modulus of the prime field")]
+    InputLargerThanModulus,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+impl From<u64> for PoseidonSyscallError {
+    fn from(v: u64) -> PoseidonSyscallError {
+        match v {
+            1 => PoseidonSyscallError::InvalidParameters,
+            2 => PoseidonSyscallError::InvalidEndianness,
+            3 => PoseidonSyscallError::InvalidNumberOfInputs,
+            4 => PoseidonSyscallError::InvalidInputLength,
+            5 => PoseidonSyscallError::InputLargerThanModulus,
+            _ => PoseidonSyscallError::Unexpected,
+        }
+    }
+}
+
+impl From<PoseidonSyscallError> for u64 {
+    fn from(v: PoseidonSyscallError) -> u64 {
+        match v {
+            PoseidonSyscallError::InvalidParameters => 1,
+            PoseidonSyscallError::InvalidEndianness => 2,
+            PoseidonSyscallError::InvalidNumberOfInputs => 3,
+            PoseidonSyscallError::InvalidInputLength => 4,
+            PoseidonSyscallError::InputLargerThanModulus => 5,
+            PoseidonSyscallError::Unexpected => 6,
+        }
+    }
+}
+
+/// Poseidon parameter sets understood by `sol_poseidon`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u64)]
+pub enum Parameters {
+    /// BN254 scalar field, x^5 S-box, 8 full rounds and the circom partial
+    /// round counts for each state width
+    Bn254X5 = 0,
+}
+
+impl TryFrom<u64> for Parameters {
+    type Error = PoseidonSyscallError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match value {
+            x if x == Parameters::Bn254X5 as u64 => Ok(Parameters::Bn254X5),
+            _ => Err(PoseidonSyscallError::InvalidParameters),
+        }
+    }
+}
+
+impl From<Parameters> for u64 {
+    fn from(value: Parameters) -> Self {
+        value as u64
+    }
+}
+
+/// Byte order of the inputs and the resulting hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u64)]
+pub enum Endianness {
+    BigEndian = 0,
+    LittleEndian = 1,
+}
+
+impl TryFrom<u64> for Endianness {
+    type Error = PoseidonSyscallError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match value {
+            x if x == Endianness::BigEndian as u64 => Ok(Endianness::BigEndian),
+            x if x == Endianness::LittleEndian as u64 => Ok(Endianness::LittleEndian),
+            _ => Err(PoseidonSyscallError::InvalidEndianness),
+        }
+    }
+}
+
+impl From<Endianness> for u64 {
+    fn from(value: Endianness) -> Self {
+        value as u64
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PoseidonHash(pub [u8; HASH_BYTES]);
+
+impl PoseidonHash {
+    pub fn new(hash: [u8; HASH_BYTES]) -> Self {
+        Self(hash)
+    }
+
+    pub fn to_bytes(self) -> [u8; HASH_BYTES] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for PoseidonHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+/// Return a Poseidon hash for the given data with the given parameters and
+/// endianness
+pub fn hash(
+    parameters: Parameters,
+    endianness: Endianness,
+    val: &[u8],
+) -> Result<PoseidonHash, PoseidonSyscallError> {
+    hashv(parameters, endianness, &[val])
+}
+
+/// Return a Poseidon hash for the given data slices with the given
+/// parameters and endianness
+pub fn hashv(
+    parameters: Parameters,
+    endianness: Endianness,
+    vals: &[&[u8]],
+) -> Result<PoseidonHash, PoseidonSyscallError> {
+    if vals.is_empty() || vals.len() > MAX_INPUTS {
+        return Err(PoseidonSyscallError::InvalidNumberOfInputs);
+    }
+    if vals.iter().any(|val| val.len() != HASH_BYTES) {
+        return Err(PoseidonSyscallError::InvalidInputLength);
+    }
+
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash_result = [0; HASH_BYTES];
+        let result = unsafe {
+            crate::syscalls::sol_poseidon(
+                parameters.into(),
+                endianness.into(),
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                &mut hash_result as *mut _ as *mut u8,
+            )
+        };
+
+        match result {
+            0 => Ok(PoseidonHash::new(hash_result)),
+            error => Err(PoseidonSyscallError::from(error)),
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let Parameters::Bn254X5 = parameters;
+        target_arch::hashv(endianness, vals)
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+mod target_arch {
+    use {
+        super::*,
+        ark_bn254::Fr,
+        light_poseidon::{Poseidon, PoseidonBytesHasher, PoseidonError},
+    };
+
+    impl From<PoseidonError> for PoseidonSyscallError {
+        fn from(error: PoseidonError) -> Self {
+            match error {
+                PoseidonError::InvalidNumberOfInputs { .. }
+                | PoseidonError::EmptyInput
+                | PoseidonError::InvalidWidthCircom { .. } => {
+                    PoseidonSyscallError::InvalidNumberOfInputs
+                }
+                PoseidonError::InvalidInputLength { .. } => {
+                    PoseidonSyscallError::InvalidInputLength
+                }
+                PoseidonError::InputLargerThanModulus => {
+                    PoseidonSyscallError::InputLargerThanModulus
+                }
+                _ => PoseidonSyscallError::Unexpected,
+            }
+        }
+    }
+
+    pub(super) fn hashv(
+        endianness: Endianness,
+        vals: &[&[u8]],
+    ) -> Result<PoseidonHash, PoseidonSyscallError> {
+        let mut hasher = Poseidon::<Fr>::new_circom(vals.len())?;
+        let hash = match endianness {
+            Endianness::BigEndian => hasher.hash_bytes_be(vals)?,
+            Endianness::LittleEndian => hasher.hash_bytes_le(vals)?,
+        };
+        Ok(PoseidonHash::new(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poseidon_input_ones_be() {
+        let input = [1u8; 32];
+
+        let hash = hash(Parameters::Bn254X5, Endianness::BigEndian, &input).unwrap();
+        assert_eq!(
+            hash.to_bytes(),
+            [
+                5, 191, 172, 229, 129, 238, 97, 119, 204, 25, 198, 197, 99, 99, 166, 136, 130, 241,
+                30, 132, 7, 172, 99, 157, 185, 145, 224, 210, 127, 27, 117, 230
+            ]
+        );
+
+        let hash = hashv(
+            Parameters::Bn254X5,
+            Endianness::BigEndian,
+            &[&input, &input],
+        )
+        .unwrap();
+        assert_eq!(
+            hash.to_bytes(),
+            [
+                46, 19, 108, 182, 161, 38, 160, 121, 136, 194, 233, 35, 200, 2, 185, 110, 20, 233,
+                197, 126, 216, 223, 25, 65, 52, 229, 74, 143, 201, 125, 219, 141
+            ]
+        );
+    }
+
+    #[test]
+    fn test_poseidon_input_ones_le() {
+        let input = [1u8; 32];
+
+        let hash = hash(Parameters::Bn254X5, Endianness::LittleEndian, &input).unwrap();
+        assert_eq!(
+            hash.to_bytes(),
+            [
+                230, 117, 27, 127, 210, 224, 145, 185, 157, 99, 172, 7, 132, 30, 241, 130, 136,
+                166, 99, 99, 197, 198, 25, 204, 119, 97, 238, 129, 229, 172, 191, 5
+            ]
+        );
+    }
+
+    #[test]
+    fn test_poseidon_invalid_inputs() {
+        let input = [1u8; 32];
+
+        assert_eq!(
+            hashv(Parameters::Bn254X5, Endianness::BigEndian, &[]),
+            Err(PoseidonSyscallError::InvalidNumberOfInputs)
+        );
+        assert_eq!(
+            hashv(
+                Parameters::Bn254X5,
+                Endianness::BigEndian,
+                &[&input[..]; MAX_INPUTS + 1]
+            ),
+            Err(PoseidonSyscallError::InvalidNumberOfInputs)
+        );
+        assert_eq!(
+            hash(Parameters::Bn254X5, Endianness::BigEndian, &input[..31]),
+            Err(PoseidonSyscallError::InvalidInputLength)
+        );
+        assert_eq!(
+            hash(Parameters::Bn254X5, Endianness::BigEndian, &[255u8; 32]),
+            Err(PoseidonSyscallError::InputLargerThanModulus)
+        );
+    }
+
+    #[test]
+    fn test_poseidon_error_codes() {
+        for error in [
+            PoseidonSyscallError::InvalidParameters,
+            PoseidonSyscallError::InvalidEndianness,
+            PoseidonSyscallError::InvalidNumberOfInputs,
+            PoseidonSyscallError::InvalidInputLength,
+            PoseidonSyscallError::InputLargerThanModulus,
+            PoseidonSyscallError::Unexpected,
+        ] {
+            assert_eq!(PoseidonSyscallError::from(u64::from(error.clone())), error);
+        }
+        assert_eq!(Parameters::try_from(0), Ok(Parameters::Bn254X5));
+        assert_eq!(
+            Parameters::try_from(1),
+            Err(PoseidonSyscallError::InvalidParameters)
+        );
+        assert_eq!(Endianness::try_from(1), Ok(Endianness::LittleEndian));
+        assert_eq!(
+            Endianness::try_from(2),
+            Err(PoseidonSyscallError::InvalidEndianness)
+        );
+    }
+}
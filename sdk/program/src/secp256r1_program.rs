@@ -0,0 +1,5 @@
+//! The [secp256r1 native program][np].
+//!
+//! [np]: https://docs.solana.com/developing/runtime-facilities/programs#secp256r1-program
+
+crate::declare_id!("Secp256r1SigVerify1111111111111111111111111");
@@ -60,6 +60,12 @@ pub enum StakeError {
 
     #[error("delegation amount is less than the minimum")]
     InsufficientDelegation,
+
+    #[error("stake account with transient or inactive stake cannot be redelegated")]
+    RedelegateTransientOrInactiveStake,
+
+    #[error("stake redelegation to the same vote account is not permitted")]
+    RedelegateToSameVoteAccount,
 }
 
 impl<E> DecodeError<E> for StakeError {
@@ -261,6 +267,67 @@ pub enum StakeInstruction {
     ///   2. `[]` Reference vote account that has voted at least once in the last
     ///      `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs
     DeactivateDelinquent,
+
+    /// Redelegate activated stake to another vote account.
+    ///
+    /// Upon success:
+    ///   * the balance of the delegated stake account will be reduced to the undelegated amount in
+    ///     the account (rent exempt minimum and any additional lamports not part of the delegation),
+    ///     and scheduled for deactivation.
+    ///   * the provided uninitialized stake account will receive the original balance of the
+    ///     delegated stake account, minus the rent exempt minimum, and scheduled for activation to
+    ///     the provided vote account. Any existing lamports in the uninitialized stake account
+    ///     will also be included in the re-delegation.
+    ///
+    /// The stake that is scheduled for deactivation and activation is bound by the warmup and
+    /// cooldown rate limits of the stake history.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account to be redelegated. The account must be fully
+    ///      activated and carry a balance greater than or equal to the minimum delegation amount
+    ///      plus rent exempt minimum
+    ///   1. `[WRITE]` Uninitialized stake account that will hold the redelegated stake
+    ///   2. `[]` Vote account to which this stake will be re-delegated
+    ///   3. `[]` Address of config account that carries stake config
+    ///   4. `[SIGNER]` Stake authority
+    Redelegate,
+
+    /// Move active stake between accounts with the same authorities and lockups, using the Staker
+    /// authority.
+    ///
+    /// The source account must be fully active. If its entire delegation is moved, it immediately
+    /// becomes inactive. Otherwise at least the minimum delegation must remain active.
+    ///
+    /// The destination account must be fully active or fully inactive. If it is active, it must
+    /// be delegated to the same vote account as the source. If it is inactive, it immediately
+    /// becomes active and must receive at least the minimum delegation. Either way the
+    /// destination must already hold its rent exempt minimum.
+    ///
+    /// Only delegated lamports are moved. Undelegated lamports are never moved, activated or
+    /// deactivated.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Fully active source stake account
+    ///   1. `[WRITE]` Fully active or inactive destination stake account
+    ///   2. `[SIGNER]` Stake authority
+    ///
+    /// The u64 is the amount of stake to move, which may be the entire delegation
+    MoveStake(u64),
+
+    /// Move undelegated lamports between accounts with the same authorities and lockups, using
+    /// the Staker authority.
+    ///
+    /// The source account must be fully active or fully inactive. The destination may be in any
+    /// mergeable state. Only lamports that neither back a delegation nor are needed for the rent
+    /// exempt minimum may be moved.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Fully active or inactive source stake account
+    ///   1. `[WRITE]` Mergeable destination stake account
+    ///   2. `[SIGNER]` Stake authority
+    ///
+    /// The u64 is the amount of lamports to move
+    MoveLamports(u64),
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
@@ -738,6 +805,99 @@ pub fn deactivate_delinquent_stake(
     Instruction::new_with_bincode(id(), &StakeInstruction::DeactivateDelinquent, account_metas)
 }
 
+fn _redelegate(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*uninitialized_stake_pubkey, false),
+        AccountMeta::new_readonly(*vote_pubkey, false),
+        AccountMeta::new_readonly(config::id(), false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(id(), &StakeInstruction::Redelegate, account_metas)
+}
+
+pub fn redelegate(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::allocate(uninitialized_stake_pubkey, StakeState::size_of() as u64),
+        system_instruction::assign(uninitialized_stake_pubkey, &id()),
+        _redelegate(
+            stake_pubkey,
+            authorized_pubkey,
+            vote_pubkey,
+            uninitialized_stake_pubkey,
+        ),
+    ]
+}
+
+pub fn redelegate_with_seed(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey, // derived using create_with_seed()
+    base: &Pubkey,                       // base
+    seed: &str,                          // seed
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::allocate_with_seed(
+            uninitialized_stake_pubkey,
+            base,
+            seed,
+            StakeState::size_of() as u64,
+            &id(),
+        ),
+        _redelegate(
+            stake_pubkey,
+            authorized_pubkey,
+            vote_pubkey,
+            uninitialized_stake_pubkey,
+        ),
+    ]
+}
+
+pub fn move_stake(
+    source_stake_pubkey: &Pubkey,
+    destination_stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*source_stake_pubkey, false),
+        AccountMeta::new(*destination_stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(id(), &StakeInstruction::MoveStake(lamports), account_metas)
+}
+
+pub fn move_lamports(
+    source_stake_pubkey: &Pubkey,
+    destination_stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*source_stake_pubkey, false),
+        AccountMeta::new(*destination_stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        id(),
+        &StakeInstruction::MoveLamports(lamports),
+        account_metas,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::instruction::InstructionError};
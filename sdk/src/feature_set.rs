@@ -424,6 +424,42 @@ pub mod enable_durable_nonce {
     solana_sdk::declare_id!("4EJQtF2pkRyawwcTVfQutzq4Sa5hRhibF6QAK1QXhtEX");
 }
 
+pub mod enable_bpf_loader_extend_program_ix {
+    solana_sdk::declare_id!("HyFsPq4Fsb5LaKsW6s4aasSVdiVE7Zoo3zESbXcCcauV");
+}
+
+pub mod stake_redelegate_instruction {
+    solana_sdk::declare_id!("GrvhCLG5hY7oeGv9BBx6d2xykfXYzRTXC25qWDqgk5qS");
+}
+
+pub mod move_stake_and_move_lamports_ixs {
+    solana_sdk::declare_id!("Bg91A12b9BbBYTNLPekMWYPUqTLTZNC3W21YbhRbQCEj");
+}
+
+pub mod timely_vote_credits {
+    solana_sdk::declare_id!("7bt71Vq9jEFLjM3Ak41NxbeKdFZPeTgCDKizhCtj45x1");
+}
+
+pub mod enable_secp256r1_precompile {
+    solana_sdk::declare_id!("7GPpHkBdeAwo61aqYEuULMkStmYdCLZubtT6M44fMGzi");
+}
+
+pub mod enable_alt_bn128_syscall {
+    solana_sdk::declare_id!("5yeC1oaFLfiEzQhbFVDkVVs1eRtbovXfoC2XJEyK6xRP");
+}
+
+pub mod enable_poseidon_syscall {
+    solana_sdk::declare_id!("CdbREp8kG2cHfueJndF7rM9iVXQmkQendci29QiZWsnb");
+}
+
+pub mod cap_transaction_accounts_data_size {
+    solana_sdk::declare_id!("5uTqu7T1nJ7ndtpFtZkiLvYL3znsy8hThAQHqggXFnYg");
+}
+
+pub mod zk_token_proof_context_state {
+    solana_sdk::declare_id!("FxuCoVGqmrrEeafEj4BJzYRKSw4zRBdmLyR94e8kdLkW");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -523,6 +559,16 @@ lazy_static! {
         (warp_timestamp_with_a_vengeance::id(), "warp timestamp again, adjust bounding to 150% slow #25666"),
         (separate_nonce_from_blockhash::id(), "separate durable nonce and blockhash domains #25744"),
         (enable_durable_nonce::id(), "enable durable nonce #25744"),
+        (enable_bpf_loader_extend_program_ix::id(), "enable bpf upgradeable loader ExtendProgram instruction"),
+        (stake_redelegate_instruction::id(), "enable the redelegate stake instruction"),
+        (move_stake_and_move_lamports_ixs::id(), "enable MoveStake and MoveLamports stake instructions"),
+        (timely_vote_credits::id(), "use vote latency to award vote credits"),
+        (enable_secp256r1_precompile::id(), "enable secp256r1 signature verify program"),
+        (enable_alt_bn128_syscall::id(), "add alt_bn128 syscalls"),
+        (curve25519_syscall_enabled::id(), "enable curve25519 syscalls"),
+        (enable_poseidon_syscall::id(), "add poseidon syscall"),
+        (cap_transaction_accounts_data_size::id(), "cap transaction accounts data size up to a limit"),
+        (zk_token_proof_context_state::id(), "enable zk token proof context state accounts, new proof instructions and per-proof compute costs"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -43,6 +43,7 @@ pub mod quic;
 pub mod recent_blockhashes_account;
 pub mod rpc_port;
 pub mod secp256k1_instruction;
+pub mod secp256r1_instruction;
 pub mod shred_version;
 pub mod signature;
 pub mod signer;
@@ -2,6 +2,8 @@
 
 #![cfg(feature = "full")]
 
+#[cfg(feature = "secp256r1")]
+use crate::feature_set::enable_secp256r1_precompile;
 use {
     crate::{
         decode_error::DecodeError,
@@ -89,6 +91,12 @@ lazy_static! {
             Some(prevent_calling_precompiles_as_programs::id()),
             crate::ed25519_instruction::verify,
         ),
+        #[cfg(feature = "secp256r1")]
+        Precompile::new(
+            crate::secp256r1_program::id(),
+            Some(enable_secp256r1_precompile::id()),
+            crate::secp256r1_instruction::verify,
+        ),
     ];
 }
 
@@ -0,0 +1,463 @@
+#![cfg(feature = "secp256r1")]
+
+use {
+    crate::{feature_set::FeatureSet, instruction::Instruction, precompiles::PrecompileError},
+    bytemuck::{bytes_of, Pod, Zeroable},
+    openssl::{
+        bn::{BigNum, BigNumContext, BigNumRef},
+        ec::{EcGroup, EcKey, EcPoint, PointConversionForm},
+        ecdsa::EcdsaSig,
+        error::ErrorStack,
+        nid::Nid,
+        pkey::Private,
+        sha::sha256,
+    },
+    std::{cmp::Ordering, convert::TryFrom, sync::Arc},
+    thiserror::Error,
+};
+
+pub const COMPRESSED_PUBKEY_SERIALIZED_SIZE: usize = 33;
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+// bytemuck requires structures to be aligned
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const DATA_START: usize = SIGNATURE_OFFSETS_SERIALIZED_SIZE + SIGNATURE_OFFSETS_START;
+pub const FIELD_SIZE: usize = 32;
+// Bounds the cost of a single instruction, which is verified in the banking stage
+pub const MAX_SIGNATURES_PER_INSTRUCTION: u8 = 8;
+
+/// Errors building a secp256r1 instruction
+#[derive(Error, Debug)]
+pub enum Secp256r1InstructionError {
+    #[error("message of {0} bytes does not fit in an instruction")]
+    MessageTooLong(usize),
+    #[error("signing failed: {0}")]
+    Signing(#[from] ErrorStack),
+}
+
+#[derive(Default, Debug, Copy, Clone, Zeroable, Pod)]
+#[repr(C)]
+pub struct Secp256r1SignatureOffsets {
+    signature_offset: u16,             // offset to r || s signature of 64 bytes
+    signature_instruction_index: u16,  // instruction index to find signature
+    public_key_offset: u16,            // offset to compressed public key of 33 bytes
+    public_key_instruction_index: u16, // instruction index to find public key
+    message_data_offset: u16,          // offset to start of message data
+    message_data_size: u16,            // size of message data
+    message_instruction_index: u16,    // index of instruction data to get message data
+}
+
+/// Signs the SHA-256 digest of `message` and builds an instruction verifying it
+///
+/// The signature is normalized to its low-S form, the only form accepted by `verify`. Errors if
+/// `message` is too long for its size and offset to be encoded.
+pub fn new_secp256r1_instruction(
+    signing_key: &EcKey<Private>,
+    message: &[u8],
+) -> Result<Instruction, Secp256r1InstructionError> {
+    let message_too_long = |_| Secp256r1InstructionError::MessageTooLong(message.len());
+    let message_data_size = u16::try_from(message.len()).map_err(message_too_long)?;
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let mut ctx = BigNumContext::new()?;
+    let pubkey =
+        signing_key
+            .public_key()
+            .to_bytes(&group, PointConversionForm::COMPRESSED, &mut ctx)?;
+
+    let signature = EcdsaSig::sign(&sha256(message), signing_key)?;
+    let mut order = BigNum::new()?;
+    group.order(&mut order, &mut ctx)?;
+    let mut half_order = BigNum::new()?;
+    half_order.rshift1(&order)?;
+    let s = if signature.s().ucmp(&half_order) == Ordering::Greater {
+        let mut s = BigNum::new()?;
+        s.checked_sub(&order, signature.s())?;
+        s
+    } else {
+        signature.s().to_owned()?
+    };
+
+    let mut signature_bytes = [0u8; SIGNATURE_SERIALIZED_SIZE];
+    copy_padded(signature.r(), &mut signature_bytes[..FIELD_SIZE]);
+    copy_padded(&s, &mut signature_bytes[FIELD_SIZE..]);
+
+    assert_eq!(pubkey.len(), COMPRESSED_PUBKEY_SERIALIZED_SIZE);
+
+    let mut instruction_data = Vec::with_capacity(
+        DATA_START
+            .saturating_add(SIGNATURE_SERIALIZED_SIZE)
+            .saturating_add(COMPRESSED_PUBKEY_SERIALIZED_SIZE)
+            .saturating_add(message.len()),
+    );
+
+    let num_signatures: u8 = 1;
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset.saturating_add(COMPRESSED_PUBKEY_SERIALIZED_SIZE);
+    let message_data_offset = signature_offset.saturating_add(SIGNATURE_SERIALIZED_SIZE);
+
+    // the message must end within the range addressable by a `u16` offset
+    u16::try_from(message_data_offset.saturating_add(message.len())).map_err(message_too_long)?;
+
+    // add padding byte so that offset structure is aligned
+    instruction_data.extend_from_slice(bytes_of(&[num_signatures, 0]));
+
+    let offsets = Secp256r1SignatureOffsets {
+        signature_offset: u16::try_from(signature_offset).map_err(message_too_long)?,
+        signature_instruction_index: u16::MAX,
+        public_key_offset: u16::try_from(public_key_offset).map_err(message_too_long)?,
+        public_key_instruction_index: u16::MAX,
+        message_data_offset: u16::try_from(message_data_offset).map_err(message_too_long)?,
+        message_data_size,
+        message_instruction_index: u16::MAX,
+    };
+
+    instruction_data.extend_from_slice(bytes_of(&offsets));
+
+    debug_assert_eq!(instruction_data.len(), public_key_offset);
+
+    instruction_data.extend_from_slice(&pubkey);
+
+    debug_assert_eq!(instruction_data.len(), signature_offset);
+
+    instruction_data.extend_from_slice(&signature_bytes);
+
+    debug_assert_eq!(instruction_data.len(), message_data_offset);
+
+    instruction_data.extend_from_slice(message);
+
+    Ok(Instruction {
+        program_id: solana_sdk::secp256r1_program::id(),
+        accounts: vec![],
+        data: instruction_data,
+    })
+}
+
+pub fn verify(
+    data: &[u8],
+    instruction_datas: &[&[u8]],
+    _feature_set: &Arc<FeatureSet>,
+) -> Result<(), PrecompileError> {
+    if data.len() < SIGNATURE_OFFSETS_START {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+    let num_signatures = data[0] as usize;
+    if num_signatures == 0 || num_signatures > MAX_SIGNATURES_PER_INSTRUCTION as usize {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+    let expected_data_size = num_signatures
+        .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+        .saturating_add(SIGNATURE_OFFSETS_START);
+    // We do not check or use the byte at data[1]
+    if data.len() < expected_data_size {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+        .map_err(|_| PrecompileError::InvalidPublicKey)?;
+    let mut ctx = BigNumContext::new().map_err(|_| PrecompileError::InvalidSignature)?;
+    let mut order = BigNum::new().map_err(|_| PrecompileError::InvalidSignature)?;
+    group
+        .order(&mut order, &mut ctx)
+        .map_err(|_| PrecompileError::InvalidSignature)?;
+    let mut half_order = BigNum::new().map_err(|_| PrecompileError::InvalidSignature)?;
+    half_order
+        .rshift1(&order)
+        .map_err(|_| PrecompileError::InvalidSignature)?;
+
+    for i in 0..num_signatures {
+        let start = i
+            .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+            .saturating_add(SIGNATURE_OFFSETS_START);
+        let end = start.saturating_add(SIGNATURE_OFFSETS_SERIALIZED_SIZE);
+
+        // bytemuck wants structures aligned
+        let offsets: &Secp256r1SignatureOffsets = bytemuck::try_from_bytes(&data[start..end])
+            .map_err(|_| PrecompileError::InvalidDataOffsets)?;
+
+        // Parse out signature
+        let signature = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.signature_instruction_index,
+            offsets.signature_offset,
+            SIGNATURE_SERIALIZED_SIZE,
+        )?;
+
+        // Parse out pubkey
+        let pubkey = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.public_key_instruction_index,
+            offsets.public_key_offset,
+            COMPRESSED_PUBKEY_SERIALIZED_SIZE,
+        )?;
+
+        // Parse out message
+        let message = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        let r = BigNum::from_slice(&signature[..FIELD_SIZE])
+            .map_err(|_| PrecompileError::InvalidSignature)?;
+        let s = BigNum::from_slice(&signature[FIELD_SIZE..])
+            .map_err(|_| PrecompileError::InvalidSignature)?;
+
+        // Both components must be in [1, n - 1], and only the low-S form is accepted so
+        // that signatures are not malleable
+        if r.num_bits() == 0
+            || s.num_bits() == 0
+            || r.ucmp(&order) != Ordering::Less
+            || s.ucmp(&half_order) == Ordering::Greater
+        {
+            return Err(PrecompileError::InvalidSignature);
+        }
+
+        let signature = EcdsaSig::from_private_components(r, s)
+            .map_err(|_| PrecompileError::InvalidSignature)?;
+
+        let publickey = EcPoint::from_bytes(&group, pubkey, &mut ctx)
+            .and_then(|point| EcKey::from_public_key(&group, &point))
+            .map_err(|_| PrecompileError::InvalidPublicKey)?;
+
+        if !signature
+            .verify(&sha256(message), &publickey)
+            .map_err(|_| PrecompileError::InvalidSignature)?
+        {
+            return Err(PrecompileError::InvalidSignature);
+        }
+    }
+    Ok(())
+}
+
+/// Writes `value` big-endian into `output`, left padded with zeroes
+fn copy_padded(value: &BigNumRef, output: &mut [u8]) {
+    let bytes = value.to_vec();
+    let start = output.len().saturating_sub(bytes.len());
+    output[..start].fill(0);
+    output[start..].copy_from_slice(&bytes);
+}
+
+fn get_data_slice<'a>(
+    data: &'a [u8],
+    instruction_datas: &'a [&[u8]],
+    instruction_index: u16,
+    offset_start: u16,
+    size: usize,
+) -> Result<&'a [u8], PrecompileError> {
+    let instruction = if instruction_index == u16::MAX {
+        data
+    } else {
+        let signature_index = instruction_index as usize;
+        if signature_index >= instruction_datas.len() {
+            return Err(PrecompileError::InvalidDataOffsets);
+        }
+        instruction_datas[signature_index]
+    };
+
+    let start = offset_start as usize;
+    let end = start.saturating_add(size);
+    if end > instruction.len() {
+        return Err(PrecompileError::InvalidDataOffsets);
+    }
+
+    Ok(&instruction[start..end])
+}
+
+#[cfg(test)]
+pub mod test {
+    use {
+        super::*,
+        crate::{
+            feature_set::FeatureSet,
+            hash::Hash,
+            secp256r1_instruction::new_secp256r1_instruction,
+            signature::{Keypair, Signer},
+            transaction::Transaction,
+        },
+        rand::{thread_rng, Rng},
+        std::sync::Arc,
+    };
+
+    fn test_case(
+        num_signatures: u16,
+        offsets: &Secp256r1SignatureOffsets,
+    ) -> Result<(), PrecompileError> {
+        assert_eq!(
+            bytemuck::bytes_of(offsets).len(),
+            SIGNATURE_OFFSETS_SERIALIZED_SIZE
+        );
+
+        let mut instruction_data = vec![0u8; DATA_START];
+        instruction_data[0..SIGNATURE_OFFSETS_START].copy_from_slice(bytes_of(&num_signatures));
+        instruction_data[SIGNATURE_OFFSETS_START..DATA_START].copy_from_slice(bytes_of(offsets));
+
+        verify(
+            &instruction_data,
+            &[&[0u8; 100]],
+            &Arc::new(FeatureSet::all_enabled()),
+        )
+    }
+
+    fn new_signing_key() -> EcKey<Private> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        EcKey::generate(&group).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_offsets() {
+        solana_logger::setup();
+
+        let mut instruction_data = vec![0u8; DATA_START];
+        let offsets = Secp256r1SignatureOffsets::default();
+        instruction_data[0..SIGNATURE_OFFSETS_START].copy_from_slice(bytes_of(&1u16));
+        instruction_data[SIGNATURE_OFFSETS_START..DATA_START].copy_from_slice(bytes_of(&offsets));
+        instruction_data.truncate(instruction_data.len() - 1);
+
+        assert_eq!(
+            verify(
+                &instruction_data,
+                &[&[0u8; 100]],
+                &Arc::new(FeatureSet::all_enabled()),
+            ),
+            Err(PrecompileError::InvalidInstructionDataSize)
+        );
+
+        let offsets = Secp256r1SignatureOffsets {
+            signature_instruction_index: 1,
+            ..Secp256r1SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(PrecompileError::InvalidDataOffsets)
+        );
+
+        let offsets = Secp256r1SignatureOffsets {
+            signature_offset: 100 - SIGNATURE_SERIALIZED_SIZE as u16 + 1,
+            ..Secp256r1SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(PrecompileError::InvalidDataOffsets)
+        );
+
+        let offsets = Secp256r1SignatureOffsets {
+            message_data_offset: 100,
+            message_data_size: 1,
+            ..Secp256r1SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(PrecompileError::InvalidDataOffsets)
+        );
+    }
+
+    #[test]
+    fn test_num_signatures() {
+        let offsets = Secp256r1SignatureOffsets::default();
+        assert_eq!(
+            test_case(0, &offsets),
+            Err(PrecompileError::InvalidInstructionDataSize)
+        );
+        assert_eq!(
+            test_case(MAX_SIGNATURES_PER_INSTRUCTION as u16 + 1, &offsets),
+            Err(PrecompileError::InvalidInstructionDataSize)
+        );
+        // Zeroed signature components are rejected before the public key is parsed
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(PrecompileError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        let signing_key = new_signing_key();
+        let max_message_len = u16::MAX as usize
+            - DATA_START
+            - COMPRESSED_PUBKEY_SERIALIZED_SIZE
+            - SIGNATURE_SERIALIZED_SIZE;
+
+        let message = vec![7u8; max_message_len];
+        let instruction = new_secp256r1_instruction(&signing_key, &message).unwrap();
+        let feature_set = Arc::new(FeatureSet::all_enabled());
+        assert!(verify(&instruction.data, &[&instruction.data], &feature_set).is_ok());
+
+        let message = vec![7u8; max_message_len + 1];
+        assert!(matches!(
+            new_secp256r1_instruction(&signing_key, &message),
+            Err(Secp256r1InstructionError::MessageTooLong(len)) if len == max_message_len + 1
+        ));
+        let message = vec![7u8; u16::MAX as usize + 1];
+        assert!(matches!(
+            new_secp256r1_instruction(&signing_key, &message),
+            Err(Secp256r1InstructionError::MessageTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn test_high_s_signature() {
+        let signing_key = new_signing_key();
+        let mut instruction = new_secp256r1_instruction(&signing_key, b"hello").unwrap();
+        let feature_set = Arc::new(FeatureSet::all_enabled());
+        assert!(verify(&instruction.data, &[&instruction.data], &feature_set).is_ok());
+
+        // Replace s with n - s, which is an equally valid ECDSA signature
+        let signature_offset = DATA_START + COMPRESSED_PUBKEY_SERIALIZED_SIZE;
+        let s_range = signature_offset + FIELD_SIZE..signature_offset + SIGNATURE_SERIALIZED_SIZE;
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let mut order = BigNum::new().unwrap();
+        group.order(&mut order, &mut ctx).unwrap();
+        let s = BigNum::from_slice(&instruction.data[s_range.clone()]).unwrap();
+        let mut high_s = BigNum::new().unwrap();
+        high_s.checked_sub(&order, &s).unwrap();
+        copy_padded(&high_s, &mut instruction.data[s_range]);
+
+        assert_eq!(
+            verify(&instruction.data, &[&instruction.data], &feature_set),
+            Err(PrecompileError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_secp256r1() {
+        solana_logger::setup();
+
+        let signing_key = new_signing_key();
+        let message_arr = b"hello";
+        let mut instruction = new_secp256r1_instruction(&signing_key, message_arr).unwrap();
+        let mint_keypair = Keypair::new();
+        let feature_set = Arc::new(FeatureSet::all_enabled());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction.clone()],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            Hash::default(),
+        );
+
+        assert!(tx.verify_precompiles(&feature_set).is_ok());
+
+        let index = loop {
+            let index = thread_rng().gen_range(0, instruction.data.len());
+            // byte 1 is not used, so this would not cause the verify to fail
+            if index != 1 {
+                break index;
+            }
+        };
+
+        instruction.data[index] = instruction.data[index].wrapping_add(12);
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            Hash::default(),
+        );
+        assert!(tx.verify_precompiles(&feature_set).is_err());
+    }
+}
@@ -188,6 +188,7 @@ impl TransactionContext {
                 program_accounts: program_accounts.to_vec(),
                 instruction_accounts: instruction_accounts.to_vec(),
                 instruction_data: instruction_data.to_vec(),
+                compute_units_consumed: 0,
             };
             self.instruction_trace.push(vec![instruction_context]);
             self.instruction_trace.len().saturating_sub(1)
@@ -198,6 +199,7 @@ impl TransactionContext {
                     program_accounts: program_accounts.to_vec(),
                     instruction_accounts: instruction_accounts.to_vec(),
                     instruction_data: instruction_data.to_vec(),
+                    compute_units_consumed: 0,
                 };
                 instruction_trace.push(instruction_context);
             }
@@ -246,6 +248,33 @@ impl TransactionContext {
         }
     }
 
+    /// Records the compute units consumed by the current InstructionContext,
+    /// including the instructions it invoked
+    pub fn set_compute_units_consumed(
+        &mut self,
+        compute_units_consumed: u64,
+    ) -> Result<(), InstructionError> {
+        let top_level_index = *self
+            .instruction_stack
+            .first()
+            .ok_or(InstructionError::CallDepth)?;
+        let cpi_index = if self.instruction_stack.len() == 1 {
+            0
+        } else {
+            *self
+                .instruction_stack
+                .last()
+                .ok_or(InstructionError::CallDepth)?
+        };
+        let instruction_context = self
+            .instruction_trace
+            .get_mut(top_level_index)
+            .and_then(|instruction_trace| instruction_trace.get_mut(cpi_index))
+            .ok_or(InstructionError::CallDepth)?;
+        instruction_context.compute_units_consumed = compute_units_consumed;
+        Ok(())
+    }
+
     /// Returns instruction trace
     pub fn get_instruction_trace(&self) -> &InstructionTrace {
         &self.instruction_trace
@@ -271,6 +300,7 @@ pub struct InstructionContext {
     program_accounts: Vec<usize>,
     instruction_accounts: Vec<InstructionAccount>,
     instruction_data: Vec<u8>,
+    compute_units_consumed: u64,
 }
 
 impl InstructionContext {
@@ -286,6 +316,7 @@ impl InstructionContext {
             program_accounts: program_accounts.to_vec(),
             instruction_accounts: instruction_accounts.to_vec(),
             instruction_data: instruction_data.to_vec(),
+            compute_units_consumed: 0,
         }
     }
 
@@ -296,6 +327,11 @@ impl InstructionContext {
         self.nesting_level.saturating_add(1)
     }
 
+    /// Compute units consumed by this Instruction, including the instructions it invoked
+    pub fn get_compute_units_consumed(&self) -> u64 {
+        self.compute_units_consumed
+    }
+
     /// Number of program accounts
     pub fn get_number_of_program_accounts(&self) -> usize {
         self.program_accounts.len()
@@ -1,9 +1,11 @@
 #![allow(clippy::integer_arithmetic)]
 use {
     log::*,
+    solana_bpf_loader_program::profiler,
     solana_cli_output::CliAccount,
     solana_client::{nonblocking, rpc_client::RpcClient},
     solana_core::{
+        fork_simulator::ForkSimulationConfig,
         tower_storage::TowerStorage,
         validator::{Validator, ValidatorConfig, ValidatorStartProgress},
     },
@@ -20,36 +22,43 @@ use {
     solana_program_runtime::compute_budget::ComputeBudget,
     solana_rpc::{rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig},
     solana_runtime::{
-        accounts_db::AccountsDbConfig, accounts_index::AccountsIndexConfig, bank_forks::BankForks,
-        genesis_utils::create_genesis_config_with_leader_ex,
+        accounts_db::AccountsDbConfig, accounts_index::AccountsIndexConfig, bank::Bank,
+        bank_forks::BankForks, genesis_utils::create_genesis_config_with_leader_ex,
         hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE, runtime_config::RuntimeConfig,
         snapshot_config::SnapshotConfig,
     },
     solana_sdk::{
-        account::{Account, AccountSharedData},
+        account::{Account, AccountSharedData, ReadableAccount},
         clock::{Slot, DEFAULT_MS_PER_SLOT},
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
         exit::Exit,
+        feature::{self, Feature},
         feature_set::FEATURE_NAMES,
         fee_calculator::{FeeCalculator, FeeRateGovernor},
         hash::Hash,
         instruction::{AccountMeta, Instruction},
         message::Message,
+        native_loader,
         native_token::sol_to_lamports,
         pubkey::Pubkey,
         rent::Rent,
         signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+        sysvar,
     },
     solana_streamer::socket::SocketAddrSpace,
     std::{
         collections::{HashMap, HashSet},
         fs::{remove_dir_all, File},
-        io::Read,
+        io::{self, Read},
         net::{IpAddr, Ipv4Addr, SocketAddr},
         path::{Path, PathBuf},
         str::FromStr,
-        sync::{Arc, RwLock},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        thread::Builder,
         time::Duration,
     },
     tokio::time::sleep,
@@ -68,7 +77,7 @@ pub struct ProgramInfo {
     pub program_path: PathBuf,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TestValidatorNodeConfig {
     gossip_addr: SocketAddr,
     port_range: PortRange,
@@ -91,6 +100,7 @@ impl Default for TestValidatorNodeConfig {
     }
 }
 
+#[derive(Clone)]
 pub struct TestValidatorGenesis {
     fee_rate_governor: FeeRateGovernor,
     ledger_path: Option<PathBuf>,
@@ -115,6 +125,8 @@ pub struct TestValidatorGenesis {
     pub accounts_db_caching_enabled: bool,
     deactivate_feature_set: HashSet<Pubkey>,
     compute_unit_limit: Option<u64>,
+    profile_file: Option<PathBuf>,
+    fork_simulation: Option<ForkSimulationConfig>,
 }
 
 impl Default for TestValidatorGenesis {
@@ -143,6 +155,8 @@ impl Default for TestValidatorGenesis {
             accounts_db_caching_enabled: bool::default(),
             deactivate_feature_set: HashSet::<Pubkey>::default(),
             compute_unit_limit: Option::<u64>::default(),
+            profile_file: Option::<PathBuf>::default(),
+            fork_simulation: Option::<ForkSimulationConfig>::default(),
         }
     }
 }
@@ -245,6 +259,22 @@ impl TestValidatorGenesis {
         self
     }
 
+    /// Profile the compute units of every BPF program run, including programs invoked via CPI,
+    /// and write the profile to `profile_file` as collapsed stacks, see
+    /// `TestValidator::write_profile`
+    pub fn profile_file<P: Into<PathBuf>>(&mut self, profile_file: P) -> &mut Self {
+        self.profile_file = Some(profile_file.into());
+        self
+    }
+
+    /// After every `interval` voted slots, build a fork and abandon it after `depth` processed
+    /// slots, so that clients observe skipped slots, rollbacks of the processed commitment and
+    /// dead slot updates
+    pub fn fork_simulation(&mut self, interval: u64, depth: u64) -> &mut Self {
+        self.fork_simulation = Some(ForkSimulationConfig { interval, depth });
+        self
+    }
+
     #[deprecated(note = "Please use `compute_unit_limit` instead")]
     pub fn max_compute_units(&mut self, max_compute_units: u64) -> &mut Self {
         self.compute_unit_limit(max_compute_units)
@@ -325,6 +355,19 @@ impl TestValidatorGenesis {
         self
     }
 
+    /// Write those of the given accounts that have been added to the test environment into `dir`
+    /// as a `solana_program_test` fixture
+    pub fn write_fixture<T>(&self, dir: &Path, addresses: T) -> io::Result<()>
+    where
+        T: IntoIterator<Item = Pubkey>,
+    {
+        let accounts = addresses
+            .into_iter()
+            .filter_map(|address| self.accounts.get_key_value(&address))
+            .collect::<Vec<_>>();
+        solana_program_test::fixture::write_fixture(dir, accounts)
+    }
+
     /// Add an account to the test environment with the account data in the provided `filename`
     pub fn add_account_with_file_data(
         &mut self,
@@ -463,6 +506,37 @@ impl TestValidatorGenesis {
     }
 }
 
+/// How often the profile of a profiling `TestValidator` is written while it runs
+const PROFILE_WRITE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Periodically write the profile to `profile_file` until the validator exits, so that a recent
+/// profile is available even if the process is killed rather than exited
+fn spawn_profile_writer(profile_file: PathBuf, validator_exit: &RwLock<Exit>) {
+    let exit = Arc::new(AtomicBool::new(false));
+    {
+        let exit = exit.clone();
+        validator_exit
+            .write()
+            .unwrap()
+            .register_exit(Box::new(move || exit.store(true, Ordering::Relaxed)));
+    }
+    Builder::new()
+        .name("solProfileWriter".to_string())
+        .spawn(move || {
+            while !exit.load(Ordering::Relaxed) {
+                std::thread::sleep(PROFILE_WRITE_INTERVAL);
+                if let Err(err) = profiler::write_profile_file(&profile_file) {
+                    warn!(
+                        "Failed to write BPF program profile to {}: {}",
+                        profile_file.display(),
+                        err
+                    );
+                }
+            }
+        })
+        .unwrap();
+}
+
 pub struct TestValidator {
     ledger_path: PathBuf,
     preserve_ledger: bool,
@@ -472,6 +546,10 @@ pub struct TestValidator {
     gossip: SocketAddr,
     validator: Option<Validator>,
     vote_account_address: Pubkey,
+    profile_file: Option<PathBuf>,
+    config: TestValidatorGenesis,
+    socket_addr_space: SocketAddrSpace,
+    saved_states: Mutex<Vec<HashMap<Pubkey, AccountSharedData>>>,
 }
 
 impl TestValidator {
@@ -645,7 +723,43 @@ impl TestValidator {
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let preserve_ledger = config.ledger_path.is_some();
         let ledger_path = TestValidator::initialize_ledger(mint_address, config)?;
+        if let Some(profile_file) = &config.profile_file {
+            profiler::enable_profiler();
+            spawn_profile_writer(profile_file.clone(), &config.validator_exit);
+        }
 
+        // The genesis accounts and programs are already in the ledger, restarts only need the
+        // rest of the config
+        let mut config = config.clone();
+        config.ledger_path = Some(ledger_path.clone());
+        config.accounts.clear();
+        config.programs.clear();
+
+        let mut test_validator = TestValidator {
+            ledger_path,
+            preserve_ledger,
+            rpc_pubsub_url: String::default(),
+            rpc_url: String::default(),
+            tpu: socketaddr!(0, 0),
+            gossip: socketaddr!(0, 0),
+            validator: None,
+            vote_account_address: Pubkey::default(),
+            profile_file: config.profile_file.clone(),
+            config,
+            socket_addr_space,
+            saved_states: Mutex::default(),
+        };
+        test_validator.start_validator(test_validator.config.warp_slot)?;
+        Ok(test_validator)
+    }
+
+    /// Starts the validator on the TestValidator's ledger, optionally warped to `warp_slot`
+    fn start_validator(
+        &mut self,
+        warp_slot: Option<Slot>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = &self.config;
+        let ledger_path = &self.ledger_path;
         let validator_identity =
             read_keypair_file(ledger_path.join("validator-keypair.json").to_str().unwrap())?;
         let validator_vote_account = read_keypair_file(
@@ -669,6 +783,7 @@ impl TestValidator {
         let vote_account_address = validator_vote_account.pubkey();
         let rpc_url = format!("http://{}", node.info.rpc);
         let rpc_pubsub_url = format!("ws://{}/", node.info.rpc_pubsub);
+        let rpc_ports = (node.info.rpc.port(), node.info.rpc_pubsub.port());
         let tpu = node.info.tpu;
         let gossip = node.info.gossip;
 
@@ -700,6 +815,18 @@ impl TestValidator {
                 }),
         };
 
+        // Every validator run exits on its own, so that a restart doesn't exit the services
+        // registered with the config's `validator_exit`, like the admin RPC service
+        let validator_exit = Arc::<RwLock<Exit>>::default();
+        {
+            let validator_exit = validator_exit.clone();
+            config
+                .validator_exit
+                .write()
+                .unwrap()
+                .register_exit(Box::new(move || validator_exit.write().unwrap().exit()));
+        }
+
         let mut validator_config = ValidatorConfig {
             geyser_plugin_config_files: config.geyser_plugin_config_files.clone(),
             accounts_db_caching_enabled: config.accounts_db_caching_enabled,
@@ -724,49 +851,47 @@ impl TestValidator {
                 ..SnapshotConfig::default()
             }),
             enforce_ulimit_nofile: false,
-            warp_slot: config.warp_slot,
-            validator_exit: config.validator_exit.clone(),
+            warp_slot,
+            validator_exit,
             rocksdb_compaction_interval: Some(100), // Compact every 100 slots
             max_ledger_shreds: config.max_ledger_shreds,
             no_wait_for_vote_to_start_leader: true,
             accounts_db_config,
             runtime_config,
+            fork_simulation: config.fork_simulation,
             ..ValidatorConfig::default_for_test()
         };
         if let Some(ref tower_storage) = config.tower_storage {
             validator_config.tower_storage = tower_storage.clone();
         }
 
-        let validator = Some(Validator::new(
+        self.validator = Some(Validator::new(
             node,
             Arc::new(validator_identity),
-            &ledger_path,
+            ledger_path,
             &vote_account_address,
             config.authorized_voter_keypairs.clone(),
             vec![],
             &validator_config,
             true, // should_check_duplicate_instance
             config.start_progress.clone(),
-            socket_addr_space,
+            self.socket_addr_space,
             false, // use_quic
         ));
 
         // Needed to avoid panics in `solana-responder-gossip` in tests that create a number of
         // test validators concurrently...
-        discover_cluster(&gossip, 1, socket_addr_space)
+        discover_cluster(&gossip, 1, self.socket_addr_space)
             .map_err(|err| format!("TestValidator startup failed: {:?}", err))?;
 
-        let test_validator = TestValidator {
-            ledger_path,
-            preserve_ledger,
-            rpc_pubsub_url,
-            rpc_url,
-            tpu,
-            gossip,
-            validator,
-            vote_account_address,
-        };
-        Ok(test_validator)
+        self.rpc_pubsub_url = rpc_pubsub_url;
+        self.rpc_url = rpc_url;
+        self.tpu = tpu;
+        self.gossip = gossip;
+        self.vote_account_address = vote_account_address;
+        // Restarted validators keep serving RPC at the same URLs
+        self.config.rpc_ports = Some(rpc_ports);
+        Ok(())
     }
 
     /// This is a hack to delay until the fees are non-zero for test consistency
@@ -869,6 +994,17 @@ impl TestValidator {
         )
     }
 
+    /// Write the profile of the BPF programs run so far to the profile file, if profiling
+    ///
+    /// The profile is also written every few seconds while the validator runs, and once more
+    /// when it's dropped.
+    pub fn write_profile(&self) -> io::Result<()> {
+        match &self.profile_file {
+            Some(profile_file) => profiler::write_profile_file(profile_file),
+            None => Ok(()),
+        }
+    }
+
     pub fn join(mut self) {
         if let Some(validator) = self.validator.take() {
             validator.join();
@@ -882,6 +1018,158 @@ impl TestValidator {
     pub fn bank_forks(&self) -> Arc<RwLock<BankForks>> {
         self.validator.as_ref().unwrap().bank_forks.clone()
     }
+
+    /// Run `f` with the bank transactions are currently processed in, so that the accounts it
+    /// stores are visible to the transactions that follow
+    ///
+    /// The bank can't freeze while `f` runs, which must not take the bank's hash lock.
+    fn with_working_bank<T, F>(&self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&Bank) -> Result<T, String>,
+    {
+        const MAX_TRIES: u64 = 20;
+        let bank_forks = self.bank_forks();
+        for _ in 0..MAX_TRIES {
+            let bank = bank_forks.read().unwrap().working_bank();
+            {
+                let hash = bank.freeze_lock();
+                if *hash == Hash::default() {
+                    return f(&bank);
+                }
+            }
+            // Wait for the bank of the next slot
+            std::thread::sleep(Duration::from_millis(DEFAULT_MS_PER_SLOT / 4));
+        }
+        Err("Timed out waiting for a working bank".to_string())
+    }
+
+    /// The accounts of `bank` that `save_state` and `restore_state` cover, which are all but the
+    /// sysvars, the builtin programs and the validator's own accounts
+    fn state_accounts(&self, bank: &Bank) -> Result<HashMap<Pubkey, AccountSharedData>, String> {
+        let identity = self.cluster_info().id();
+        Ok(bank
+            .get_all_accounts_with_modified_slots()
+            .map_err(|err| format!("Failed to scan accounts: {:?}", err))?
+            .into_iter()
+            .filter(|(address, account, _)| {
+                account.lamports() > 0
+                    && *address != identity
+                    && *address != self.vote_account_address
+                    && *account.owner() != sysvar::id()
+                    && *account.owner() != native_loader::id()
+            })
+            .map(|(address, account, _)| (address, account))
+            .collect())
+    }
+
+    /// Store `accounts` in the running validator
+    ///
+    /// The accounts are updated in the bank of the current slot, so they're visible at the
+    /// `processed` commitment right away and at other commitments once that slot reaches them.
+    pub fn set_accounts(&self, accounts: &[(Pubkey, AccountSharedData)]) -> Result<(), String> {
+        self.with_working_bank(|bank| {
+            bank.store_accounts_for_test_validator(accounts);
+            Ok(())
+        })
+    }
+
+    /// Store `account` at `address` in the running validator
+    pub fn set_account(&self, address: Pubkey, account: AccountSharedData) -> Result<(), String> {
+        self.set_accounts(&[(address, account)])
+    }
+
+    /// Request the activation of a feature in the running validator
+    ///
+    /// As on a real cluster, the feature is activated at the next epoch boundary.
+    pub fn activate_feature(&self, feature_id: &Pubkey) -> Result<(), String> {
+        self.with_working_bank(|bank| {
+            if bank.feature_set.is_active(feature_id) {
+                return Err(format!("Feature {} is already active", feature_id));
+            }
+            if bank.get_account(feature_id).is_none() {
+                let account = feature::create_account(
+                    &Feature::default(),
+                    bank.get_minimum_balance_for_rent_exemption(Feature::size_of()),
+                );
+                bank.store_accounts_for_test_validator(&[(*feature_id, account)]);
+            }
+            Ok(())
+        })
+    }
+
+    /// Withdraw the activation request of a feature in the running validator
+    ///
+    /// Active features can't be deactivated, use `TestValidatorGenesis::deactivate_features`
+    /// instead.
+    pub fn deactivate_feature(&self, feature_id: &Pubkey) -> Result<(), String> {
+        self.with_working_bank(|bank| {
+            if bank.feature_set.is_active(feature_id) {
+                return Err(format!(
+                    "Feature {} is already active, it can only be deactivated at genesis",
+                    feature_id
+                ));
+            }
+            if bank.get_account(feature_id).is_some() {
+                bank.store_accounts_for_test_validator(&[(
+                    *feature_id,
+                    AccountSharedData::default(),
+                )]);
+            }
+            Ok(())
+        })
+    }
+
+    /// Restart the validator warped to `warp_slot`, keeping the state of the current root bank
+    ///
+    /// The RPC URLs stay the same, but the TPU and gossip addresses, as well as
+    /// `cluster_info()` and `bank_forks()`, change.
+    pub fn warp_to_slot(&mut self, warp_slot: Slot) -> Result<(), Box<dyn std::error::Error>> {
+        let working_slot = self.bank_forks().read().unwrap().working_bank().slot();
+        if warp_slot <= working_slot {
+            return Err(format!(
+                "Warp slot {} must be greater than the working bank slot {}",
+                warp_slot, working_slot
+            )
+            .into());
+        }
+        if let Some(validator) = self.validator.take() {
+            validator.close();
+        }
+        self.start_validator(Some(warp_slot))
+    }
+
+    /// Save the accounts of the running validator, returning an id to restore them with
+    pub fn save_state(&self) -> Result<usize, String> {
+        let accounts = self.with_working_bank(|bank| self.state_accounts(bank))?;
+        let mut saved_states = self.saved_states.lock().unwrap();
+        saved_states.push(accounts);
+        Ok(saved_states.len() - 1)
+    }
+
+    /// Restore the accounts saved by `save_state`, removing the accounts created since
+    ///
+    /// Only accounts are restored, the slot and the clock keep advancing.
+    pub fn restore_state(&self, id: usize) -> Result<(), String> {
+        let saved_states = self.saved_states.lock().unwrap();
+        let saved_accounts = saved_states
+            .get(id)
+            .ok_or_else(|| format!("No saved state {}", id))?;
+        self.with_working_bank(|bank| {
+            let accounts = self.state_accounts(bank)?;
+            let removed_accounts = accounts
+                .keys()
+                .filter(|address| !saved_accounts.contains_key(*address))
+                .map(|address| (*address, AccountSharedData::default()));
+            let changed_accounts = saved_accounts
+                .iter()
+                .filter(|(address, saved_account)| accounts.get(*address) != Some(*saved_account))
+                .map(|(address, saved_account)| (*address, saved_account.clone()));
+            bank.store_accounts_for_test_validator(
+                &removed_accounts.chain(changed_accounts).collect::<Vec<_>>(),
+            );
+            Ok(())
+        })
+    }
 }
 
 impl Drop for TestValidator {
@@ -889,6 +1177,16 @@ impl Drop for TestValidator {
         if let Some(validator) = self.validator.take() {
             validator.close();
         }
+        self.config.validator_exit.write().unwrap().exit();
+        if let Some(profile_file) = &self.profile_file {
+            if let Err(err) = profiler::write_profile_file(profile_file) {
+                warn!(
+                    "Failed to write BPF program profile to {}: {}",
+                    profile_file.display(),
+                    err
+                );
+            }
+        }
         if !self.preserve_ledger {
             remove_dir_all(&self.ledger_path).unwrap_or_else(|err| {
                 panic!(
@@ -919,6 +1217,26 @@ mod test {
         rpc_client.get_health().await.expect("health");
     }
 
+    #[test]
+    fn set_and_restore_accounts() {
+        let (test_validator, _payer) = TestValidatorGenesis::default().start();
+        let rpc_client = test_validator.get_rpc_client();
+        let address = Pubkey::new_unique();
+        let lamports = sol_to_lamports(1.0);
+
+        let state = test_validator.save_state().unwrap();
+        test_validator
+            .set_account(
+                address,
+                AccountSharedData::new(lamports, 0, &solana_sdk::system_program::id()),
+            )
+            .unwrap();
+        assert_eq!(rpc_client.get_balance(&address).unwrap(), lamports);
+
+        test_validator.restore_state(state).unwrap();
+        assert_eq!(rpc_client.get_balance(&address).unwrap(), 0);
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn document_tokio_panic() {
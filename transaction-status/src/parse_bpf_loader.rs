@@ -144,6 +144,29 @@ pub fn parse_bpf_upgradeable_loader(
                 }),
             })
         }
+        UpgradeableLoaderInstruction::ExtendProgram { additional_bytes } => {
+            check_num_bpf_upgradeable_loader_accounts(&instruction.accounts, 2)?;
+            let mut value = json!({
+                "additionalBytes": additional_bytes,
+                "programDataAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                "programAccount": account_keys[instruction.accounts[1] as usize].to_string()
+            });
+            let map = value.as_object_mut().unwrap();
+            if instruction.accounts.len() >= 4 {
+                map.insert(
+                    "systemProgram".to_string(),
+                    json!(account_keys[instruction.accounts[2] as usize].to_string()),
+                );
+                map.insert(
+                    "payerAccount".to_string(),
+                    json!(account_keys[instruction.accounts[3] as usize].to_string()),
+                );
+            }
+            Ok(ParsedInstructionEnum {
+                instruction_type: "extendProgram".to_string(),
+                info: value,
+            })
+        }
     }
 }
 
@@ -544,4 +567,57 @@ mod test {
         )
         .is_err());
     }
+
+    #[test]
+    fn test_parse_bpf_upgradeable_loader_extend_program_ix() {
+        let program_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let (programdata_address, _) = Pubkey::find_program_address(
+            &[program_address.as_ref()],
+            &bpf_loader_upgradeable::id(),
+        );
+        let instruction =
+            bpf_loader_upgradeable::extend_program(&program_address, Some(&payer_address), 1024);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_bpf_upgradeable_loader(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "extendProgram".to_string(),
+                info: json!({
+                    "additionalBytes": 1024,
+                    "programDataAccount": programdata_address.to_string(),
+                    "programAccount": program_address.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "payerAccount": payer_address.to_string(),
+                }),
+            }
+        );
+
+        let instruction = bpf_loader_upgradeable::extend_program(&program_address, None, 1024);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_bpf_upgradeable_loader(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "extendProgram".to_string(),
+                info: json!({
+                    "additionalBytes": 1024,
+                    "programDataAccount": programdata_address.to_string(),
+                    "programAccount": program_address.to_string(),
+                }),
+            }
+        );
+        assert!(parse_bpf_upgradeable_loader(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..1], None)
+        )
+        .is_err());
+    }
 }
@@ -284,6 +284,43 @@ pub fn parse_stake(
                 }),
             })
         }
+        StakeInstruction::Redelegate => {
+            check_num_stake_accounts(&instruction.accounts, 5)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "redelegate".to_string(),
+                info: json!({
+                    "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "newStakeAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "voteAccount": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "stakeConfigAccount": account_keys[instruction.accounts[3] as usize].to_string(),
+                    "stakeAuthority": account_keys[instruction.accounts[4] as usize].to_string(),
+                }),
+            })
+        }
+        StakeInstruction::MoveStake(lamports) => {
+            check_num_stake_accounts(&instruction.accounts, 3)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "moveStake".to_string(),
+                info: json!({
+                    "source": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "destination": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "stakeAuthority": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "lamports": lamports,
+                }),
+            })
+        }
+        StakeInstruction::MoveLamports(lamports) => {
+            check_num_stake_accounts(&instruction.accounts, 3)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "moveLamports".to_string(),
+                info: json!({
+                    "source": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "destination": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "stakeAuthority": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "lamports": lamports,
+                }),
+            })
+        }
     }
 }
 
@@ -463,6 +500,96 @@ mod test {
         .is_err());
     }
 
+    #[test]
+    fn test_parse_stake_redelegate_ix() {
+        let stake_pubkey = Pubkey::new_unique();
+        let authorized_pubkey = Pubkey::new_unique();
+        let vote_pubkey = Pubkey::new_unique();
+        let new_stake_pubkey = Pubkey::new_unique();
+        let instructions = instruction::redelegate(
+            &stake_pubkey,
+            &authorized_pubkey,
+            &vote_pubkey,
+            &new_stake_pubkey,
+        );
+        let message = Message::new(&[instructions[2].clone()], None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "redelegate".to_string(),
+                info: json!({
+                    "stakeAccount": stake_pubkey.to_string(),
+                    "newStakeAccount": new_stake_pubkey.to_string(),
+                    "voteAccount": vote_pubkey.to_string(),
+                    "stakeConfigAccount": config::ID.to_string(),
+                    "stakeAuthority": authorized_pubkey.to_string(),
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..4], None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_stake_move_ix() {
+        let source_stake_pubkey = Pubkey::new_unique();
+        let destination_stake_pubkey = Pubkey::new_unique();
+        let authorized_pubkey = Pubkey::new_unique();
+        let lamports = 55;
+
+        let instructions = [
+            (
+                instruction::move_stake(
+                    &source_stake_pubkey,
+                    &destination_stake_pubkey,
+                    &authorized_pubkey,
+                    lamports,
+                ),
+                "moveStake",
+            ),
+            (
+                instruction::move_lamports(
+                    &source_stake_pubkey,
+                    &destination_stake_pubkey,
+                    &authorized_pubkey,
+                    lamports,
+                ),
+                "moveLamports",
+            ),
+        ];
+        for (instruction, instruction_type) in instructions {
+            let message = Message::new(&[instruction], None);
+            assert_eq!(
+                parse_stake(
+                    &message.instructions[0],
+                    &AccountKeys::new(&message.account_keys, None)
+                )
+                .unwrap(),
+                ParsedInstructionEnum {
+                    instruction_type: instruction_type.to_string(),
+                    info: json!({
+                        "source": source_stake_pubkey.to_string(),
+                        "destination": destination_stake_pubkey.to_string(),
+                        "stakeAuthority": authorized_pubkey.to_string(),
+                        "lamports": lamports,
+                    }),
+                }
+            );
+            assert!(parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys[0..2], None)
+            )
+            .is_err());
+        }
+    }
+
     #[test]
     fn test_parse_stake_split_ix() {
         let lamports = 55;
@@ -6,21 +6,26 @@ use {
     jsonrpc_server_utils::tokio,
     log::*,
     serde::{Deserialize, Serialize},
+    solana_account_decoder::UiAccount,
     solana_core::{
         consensus::Tower, tower_storage::TowerStorage, validator::ValidatorStartProgress,
     },
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfo},
     solana_runtime::bank_forks::BankForks,
     solana_sdk::{
+        account::AccountSharedData,
+        clock::Slot,
         exit::Exit,
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
     },
+    solana_test_validator::TestValidator,
     std::{
         fmt::{self, Display},
         net::SocketAddr,
         path::{Path, PathBuf},
-        sync::{Arc, RwLock},
+        str::FromStr,
+        sync::{Arc, Mutex, RwLock},
         thread::{self, Builder},
         time::{Duration, SystemTime},
     },
@@ -31,6 +36,8 @@ pub struct AdminRpcRequestMetadataPostInit {
     pub cluster_info: Arc<ClusterInfo>,
     pub bank_forks: Arc<RwLock<BankForks>>,
     pub vote_account: Pubkey,
+    /// Set by `solana-test-validator`, whose bank state can be manipulated
+    pub test_validator: Option<Arc<Mutex<TestValidator>>>,
 }
 
 #[derive(Clone)]
@@ -58,6 +65,29 @@ impl AdminRpcRequestMetadata {
             ))
         }
     }
+
+    fn with_test_validator<F, R>(&self, func: F) -> Result<R>
+    where
+        F: FnOnce(&mut TestValidator) -> std::result::Result<R, String>,
+    {
+        let test_validator = self.with_post_init(|post_init| {
+            post_init
+                .test_validator
+                .clone()
+                .ok_or_else(jsonrpc_core::error::Error::method_not_found)
+        })?;
+        let mut test_validator = test_validator.lock().unwrap();
+        func(&mut test_validator).map_err(jsonrpc_core::error::Error::invalid_params)
+    }
+}
+
+fn pubkey_from_str(param_name: &str, pubkey_str: &str) -> Result<Pubkey> {
+    Pubkey::from_str(pubkey_str).map_err(|err| {
+        jsonrpc_core::error::Error::invalid_params(format!(
+            "Invalid {} {}: {}",
+            param_name, pubkey_str, err
+        ))
+    })
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -165,6 +195,29 @@ pub trait AdminRpc {
 
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
+
+    #[rpc(meta, name = "setAccount")]
+    fn set_account(
+        &self,
+        meta: Self::Metadata,
+        pubkey_str: String,
+        account: UiAccount,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "activateFeature")]
+    fn activate_feature(&self, meta: Self::Metadata, feature_id_str: String) -> Result<()>;
+
+    #[rpc(meta, name = "deactivateFeature")]
+    fn deactivate_feature(&self, meta: Self::Metadata, feature_id_str: String) -> Result<()>;
+
+    #[rpc(meta, name = "warpSlot")]
+    fn warp_slot(&self, meta: Self::Metadata, warp_slot: Slot) -> Result<()>;
+
+    #[rpc(meta, name = "saveState")]
+    fn save_state(&self, meta: Self::Metadata) -> Result<usize>;
+
+    #[rpc(meta, name = "restoreState")]
+    fn restore_state(&self, meta: Self::Metadata, state_id: usize) -> Result<()>;
 }
 
 pub struct AdminRpcImpl;
@@ -280,6 +333,58 @@ impl AdminRpc for AdminRpcImpl {
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo> {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
+
+    fn set_account(
+        &self,
+        meta: Self::Metadata,
+        pubkey_str: String,
+        account: UiAccount,
+    ) -> Result<()> {
+        debug!("set_account request received");
+        let address = pubkey_from_str("account address", &pubkey_str)?;
+        let account = account.decode::<AccountSharedData>().ok_or_else(|| {
+            jsonrpc_core::error::Error::invalid_params("Invalid account, data must be binary")
+        })?;
+        meta.with_test_validator(|test_validator| test_validator.set_account(address, account))
+    }
+
+    fn activate_feature(&self, meta: Self::Metadata, feature_id_str: String) -> Result<()> {
+        debug!("activate_feature request received");
+        let feature_id = pubkey_from_str("feature id", &feature_id_str)?;
+        meta.with_test_validator(|test_validator| test_validator.activate_feature(&feature_id))
+    }
+
+    fn deactivate_feature(&self, meta: Self::Metadata, feature_id_str: String) -> Result<()> {
+        debug!("deactivate_feature request received");
+        let feature_id = pubkey_from_str("feature id", &feature_id_str)?;
+        meta.with_test_validator(|test_validator| test_validator.deactivate_feature(&feature_id))
+    }
+
+    fn warp_slot(&self, meta: Self::Metadata, warp_slot: Slot) -> Result<()> {
+        debug!("warp_slot request received");
+        let (bank_forks, cluster_info) = meta.with_test_validator(|test_validator| {
+            test_validator
+                .warp_to_slot(warp_slot)
+                .map_err(|err| err.to_string())?;
+            Ok((test_validator.bank_forks(), test_validator.cluster_info()))
+        })?;
+        // The restarted validator has new banks and gossip
+        if let Some(post_init) = meta.post_init.write().unwrap().as_mut() {
+            post_init.bank_forks = bank_forks;
+            post_init.cluster_info = cluster_info;
+        }
+        Ok(())
+    }
+
+    fn save_state(&self, meta: Self::Metadata) -> Result<usize> {
+        debug!("save_state request received");
+        meta.with_test_validator(|test_validator| test_validator.save_state())
+    }
+
+    fn restore_state(&self, meta: Self::Metadata, state_id: usize) -> Result<()> {
+        debug!("restore_state request received");
+        meta.with_test_validator(|test_validator| test_validator.restore_state(state_id))
+    }
 }
 
 // Start the Admin RPC interface
@@ -39,7 +39,7 @@ use {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         path::{Path, PathBuf},
         process::exit,
-        sync::{Arc, RwLock},
+        sync::{Arc, Mutex, RwLock},
         time::{Duration, SystemTime, UNIX_EPOCH},
     },
 };
@@ -310,6 +310,16 @@ fn main() {
                      If the ledger already exists then this parameter is silently ignored",
                 ),
         )
+        .arg(
+            Arg::with_name("dump_fixture")
+                .long("dump-fixture")
+                .value_name("DIR")
+                .takes_value(true)
+                .help(
+                    "Also write the accounts copied with --clone and --maybe-clone into DIR, \
+                     in the fixture format loaded by `ProgramTest::add_accounts_from_fixture`",
+                ),
+        )
         .arg(
             Arg::with_name("warp_slot")
                 .required(false)
@@ -377,6 +387,29 @@ fn main() {
                 .takes_value(true)
                 .help("Override the runtime's compute unit limit per transaction")
         )
+        .arg(
+            Arg::with_name("profile_file")
+                .long("profile-file")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "Profile the compute units of BPF programs, and write the profile to FILE \
+                     as collapsed stacks for flamegraph tools every few seconds",
+                ),
+        )
+        .arg(
+            Arg::with_name("simulate_forks")
+                .long("simulate-forks")
+                .value_name("INTERVAL DEPTH")
+                .takes_value(true)
+                .number_of_values(2)
+                .validator(is_parsable::<u64>)
+                .help(
+                    "After every INTERVAL voted slots, build a fork without voting on it and \
+                     abandon it after DEPTH processed slots. The fork's slots are skipped and \
+                     reported as dead, and the processed commitment rolls back to its parent",
+                ),
+        )
         .get_matches();
 
     let output = if matches.is_present("quiet") {
@@ -702,7 +735,7 @@ fn main() {
 
     if !accounts_to_clone.is_empty() {
         genesis.clone_accounts(
-            accounts_to_clone,
+            accounts_to_clone.iter().copied(),
             cluster_rpc_client
                 .as_ref()
                 .expect("bug: --url argument missing?"),
@@ -712,7 +745,7 @@ fn main() {
 
     if !accounts_to_maybe_clone.is_empty() {
         genesis.clone_accounts(
-            accounts_to_maybe_clone,
+            accounts_to_maybe_clone.iter().copied(),
             cluster_rpc_client
                 .as_ref()
                 .expect("bug: --url argument missing?"),
@@ -720,6 +753,17 @@ fn main() {
         );
     }
 
+    if let Some(fixture_dir) = matches.value_of("dump_fixture") {
+        let cloned_accounts = accounts_to_clone
+            .iter()
+            .chain(accounts_to_maybe_clone.iter())
+            .copied();
+        if let Err(err) = genesis.write_fixture(Path::new(fixture_dir), cloned_accounts) {
+            println!("Error: failed to write fixture to {}: {}", fixture_dir, err);
+            exit(1);
+        }
+    }
+
     if let Some(warp_slot) = warp_slot {
         genesis.warp_slot(warp_slot);
     }
@@ -767,18 +811,52 @@ fn main() {
         genesis.compute_unit_limit(compute_unit_limit);
     }
 
+    if let Some(profile_file) = matches.value_of("profile_file") {
+        genesis.profile_file(profile_file);
+    }
+
+    if matches.is_present("simulate_forks") {
+        let values = values_t_or_exit!(matches, "simulate_forks", u64);
+        let (interval, depth) = (values[0], values[1]);
+        if interval == 0 {
+            eprintln!("Error: --simulate-forks INTERVAL must be at least 1");
+            exit(1);
+        }
+        genesis.fork_simulation(interval, depth);
+    }
+
+    let (exit_sender, exit_receiver) = unbounded();
+    genesis
+        .validator_exit
+        .write()
+        .unwrap()
+        .register_exit(Box::new(move || {
+            let _ = exit_sender.send(());
+        }));
+
     match genesis.start_with_mint_address(mint_address, socket_addr_space) {
         Ok(test_validator) => {
+            let bank_forks = test_validator.bank_forks();
+            let cluster_info = test_validator.cluster_info();
+            let vote_account = test_validator.vote_account_address();
+            let test_validator = Arc::new(Mutex::new(test_validator));
             *admin_service_post_init.write().unwrap() =
                 Some(admin_rpc_service::AdminRpcRequestMetadataPostInit {
-                    bank_forks: test_validator.bank_forks(),
-                    cluster_info: test_validator.cluster_info(),
-                    vote_account: test_validator.vote_account_address(),
+                    bank_forks,
+                    cluster_info,
+                    vote_account,
+                    test_validator: Some(test_validator.clone()),
                 });
             if let Some(dashboard) = dashboard {
                 dashboard.run(Duration::from_millis(250));
             }
-            test_validator.join();
+            // The admin RPC service may restart the validator, so wait for the exit request
+            // rather than for the validator
+            let _ = exit_receiver.recv();
+            admin_service_post_init.write().unwrap().take();
+            if let Ok(test_validator) = Arc::try_unwrap(test_validator) {
+                test_validator.into_inner().unwrap().join();
+            }
         }
         Err(err) => {
             drop(dashboard);
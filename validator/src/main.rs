@@ -679,6 +679,14 @@ pub fn main() {
                 .help("Include CPI inner instructions, logs, and return data in \
                        the historical transaction info stored"),
         )
+        .arg(
+            Arg::with_name("enable_rpc_account_history")
+                .long("enable-rpc-account-history")
+                .takes_value(false)
+                .help("Record every account version written at root and serve it over JSON RPC, \
+                       including the 'getAccountHistory' API and the 'slot' parameter of \
+                       'getAccountInfo'.  This will cause a large increase in disk usage and IOPS"),
+        )
         .arg(
             Arg::with_name("rpc_max_multiple_accounts")
                 .long("rpc-max-multiple-accounts")
@@ -1561,10 +1569,26 @@ pub fn main() {
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "spl-token-delegate",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
+        .arg(
+            Arg::with_name("account_index_program_data_key")
+                .long("account-index-program-data-key")
+                .takes_value(true)
+                .multiple(true)
+                .validator(|value| parse_program_data_key_index(&value).map(|_| ()))
+                .value_name("PROGRAM_ID:OFFSET")
+                .help("Enable an accounts index of the accounts owned by PROGRAM_ID, \
+                       indexed by the 32-byte key at OFFSET of their data. \
+                       getProgramAccounts requests with a matching memcmp filter use this index."),
+        )
         .arg(
             Arg::with_name("account_index_exclude_key")
                 .long(EXCLUDE_KEY)
@@ -1608,6 +1632,16 @@ pub fn main() {
                 .help("AppendVecs that are older than an epoch are squashed together.")
                       .hidden(true),
         )
+        .arg(
+            Arg::with_name("accounts_db_cold_storage_epochs")
+                .long("accounts-db-cold-storage-epochs")
+                .value_name("EPOCHS")
+                .validator(is_parsable::<u64>)
+                .takes_value(true)
+                .help("AppendVecs that have not been written to for this many epochs are compressed \
+                      into cold storage files which are read on demand instead of being memory mapped.")
+                      .hidden(true),
+        )
         .arg(
             Arg::with_name("accounts_db_cache_limit_mb")
                 .long("accounts-db-cache-limit-mb")
@@ -2346,6 +2380,7 @@ pub fn main() {
             .map(|mb| mb * MB as u64),
         skip_rewrites: matches.is_present("accounts_db_skip_rewrites"),
         ancient_append_vecs: matches.is_present("accounts_db_ancient_append_vecs"),
+        cold_storage_epochs: value_t!(matches, "accounts_db_cold_storage_epochs", u64).ok(),
         ..AccountsDbConfig::default()
     };
 
@@ -2439,6 +2474,7 @@ pub fn main() {
             enable_rpc_transaction_history: matches.is_present("enable_rpc_transaction_history"),
             enable_extended_tx_metadata_storage: matches.is_present("enable_cpi_and_log_storage")
                 || matches.is_present("enable_extended_tx_metadata_storage"),
+            enable_rpc_account_history: matches.is_present("enable_rpc_account_history"),
             rpc_bigtable_config,
             faucet_addr: matches.value_of("rpc_faucet_addr").map(|address| {
                 solana_net_utils::parse_host_port(address).expect("failed to parse faucet address")
@@ -2980,6 +3016,7 @@ pub fn main() {
             bank_forks: validator.bank_forks.clone(),
             cluster_info: validator.cluster_info.clone(),
             vote_account,
+            test_validator: None,
         });
 
     if let Some(filename) = init_complete_file {
@@ -2993,17 +3030,37 @@ pub fn main() {
     info!("Validator exiting..");
 }
 
+fn parse_program_data_key_index(value: &str) -> Result<AccountIndex, String> {
+    let (program_id, offset) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected PROGRAM_ID:OFFSET, got {}", value))?;
+    let program_id = program_id
+        .parse::<Pubkey>()
+        .map_err(|err| format!("invalid program id {}: {}", program_id, err))?;
+    let offset = offset
+        .parse::<usize>()
+        .map_err(|err| format!("invalid offset {}: {}", offset, err))?;
+    Ok(AccountIndex::ProgramDataKey { program_id, offset })
+}
+
 fn process_account_indexes(matches: &ArgMatches) -> AccountSecondaryIndexes {
-    let account_indexes: HashSet<AccountIndex> = matches
+    let mut account_indexes: HashSet<AccountIndex> = matches
         .values_of("account_indexes")
         .unwrap_or_default()
-        .map(|value| match value {
-            "program-id" => AccountIndex::ProgramId,
-            "spl-token-mint" => AccountIndex::SplTokenMint,
-            "spl-token-owner" => AccountIndex::SplTokenOwner,
+        .flat_map(|value| match value {
+            "program-id" => vec![AccountIndex::ProgramId],
+            "spl-token-mint" => vec![AccountIndex::SplTokenMint],
+            "spl-token-owner" => vec![AccountIndex::SplTokenOwner],
+            "spl-token-delegate" => AccountIndex::spl_token_delegate().to_vec(),
             _ => unreachable!(),
         })
         .collect();
+    account_indexes.extend(
+        matches
+            .values_of("account_index_program_data_key")
+            .unwrap_or_default()
+            .map(|value| parse_program_data_key_index(value).unwrap()),
+    );
 
     let account_indexes_include_keys: HashSet<Pubkey> =
         values_t!(matches, "account_index_include_key", Pubkey)
@@ -80,10 +80,10 @@ pub const MUL: u64 = 2;
 
 // Functions are organized by the curve traits, which can be instantiated by multiple curve
 // representations. The functions take in a `curve_id` (e.g. `CURVE25519_EDWARDS`) and should run
-// the associated functions in the appropriate trait instantiation. The `curve_op` function
+// the associated functions in the appropriate trait instantiation. The `curve_group_op` function
 // additionally takes in an `op_id` (e.g. `ADD`) that controls which associated functions to run in
 // `GroupOperations`.
 #[cfg(target_os = "solana")]
 pub use solana_program::syscalls::{
-    sol_curve_multiscalar_mul, sol_curve_op, sol_curve_pairing_map, sol_curve_validate_point,
+    sol_curve_group_op, sol_curve_multiscalar_mul, sol_curve_pairing_map, sol_curve_validate_point,
 };
@@ -130,8 +130,12 @@ mod target_arch {
 mod target_arch {
     use {
         super::*,
-        crate::curve25519::curve_syscall_traits::{
-            sol_curve_op, sol_curve_validate_point, ADD, CURVE25519_RISTRETTO, MUL, SUB,
+        crate::curve25519::{
+            curve_syscall_traits::{
+                sol_curve_group_op, sol_curve_multiscalar_mul, sol_curve_validate_point, ADD,
+                CURVE25519_RISTRETTO, MUL, SUB,
+            },
+            scalar::PodScalar,
         },
     };
 
@@ -154,7 +158,7 @@ mod target_arch {
     ) -> Option<PodRistrettoPoint> {
         let mut result_point = PodRistrettoPoint::zeroed();
         let result = unsafe {
-            sol_curve_op(
+            sol_curve_group_op(
                 CURVE25519_RISTRETTO,
                 ADD,
                 &left_point.0 as *const u8,
@@ -176,7 +180,7 @@ mod target_arch {
     ) -> Option<PodRistrettoPoint> {
         let mut result_point = PodRistrettoPoint::zeroed();
         let result = unsafe {
-            sol_curve_op(
+            sol_curve_group_op(
                 CURVE25519_RISTRETTO,
                 SUB,
                 &left_point.0 as *const u8,
@@ -193,16 +197,38 @@ mod target_arch {
     }
 
     pub fn multiply_ristretto(
-        left_point: &PodRistrettoPoint,
-        right_point: &PodRistrettoPoint,
+        scalar: &PodScalar,
+        point: &PodRistrettoPoint,
     ) -> Option<PodRistrettoPoint> {
         let mut result_point = PodRistrettoPoint::zeroed();
         let result = unsafe {
-            sol_curve_op(
+            sol_curve_group_op(
                 CURVE25519_RISTRETTO,
                 MUL,
-                &left_point.0 as *const u8,
-                &right_point.0 as *const u8,
+                &scalar.0 as *const u8,
+                &point.0 as *const u8,
+                &mut result_point.0 as *mut u8,
+            )
+        };
+
+        if result == 0 {
+            Some(result_point)
+        } else {
+            None
+        }
+    }
+
+    pub fn multiscalar_multiply_ristretto(
+        scalars: &[PodScalar],
+        points: &[PodRistrettoPoint],
+    ) -> Option<PodRistrettoPoint> {
+        let mut result_point = PodRistrettoPoint::zeroed();
+        let result = unsafe {
+            sol_curve_multiscalar_mul(
+                CURVE25519_RISTRETTO,
+                scalars.as_ptr() as *const u8,
+                points.as_ptr() as *const u8,
+                points.len() as u64,
                 &mut result_point.0 as *mut u8,
             )
         };
@@ -22,6 +22,8 @@ pub enum ProofError {
     ZeroBalanceProof,
     #[error("validity proof failed to verify")]
     ValidityProof,
+    #[error("public-key validity proof failed to verify")]
+    PubkeyValidityProof,
     #[error(
         "`zk_token_elgamal::pod::ElGamalCiphertext` contains invalid ElGamalCiphertext ciphertext"
     )]
@@ -66,3 +68,9 @@ impl From<ValidityProofError> for ProofError {
         Self::ValidityProof
     }
 }
+
+impl From<PubkeyValidityProofError> for ProofError {
+    fn from(_err: PubkeyValidityProofError) -> Self {
+        Self::PubkeyValidityProof
+    }
+}
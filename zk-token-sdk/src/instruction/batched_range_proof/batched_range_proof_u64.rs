@@ -0,0 +1,201 @@
+//! The 64-bit batched range proof instruction.
+
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::{
+        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        errors::ProofError,
+        instruction::{batched_range_proof::batched_bit_length, Verifiable},
+        range_proof::RangeProof,
+    },
+    std::convert::TryInto,
+};
+use {
+    crate::{
+        instruction::{batched_range_proof::BatchedRangeProofContext, ZkProofData},
+        zk_token_elgamal::pod,
+        zk_token_proof_state::ProofType,
+    },
+    bytemuck::{Pod, Zeroable},
+};
+
+#[cfg(not(target_os = "solana"))]
+const BATCHED_RANGE_PROOF_U64_BIT_LENGTH: usize = 64;
+
+/// The instruction data that is needed for the `ProofInstruction::VerifyBatchedRangeProofU64`
+/// instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct BatchedRangeProofU64Data {
+    /// The context data for a batched range proof
+    pub context: BatchedRangeProofContext, // 264 bytes
+
+    /// The batched range proof
+    pub proof: pod::RangeProof64, // 672 bytes
+}
+
+#[cfg(not(target_os = "solana"))]
+impl BatchedRangeProofU64Data {
+    pub fn new(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+    ) -> Result<Self, ProofError> {
+        // the sum of the bit lengths must be 64
+        if batched_bit_length(&bit_lengths)? != BATCHED_RANGE_PROOF_U64_BIT_LENGTH {
+            return Err(ProofError::Generation);
+        }
+
+        let context =
+            BatchedRangeProofContext::new(&commitments, &amounts, &bit_lengths, &openings)?;
+
+        let mut transcript = context.new_transcript();
+        let proof = RangeProof::new(amounts, bit_lengths, openings, &mut transcript).try_into()?;
+
+        Ok(Self { context, proof })
+    }
+}
+
+impl ZkProofData<BatchedRangeProofContext> for BatchedRangeProofU64Data {
+    const PROOF_TYPE: ProofType = ProofType::BatchedRangeProofU64;
+
+    fn context_data(&self) -> &BatchedRangeProofContext {
+        &self.context
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+impl Verifiable for BatchedRangeProofU64Data {
+    fn verify(&self) -> Result<(), ProofError> {
+        let (commitments, bit_lengths) = self.context.commitments_and_bit_lengths()?;
+        if bit_lengths.iter().sum::<usize>() != BATCHED_RANGE_PROOF_U64_BIT_LENGTH {
+            return Err(ProofError::Verification);
+        }
+
+        let mut transcript = self.context.new_transcript();
+        let proof: RangeProof = self.proof.try_into()?;
+
+        proof.verify(commitments.iter().collect(), bit_lengths, &mut transcript)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::pedersen::Pedersen};
+
+    #[test]
+    fn test_batched_range_proof_u64_instruction_correctness() {
+        let amount_1 = 255_u64;
+        let amount_2 = 77_u64;
+        let amount_3 = 99_u64;
+        let amount_4 = 99_u64;
+        let amount_5 = 11_u64;
+        let amount_6 = 33_u64;
+        let amount_7 = 99_u64;
+        let amount_8 = 99_u64;
+
+        let (commitment_1, opening_1) = Pedersen::new(amount_1);
+        let (commitment_2, opening_2) = Pedersen::new(amount_2);
+        let (commitment_3, opening_3) = Pedersen::new(amount_3);
+        let (commitment_4, opening_4) = Pedersen::new(amount_4);
+        let (commitment_5, opening_5) = Pedersen::new(amount_5);
+        let (commitment_6, opening_6) = Pedersen::new(amount_6);
+        let (commitment_7, opening_7) = Pedersen::new(amount_7);
+        let (commitment_8, opening_8) = Pedersen::new(amount_8);
+
+        let proof_data = BatchedRangeProofU64Data::new(
+            vec![
+                &commitment_1,
+                &commitment_2,
+                &commitment_3,
+                &commitment_4,
+                &commitment_5,
+                &commitment_6,
+                &commitment_7,
+                &commitment_8,
+            ],
+            vec![
+                amount_1, amount_2, amount_3, amount_4, amount_5, amount_6, amount_7, amount_8,
+            ],
+            vec![8, 8, 8, 8, 8, 8, 8, 8],
+            vec![
+                &opening_1, &opening_2, &opening_3, &opening_4, &opening_5, &opening_6, &opening_7,
+                &opening_8,
+            ],
+        )
+        .unwrap();
+
+        assert!(proof_data.verify().is_ok());
+
+        // an amount that does not fit in its bit-length should not verify
+        let amount_1 = 256_u64; // not representable as an 8-bit number
+        let (commitment_1, opening_1) = Pedersen::new(amount_1);
+
+        let proof_data = BatchedRangeProofU64Data::new(
+            vec![
+                &commitment_1,
+                &commitment_2,
+                &commitment_3,
+                &commitment_4,
+                &commitment_5,
+                &commitment_6,
+                &commitment_7,
+                &commitment_8,
+            ],
+            vec![
+                amount_1, amount_2, amount_3, amount_4, amount_5, amount_6, amount_7, amount_8,
+            ],
+            vec![8, 8, 8, 8, 8, 8, 8, 8],
+            vec![
+                &opening_1, &opening_2, &opening_3, &opening_4, &opening_5, &opening_6, &opening_7,
+                &opening_8,
+            ],
+        )
+        .unwrap();
+
+        assert!(proof_data.verify().is_err());
+    }
+
+    #[test]
+    fn test_batched_range_proof_u64_instruction_context() {
+        let amount_1 = 23_u64;
+        let amount_2 = 24_u64;
+
+        let (commitment_1, opening_1) = Pedersen::new(amount_1);
+        let (commitment_2, opening_2) = Pedersen::new(amount_2);
+
+        // fewer commitments than the maximum leave the remaining slots zeroed
+        let mut proof_data = BatchedRangeProofU64Data::new(
+            vec![&commitment_1, &commitment_2],
+            vec![amount_1, amount_2],
+            vec![32, 32],
+            vec![&opening_1, &opening_2],
+        )
+        .unwrap();
+
+        assert!(proof_data.verify().is_ok());
+        assert_eq!(proof_data.context.bit_lengths, [32, 32, 0, 0, 0, 0, 0, 0]);
+
+        // data in an unused slot is not covered by the proof and must be rejected
+        proof_data.context.commitments[2] = pod::PedersenCommitment(commitment_1.to_bytes());
+        assert!(proof_data.verify().is_err());
+
+        // the bit-lengths must add up to 64
+        assert_eq!(
+            BatchedRangeProofU64Data::new(
+                vec![&commitment_1, &commitment_2],
+                vec![amount_1, amount_2],
+                vec![32, 16],
+                vec![&opening_1, &opening_2],
+            )
+            .err(),
+            Some(ProofError::Generation)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! The batched range proof instructions.
+//!
+//! A batched range proof is defined with respect to a sequence of Pedersen commitments `[C_1, ...,
+//! C_N]` and bit-lengths `[n_1, ..., n_N]`. It certifies that each `C_i` is a commitment to a
+//! number of bit-length `n_i`.
+//!
+//! There are two batched range proof instructions: `VerifyBatchedRangeProofU64` and
+//! `VerifyBatchedRangeProofU128`. The sum of the bit-lengths of the commitments must be 64 and
+//! 128 respectively. At most `MAX_COMMITMENTS` commitments can be proved in a single
+//! instruction.
+
+pub mod batched_range_proof_u128;
+pub mod batched_range_proof_u64;
+
+use {
+    crate::zk_token_elgamal::pod,
+    bytemuck::{Pod, Zeroable},
+};
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::{
+        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        errors::ProofError,
+    },
+    bytemuck::bytes_of,
+    merlin::Transcript,
+    std::convert::TryInto,
+};
+
+/// The maximum number of commitments that can be proved in a single batched range proof
+pub const MAX_COMMITMENTS: usize = 8;
+
+/// The maximum bit-length of a single commitment in a batched range proof
+#[cfg(not(target_os = "solana"))]
+const MAX_SINGLE_BIT_LENGTH: usize = 64;
+
+/// The context data needed to verify a batched range proof.
+///
+/// Unused commitment slots are zeroed and have a bit-length of zero. The commitments that are
+/// proved must be placed at the front of the array.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct BatchedRangeProofContext {
+    /// The Pedersen commitments to be proved
+    pub commitments: [pod::PedersenCommitment; MAX_COMMITMENTS], // 256 bytes
+
+    /// The bit-lengths of the committed amounts
+    pub bit_lengths: [u8; MAX_COMMITMENTS], // 8 bytes
+}
+
+#[cfg(not(target_os = "solana"))]
+impl BatchedRangeProofContext {
+    fn new_transcript(&self) -> Transcript {
+        let mut transcript = Transcript::new(b"BatchedRangeProof");
+        transcript.append_message(b"commitments", bytes_of(&self.commitments));
+        transcript.append_message(b"bit-lengths", bytes_of(&self.bit_lengths));
+        transcript
+    }
+
+    fn new(
+        commitments: &[&PedersenCommitment],
+        amounts: &[u64],
+        bit_lengths: &[usize],
+        openings: &[&PedersenOpening],
+    ) -> Result<Self, ProofError> {
+        // the number of commitments is capped at `MAX_COMMITMENTS`
+        let num_commitments = commitments.len();
+        if num_commitments == 0
+            || num_commitments > MAX_COMMITMENTS
+            || num_commitments != amounts.len()
+            || num_commitments != bit_lengths.len()
+            || num_commitments != openings.len()
+        {
+            return Err(ProofError::Generation);
+        }
+
+        let mut pod_commitments = [pod::PedersenCommitment::zeroed(); MAX_COMMITMENTS];
+        for (pod_commitment, commitment) in pod_commitments.iter_mut().zip(commitments) {
+            *pod_commitment = pod::PedersenCommitment(commitment.to_bytes());
+        }
+
+        let mut pod_bit_lengths = [0; MAX_COMMITMENTS];
+        for (pod_bit_length, bit_length) in pod_bit_lengths.iter_mut().zip(bit_lengths) {
+            if *bit_length == 0 || *bit_length > MAX_SINGLE_BIT_LENGTH {
+                return Err(ProofError::Generation);
+            }
+            *pod_bit_length = *bit_length as u8;
+        }
+
+        Ok(BatchedRangeProofContext {
+            commitments: pod_commitments,
+            bit_lengths: pod_bit_lengths,
+        })
+    }
+
+    /// Returns the commitments to be proved along with their bit-lengths.
+    ///
+    /// Errors if an unused slot is not zeroed or a bit-length is out of range, so that the context
+    /// stored in an account never contains data that was not covered by the proof.
+    fn commitments_and_bit_lengths(
+        &self,
+    ) -> Result<(Vec<PedersenCommitment>, Vec<usize>), ProofError> {
+        let num_commitments = self
+            .bit_lengths
+            .iter()
+            .take_while(|bit_length| **bit_length != 0)
+            .count();
+        if num_commitments == 0 {
+            return Err(ProofError::Verification);
+        }
+
+        let (commitments, unused_commitments) = self.commitments.split_at(num_commitments);
+        let (bit_lengths, unused_bit_lengths) = self.bit_lengths.split_at(num_commitments);
+
+        if unused_bit_lengths.iter().any(|bit_length| *bit_length != 0)
+            || unused_commitments
+                .iter()
+                .any(|commitment| *commitment != pod::PedersenCommitment::zeroed())
+        {
+            return Err(ProofError::Verification);
+        }
+
+        let commitments = commitments
+            .iter()
+            .map(|commitment| (*commitment).try_into())
+            .collect::<Result<Vec<PedersenCommitment>, _>>()?;
+
+        let bit_lengths = bit_lengths
+            .iter()
+            .map(|bit_length| *bit_length as usize)
+            .collect::<Vec<_>>();
+        if bit_lengths
+            .iter()
+            .any(|bit_length| *bit_length > MAX_SINGLE_BIT_LENGTH)
+        {
+            return Err(ProofError::Verification);
+        }
+
+        Ok((commitments, bit_lengths))
+    }
+}
+
+/// Returns the sum of the bit-lengths or an error on overflow
+#[cfg(not(target_os = "solana"))]
+fn batched_bit_length(bit_lengths: &[usize]) -> Result<usize, ProofError> {
+    bit_lengths
+        .iter()
+        .try_fold(0_usize, |acc, bit_length| acc.checked_add(*bit_length))
+        .ok_or(ProofError::Generation)
+}
@@ -0,0 +1,231 @@
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+            pedersen::PedersenOpening,
+        },
+        errors::ProofError,
+        instruction::Verifiable,
+        sigma_proofs::equality_proof::CtxtCtxtEqualityProof,
+        transcript::TranscriptProtocol,
+    },
+    merlin::Transcript,
+    std::convert::TryInto,
+};
+use {
+    crate::{instruction::ZkProofData, zk_token_elgamal::pod, zk_token_proof_state::ProofType},
+    bytemuck::{Pod, Zeroable},
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyCiphertextCiphertextEquality` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct CiphertextCiphertextEqualityProofData {
+    /// The context data for the ciphertext-ciphertext equality proof
+    pub context: CiphertextCiphertextEqualityProofContext, // 192 bytes
+
+    /// Proof that the two ciphertexts encrypt the same message
+    pub proof: CiphertextCiphertextEqualityProof, // 224 bytes
+}
+
+/// The context data needed to verify a ciphertext-ciphertext equality proof.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct CiphertextCiphertextEqualityProofContext {
+    /// The ElGamal pubkey associated with the source ciphertext
+    pub source_pubkey: pod::ElGamalPubkey, // 32 bytes
+
+    /// The ElGamal pubkey associated with the destination ciphertext
+    pub destination_pubkey: pod::ElGamalPubkey, // 32 bytes
+
+    /// The source ciphertext
+    pub source_ciphertext: pod::ElGamalCiphertext, // 64 bytes
+
+    /// The destination ciphertext
+    pub destination_ciphertext: pod::ElGamalCiphertext, // 64 bytes
+}
+
+#[cfg(not(target_os = "solana"))]
+impl CiphertextCiphertextEqualityProofData {
+    pub fn new(
+        source_keypair: &ElGamalKeypair,
+        destination_pubkey: &ElGamalPubkey,
+        source_ciphertext: &ElGamalCiphertext,
+        destination_ciphertext: &ElGamalCiphertext,
+        destination_opening: &PedersenOpening,
+        amount: u64,
+    ) -> Result<Self, ProofError> {
+        let context = CiphertextCiphertextEqualityProofContext {
+            source_pubkey: pod::ElGamalPubkey(source_keypair.public.to_bytes()),
+            destination_pubkey: pod::ElGamalPubkey(destination_pubkey.to_bytes()),
+            source_ciphertext: pod::ElGamalCiphertext(source_ciphertext.to_bytes()),
+            destination_ciphertext: pod::ElGamalCiphertext(destination_ciphertext.to_bytes()),
+        };
+
+        let mut transcript = CiphertextCiphertextEqualityProof::transcript_new(&context);
+
+        let proof = CiphertextCiphertextEqualityProof::new(
+            source_keypair,
+            destination_pubkey,
+            source_ciphertext,
+            amount,
+            destination_opening,
+            &mut transcript,
+        );
+
+        Ok(Self { context, proof })
+    }
+}
+
+impl ZkProofData<CiphertextCiphertextEqualityProofContext>
+    for CiphertextCiphertextEqualityProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::CiphertextCiphertextEquality;
+
+    fn context_data(&self) -> &CiphertextCiphertextEqualityProofContext {
+        &self.context
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+impl Verifiable for CiphertextCiphertextEqualityProofData {
+    fn verify(&self) -> Result<(), ProofError> {
+        let mut transcript = CiphertextCiphertextEqualityProof::transcript_new(&self.context);
+
+        let source_pubkey = self.context.source_pubkey.try_into()?;
+        let destination_pubkey = self.context.destination_pubkey.try_into()?;
+        let source_ciphertext = self.context.source_ciphertext.try_into()?;
+        let destination_ciphertext = self.context.destination_ciphertext.try_into()?;
+
+        self.proof.verify(
+            &source_pubkey,
+            &destination_pubkey,
+            &source_ciphertext,
+            &destination_ciphertext,
+            &mut transcript,
+        )
+    }
+}
+
+/// This struct represents the cryptographic proof component that certifies that two ElGamal
+/// ciphertexts encrypt the same message
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct CiphertextCiphertextEqualityProof {
+    pub proof: pod::CtxtCtxtEqualityProof,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl CiphertextCiphertextEqualityProof {
+    fn transcript_new(context: &CiphertextCiphertextEqualityProofContext) -> Transcript {
+        let mut transcript = Transcript::new(b"CiphertextCiphertextEqualityProof");
+
+        transcript.append_pubkey(b"source-pubkey", &context.source_pubkey);
+        transcript.append_pubkey(b"destination-pubkey", &context.destination_pubkey);
+
+        transcript.append_ciphertext(b"source-ciphertext", &context.source_ciphertext);
+        transcript.append_ciphertext(b"destination-ciphertext", &context.destination_ciphertext);
+
+        transcript
+    }
+
+    pub fn new(
+        source_keypair: &ElGamalKeypair,
+        destination_pubkey: &ElGamalPubkey,
+        source_ciphertext: &ElGamalCiphertext,
+        amount: u64,
+        destination_opening: &PedersenOpening,
+        transcript: &mut Transcript,
+    ) -> Self {
+        let proof = CtxtCtxtEqualityProof::new(
+            source_keypair,
+            destination_pubkey,
+            source_ciphertext,
+            amount,
+            destination_opening,
+            transcript,
+        );
+
+        Self {
+            proof: proof.into(),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        source_pubkey: &ElGamalPubkey,
+        destination_pubkey: &ElGamalPubkey,
+        source_ciphertext: &ElGamalCiphertext,
+        destination_ciphertext: &ElGamalCiphertext,
+        transcript: &mut Transcript,
+    ) -> Result<(), ProofError> {
+        let proof: CtxtCtxtEqualityProof = self.proof.try_into()?;
+        proof.verify(
+            source_pubkey,
+            destination_pubkey,
+            source_ciphertext,
+            destination_ciphertext,
+            transcript,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ciphertext_ciphertext_instruction_correctness() {
+        let source_keypair = ElGamalKeypair::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+
+        // equal amounts
+        let amount: u64 = 0;
+        let source_ciphertext = source_keypair.public.encrypt(amount);
+
+        let destination_opening = PedersenOpening::new_rand();
+        let destination_ciphertext = destination_keypair
+            .public
+            .encrypt_with(amount, &destination_opening);
+
+        let proof_data = CiphertextCiphertextEqualityProofData::new(
+            &source_keypair,
+            &destination_keypair.public,
+            &source_ciphertext,
+            &destination_ciphertext,
+            &destination_opening,
+            amount,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify().is_ok());
+
+        // different amounts
+        let amount: u64 = 55;
+        let source_ciphertext = source_keypair.public.encrypt(amount);
+
+        let destination_opening = PedersenOpening::new_rand();
+        let destination_ciphertext = destination_keypair
+            .public
+            .encrypt_with(amount + 1, &destination_opening);
+
+        let proof_data = CiphertextCiphertextEqualityProofData::new(
+            &source_keypair,
+            &destination_keypair.public,
+            &source_ciphertext,
+            &destination_ciphertext,
+            &destination_opening,
+            amount,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify().is_err());
+    }
+}
@@ -1,9 +1,14 @@
+pub mod batched_range_proof;
 pub mod close_account;
+pub mod ctxt_ctxt_equality;
+pub mod pubkey_validity;
 pub mod transfer;
 pub mod transfer_with_fee;
 pub mod withdraw;
 pub mod withdraw_withheld;
 
+use {crate::zk_token_proof_state::ProofType, bytemuck::Pod};
+
 #[cfg(not(target_os = "solana"))]
 use {
     crate::{
@@ -17,8 +22,18 @@ use {
     subtle::ConstantTimeEq,
 };
 pub use {
-    close_account::CloseAccountData, transfer::TransferData,
-    transfer_with_fee::TransferWithFeeData, withdraw::WithdrawData,
+    batched_range_proof::{
+        batched_range_proof_u128::BatchedRangeProofU128Data,
+        batched_range_proof_u64::BatchedRangeProofU64Data, BatchedRangeProofContext,
+    },
+    close_account::CloseAccountData,
+    ctxt_ctxt_equality::{
+        CiphertextCiphertextEqualityProofContext, CiphertextCiphertextEqualityProofData,
+    },
+    pubkey_validity::{PubkeyValidityData, PubkeyValidityProofContext},
+    transfer::TransferData,
+    transfer_with_fee::TransferWithFeeData,
+    withdraw::WithdrawData,
     withdraw_withheld::WithdrawWithheldTokensData,
 };
 
@@ -27,6 +42,16 @@ pub trait Verifiable {
     fn verify(&self) -> Result<(), ProofError>;
 }
 
+/// Proof data whose context can be stored in a proof context state account once the proof is
+/// verified
+pub trait ZkProofData<T: Pod> {
+    /// The type of the proof
+    const PROOF_TYPE: ProofType;
+
+    /// The public context of the proof
+    fn context_data(&self) -> &T;
+}
+
 #[cfg(not(target_os = "solana"))]
 #[derive(Debug, Copy, Clone)]
 pub enum Role {
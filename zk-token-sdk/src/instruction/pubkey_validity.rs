@@ -0,0 +1,125 @@
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::{
+        encryption::elgamal::{ElGamalKeypair, ElGamalPubkey},
+        errors::ProofError,
+        instruction::Verifiable,
+        sigma_proofs::pubkey_proof::PubkeyValidityProof as PubkeySigmaProof,
+        transcript::TranscriptProtocol,
+    },
+    merlin::Transcript,
+    std::convert::TryInto,
+};
+use {
+    crate::{instruction::ZkProofData, zk_token_elgamal::pod, zk_token_proof_state::ProofType},
+    bytemuck::{Pod, Zeroable},
+};
+
+/// The instruction data that is needed for the `ProofInstruction::VerifyPubkeyValidity`
+/// instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct PubkeyValidityData {
+    /// The context data for the public key validity proof
+    pub context: PubkeyValidityProofContext, // 32 bytes
+
+    /// Proof that the public key is well-formed
+    pub proof: PubkeyValidityProof, // 64 bytes
+}
+
+/// The context data needed to verify a pubkey validity proof.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct PubkeyValidityProofContext {
+    /// The public key to be proved
+    pub pubkey: pod::ElGamalPubkey, // 32 bytes
+}
+
+#[cfg(not(target_os = "solana"))]
+impl PubkeyValidityData {
+    pub fn new(keypair: &ElGamalKeypair) -> Result<Self, ProofError> {
+        let pod_pubkey = pod::ElGamalPubkey(keypair.public.to_bytes());
+
+        let context = PubkeyValidityProofContext { pubkey: pod_pubkey };
+
+        let mut transcript = PubkeyValidityProof::transcript_new(&pod_pubkey);
+        let proof = PubkeyValidityProof::new(keypair, &mut transcript);
+
+        Ok(PubkeyValidityData { context, proof })
+    }
+}
+
+impl ZkProofData<PubkeyValidityProofContext> for PubkeyValidityData {
+    const PROOF_TYPE: ProofType = ProofType::PubkeyValidity;
+
+    fn context_data(&self) -> &PubkeyValidityProofContext {
+        &self.context
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+impl Verifiable for PubkeyValidityData {
+    fn verify(&self) -> Result<(), ProofError> {
+        let mut transcript = PubkeyValidityProof::transcript_new(&self.context.pubkey);
+        let pubkey = self.context.pubkey.try_into()?;
+        self.proof.verify(&pubkey, &mut transcript)
+    }
+}
+
+/// This struct represents the cryptographic proof component that certifies that an ElGamal
+/// public key is well-formed
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct PubkeyValidityProof {
+    pub proof: pod::PubkeyValidityProof,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl PubkeyValidityProof {
+    fn transcript_new(pubkey: &pod::ElGamalPubkey) -> Transcript {
+        let mut transcript = Transcript::new(b"PubkeyProof");
+        transcript.append_pubkey(b"pubkey", pubkey);
+        transcript
+    }
+
+    pub fn new(keypair: &ElGamalKeypair, transcript: &mut Transcript) -> Self {
+        let proof = PubkeySigmaProof::new(keypair, transcript);
+
+        Self {
+            proof: proof.into(),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        pubkey: &ElGamalPubkey,
+        transcript: &mut Transcript,
+    ) -> Result<(), ProofError> {
+        let proof: PubkeySigmaProof = self.proof.try_into()?;
+        proof.verify(pubkey, transcript)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pubkey_validity_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let pubkey_validity_data = PubkeyValidityData::new(&keypair).unwrap();
+        assert!(pubkey_validity_data.verify().is_ok());
+
+        // a proof generated for one key should not verify against another key
+        let other_keypair = ElGamalKeypair::new_rand();
+        let mut pubkey_validity_data = PubkeyValidityData::new(&keypair).unwrap();
+        pubkey_validity_data.context.pubkey = pod::ElGamalPubkey(other_keypair.public.to_bytes());
+        assert!(pubkey_validity_data.verify().is_err());
+    }
+}
@@ -37,3 +37,4 @@ pub mod instruction;
 pub mod zk_token_elgamal;
 pub mod zk_token_proof_instruction;
 pub mod zk_token_proof_program;
+pub mod zk_token_proof_state;
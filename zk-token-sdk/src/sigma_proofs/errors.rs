@@ -72,3 +72,21 @@ impl From<TranscriptError> for FeeSigmaProofError {
         Self::Transcript
     }
 }
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum PubkeyValidityProofError {
+    #[error("the required algebraic relation does not hold")]
+    AlgebraicRelation,
+    #[error("malformed proof")]
+    Format,
+    #[error("multiscalar multiplication failed")]
+    MultiscalarMul,
+    #[error("transcript failed to produce a challenge")]
+    Transcript,
+}
+
+impl From<TranscriptError> for PubkeyValidityProofError {
+    fn from(_err: TranscriptError) -> Self {
+        Self::Transcript
+    }
+}
@@ -11,6 +11,8 @@
 //! message 0.
 //! - Fee proof: can be used to certify that an ElGamal ciphertext properly encrypts a transfer
 //! fee.
+//! - Public-key validity proof: can be used to certify that an ElGamal public key is valid, i.e.
+//! that the prover knows the corresponding secret key.
 //!
 //! We refer to the zk-token paper for the formal details and security proofs of these argument
 //! systems.
@@ -18,5 +20,6 @@
 pub mod equality_proof;
 pub mod errors;
 pub mod fee_proof;
+pub mod pubkey_proof;
 pub mod validity_proof;
 pub mod zero_balance_proof;
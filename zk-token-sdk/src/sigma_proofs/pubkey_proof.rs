@@ -0,0 +1,177 @@
+//! The public-key validity sigma proof system.
+//!
+//! A public-key validity proof is defined with respect to an ElGamal public key. The proof
+//! certifies that a given public key is a valid ElGamal public key (i.e. the prover knows a
+//! corresponding secret key). To generate the proof, a prover must provide the secret key for the
+//! public key.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::encryption::{
+        elgamal::{ElGamalKeypair, ElGamalPubkey},
+        pedersen::H,
+    },
+    rand::rngs::OsRng,
+    zeroize::Zeroize,
+};
+use {
+    crate::{sigma_proofs::errors::PubkeyValidityProofError, transcript::TranscriptProtocol},
+    arrayref::{array_ref, array_refs},
+    curve25519_dalek::{
+        ristretto::{CompressedRistretto, RistrettoPoint},
+        scalar::Scalar,
+        traits::{IsIdentity, VartimeMultiscalarMul},
+    },
+    merlin::Transcript,
+};
+
+/// Public-key validity proof.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct PubkeyValidityProof {
+    Y: CompressedRistretto,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+#[cfg(not(target_os = "solana"))]
+impl PubkeyValidityProof {
+    /// Public-key validity proof constructor.
+    ///
+    /// The function does *not* hash the public key into the transcript. For security, the caller
+    /// (the main protocol) should hash this public component prior to invoking this constructor.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `elgamal_keypair` - The ElGamal keypair associated with the public key to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(elgamal_keypair: &ElGamalKeypair, transcript: &mut Transcript) -> Self {
+        transcript.pubkey_proof_domain_sep();
+
+        // extract the relevant scalar from the input; an ElGamal public key is defined as
+        // `P = s^-1 * H`, so the prover proves knowledge of `s^-1`
+        let s = elgamal_keypair.secret.get_scalar();
+        assert!(s != &Scalar::zero());
+        let s_inv = s.invert();
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = Scalar::random(&mut OsRng);
+        let Y = (&y * &(*H)).compress();
+
+        // record Y in the transcript and receive a challenge scalar
+        transcript.append_point(b"Y", &Y);
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute the masked secret key
+        let z = &(&c * &s_inv) + &y;
+
+        // zeroize random scalar
+        y.zeroize();
+
+        Self { Y, z }
+    }
+
+    /// Public-key validity proof verifier.
+    ///
+    /// * `elgamal_pubkey` - The ElGamal public key to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        elgamal_pubkey: &ElGamalPubkey,
+        transcript: &mut Transcript,
+    ) -> Result<(), PubkeyValidityProofError> {
+        transcript.pubkey_proof_domain_sep();
+
+        // record Y to transcript and receive a challenge scalar
+        transcript.validate_and_append_point(b"Y", &self.Y)?;
+        let c = transcript.challenge_scalar(b"c");
+
+        // check the required algebraic relation
+        let Y = self
+            .Y
+            .decompress()
+            .ok_or(PubkeyValidityProofError::Format)?;
+        let P = elgamal_pubkey.get_point();
+
+        let check = RistrettoPoint::vartime_multiscalar_mul(
+            vec![&self.z, &(-&c), &(-&Scalar::one())],
+            vec![&(*H), P, &Y],
+        );
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(PubkeyValidityProofError::AlgebraicRelation)
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut buf = [0_u8; 64];
+        buf[..32].copy_from_slice(self.Y.as_bytes());
+        buf[32..64].copy_from_slice(self.z.as_bytes());
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PubkeyValidityProofError> {
+        let bytes = array_ref![bytes, 0, 64];
+        let (Y, z) = array_refs![bytes, 32, 32];
+
+        let Y = CompressedRistretto::from_slice(Y);
+        let z = Scalar::from_canonical_bytes(*z).ok_or(PubkeyValidityProofError::Format)?;
+
+        Ok(PubkeyValidityProof { Y, z })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::elgamal::{ElGamalPubkey, ElGamalSecretKey},
+    };
+
+    #[test]
+    fn test_pubkey_proof_correctness() {
+        // random ElGamal keypair
+        let keypair = ElGamalKeypair::new_rand();
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+        assert!(proof
+            .verify(&keypair.public, &mut verifier_transcript)
+            .is_ok());
+
+        // proof for a different public key should fail
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let other_keypair = ElGamalKeypair::new_rand();
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+        assert!(proof
+            .verify(&other_keypair.public, &mut verifier_transcript)
+            .is_err());
+    }
+
+    #[test]
+    fn test_pubkey_proof_edge_cases() {
+        // if the public key is zero, then the proof should always reject
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let public = ElGamalPubkey::from_bytes(&[0u8; 32]).unwrap();
+        let secret = ElGamalSecretKey::new_rand();
+        let keypair = ElGamalKeypair { public, secret };
+
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+        assert!(proof
+            .verify(&keypair.public, &mut verifier_transcript)
+            .is_err());
+    }
+}
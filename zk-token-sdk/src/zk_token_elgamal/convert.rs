@@ -66,6 +66,7 @@ mod target_arch {
                 equality_proof::{CtxtCommEqualityProof, CtxtCtxtEqualityProof},
                 errors::*,
                 fee_proof::FeeSigmaProof,
+                pubkey_proof::PubkeyValidityProof,
                 validity_proof::{AggregatedValidityProof, ValidityProof},
                 zero_balance_proof::ZeroBalanceProof,
             },
@@ -271,6 +272,20 @@ mod target_arch {
         }
     }
 
+    impl From<PubkeyValidityProof> for pod::PubkeyValidityProof {
+        fn from(proof: PubkeyValidityProof) -> Self {
+            Self(proof.to_bytes())
+        }
+    }
+
+    impl TryFrom<pod::PubkeyValidityProof> for PubkeyValidityProof {
+        type Error = PubkeyValidityProofError;
+
+        fn try_from(pod: pod::PubkeyValidityProof) -> Result<Self, Self::Error> {
+            Self::from_bytes(&pod.0)
+        }
+    }
+
     impl TryFrom<RangeProof> for pod::RangeProof64 {
         type Error = RangeProofError;
 
@@ -133,6 +133,11 @@ pub struct ZeroBalanceProof(pub [u8; 96]);
 unsafe impl Zeroable for ZeroBalanceProof {}
 unsafe impl Pod for ZeroBalanceProof {}
 
+/// Serialization of public-key validity proofs
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PubkeyValidityProof(pub [u8; 64]);
+
 /// Serialization of fee sigma proof
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
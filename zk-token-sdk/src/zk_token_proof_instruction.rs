@@ -4,7 +4,10 @@ use {
     bytemuck::{bytes_of, Pod},
     num_derive::{FromPrimitive, ToPrimitive},
     num_traits::{FromPrimitive, ToPrimitive},
-    solana_program::instruction::Instruction,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
 };
 
 #[derive(Clone, Copy, Debug, FromPrimitive, ToPrimitive, PartialEq, Eq)]
@@ -59,6 +62,109 @@ pub enum ProofInstruction {
     ///   `TransferWithFeeData`
     ///
     VerifyTransferWithFee,
+
+    /// Close a proof context state account and reclaim its lamports.
+    ///
+    /// Accounts expected by this instruction:
+    ///   0. `[writable]` The proof context account to close
+    ///   1. `[writable]` The destination account for lamports
+    ///   2. `[signer]` The context state authority of the proof context account
+    ///
+    /// Data expected by this instruction:
+    ///   None
+    ///
+    CloseContextState,
+
+    /// Verify a `PubkeyValidityData` struct
+    ///
+    /// The context of the verified proof is optionally written into a proof context state
+    /// account so that it can be read later by other programs. The account must be owned by the
+    /// ZkToken Proof program and sized to hold a
+    /// `ProofContextState<PubkeyValidityProofContext>`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Creating a proof context account
+    ///   0. `[writable]` The proof context account
+    ///   1. `[]` The context state authority of the proof context account
+    ///
+    ///   * Otherwise
+    ///   None
+    ///
+    /// Data expected by this instruction:
+    ///   `PubkeyValidityData`
+    ///
+    VerifyPubkeyValidity,
+
+    /// Verify a `CiphertextCiphertextEqualityProofData` struct
+    ///
+    /// The context of the verified proof is optionally written into a proof context state
+    /// account so that it can be read later by other programs. The account must be owned by the
+    /// ZkToken Proof program and sized to hold a
+    /// `ProofContextState<CiphertextCiphertextEqualityProofContext>`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Creating a proof context account
+    ///   0. `[writable]` The proof context account
+    ///   1. `[]` The context state authority of the proof context account
+    ///
+    ///   * Otherwise
+    ///   None
+    ///
+    /// Data expected by this instruction:
+    ///   `CiphertextCiphertextEqualityProofData`
+    ///
+    VerifyCiphertextCiphertextEquality,
+
+    /// Verify a `BatchedRangeProofU64Data` struct
+    ///
+    /// The context of the verified proof is optionally written into a proof context state
+    /// account so that it can be read later by other programs. The account must be owned by the
+    /// ZkToken Proof program and sized to hold a
+    /// `ProofContextState<BatchedRangeProofContext>`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Creating a proof context account
+    ///   0. `[writable]` The proof context account
+    ///   1. `[]` The context state authority of the proof context account
+    ///
+    ///   * Otherwise
+    ///   None
+    ///
+    /// Data expected by this instruction:
+    ///   `BatchedRangeProofU64Data`
+    ///
+    VerifyBatchedRangeProofU64,
+
+    /// Verify a `BatchedRangeProofU128Data` struct
+    ///
+    /// The context of the verified proof is optionally written into a proof context state
+    /// account so that it can be read later by other programs. The account must be owned by the
+    /// ZkToken Proof program and sized to hold a
+    /// `ProofContextState<BatchedRangeProofContext>`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Creating a proof context account
+    ///   0. `[writable]` The proof context account
+    ///   1. `[]` The context state authority of the proof context account
+    ///
+    ///   * Otherwise
+    ///   None
+    ///
+    /// Data expected by this instruction:
+    ///   `BatchedRangeProofU128Data`
+    ///
+    VerifyBatchedRangeProofU128,
+}
+
+/// Pubkeys associated with a proof context state account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContextStateInfo<'a> {
+    pub context_state_account: &'a Pubkey,
+    pub context_state_authority: &'a Pubkey,
 }
 
 impl ProofInstruction {
@@ -72,6 +178,27 @@ impl ProofInstruction {
         }
     }
 
+    /// Encode a proof verification instruction that optionally writes the proof context into
+    /// the account given by `context_state_info`
+    pub fn encode_verify_proof<T, U>(
+        &self,
+        context_state_info: Option<ContextStateInfo>,
+        proof_data: &T,
+    ) -> Instruction
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let mut instruction = self.encode(proof_data);
+        if let Some(context_state_info) = context_state_info {
+            instruction.accounts = vec![
+                AccountMeta::new(*context_state_info.context_state_account, false),
+                AccountMeta::new_readonly(*context_state_info.context_state_authority, false),
+            ];
+        }
+        instruction
+    }
+
     pub fn decode_type(input: &[u8]) -> Option<Self> {
         input.get(0).and_then(|x| FromPrimitive::from_u8(*x))
     }
@@ -104,3 +231,48 @@ pub fn verify_transfer(proof_data: &TransferData) -> Instruction {
 pub fn verify_transfer_with_fee(proof_data: &TransferWithFeeData) -> Instruction {
     ProofInstruction::VerifyTransferWithFee.encode(proof_data)
 }
+
+pub fn close_context_state(
+    context_state_info: ContextStateInfo,
+    destination_account: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::zk_token_proof_program::id(),
+        accounts: vec![
+            AccountMeta::new(*context_state_info.context_state_account, false),
+            AccountMeta::new(*destination_account, false),
+            AccountMeta::new_readonly(*context_state_info.context_state_authority, true),
+        ],
+        data: vec![ToPrimitive::to_u8(&ProofInstruction::CloseContextState).unwrap()],
+    }
+}
+
+pub fn verify_pubkey_validity(
+    context_state_info: Option<ContextStateInfo>,
+    proof_data: &PubkeyValidityData,
+) -> Instruction {
+    ProofInstruction::VerifyPubkeyValidity.encode_verify_proof(context_state_info, proof_data)
+}
+
+pub fn verify_ciphertext_ciphertext_equality(
+    context_state_info: Option<ContextStateInfo>,
+    proof_data: &CiphertextCiphertextEqualityProofData,
+) -> Instruction {
+    ProofInstruction::VerifyCiphertextCiphertextEquality
+        .encode_verify_proof(context_state_info, proof_data)
+}
+
+pub fn verify_batched_range_proof_u64(
+    context_state_info: Option<ContextStateInfo>,
+    proof_data: &BatchedRangeProofU64Data,
+) -> Instruction {
+    ProofInstruction::VerifyBatchedRangeProofU64.encode_verify_proof(context_state_info, proof_data)
+}
+
+pub fn verify_batched_range_proof_u128(
+    context_state_info: Option<ContextStateInfo>,
+    proof_data: &BatchedRangeProofU128Data,
+) -> Instruction {
+    ProofInstruction::VerifyBatchedRangeProofU128
+        .encode_verify_proof(context_state_info, proof_data)
+}
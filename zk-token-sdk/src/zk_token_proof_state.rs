@@ -0,0 +1,147 @@
+//! State of accounts owned by the ZkToken Proof program
+use {
+    bytemuck::{bytes_of, Pod, Zeroable},
+    num_derive::{FromPrimitive, ToPrimitive},
+    num_traits::{FromPrimitive, ToPrimitive},
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    std::mem::size_of,
+};
+
+/// The type of a proof whose context is stored in a proof context state account
+#[derive(Clone, Copy, Debug, FromPrimitive, ToPrimitive, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ProofType {
+    /// Empty proof type used to distinguish if a proof context account is initialized
+    Uninitialized,
+    PubkeyValidity,
+    CiphertextCiphertextEquality,
+    BatchedRangeProofU64,
+    BatchedRangeProofU128,
+}
+
+/// Serialization of `ProofType`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodProofType(u8);
+
+impl From<ProofType> for PodProofType {
+    fn from(proof_type: ProofType) -> Self {
+        Self(ToPrimitive::to_u8(&proof_type).unwrap())
+    }
+}
+
+impl TryFrom<PodProofType> for ProofType {
+    type Error = InstructionError;
+
+    fn try_from(pod: PodProofType) -> Result<Self, Self::Error> {
+        FromPrimitive::from_u8(pod.0).ok_or(InstructionError::InvalidAccountData)
+    }
+}
+
+/// The proof context information of a verified proof
+///
+/// A proof context state account stores the context of a proof so that other programs can read
+/// it without having to verify the proof again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct ProofContextState<T: Pod> {
+    /// The proof context authority that can close the account
+    pub context_state_authority: Pubkey,
+    /// The proof type for the context data
+    pub proof_type: PodProofType,
+    /// The proof context data
+    pub proof_context: T,
+}
+
+// `ProofContextState` is a Pod and Zeroable.
+// Add the marker traits manually because `bytemuck` does not derive them for generic structs.
+// All fields, including every proof context, are byte arrays so the struct contains no padding.
+unsafe impl<T: Pod> Zeroable for ProofContextState<T> {}
+unsafe impl<T: Pod> Pod for ProofContextState<T> {}
+
+impl<T: Pod> ProofContextState<T> {
+    /// Serialize a proof context state
+    pub fn encode(
+        context_state_authority: &Pubkey,
+        proof_type: ProofType,
+        proof_context: &T,
+    ) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size_of::<Self>());
+        buf.extend_from_slice(context_state_authority.as_ref());
+        buf.push(ToPrimitive::to_u8(&proof_type).unwrap());
+        buf.extend_from_slice(bytes_of(proof_context));
+        buf
+    }
+
+    /// Interpret a slice as a `ProofContextState`
+    ///
+    /// This function requires a type annotation for the proof context, e.g.
+    /// `ProofContextState::<PubkeyValidityProofContext>::try_from_bytes(data)`
+    pub fn try_from_bytes(input: &[u8]) -> Result<&Self, InstructionError> {
+        bytemuck::try_from_bytes(input).map_err(|_| InstructionError::InvalidAccountData)
+    }
+}
+
+/// The `ProofContextState` without the proof context itself. This struct exists to facilitate the
+/// decoding of generic-independent fields in `ProofContextState`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ProofContextStateMeta {
+    /// The proof context authority that can close the account
+    pub context_state_authority: Pubkey,
+    /// The proof type for the context data
+    pub proof_type: PodProofType,
+}
+
+impl ProofContextStateMeta {
+    /// Interpret the generic-independent prefix of a proof context state account
+    pub fn try_from_bytes(input: &[u8]) -> Result<&Self, InstructionError> {
+        input
+            .get(..size_of::<ProofContextStateMeta>())
+            .and_then(|data| bytemuck::try_from_bytes(data).ok())
+            .ok_or(InstructionError::InvalidAccountData)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::zk_token_elgamal::pod};
+
+    #[test]
+    fn test_proof_context_state_encoding() {
+        let context_state_authority = Pubkey::new_unique();
+        let proof_context = pod::ElGamalPubkey([7; 32]);
+
+        let data = ProofContextState::encode(
+            &context_state_authority,
+            ProofType::PubkeyValidity,
+            &proof_context,
+        );
+        assert_eq!(
+            data.len(),
+            size_of::<ProofContextState<pod::ElGamalPubkey>>()
+        );
+
+        let state = ProofContextState::<pod::ElGamalPubkey>::try_from_bytes(&data).unwrap();
+        assert_eq!(state.context_state_authority, context_state_authority);
+        assert_eq!(
+            ProofType::try_from(state.proof_type),
+            Ok(ProofType::PubkeyValidity)
+        );
+        assert_eq!(state.proof_context, proof_context);
+
+        let meta = ProofContextStateMeta::try_from_bytes(&data).unwrap();
+        assert_eq!(meta.context_state_authority, context_state_authority);
+        assert_eq!(meta.proof_type, ProofType::PubkeyValidity.into());
+
+        // an empty account cannot be interpreted as a proof context state
+        assert_eq!(
+            ProofContextStateMeta::try_from_bytes(&[]),
+            Err(InstructionError::InvalidAccountData)
+        );
+        assert_eq!(
+            ProofContextState::<pod::ElGamalPubkey>::try_from_bytes(&data[1..]),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+}